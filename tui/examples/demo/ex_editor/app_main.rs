@@ -15,6 +15,8 @@
  *   limitations under the License.
  */
 
+use std::sync::Arc;
+
 use r3bl_rs_utils_core::*;
 use r3bl_rs_utils_macro::style;
 use r3bl_tui::*;
@@ -33,6 +35,7 @@ pub enum Id {
     DialogStyleNameTitle = 6,
     DialogStyleNameEditor = 7,
     DialogStyleNameResultsPanel = 8,
+    KillRingDialog = 9,
 }
 
 mod id_impl {
@@ -148,7 +151,10 @@ mod app_main_impl_app_trait {
 
                 // Create a surface and then run the SurfaceRenderer (ContainerSurfaceRender) on it.
                 let mut surface = {
-                    let mut it = surface!(stylesheet: stylesheet::create_stylesheet()?);
+                    let mut it = surface!(
+                        stylesheet: stylesheet::create_stylesheet()?,
+                        has_focus:  has_focus
+                    );
 
                     it.surface_start(SurfaceProps {
                         pos: position!(col_index: 0, row_index: 0),
@@ -186,12 +192,12 @@ mod modal_dialogs {
     // This runs on every keystroke, so it should be fast.
     pub fn dialog_component_update_content(state: &mut State, id: FlexBoxId) {
         // This is Some only if the content has changed (ignoring caret movements).
-        let maybe_changed_results: Option<Vec<String>> = {
+        let maybe_changed_results: Option<Vec<CompletionItem>> = {
             if let Some(dialog_buffer) = state.dialog_buffers.get_mut(&id) {
                 let vec_result = generate_random_results(
                     dialog_buffer.editor_buffer.get_as_string().as_str(),
                 );
-                Some(vec_result)
+                Some(vec_result.into_iter().map(CompletionItem::from).collect())
             } else {
                 None
             }
@@ -308,6 +314,33 @@ mod modal_dialogs {
             };
         };
 
+        // "Ctrl + p" => activate Kill ring.
+        if input_event.matches_keypress(KeyPress::WithModifiers {
+            key: Key::Character('p'),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        }) {
+            // Reset the dialog component prior to activating / showing it.
+            ComponentRegistry::reset_component(
+                component_registry_map,
+                FlexBoxId::from(Id::KillRingDialog),
+            );
+            return match activate_kill_ring_modal(has_focus, state) {
+                Ok(_) => ModalActivateResult::Yes,
+                Err(err) => {
+                    if let Some(CommonError {
+                        err_type: _,
+                        err_msg: msg,
+                    }) = err.downcast_ref::<CommonError>()
+                    {
+                        log_error(format!(
+                            "📣 Error activating kill ring modal: {msg:?}"
+                        ));
+                    }
+                    ModalActivateResult::No
+                }
+            };
+        };
+
         ModalActivateResult::No
     }
 
@@ -387,30 +420,30 @@ mod modal_dialogs {
         has_focus: &mut HasFocus,
         state: &mut State,
     ) -> CommonResult<()> {
-        // Initialize the dialog buffer with title & text.
+        // Initialize the dialog buffer with title.
         let title = "Autocomplete Modal Dialog Title";
-        let text = {
-            if let Some(editor_buffer) =
-                state.get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
-            {
-                editor_buffer.get_as_string()
-            } else {
-                "".to_string()
-            }
-        };
 
         // Setting the has_focus to Id::Dialog will cause the dialog to appear on the next
         // render.
         has_focus.try_set_modal_id(FlexBoxId::from(Id::AutocompleteDialog))?;
 
         // Change the state so that it will trigger a render. This will show the title &
-        // text on the next render.
+        // the text before the caret in the editor (which is what gets completed) on the
+        // next render.
         dialog_component_initialize_focused(
             state,
             FlexBoxId::from(Id::AutocompleteDialog),
             title.to_owned(),
-            text,
+            "".to_owned(),
         );
+        if let (Some(editor_buffer), Some(dialog_buffer)) = (
+            state.editor_buffers.get(&FlexBoxId::from(Id::Editor)),
+            state
+                .dialog_buffers
+                .get_mut(&FlexBoxId::from(Id::AutocompleteDialog)),
+        ) {
+            dialog_buffer.set_completion_origin(editor_buffer);
+        }
 
         call_if_true!(DEBUG_TUI_MOD, {
             let msg = format!("📣 activate modal autocomplete: {:?}", has_focus);
@@ -419,6 +452,58 @@ mod modal_dialogs {
 
         Ok(())
     }
+
+    fn activate_kill_ring_modal(
+        has_focus: &mut HasFocus,
+        state: &mut State,
+    ) -> CommonResult<()> {
+        // Setting the has_focus to Id::KillRingDialog will cause the dialog to appear on
+        // the next render.
+        has_focus.try_set_modal_id(FlexBoxId::from(Id::KillRingDialog))?;
+
+        dialog_component_initialize_focused(
+            state,
+            FlexBoxId::from(Id::KillRingDialog),
+            "Paste from kill ring".to_owned(),
+            "".to_owned(),
+        );
+        kill_ring_dialog_update_content(state);
+
+        call_if_true!(DEBUG_TUI_MOD, {
+            let msg = format!("📣 activate modal kill ring: {:?}", has_focus);
+            log_debug(msg);
+        });
+
+        Ok(())
+    }
+
+    /// Show the entries of the editor's [KillRing] that contain the text typed into the
+    /// dialog.
+    pub fn kill_ring_dialog_update_content(state: &mut State) {
+        let Some(editor_buffer) = state.editor_buffers.get(&FlexBoxId::from(Id::Editor))
+        else {
+            return;
+        };
+        let Some(dialog_buffer) = state
+            .dialog_buffers
+            .get_mut(&FlexBoxId::from(Id::KillRingDialog))
+        else {
+            return;
+        };
+
+        let filter = dialog_buffer.editor_buffer.get_as_string();
+        let results: Vec<CompletionItem> = editor_buffer
+            .get_kill_ring()
+            .get_completion_items()
+            .into_iter()
+            .filter(|item| item.insert_text.contains(&filter))
+            .collect();
+        dialog_buffer.maybe_results = if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        };
+    }
 }
 
 mod perform_layout {
@@ -481,6 +566,19 @@ mod perform_layout {
                       has_focus:          has_focus
                     };
                 }
+
+                // Or, render kill ring modal dialog (if it is active, on top of the editor
+                // component).
+                if has_focus.is_modal_id(FlexBoxId::from(Id::KillRingDialog)) {
+                    render_component_in_given_box! {
+                      in:                 surface,
+                      box:                FlexBox::default(), /* This is not used as the modal breaks out of its box. */
+                      component_id:       FlexBoxId::from(Id::KillRingDialog),
+                      from:               component_registry_map,
+                      global_data:        global_data,
+                      has_focus:          has_focus
+                    };
+                }
             });
         }
     }
@@ -498,6 +596,7 @@ mod populate_component_registry {
         insert_editor_component(component_registry_map);
         insert_dialog_component_simple(component_registry_map);
         insert_dialog_component_autocomplete(component_registry_map);
+        insert_dialog_component_kill_ring(component_registry_map);
 
         // Switch focus to the editor component if focus is not set.
         let id = FlexBoxId::from(Id::Editor);
@@ -561,6 +660,7 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let boxed_dialog_component = {
@@ -637,10 +737,11 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let boxed_dialog_component = {
-            let it = DialogComponent::new_boxed(
+            let mut it = DialogComponent::new_boxed(
                 FlexBoxId::from(Id::AutocompleteDialog),
                 dialog_options,
                 editor_options,
@@ -648,32 +749,88 @@ mod populate_component_registry {
                 on_dialog_editor_change_handler,
             );
 
+            // Complete file paths as the user types.
+            it.data.dialog_engine.maybe_completion_provider =
+                Some(Arc::new(FilePathCompletionProvider::default()));
+
+            // The accepted completion replaces the text before the caret in the editor (as
+            // a single undo step).
             fn on_dialog_press_handler(dialog_choice: DialogChoice, state: &mut State) {
-                match dialog_choice {
-                    DialogChoice::Yes(text) => {
-                        modal_dialogs::dialog_component_initialize_focused(
-                            state,
-                            FlexBoxId::from(Id::AutocompleteDialog),
-                            "Yes".to_string(),
-                            text,
-                        );
+                if let DialogChoice::Yes(text) = dialog_choice {
+                    if let Some(editor_buffer) =
+                        state.get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+                    {
+                        editor_buffer.insert_completion(&text);
                     }
-                    DialogChoice::No => {
-                        modal_dialogs::dialog_component_initialize_focused(
-                            state,
-                            FlexBoxId::from(Id::AutocompleteDialog),
-                            "No".to_string(),
-                            "".to_string(),
-                        );
+                }
+            }
+
+            // The results panel is filled by the completion provider.
+            fn on_dialog_editor_change_handler(_state: &mut State) {}
+
+            it
+        };
+
+        ComponentRegistry::put(
+            component_registry_map,
+            FlexBoxId::from(Id::AutocompleteDialog),
+            boxed_dialog_component,
+        );
+
+        call_if_true!(DEBUG_TUI_MOD, {
+            let msg = format!(
+                "🪙 {}",
+                "construct DialogComponent (autocomplete) { on_dialog_press }"
+            );
+            log_debug(msg);
+        });
+    }
+
+    /// Insert kill ring dialog component into registry if it's not already there.
+    fn insert_dialog_component_kill_ring(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+    ) {
+        let result_stylesheet = stylesheet::create_stylesheet();
+
+        let dialog_options = DialogEngineConfigOptions {
+            mode: DialogEngineMode::ModalAutocomplete,
+            maybe_style_border: get_style! { @from_result: result_stylesheet , Id::DialogStyleNameBorder.into() },
+            maybe_style_title: get_style! { @from_result: result_stylesheet , Id::DialogStyleNameTitle.into() },
+            maybe_style_editor: get_style! { @from_result: result_stylesheet , Id::DialogStyleNameEditor.into() },
+            maybe_style_results_panel: get_style! { @from_result: result_stylesheet , Id::DialogStyleNameResultsPanel.into() },
+            ..Default::default()
+        };
+
+        let editor_options = EditorEngineConfig {
+            multiline_mode: LineMode::SingleLine,
+            syntax_highlight: SyntaxHighlightMode::Disable,
+            edit_mode: EditMode::ReadWrite,
+            ..Default::default()
+        };
+
+        let boxed_dialog_component = {
+            let it = DialogComponent::new_boxed(
+                FlexBoxId::from(Id::KillRingDialog),
+                dialog_options,
+                editor_options,
+                on_dialog_press_handler,
+                on_dialog_editor_change_handler,
+            );
+
+            // The text of the picked entry is pasted into the editor (as a single undo
+            // step).
+            fn on_dialog_press_handler(dialog_choice: DialogChoice, state: &mut State) {
+                if let DialogChoice::Yes(text) = dialog_choice {
+                    if let Some(editor_buffer) =
+                        state.get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+                    {
+                        editor_buffer.paste_text(&text);
                     }
                 }
             }
 
             fn on_dialog_editor_change_handler(state: &mut State) {
-                modal_dialogs::dialog_component_update_content(
-                    state,
-                    FlexBoxId::from(Id::AutocompleteDialog),
-                );
+                modal_dialogs::kill_ring_dialog_update_content(state);
             }
 
             it
@@ -681,14 +838,14 @@ mod populate_component_registry {
 
         ComponentRegistry::put(
             component_registry_map,
-            FlexBoxId::from(Id::AutocompleteDialog),
+            FlexBoxId::from(Id::KillRingDialog),
             boxed_dialog_component,
         );
 
         call_if_true!(DEBUG_TUI_MOD, {
             let msg = format!(
                 "🪙 {}",
-                "construct DialogComponent (autocomplete) { on_dialog_press }"
+                "construct DialogComponent (kill ring) { on_dialog_press }"
             );
             log_debug(msg);
         });
@@ -753,6 +910,9 @@ mod status_bar {
             styled_text! { @style: style!(attrib: [dim, underline]) , @text: "Ctrl + k"},
             styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Autocomplete 🤖"},
             styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
+            styled_text! { @style: style!(attrib: [dim, underline]) , @text: "Ctrl + p"},
+            styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Kill ring 📋"},
+            styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
             styled_text! { @style: style!(attrib: [underline]) ,      @text: "Type content 🌊"},
         };
 
@@ -274,7 +274,10 @@ mod app_main_impl_app_trait {
 
                 // Create a surface and then run the SurfaceRenderer (ContainerSurfaceRender) on it.
                 let mut surface = {
-                    let mut it = surface!(stylesheet: stylesheet::create_stylesheet()?);
+                    let mut it = surface!(
+                        stylesheet: stylesheet::create_stylesheet()?,
+                        has_focus:  has_focus
+                    );
 
                     it.surface_start(SurfaceProps {
                         pos: position!(col_index: 0, row_index: 0),
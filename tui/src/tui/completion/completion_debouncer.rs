@@ -0,0 +1,118 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{sync::{atomic::{AtomicUsize, Ordering},
+                 Arc,
+                 Mutex},
+          time::Duration};
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+pub const DEFAULT_COMPLETION_DEBOUNCE_DELAY: Duration = Duration::from_millis(150);
+
+/// Calls a [CompletionProvider] only once the user has stopped typing for `delay`.
+///
+/// Each call to [request](CompletionDebouncer::request) supersedes the previous one:
+/// 1. A request waits for `delay` before calling the provider. If another request is made
+///    in the meantime, it is dropped w/out calling the provider.
+/// 2. If another request is made while the provider is running, its results are dropped
+///    (so stale results never replace newer ones).
+/// 3. Otherwise the results are saved, and can be retrieved w/
+///    [take_completions](CompletionDebouncer::take_completions). The `on_completions_ready`
+///    callback is called so that a render can be requested.
+///
+/// Clones share the same state, so a clone can be moved into a task. This must be used
+/// from inside a [tokio] runtime.
+#[derive(Clone, Debug)]
+pub struct CompletionDebouncer {
+    pub delay: Duration,
+    generation: Arc<AtomicUsize>,
+    maybe_completions: Arc<Mutex<Option<Vec<CompletionItem>>>>,
+}
+
+impl Default for CompletionDebouncer {
+    fn default() -> Self { Self::new(DEFAULT_COMPLETION_DEBOUNCE_DELAY) }
+}
+
+impl CompletionDebouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            generation: Default::default(),
+            maybe_completions: Default::default(),
+        }
+    }
+
+    pub fn request<F>(
+        &self,
+        provider: Arc<dyn CompletionProvider>,
+        query: CompletionQuery,
+        on_completions_ready: F,
+    ) where
+        F: FnOnce() + Send + 'static,
+    {
+        let my_generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let this = self.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(this.delay).await;
+            if !this.is_current(my_generation) {
+                return;
+            }
+
+            let completions = match provider.get_completions(&query).await {
+                Ok(it) => it,
+                Err(error) => {
+                    log_error(format!("🐝 CompletionDebouncer::request: {error}"));
+                    return;
+                }
+            };
+            if !this.is_current(my_generation) {
+                return;
+            }
+
+            if let Ok(mut maybe_completions) = this.maybe_completions.lock() {
+                maybe_completions.replace(completions);
+            }
+            on_completions_ready();
+        });
+    }
+
+    /// Drop any pending request (eg: when the dialog is closed), and any results that
+    /// have not been taken yet.
+    pub fn cancel(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut maybe_completions) = self.maybe_completions.lock() {
+            maybe_completions.take();
+        }
+    }
+
+    /// Returns the results of the latest request, if they are ready & have not been taken
+    /// yet.
+    pub fn take_completions(&self) -> Option<Vec<CompletionItem>> {
+        match self.maybe_completions.lock() {
+            Ok(mut maybe_completions) => maybe_completions.take(),
+            Err(_) => None,
+        }
+    }
+
+    fn is_current(&self, generation: usize) -> bool {
+        self.generation.load(Ordering::SeqCst) == generation
+    }
+}
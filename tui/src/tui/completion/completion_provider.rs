@@ -0,0 +1,151 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use futures::future::BoxFuture;
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::*;
+
+/// A single choice that is offered to the user by a [CompletionProvider].
+///
+/// - `label` is what is displayed in the results panel of the autocomplete dialog.
+/// - `detail` is optional extra information (eg: the kind of the item) that is displayed
+///   next to the label.
+/// - `insert_text` is what replaces [CompletionQuery::text] when this item is accepted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+pub struct CompletionItem {
+    pub label: String,
+    pub detail: Option<String>,
+    pub insert_text: String,
+}
+
+impl CompletionItem {
+    pub fn new(
+        label: impl Into<String>,
+        maybe_detail: Option<String>,
+        insert_text: impl Into<String>,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            detail: maybe_detail,
+            insert_text: insert_text.into(),
+        }
+    }
+}
+
+/// The label & the insert text are the same.
+impl From<String> for CompletionItem {
+    fn from(value: String) -> Self {
+        Self {
+            label: value.clone(),
+            detail: None,
+            insert_text: value,
+        }
+    }
+}
+
+impl From<&str> for CompletionItem {
+    fn from(value: &str) -> Self { Self::from(value.to_string()) }
+}
+
+/// What is passed to a [CompletionProvider].
+///
+/// - `text` is the text that is being completed. For an [crate::EditorBuffer] this is the
+///   text before the caret, up to the previous whitespace (see
+///   [EditorBuffer::get_completion_query](crate::EditorBuffer::get_completion_query)).
+/// - `lines` are all the lines of the buffer that the query comes from, so that providers
+///   can use the content of the buffer (eg:
+///   [BufferWordsCompletionProvider](crate::BufferWordsCompletionProvider)).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompletionQuery {
+    pub text: String,
+    pub lines: Vec<String>,
+}
+
+impl CompletionQuery {
+    pub fn new(text: impl Into<String>, lines: Vec<String>) -> Self {
+        Self {
+            text: text.into(),
+            lines,
+        }
+    }
+}
+
+/// Async source of [CompletionItem]s, which are used by the autocomplete dialog
+/// ([crate::DialogEngineMode::ModalAutocomplete]). The returned items should already be
+/// ranked (best match first), which [completion_support::rank_completion_items] can be
+/// used for.
+///
+/// Built-in providers:
+/// - [FilePathCompletionProvider](crate::FilePathCompletionProvider) completes file &
+///   folder paths.
+/// - [BufferWordsCompletionProvider](crate::BufferWordsCompletionProvider) completes
+///   words that are already in the buffer.
+///
+/// Since providers are async (eg: they can read from disk or make network requests), they
+/// are not called on every keystroke. Instead a
+/// [CompletionDebouncer](crate::CompletionDebouncer) is used to only call them once the
+/// user has stopped typing.
+pub trait CompletionProvider: Debug + Send + Sync {
+    fn get_completions<'a>(
+        &'a self,
+        query: &'a CompletionQuery,
+    ) -> BoxFuture<'a, CommonResult<Vec<CompletionItem>>>;
+}
+
+pub mod completion_support {
+    use super::*;
+
+    /// Ranks `items` by how well their labels match `needle`, and drops the items that
+    /// don't match at all. The ranking is:
+    /// 1. Labels that start w/ `needle` (case sensitive).
+    /// 2. Labels that start w/ `needle` (ignoring case).
+    /// 3. Labels that contain `needle` (ignoring case).
+    ///
+    /// Items w/ the same rank keep their order, so providers can sort their items (eg:
+    /// alphabetically, or by frequency) before ranking them.
+    pub fn rank_completion_items(
+        needle: &str,
+        items: Vec<CompletionItem>,
+    ) -> Vec<CompletionItem> {
+        let needle_lowercase = needle.to_lowercase();
+
+        let mut ranked_items: Vec<(usize, CompletionItem)> = items
+            .into_iter()
+            .filter_map(|item| {
+                let label_lowercase = item.label.to_lowercase();
+                let rank = if item.label.starts_with(needle) {
+                    0
+                } else if label_lowercase.starts_with(&needle_lowercase) {
+                    1
+                } else if label_lowercase.contains(&needle_lowercase) {
+                    2
+                } else {
+                    return None;
+                };
+                Some((rank, item))
+            })
+            .collect();
+
+        // This is a stable sort.
+        ranked_items.sort_by_key(|(rank, _)| *rank);
+
+        ranked_items.into_iter().map(|(_, item)| item).collect()
+    }
+}
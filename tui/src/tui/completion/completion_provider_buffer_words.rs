@@ -0,0 +1,99 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use futures::future::BoxFuture;
+use r3bl_rs_utils_core::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::*;
+
+/// Completes the word at the end of [CompletionQuery::text] w/ the words that are already
+/// in [CompletionQuery::lines] (which is the content of the buffer).
+///
+/// ```text
+///   query text      word prefix   items (insert text)
+///   ─────────────   ───────────   ────────────────────────────────
+///   "(foo"       →  "foo"      →  "(foo_bar", "(foobar", "(FooBaz"
+/// ```
+///
+/// 1. Words come from Unicode word segmentation, just like [word_support].
+/// 2. Words that occur more often in the buffer are ranked higher (after ranking by how
+///    well they match). The word that is being typed itself is not offered.
+/// 3. If there is no word prefix (eg: the query text ends w/ whitespace or punctuation),
+///    there are no completions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BufferWordsCompletionProvider;
+
+impl CompletionProvider for BufferWordsCompletionProvider {
+    fn get_completions<'a>(
+        &'a self,
+        query: &'a CompletionQuery,
+    ) -> BoxFuture<'a, CommonResult<Vec<CompletionItem>>> {
+        Box::pin(async move {
+            let word_prefix = get_trailing_word(&query.text);
+            if word_prefix.is_empty() {
+                return Ok(vec![]);
+            }
+            let text_before_word = &query.text[..query.text.len() - word_prefix.len()];
+
+            let mut word_counts: HashMap<&str, usize> = HashMap::new();
+            for line in &query.lines {
+                for word in line.unicode_words() {
+                    *word_counts.entry(word).or_default() += 1;
+                }
+            }
+            word_counts.remove(word_prefix);
+
+            // Most frequent first, then alphabetical.
+            let mut words: Vec<(&str, usize)> = word_counts.into_iter().collect();
+            words.sort_by(|(lhs_word, lhs_count), (rhs_word, rhs_count)| {
+                rhs_count
+                    .cmp(lhs_count)
+                    .then_with(|| lhs_word.cmp(rhs_word))
+            });
+
+            let items = words
+                .into_iter()
+                .map(|(word, count)| {
+                    CompletionItem::new(
+                        word,
+                        Some(format!("{count}×")),
+                        format!("{text_before_word}{word}"),
+                    )
+                })
+                .collect();
+
+            Ok(completion_support::rank_completion_items(
+                word_prefix,
+                items,
+            ))
+        })
+    }
+}
+
+fn get_trailing_word(text: &str) -> &str {
+    let word_start_index = text
+        .char_indices()
+        .rev()
+        .take_while(|(_, character)| character.is_alphanumeric() || *character == '_')
+        .last()
+        .map(|(byte_index, _)| byte_index)
+        .unwrap_or(text.len());
+    &text[word_start_index..]
+}
@@ -0,0 +1,123 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::path::{is_separator, Path, PathBuf};
+
+use futures::future::BoxFuture;
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Completes the paths of files & folders. The [CompletionQuery::text] is split at the
+/// last path separator into the folder to look in, and the prefix of the names to look
+/// for.
+///
+/// ```text
+///   query text       folder     prefix     items (insert text)
+///   ──────────────   ────────   ────────   ─────────────────────────────
+///   "src/ma"      →  "src/"     "ma"   →   "src/main.rs", "src/macros/"
+///   "Car"         →  "."        "Car"  →   "Cargo.toml", "Cargo.lock"
+/// ```
+///
+/// 1. Relative paths are resolved against `maybe_base_dir` (or the current working
+///    directory if it is [None]).
+/// 2. Folders end w/ a path separator, so that accepting one and asking for completions
+///    again lists what is inside it.
+/// 3. Only the names that start w/ the prefix (ignoring case) are offered. Hidden files
+///    (whose names start w/ `.`) are only offered if the prefix starts w/ `.` as well.
+/// 4. If the folder does not exist, there are no completions (this isn't an error, since
+///    the user might still be typing its name).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FilePathCompletionProvider {
+    pub maybe_base_dir: Option<PathBuf>,
+}
+
+impl FilePathCompletionProvider {
+    pub fn new(maybe_base_dir: Option<PathBuf>) -> Self { Self { maybe_base_dir } }
+
+    fn get_dir_to_read(&self, dir_part: &str) -> PathBuf {
+        let dir_path = Path::new(if dir_part.is_empty() { "." } else { dir_part });
+        match &self.maybe_base_dir {
+            Some(base_dir) if dir_path.is_relative() => base_dir.join(dir_path),
+            _ => dir_path.to_path_buf(),
+        }
+    }
+}
+
+impl CompletionProvider for FilePathCompletionProvider {
+    fn get_completions<'a>(
+        &'a self,
+        query: &'a CompletionQuery,
+    ) -> BoxFuture<'a, CommonResult<Vec<CompletionItem>>> {
+        Box::pin(async move {
+            let (dir_part, name_prefix) = split_at_last_separator(&query.text);
+
+            let mut read_dir =
+                match tokio::fs::read_dir(self.get_dir_to_read(dir_part)).await {
+                    Ok(it) => it,
+                    Err(_) => return Ok(vec![]),
+                };
+
+            let name_prefix_lowercase = name_prefix.to_lowercase();
+            let mut items = vec![];
+            while let Some(entry) = read_dir.next_entry().await? {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if !file_name.to_lowercase().starts_with(&name_prefix_lowercase)
+                    || file_name.starts_with('.') && !name_prefix.starts_with('.')
+                {
+                    continue;
+                }
+
+                // Follow symlinks to find out if this is a folder.
+                let is_dir = tokio::fs::metadata(entry.path())
+                    .await
+                    .map(|metadata| metadata.is_dir())
+                    .unwrap_or(false);
+
+                let (label, detail) = if is_dir {
+                    (
+                        format!("{file_name}{}", std::path::MAIN_SEPARATOR),
+                        "folder",
+                    )
+                } else {
+                    (file_name, "file")
+                };
+                let insert_text = format!("{dir_part}{label}");
+                items.push(CompletionItem::new(
+                    label,
+                    Some(detail.to_string()),
+                    insert_text,
+                ));
+            }
+
+            items.sort_by(|lhs, rhs| lhs.label.cmp(&rhs.label));
+
+            Ok(completion_support::rank_completion_items(
+                name_prefix,
+                items,
+            ))
+        })
+    }
+}
+
+/// Returns the folder part (including the trailing separator) & the name part.
+fn split_at_last_separator(text: &str) -> (&str, &str) {
+    match text.rfind(is_separator) {
+        Some(separator_index) => text.split_at(separator_index + 1),
+        None => ("", text),
+    }
+}
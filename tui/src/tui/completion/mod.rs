@@ -0,0 +1,31 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+// Attach.
+pub mod completion_debouncer;
+pub mod completion_provider;
+pub mod completion_provider_buffer_words;
+pub mod completion_provider_file_path;
+
+// Re-export.
+pub use completion_debouncer::*;
+pub use completion_provider::*;
+pub use completion_provider_buffer_words::*;
+pub use completion_provider_file_path::*;
+
+// Tests.
+mod test_completion;
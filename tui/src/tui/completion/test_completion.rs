@@ -0,0 +1,295 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod test_completion_providers {
+    use std::{fs, path::PathBuf};
+
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    fn get_insert_texts(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|it| it.insert_text.as_str()).collect()
+    }
+
+    fn make_temp_dir_path(dir_name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("r3bl_tui_{}_{}", std::process::id(), dir_name))
+    }
+
+    #[test]
+    fn test_rank_completion_items() {
+        let items = ["xfoo", "Foobar", "bar", "foo_bar", "FOO"]
+            .into_iter()
+            .map(CompletionItem::from)
+            .collect();
+
+        let ranked_items = completion_support::rank_completion_items("foo", items);
+
+        assert_eq2!(
+            get_insert_texts(&ranked_items),
+            vec!["foo_bar", "Foobar", "FOO", "xfoo"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_path_provider() {
+        let temp_dir_path = make_temp_dir_path("completion_file_path");
+        fs::create_dir_all(temp_dir_path.join("src/macros")).unwrap();
+        fs::write(temp_dir_path.join("src/main.rs"), "").unwrap();
+        fs::write(temp_dir_path.join("src/lib.rs"), "").unwrap();
+        fs::write(temp_dir_path.join("src/.hidden"), "").unwrap();
+
+        let provider = FilePathCompletionProvider::new(Some(temp_dir_path.clone()));
+        let get_completions = |text: &str| {
+            let query = CompletionQuery::new(text, vec![]);
+            let provider = provider.clone();
+            async move { provider.get_completions(&query).await.unwrap() }
+        };
+
+        // Folders end w/ a separator, and are completed w/ the folder part of the query.
+        let items = get_completions("src/ma").await;
+        assert_eq2!(
+            get_insert_texts(&items),
+            vec![
+                format!("src/macros{}", std::path::MAIN_SEPARATOR).as_str(),
+                "src/main.rs"
+            ]
+        );
+        assert_eq2!(items[1].label, "main.rs");
+        assert_eq2!(items[1].detail, Some("file".to_string()));
+
+        // Hidden files are only offered when asked for.
+        let items = get_completions("src/").await;
+        assert_eq2!(items.len(), 3);
+        let items = get_completions("src/.").await;
+        assert_eq2!(get_insert_texts(&items), vec!["src/.hidden"]);
+
+        // Folders that don't exist have no completions.
+        let items = get_completions("nope/ma").await;
+        assert_eq2!(items.len(), 0);
+
+        fs::remove_dir_all(temp_dir_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_buffer_words_provider() {
+        let lines = vec![
+            "let foo_bar = foobar(FooBaz);".to_string(),
+            "foo_bar + foo_bar + foo".to_string(),
+        ];
+
+        // The word that is being typed ("foo") is not offered, and more frequent words
+        // come first.
+        let query = CompletionQuery::new("(foo", lines.clone());
+        let items = BufferWordsCompletionProvider
+            .get_completions(&query)
+            .await
+            .unwrap();
+        assert_eq2!(
+            get_insert_texts(&items),
+            vec!["(foo_bar", "(foobar", "(FooBaz"]
+        );
+        assert_eq2!(items[0].label, "foo_bar");
+        assert_eq2!(items[0].detail, Some("3×".to_string()));
+
+        // No word prefix.
+        let query = CompletionQuery::new("foo ", lines);
+        let items = BufferWordsCompletionProvider
+            .get_completions(&query)
+            .await
+            .unwrap();
+        assert_eq2!(items.len(), 0);
+    }
+}
+
+#[cfg(test)]
+mod test_completion_debouncer {
+    use std::{sync::{atomic::{AtomicUsize, Ordering},
+                     Arc},
+              time::Duration};
+
+    use futures::future::BoxFuture;
+    use r3bl_rs_utils_core::*;
+    use tokio::sync::mpsc;
+
+    use crate::*;
+
+    /// Completes the query text w/ itself, and counts how often it is called.
+    #[derive(Debug, Default)]
+    struct EchoCompletionProvider {
+        call_count: Arc<AtomicUsize>,
+    }
+
+    impl CompletionProvider for EchoCompletionProvider {
+        fn get_completions<'a>(
+            &'a self,
+            query: &'a CompletionQuery,
+        ) -> BoxFuture<'a, CommonResult<Vec<CompletionItem>>> {
+            Box::pin(async move {
+                self.call_count.fetch_add(1, Ordering::SeqCst);
+                Ok(vec![CompletionItem::from(query.text.as_str())])
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_the_last_request_is_made() {
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let provider: Arc<dyn CompletionProvider> = Arc::new(EchoCompletionProvider {
+            call_count: call_count.clone(),
+        });
+        let debouncer = CompletionDebouncer::new(Duration::from_millis(20));
+        let (sender, mut receiver) = mpsc::channel::<()>(8);
+
+        for text in ["a", "ab", "abc"] {
+            let sender = sender.clone();
+            debouncer.request(
+                provider.clone(),
+                CompletionQuery::new(text, vec![]),
+                move || {
+                    let _ = sender.try_send(());
+                },
+            );
+        }
+        receiver.recv().await.unwrap();
+
+        assert_eq2!(
+            debouncer.take_completions(),
+            Some(vec![CompletionItem::from("abc")])
+        );
+        assert_eq2!(call_count.load(Ordering::SeqCst), 1);
+        assert_eq2!(debouncer.take_completions(), None);
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let provider: Arc<dyn CompletionProvider> = Arc::new(EchoCompletionProvider {
+            call_count: call_count.clone(),
+        });
+        let debouncer = CompletionDebouncer::new(Duration::from_millis(10));
+
+        debouncer.request(provider, CompletionQuery::new("a", vec![]), || {});
+        debouncer.cancel();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq2!(debouncer.take_completions(), None);
+        assert_eq2!(call_count.load(Ordering::SeqCst), 0);
+    }
+}
+
+#[cfg(test)]
+mod test_editor_buffer_completion {
+    use r3bl_rs_utils_core::*;
+
+    use crate::{test_dialog::mock_real_objects_for_dialog, *};
+
+    fn make_editor_buffer(line: &str, caret_col_index: usize) -> EditorBuffer {
+        let mut editor_buffer = EditorBuffer::new_empty(None);
+        editor_buffer.set_lines(vec![line.to_string()]);
+        let (_, caret, _, _) = editor_buffer.get_mut();
+        caret.col_index = ch!(caret_col_index);
+        editor_buffer
+    }
+
+    #[test]
+    fn test_get_completion_query() {
+        let editor_buffer = make_editor_buffer("open src/ma now", 11);
+        let query = editor_buffer.get_completion_query();
+        assert_eq2!(query.text, "src/ma");
+        assert_eq2!(query.lines, vec!["open src/ma now".to_string()]);
+
+        let editor_buffer = make_editor_buffer("open ", 5);
+        assert_eq2!(editor_buffer.get_completion_query().text, "");
+    }
+
+    #[test]
+    fn test_insert_completion() {
+        let mut editor_buffer = make_editor_buffer("open src/ma now", 11);
+
+        editor_buffer.insert_completion("src/main.rs");
+        assert_eq2!(editor_buffer.get_as_string(), "open src/main.rs now");
+        assert_eq2!(
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 16, row_index: 0)
+        );
+
+        // Inserting a completion is a single undo step.
+        history::undo(&mut editor_buffer);
+        assert_eq2!(editor_buffer.get_as_string(), "open src/ma now");
+    }
+
+    #[test]
+    fn test_accept_completion_from_dialog() {
+        let mut editor_buffer = make_editor_buffer("open src/ma now", 11);
+
+        // Show the autocomplete dialog for the editor buffer.
+        let self_id = FlexBoxId::from(0);
+        let state = &mut mock_real_objects_for_dialog::create_state();
+        let dialog_buffer = state.get_mut_dialog_buffer(self_id).unwrap();
+        dialog_buffer.set_completion_origin(&editor_buffer);
+        dialog_buffer.maybe_results = Some(vec![
+            CompletionItem::from("src/main.rs"),
+            CompletionItem::from("src/mod.rs"),
+        ]);
+        let dialog_engine = &mut DialogEngine {
+            dialog_options: DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalAutocomplete,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_dialog::make_dialog_engine()
+        };
+
+        // Pick the 2nd result.
+        for special_key in [SpecialKey::Down, SpecialKey::Enter] {
+            let input_event = InputEvent::Keyboard(keypress!(@special special_key));
+            let response = DialogEngineApi::apply_event::<_, ()>(
+                state,
+                self_id,
+                dialog_engine,
+                input_event,
+            )
+            .unwrap();
+            if let DialogEngineApplyResponse::DialogChoice(DialogChoice::Yes(text)) =
+                response
+            {
+                editor_buffer.insert_completion(&text);
+            }
+        }
+
+        assert_eq2!(editor_buffer.get_as_string(), "open src/mod.rs now");
+    }
+
+    #[test]
+    fn test_dialog_buffer_set_completion_origin() {
+        let editor_buffer = make_editor_buffer("open src/ma now", 11);
+        let mut dialog_buffer = DialogBuffer::new_empty();
+
+        dialog_buffer.set_completion_origin(&editor_buffer);
+
+        assert_eq2!(dialog_buffer.editor_buffer.get_as_string(), "src/ma");
+        assert_eq2!(
+            dialog_buffer.editor_buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 6, row_index: 0)
+        );
+        assert_eq2!(
+            dialog_buffer.completion_origin_lines,
+            vec!["open src/ma now".to_string()]
+        );
+    }
+}
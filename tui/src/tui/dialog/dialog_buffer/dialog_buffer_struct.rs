@@ -18,7 +18,7 @@
 use std::fmt::{Debug, Formatter, Result};
 
 use get_size::GetSize;
-use r3bl_rs_utils_core::{ch, ChUnit};
+use r3bl_rs_utils_core::{ch, ChUnit, UnicodeString};
 use serde::*;
 
 use crate::*;
@@ -32,7 +32,11 @@ use crate::*;
 pub struct DialogBuffer {
     pub editor_buffer: EditorBuffer,
     pub title: String,
-    pub maybe_results: Option<Vec<String>>,
+    pub maybe_results: Option<Vec<CompletionItem>>,
+    /// The lines of the [EditorBuffer] that is being completed (if any), which are passed
+    /// to the [CompletionProvider] of the autocomplete dialog. Use
+    /// [set_completion_origin](DialogBuffer::set_completion_origin) to set this.
+    pub completion_origin_lines: Vec<String>,
 }
 
 impl DialogBuffer {
@@ -53,8 +57,28 @@ impl DialogBuffer {
             )),
             title: Default::default(),
             maybe_results: None,
+            completion_origin_lines: vec![],
         }
     }
+
+    /// Use this before showing the autocomplete dialog for `origin`. It puts the text that
+    /// is being completed (see [EditorBuffer::get_completion_query]) into the dialog's
+    /// editor, and saves the lines of `origin` for the [CompletionProvider].
+    ///
+    /// Once the user accepts a result, the [DialogChoice::Yes] contains its
+    /// [CompletionItem::insert_text], which can be passed to
+    /// [EditorBuffer::insert_completion] for `origin`.
+    pub fn set_completion_origin(&mut self, origin: &EditorBuffer) {
+        let CompletionQuery { text, lines } = origin.get_completion_query();
+        let text_width = UnicodeString::from(text.as_str()).display_width;
+
+        self.editor_buffer.set_lines(vec![text]);
+        let (_, caret, _, _) = self.editor_buffer.get_mut();
+        caret.col_index = text_width;
+
+        self.completion_origin_lines = lines;
+        self.maybe_results = None;
+    }
 }
 
 impl Debug for DialogBuffer {
@@ -51,7 +51,7 @@ where
 impl<'a, S, A> Component<S, A> for DialogComponent<S, A>
 where
     S: Debug + Default + Clone + Sync + Send + HasDialogBuffers,
-    A: Debug + Default + Clone + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send + 'static,
 {
     fn reset(&mut self) { self.data.dialog_engine.reset(); }
 
@@ -122,7 +122,11 @@ where
         has_focus: &mut HasFocus,
     ) -> CommonResult<EventPropagation> {
         // Unpack the global data.
-        let GlobalData { state, .. } = global_data;
+        let GlobalData {
+            state,
+            main_thread_channel_sender,
+            ..
+        } = global_data;
 
         let DialogComponentData {
             id,
@@ -164,6 +168,16 @@ where
 
                     // Handler user input that has updated the dialog_buffer.editor_buffer.
                     DialogEngineApplyResponse::UpdateEditorBuffer => {
+                        // Ask the completion provider (if any) for new results.
+                        if let Some(dialog_buffer) = state.get_mut_dialog_buffer(id) {
+                            DialogEngineApi::request_completions(
+                                dialog_buffer,
+                                id,
+                                dialog_engine,
+                                main_thread_channel_sender.clone(),
+                            );
+                        }
+
                         // Run the handler (if any) w/ `new_editor_buffer`.
                         if let Some(it) = &on_dialog_editor_change_handler {
                             it(state);
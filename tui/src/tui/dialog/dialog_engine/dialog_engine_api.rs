@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 
 use r3bl_rs_utils_core::*;
+use tokio::sync::mpsc::Sender;
 
 use crate::*;
 
@@ -51,6 +52,14 @@ impl DialogEngineApi {
         // Unpack global data.
         let GlobalData { state, .. } = global_data;
 
+        // Show the completions from the provider (if they are ready).
+        if let Some(completions) = dialog_engine.completion_debouncer.take_completions() {
+            if let Some(dialog_buffer) = state.get_mut_dialog_buffer(self_id) {
+                dialog_buffer.maybe_results = Some(completions);
+                dialog_engine.reset();
+            }
+        }
+
         let mode = dialog_engine.dialog_options.mode;
         let overlay_flex_box: PartialFlexBox = {
             let window_size = global_data.window_size;
@@ -165,6 +174,7 @@ impl DialogEngineApi {
             dialog_engine,
         ) {
             dialog_engine.reset();
+            dialog_engine.completion_debouncer.cancel();
             return Ok(DialogEngineApplyResponse::DialogChoice(choice));
         }
 
@@ -204,6 +214,47 @@ impl DialogEngineApi {
             }
         }
     }
+
+    /// In [DialogEngineMode::ModalAutocomplete] mode, ask the
+    /// [DialogEngine::maybe_completion_provider] (if any) for completions of the text in
+    /// the dialog's editor. This is debounced, so it can be called on every keystroke.
+    /// Once the completions are ready, a render is requested, and
+    /// [render_engine](DialogEngineApi::render_engine) puts them in
+    /// [DialogBuffer::maybe_results].
+    pub fn request_completions<A>(
+        dialog_buffer: &DialogBuffer,
+        self_id: FlexBoxId,
+        dialog_engine: &DialogEngine,
+        main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<A>>,
+    ) where
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        if !matches!(
+            dialog_engine.dialog_options.mode,
+            DialogEngineMode::ModalAutocomplete
+        ) {
+            return;
+        }
+
+        let Some(provider) = dialog_engine.maybe_completion_provider.clone() else {
+            return;
+        };
+
+        let query = CompletionQuery::new(
+            dialog_buffer.editor_buffer.get_as_string(),
+            dialog_buffer.completion_origin_lines.clone(),
+        );
+
+        dialog_engine
+            .completion_debouncer
+            .request(provider, query, move || {
+                tokio::spawn(async move {
+                    let _ = main_thread_channel_sender
+                        .send(TerminalWindowMainThreadSignal::Render(Some(self_id)))
+                        .await;
+                });
+            });
+    }
 }
 
 #[repr(u16)]
@@ -452,7 +503,7 @@ mod internal_impl {
             ops: &mut RenderOps,
             origin_pos: &Position,
             bounds_size: &Size,
-            results: &[String],
+            results: &[CompletionItem],
             dialog_engine: &DialogEngine,
         ) {
             let col_start_index = ch!(1);
@@ -476,8 +527,11 @@ mod internal_impl {
 
                 rel_insertion_pos.add_row(1);
 
-                let text = UnicodeString::from(item.as_str());
                 let max_display_col_count = bounds_size.col_count - 2;
+                let text = UnicodeString::from(get_result_display_text(
+                    item,
+                    max_display_col_count,
+                ));
                 let clipped_text = if text.display_width > max_display_col_count {
                     let snip_len = ch!(2); /* `..` */
                     let postfix_len = ch!(5); /* last 5 characters */
@@ -551,6 +605,30 @@ mod internal_impl {
         }
     }
 
+    /// The label of the `item`, w/ its detail (if any) right aligned if there is room.
+    fn get_result_display_text(
+        item: &CompletionItem,
+        max_display_col_count: ChUnit,
+    ) -> String {
+        let Some(detail) = &item.detail else {
+            return item.label.clone();
+        };
+
+        let label_width = UnicodeString::from(item.label.as_str()).display_width;
+        let detail_width = UnicodeString::from(detail.as_str()).display_width;
+        let min_gap_width = ch!(2);
+        if label_width + min_gap_width + detail_width > max_display_col_count {
+            return item.label.clone();
+        }
+
+        let gap_width = max_display_col_count - label_width - detail_width;
+        format!(
+            "{}{}{detail}",
+            item.label,
+            SPACER.repeat(ch!(@to_usize gap_width))
+        )
+    }
+
     pub fn render_title(
         origin_pos: &Position,
         bounds_size: &Size,
@@ -616,6 +694,10 @@ mod internal_impl {
     ) -> RenderOps {
         let mut ops = render_ops!();
         let inner_spaces = SPACER.repeat(ch!(@to_usize bounds_size.col_count - 2));
+        let glyphs = dialog_engine.dialog_options.border_style.get_glyphs();
+        let inner_line = glyphs
+            .horizontal
+            .repeat(ch!(@to_usize bounds_size.col_count - 2));
         let maybe_style = dialog_engine.dialog_options.maybe_style_border;
 
         for row_idx in 0..*bounds_size.row_count {
@@ -631,14 +713,8 @@ mod internal_impl {
             match (is_first_line, is_last_line) {
                 // First line.
                 (true, false) => {
-                    let text_content = format!(
-                        "{}{}{}",
-                        BorderGlyphCharacter::TopLeft.as_ref(),
-                        BorderGlyphCharacter::Horizontal
-                            .as_ref()
-                            .repeat(ch!(@to_usize bounds_size.col_count - 2)),
-                        BorderGlyphCharacter::TopRight.as_ref()
-                    );
+                    let text_content =
+                        format!("{}{}{}", glyphs.top_left, inner_line, glyphs.top_right);
 
                     // Apply lolcat override (if enabled) to the fg_color of text_content.
                     lolcat_from_style(
@@ -651,12 +727,8 @@ mod internal_impl {
 
                 // Middle line.
                 (false, false) => {
-                    let text_content = format!(
-                        "{}{}{}",
-                        BorderGlyphCharacter::Vertical.as_ref(),
-                        inner_spaces,
-                        BorderGlyphCharacter::Vertical.as_ref()
-                    );
+                    let text_content =
+                        format!("{}{}{}", glyphs.vertical, inner_spaces, glyphs.vertical);
                     // Apply lolcat override (if enabled) to the fg_color of text_content.
                     lolcat_from_style(
                         &mut ops,
@@ -671,11 +743,7 @@ mod internal_impl {
                     // Paint bottom border.
                     let text_content = format!(
                         "{}{}{}",
-                        BorderGlyphCharacter::BottomLeft.as_ref(),
-                        BorderGlyphCharacter::Horizontal
-                            .as_ref()
-                            .repeat(ch!(@to_usize bounds_size.col_count - 2)),
-                        BorderGlyphCharacter::BottomRight.as_ref(),
+                        glyphs.bottom_left, inner_line, glyphs.bottom_right
                     );
                     // Apply lolcat override (if enabled) to the fg_color of text_content.
                     lolcat_from_style(
@@ -692,16 +760,9 @@ mod internal_impl {
             match dialog_engine.dialog_options.mode {
                 DialogEngineMode::ModalSimple => {}
                 DialogEngineMode::ModalAutocomplete => {
-                    let inner_line = BorderGlyphCharacter::Horizontal
-                        .as_ref()
-                        .repeat(ch!(@to_usize bounds_size.col_count - 2))
-                        .to_string();
-
                     let text_content = format!(
                         "{}{}{}",
-                        BorderGlyphCharacter::LineUpDownRight.as_ref(),
-                        inner_line,
-                        BorderGlyphCharacter::LineUpDownLeft.as_ref()
+                        glyphs.left_junction, inner_line, glyphs.right_junction
                     );
 
                     let col_start_index = ch!(0);
@@ -755,7 +816,9 @@ mod internal_impl {
                     let selected_index = ch!(@to_usize dialog_engine.selected_row_index);
                     if let Some(results) = &dialog_buffer.maybe_results {
                         if let Some(selected_result) = results.get(selected_index) {
-                            return Some(DialogChoice::Yes(selected_result.clone()));
+                            return Some(DialogChoice::Yes(
+                                selected_result.insert_text.clone(),
+                            ));
                         }
                     }
                     return Some(DialogChoice::No);
@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use r3bl_rs_utils_core::*;
 use serde::*;
@@ -57,6 +57,15 @@ pub struct DialogEngine {
     pub maybe_surface_bounds: Option<SurfaceBounds>,
     pub selected_row_index: ChUnit,
     pub scroll_offset_row_index: ChUnit,
+    /// In [DialogEngineMode::ModalAutocomplete] mode, this fills the results panel (ie,
+    /// [DialogBuffer::maybe_results]) as the user types. If [None], the app has to fill it
+    /// instead, eg: in its [OnDialogEditorChangeFn].
+    #[serde(skip)]
+    pub maybe_completion_provider: Option<Arc<dyn CompletionProvider>>,
+    /// Makes sure that [DialogEngine::maybe_completion_provider] isn't called on every
+    /// keystroke.
+    #[serde(skip)]
+    pub completion_debouncer: CompletionDebouncer,
 }
 
 impl DialogEngine {
@@ -111,6 +120,8 @@ pub struct DialogEngineConfigOptions {
     pub mode: DialogEngineMode,
     /// Max height of the results panel.
     pub result_panel_display_row_count: ChUnit,
+    /// The characters that are used to paint the border of the dialog.
+    pub border_style: BorderStyle,
     pub maybe_style_border: Option<Style>,
     pub maybe_style_title: Option<Style>,
     pub maybe_style_editor: Option<Style>,
@@ -127,6 +138,7 @@ mod dialog_engine_config_options_impl {
                 result_panel_display_row_count: ch!(
                     DisplayConstants::DefaultResultsPanelRowCount as u16
                 ),
+                border_style: BorderStyle::Rounded,
                 maybe_style_border: None,
                 maybe_style_editor: None,
                 maybe_style_title: None,
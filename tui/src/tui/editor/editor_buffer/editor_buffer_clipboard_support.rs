@@ -15,18 +15,206 @@
  *   limitations under the License.
  */
 
-use std::error::Error;
+use std::{env,
+          fmt::Debug,
+          io::{stdout, Write},
+          sync::{Arc, Mutex}};
 
-use clipboard_provider::*;
 use crossterm::style::Stylize;
-use r3bl_rs_utils_core::{call_if_true, ch, log_debug, UnicodeString};
+use r3bl_rs_utils_core::*;
 
 use crate::*;
 
+/// Where the text that is copied (or cut) from the editor goes, and where the text that
+/// is pasted into it comes from. Please take a look at [SharedClipboard] for how it is
+/// used by the [EditorEngine].
+///
+/// Implementations:
+/// - [SystemClipboard] uses the clipboard of the OS (falling back to [InMemoryClipboard]
+///   if it isn't available, eg: on a headless Linux box w/out X11 or Wayland).
+/// - [Osc52Clipboard] asks the terminal to set its clipboard, which works over SSH.
+/// - [InMemoryClipboard] only lives as long as the process, which is useful for tests.
+pub trait ClipboardService: Debug + Send {
+    fn try_to_put_content_into_clipboard(&mut self, content: &str) -> CommonResult<()>;
+
+    fn try_to_get_content_from_clipboard(&mut self) -> CommonResult<String>;
+}
+
+/// In-process clipboard, which isn't shared w/ other apps.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InMemoryClipboard {
+    maybe_content: Option<String>,
+}
+
+impl ClipboardService for InMemoryClipboard {
+    fn try_to_put_content_into_clipboard(&mut self, content: &str) -> CommonResult<()> {
+        self.maybe_content = Some(content.to_string());
+        Ok(())
+    }
+
+    fn try_to_get_content_from_clipboard(&mut self) -> CommonResult<String> {
+        match &self.maybe_content {
+            Some(content) => Ok(content.clone()),
+            None => CommonError::new(CommonErrorType::NotFound, "The clipboard is empty"),
+        }
+    }
+}
+
+/// The clipboard of the OS. If it can't be used, then the [InMemoryClipboard] is used
+/// instead, so that copy & paste still work inside of the app.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SystemClipboard {
+    fallback: InMemoryClipboard,
+}
+
+impl ClipboardService for SystemClipboard {
+    fn try_to_put_content_into_clipboard(&mut self, content: &str) -> CommonResult<()> {
+        if let Err(error) = clipboard_provider::try_to_put_content_into_clipboard(content)
+        {
+            log_clipboard_error("copy to the system clipboard", &error);
+            return self.fallback.try_to_put_content_into_clipboard(content);
+        }
+        Ok(())
+    }
+
+    fn try_to_get_content_from_clipboard(&mut self) -> CommonResult<String> {
+        match clipboard_provider::try_to_get_content_from_clipboard() {
+            Ok(content) => Ok(content),
+            Err(error) => {
+                log_clipboard_error("paste from the system clipboard", &error);
+                self.fallback.try_to_get_content_from_clipboard()
+            }
+        }
+    }
+}
+
+/// Sets the clipboard of the terminal using the [OSC
+/// 52](https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h3-Operating-System-Commands)
+/// escape sequence, which also works when the app is running on a remote machine (over
+/// SSH). Most terminals don't allow apps to read their clipboard, so pasting uses the
+/// [InMemoryClipboard] (and the terminal's own paste, which is bracketed paste, still
+/// works as usual).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Osc52Clipboard {
+    in_memory: InMemoryClipboard,
+}
+
+impl Osc52Clipboard {
+    /// Returns the escape sequence that sets the terminal's clipboard to `content`.
+    pub fn make_escape_sequence(content: &str) -> String {
+        format!("\x1b]52;c;{}\x07", encode_base64(content.as_bytes()))
+    }
+}
+
+impl ClipboardService for Osc52Clipboard {
+    fn try_to_put_content_into_clipboard(&mut self, content: &str) -> CommonResult<()> {
+        self.in_memory.try_to_put_content_into_clipboard(content)?;
+        let mut stdout = stdout();
+        stdout.write_all(Self::make_escape_sequence(content).as_bytes())?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn try_to_get_content_from_clipboard(&mut self) -> CommonResult<String> {
+        self.in_memory.try_to_get_content_from_clipboard()
+    }
+}
+
+/// A [ClipboardService] that can be shared. Cloning this shares the same clipboard, eg:
+/// all the [EditorEngine]s of an app can use the same one.
+///
+/// The default is the [Osc52Clipboard] in SSH sessions, and the [SystemClipboard]
+/// otherwise.
+#[derive(Clone, Debug)]
+pub struct SharedClipboard(Arc<Mutex<dyn ClipboardService>>);
+
+impl Default for SharedClipboard {
+    fn default() -> Self {
+        if is_ssh_session() {
+            Self::new(Osc52Clipboard::default())
+        } else {
+            Self::new(SystemClipboard::default())
+        }
+    }
+}
+
+impl SharedClipboard {
+    pub fn new(clipboard_service: impl ClipboardService + 'static) -> Self {
+        Self(Arc::new(Mutex::new(clipboard_service)))
+    }
+
+    pub fn try_to_put_content_into_clipboard(&self, content: &str) -> CommonResult<()> {
+        match self.0.lock() {
+            Ok(mut clipboard_service) => {
+                clipboard_service.try_to_put_content_into_clipboard(content)
+            }
+            Err(_) => CommonError::new(
+                CommonErrorType::InvalidState,
+                "The clipboard lock is poisoned",
+            ),
+        }
+    }
+
+    pub fn try_to_get_content_from_clipboard(&self) -> CommonResult<String> {
+        match self.0.lock() {
+            Ok(mut clipboard_service) => {
+                clipboard_service.try_to_get_content_from_clipboard()
+            }
+            Err(_) => CommonError::new(
+                CommonErrorType::InvalidState,
+                "The clipboard lock is poisoned",
+            ),
+        }
+    }
+}
+
+fn is_ssh_session() -> bool {
+    env::var_os("SSH_CONNECTION").is_some() || env::var_os("SSH_TTY").is_some()
+}
+
+fn log_clipboard_error(what: &str, error: &impl std::fmt::Display) {
+    call_if_true!(DEBUG_TUI_COPY_PASTE, {
+        log_debug(
+            format!(
+                "\n📋📋📋 Failed to {0}: {1}",
+                /* 0 */
+                what,
+                /* 1 */
+                format!("{error}").white(),
+            )
+            .on_dark_red()
+            .to_string(),
+        )
+    });
+}
+
+/// Standard base64 (w/ padding), which is what [OSC 52](Osc52Clipboard) expects.
+fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut it = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = chunk.iter().enumerate().fold(0_u32, |acc, (index, byte)| {
+            acc | (*byte as u32) << (16 - index * 8)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (triple >> (18 - index * 6)) & 0b11_1111;
+                it.push(ALPHABET[sextet as usize] as char);
+            } else {
+                it.push('=');
+            }
+        }
+    }
+    it
+}
+
 pub mod clipboard_support {
     use super::*;
 
-    pub fn copy(buffer: &EditorBuffer) {
+    /// Returns the selected text (the selected parts of the lines are joined w/ `\n`).
+    pub fn get_selected_text(buffer: &EditorBuffer) -> String {
         let lines: &Vec<UnicodeString> = buffer.get_lines();
         let selection_map = buffer.get_selection_map();
 
@@ -46,64 +234,27 @@ pub mod clipboard_support {
             }
         }
 
-        if let Err(error) = try_to_put_content_into_clipboard(&vec_str) {
-            call_if_true!(DEBUG_TUI_COPY_PASTE, {
-                log_debug(
-                    format!(
-                        "\n📋📋📋 Failed to copy selected text to clipboard: {0}",
-                        /* 0 */
-                        format!("{error}").white(),
-                    )
-                    .on_dark_red()
-                    .to_string(),
-                )
-            });
-        }
+        vec_str.join("\n")
     }
 
-    pub fn paste(args: EditorArgsMut<'_>) {
-        match try_to_get_content_from_clipboard() {
-            Ok(clipboard_text) => {
-                // If the clipboard text does not contain a new line, then insert the text.
-                if !clipboard_text.contains(&"\n") {
-                    EditorEngineInternalApi::insert_str_at_caret(
-                        EditorArgsMut {
-                            editor_engine: args.editor_engine,
-                            editor_buffer: args.editor_buffer,
-                        },
-                        clipboard_text.as_str(),
-                    );
-                }
-                // If the clipboard text contains a new line, then insert the text line by line.
-                else {
-                    let lines = clipboard_text.split("\n");
-                    let line_count = lines.clone().count();
-                    for (line_index, line) in lines.enumerate() {
-                        EditorEngineInternalApi::insert_str_at_caret(
-                            EditorArgsMut {
-                                editor_engine: args.editor_engine,
-                                editor_buffer: args.editor_buffer,
-                            },
-                            line,
-                        );
-                        // This is not the last line, so insert a new line.
-                        if line_index < line_count - 1 {
-                            EditorEngineInternalApi::insert_new_line_at_caret(
-                                EditorArgsMut {
-                                    editor_engine: args.editor_engine,
-                                    editor_buffer: args.editor_buffer,
-                                },
-                            );
-                        }
-                    }
-                }
+    /// Copy the selected text to the clipboard, and remember it in the [KillRing] of the
+    /// buffer.
+    pub fn copy(buffer: &mut EditorBuffer, clipboard: &SharedClipboard) {
+        if !buffer.has_selection() {
+            return;
+        }
 
+        let content = get_selected_text(buffer);
+        buffer.kill_ring.push(&content);
+
+        match clipboard.try_to_put_content_into_clipboard(&content) {
+            Ok(_) => {
                 call_if_true!(DEBUG_TUI_COPY_PASTE, {
                     log_debug(
                         format!(
-                            "\n📋📋📋 Text was pasted from clipboard: \n{0}",
+                            "\n📋📋📋 Selected Text was copied to clipboard: \n{0}",
                             /* 0 */
-                            clipboard_text.clone().dark_red()
+                            content.dark_red()
                         )
                         .black()
                         .on_green()
@@ -111,60 +262,153 @@ pub mod clipboard_support {
                     )
                 });
             }
+            Err(error) => log_clipboard_error("copy selected text to clipboard", &error),
+        }
+    }
+
+    pub fn paste(args: EditorArgsMut<'_>) {
+        match args
+            .editor_engine
+            .clipboard
+            .try_to_get_content_from_clipboard()
+        {
+            Ok(clipboard_text) => {
+                paste_text_at_caret(args, &clipboard_text);
 
-            Err(error) => {
                 call_if_true!(DEBUG_TUI_COPY_PASTE, {
                     log_debug(
                         format!(
-                            "\n📋📋📋 Failed to paste the text from clipboard: {0}",
+                            "\n📋📋📋 Text was pasted from clipboard: \n{0}",
                             /* 0 */
-                            format!("{error}").white(),
+                            clipboard_text.clone().dark_red()
                         )
-                        .on_dark_red()
+                        .black()
+                        .on_green()
                         .to_string(),
                     )
                 });
             }
+
+            Err(error) => log_clipboard_error("paste the text from clipboard", &error),
         }
     }
+
+    /// Paste the entry of the [KillRing] at `index` (the most recent one is at `0`)
+    /// instead of the content of the clipboard. Nothing happens if there is no such
+    /// entry.
+    pub fn paste_from_kill_ring(args: EditorArgsMut<'_>, index: usize) {
+        let Some(text) = args
+            .editor_buffer
+            .kill_ring
+            .get_entries()
+            .get(index)
+            .cloned()
+        else {
+            return;
+        };
+        paste_text_at_caret(args, &text);
+    }
+
+    fn paste_text_at_caret(args: EditorArgsMut<'_>, text: &str) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        // Just like typing enter, new lines are ignored in single line mode.
+        let text = match editor_engine.config_options.multiline_mode {
+            LineMode::MultiLine => text.to_string(),
+            LineMode::SingleLine => text.replace(['\r', '\n'], ""),
+        };
+        if let Some(caret_adj) = insert_text_at_caret(editor_buffer, &text) {
+            EditorEngineInternalApi::move_caret_to(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                caret_adj,
+            );
+        }
+    }
+
+    /// Insert `text` (which can have multiple lines) at the caret, as is (eg: w/out auto
+    /// indent). Returns where the caret should go (the end of the inserted text), which is
+    /// a scroll adjusted position. The caret itself is not moved.
+    pub fn insert_text_at_caret(
+        buffer: &mut EditorBuffer,
+        text: &str,
+    ) -> Option<Position> {
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_index = ch!(@to_usize caret_adj.row_index);
+        let line = buffer.get_lines().get(row_index)?;
+
+        let caret_byte_index = line.clip_to_width(ch!(0), caret_adj.col_index).len();
+        let (text_before_caret, text_after_caret) =
+            line.string.split_at(caret_byte_index);
+
+        let mut new_lines: Vec<String> = text
+            .split('\n')
+            .map(|it| it.strip_suffix('\r').unwrap_or(it).to_string())
+            .collect();
+        let last_index = new_lines.len() - 1;
+        let new_caret_col_index = if last_index == 0 {
+            caret_adj.col_index + UnicodeString::from(new_lines[0].as_str()).display_width
+        } else {
+            UnicodeString::from(new_lines[last_index].as_str()).display_width
+        };
+        new_lines[0] = format!("{text_before_caret}{}", new_lines[0]);
+        new_lines[last_index].push_str(text_after_caret);
+
+        let (lines, _, _, _) = buffer.get_mut();
+        lines.splice(
+            row_index..=row_index,
+            new_lines.into_iter().map(UnicodeString::from),
+        );
+
+        Some(position!(
+            col_index: new_caret_col_index,
+            row_index: row_index + last_index
+        ))
+    }
+}
+
+impl EditorBuffer {
+    /// Insert `text` (which can have multiple lines) at the caret, and move the caret to
+    /// the end of it. Unlike [EditorEvent::Paste], this doesn't need an [EditorEngine], so
+    /// it can be used in an [OnDialogPressFn], eg: w/ the entry of the [KillRing] that the
+    /// user picked. This is a single undo step.
+    ///
+    /// The viewport is scrolled to the caret when the editor is rendered next.
+    pub fn paste_text(&mut self, text: &str) {
+        // Record the selection before the change (so that undo can restore it).
+        history::push(self);
+
+        self.clear_selection();
+        if let Some(caret_adj) = clipboard_support::insert_text_at_caret(self, text) {
+            self.set_caret_scroll_adjusted(caret_adj);
+        }
+
+        history::push(self);
+    }
 }
 
 /// <https://docs.rs/copypasta-ext/latest/copypasta_ext/>
 mod clipboard_provider {
     use copypasta_ext::{prelude::*, x11_fork::ClipboardContext};
-    use r3bl_rs_utils_core::throws;
 
     use super::*;
 
-    type ClipboardResult<T> = Result<T, Box<dyn Error + Send + Sync + 'static>>;
-
     /// Wrap the call to the clipboard crate, so it returns a [Result]. This is to avoid
     /// calling `unwrap()` on the [ClipboardContext] object.
-    pub fn try_to_put_content_into_clipboard(vec_str: &[&str]) -> ClipboardResult<()> {
-        throws!({
-            let content = vec_str.join("\n");
-
-            let mut ctx = ClipboardContext::new()?;
-            ctx.set_contents(content.clone())?;
-
-            call_if_true!(DEBUG_TUI_COPY_PASTE, {
-                log_debug(
-                    format!(
-                        "\n📋📋📋 Selected Text was copied to clipboard: \n{0}",
-                        /* 0 */
-                        content.dark_red()
-                    )
-                    .black()
-                    .on_green()
-                    .to_string(),
-                )
-            });
-        });
+    pub fn try_to_put_content_into_clipboard(content: &str) -> CommonResult<()> {
+        let mut ctx = ClipboardContext::new()?;
+        ctx.set_contents(content.to_string())?;
+        Ok(())
     }
 
     /// Wrap the call to the clipboard crate, so it returns a [Result]. This is to avoid
     /// calling `unwrap()` on the [ClipboardContext] object.
-    pub fn try_to_get_content_from_clipboard() -> ClipboardResult<String> {
+    pub fn try_to_get_content_from_clipboard() -> CommonResult<String> {
         let mut ctx = ClipboardContext::new()?;
         let content = ctx.get_contents()?;
 
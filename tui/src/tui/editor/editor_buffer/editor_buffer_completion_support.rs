@@ -0,0 +1,108 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Support for completing the text before the caret w/ a [CompletionProvider].
+///
+/// The text that is completed is the text before the caret, back to the previous
+/// whitespace (or the start of the line). Accepting a [CompletionItem] replaces that text
+/// w/ its `insert_text`.
+///
+/// ```text
+///   Line before            Query       Insert text        Line after
+///   ───────────────────    ─────────   ────────────────   ─────────────────────────
+///   "open src/ma▴ now"  →  "src/ma"  + "src/main.rs"    → "open src/main.rs▴ now"
+/// ```
+impl EditorBuffer {
+    /// Returns the [CompletionQuery] for the text before the caret, w/ all the lines of
+    /// this buffer.
+    pub fn get_completion_query(&self) -> CompletionQuery {
+        let lines = self
+            .get_lines()
+            .iter()
+            .map(|it| it.string.clone())
+            .collect();
+        let text = match self.get_text_before_caret() {
+            Some(text_before_caret) => get_trailing_non_whitespace(text_before_caret),
+            None => "",
+        };
+        CompletionQuery::new(text, lines)
+    }
+
+    /// Replace the text that [get_completion_query](EditorBuffer::get_completion_query)
+    /// returns w/ `insert_text` (which is a single line), and move the caret to the end
+    /// of it. This is a single undo step.
+    ///
+    /// The viewport is scrolled to the caret when the editor is rendered next.
+    pub fn insert_completion(&mut self, insert_text: &str) {
+        let Some(text_before_caret) = self.get_text_before_caret() else {
+            return;
+        };
+        let query_text = get_trailing_non_whitespace(text_before_caret);
+        let caret_byte_index = text_before_caret.len();
+        let query_start_byte_index = caret_byte_index - query_text.len();
+        let query_start_col_index =
+            UnicodeString::from(&text_before_caret[..query_start_byte_index])
+                .display_width;
+        let new_caret_col_index =
+            query_start_col_index + UnicodeString::from(insert_text).display_width;
+
+        // Record the selection before the change (so that undo can restore it).
+        history::push(self);
+
+        let row_index = self.get_caret(CaretKind::ScrollAdjusted).row_index;
+        let (lines, _, _, _) = self.get_mut();
+        let line = &lines[ch!(@to_usize row_index)].string;
+        let new_line = format!(
+            "{}{insert_text}{}",
+            &line[..query_start_byte_index],
+            &line[caret_byte_index..]
+        );
+        lines[ch!(@to_usize row_index)] = UnicodeString::from(new_line);
+
+        self.set_caret_scroll_adjusted(position!(
+            col_index: new_caret_col_index,
+            row_index: row_index
+        ));
+
+        self.clear_selection();
+        history::push(self);
+    }
+
+    fn get_text_before_caret(&self) -> Option<&str> {
+        let caret = self.get_caret(CaretKind::ScrollAdjusted);
+        let line = self.get_lines().get(ch!(@to_usize caret.row_index))?;
+        Some(line.clip_to_width(ch!(0), caret.col_index))
+    }
+}
+
+fn get_trailing_non_whitespace(text: &str) -> &str {
+    match text.rfind(char::is_whitespace) {
+        Some(whitespace_index) => {
+            let whitespace_len = text[whitespace_index..]
+                .chars()
+                .next()
+                .map(char::len_utf8)
+                .unwrap_or(0);
+            &text[whitespace_index + whitespace_len..]
+        }
+        None => text,
+    }
+}
@@ -0,0 +1,399 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fs,
+          io::Write,
+          path::{Path, PathBuf}};
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::*;
+
+use crate::*;
+
+/// The file that an [EditorBuffer] was loaded from (using
+/// [open_file](EditorBuffer::open_file)) or saved to, along w/ the properties of its
+/// content that are preserved when it is saved.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+pub struct EditorBufferFileInfo {
+    pub file_path: String,
+    pub file_format: FileFormat,
+}
+
+/// The properties of the content of a file that are not part of the lines of an
+/// [EditorBuffer].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize, Default)]
+pub struct FileFormat {
+    pub line_ending: LineEnding,
+    /// Whether the last line ends w/ a line ending.
+    pub has_trailing_newline: bool,
+    /// Whether the file starts w/ the UTF-8 byte order mark.
+    pub has_bom: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize, Default)]
+pub enum LineEnding {
+    /// `\n`.
+    #[default]
+    Lf,
+    /// `\r\n`.
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Load and save the lines of an [EditorBuffer] from and to files.
+///
+/// 1. The content of a file is decoded (using [decode](file_support::decode)) into lines
+///    and a [FileFormat], which is used to encode (using
+///    [encode](file_support::encode)) the lines when the buffer is saved. This way the
+///    line endings, trailing newline, and byte order mark of the file are preserved.
+/// 2. Saving is atomic. The content is written to a temp file in the same folder, which is
+///    then renamed to the file, so the file is never left half written.
+/// 3. The buffer is dirty when its content has changed since it was loaded or saved. This
+///    is tied to the position in the undo / redo history, so undoing back to the saved
+///    version makes the buffer clean again. Please take a look at [history::is_dirty].
+pub mod file_support {
+    use super::*;
+
+    const UTF8_BOM: &str = "\u{feff}";
+
+    /// Split the content of a file into lines. Files that are not valid UTF-8 can't be
+    /// edited, and result in an error.
+    ///
+    /// - The line ending is [LineEnding::CrLf] if most of the lines end w/ `\r\n`.
+    /// - A line ending at the end of the file does not start a new (empty) line.
+    pub fn decode(bytes: &[u8]) -> CommonResult<(Vec<String>, FileFormat)> {
+        let Ok(content) = std::str::from_utf8(bytes) else {
+            return CommonError::new(
+                CommonErrorType::ParsingError,
+                "File content is not valid UTF-8",
+            );
+        };
+
+        let (has_bom, content) = match content.strip_prefix(UTF8_BOM) {
+            Some(rest) => (true, rest),
+            None => (false, content),
+        };
+
+        let (has_trailing_newline, content) = match content.strip_suffix('\n') {
+            Some(rest) => (true, rest),
+            None => (false, content),
+        };
+
+        let lines: Vec<&str> = content.split('\n').collect();
+
+        // The line ending of the last line is part of the trailing newline.
+        let crlf_count = lines
+            .iter()
+            .enumerate()
+            .filter(|(index, line)| {
+                line.ends_with('\r') && (*index + 1 < lines.len() || has_trailing_newline)
+            })
+            .count();
+        let line_ending_count = lines.len() - 1 + usize::from(has_trailing_newline);
+        let line_ending = if crlf_count > 0 && crlf_count * 2 >= line_ending_count {
+            LineEnding::CrLf
+        } else {
+            LineEnding::Lf
+        };
+
+        let last_index = lines.len() - 1;
+        let lines = lines
+            .into_iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let has_line_ending = index < last_index || has_trailing_newline;
+                match line_ending {
+                    LineEnding::CrLf if has_line_ending => {
+                        line.strip_suffix('\r').unwrap_or(line).to_string()
+                    }
+                    _ => line.to_string(),
+                }
+            })
+            .collect();
+
+        Ok((
+            lines,
+            FileFormat {
+                line_ending,
+                has_trailing_newline,
+                has_bom,
+            },
+        ))
+    }
+
+    /// The opposite of [decode].
+    pub fn encode(lines: &[UnicodeString], file_format: FileFormat) -> String {
+        let mut it = String::new();
+        if file_format.has_bom {
+            it.push_str(UTF8_BOM);
+        }
+        for (index, line) in lines.iter().enumerate() {
+            if index > 0 {
+                it.push_str(file_format.line_ending.as_str());
+            }
+            it.push_str(&line.string);
+        }
+        if file_format.has_trailing_newline {
+            it.push_str(file_format.line_ending.as_str());
+        }
+        it
+    }
+
+    /// The extension of the file (eg: `md` or `rs`) is used for syntax highlighting.
+    pub fn get_file_extension(file_path: &Path) -> Option<String> {
+        file_path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_string())
+    }
+
+    /// Write `content` to a temp file (in the same folder as `file_path`, so that both are
+    /// on the same file system), and then rename it to `file_path`. The permissions of the
+    /// existing file (if any) are preserved.
+    pub fn write_atomically(file_path: &Path, content: &str) -> CommonResult<()> {
+        let Some(file_name) = file_path.file_name() else {
+            return CommonError::new(
+                CommonErrorType::InvalidArguments,
+                &format!("Not a file path: {file_path:?}"),
+            );
+        };
+
+        let temp_file_path = file_path.with_file_name(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+
+        let result = write_and_rename(&temp_file_path, file_path, content);
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_file_path);
+        }
+
+        result
+    }
+
+    fn write_and_rename(
+        temp_file_path: &Path,
+        file_path: &Path,
+        content: &str,
+    ) -> CommonResult<()> {
+        let mut temp_file = fs::File::create(temp_file_path)?;
+        temp_file.write_all(content.as_bytes())?;
+        temp_file.sync_all()?;
+        if let Ok(metadata) = fs::metadata(file_path) {
+            fs::set_permissions(temp_file_path, metadata.permissions())?;
+        }
+        fs::rename(temp_file_path, file_path)?;
+        Ok(())
+    }
+}
+
+impl EditorBuffer {
+    /// Load the file at `file_path` into a new buffer (which is clean). The file extension
+    /// is used for syntax highlighting.
+    pub fn open_file(file_path: impl AsRef<Path>) -> CommonResult<Self> {
+        let file_path = file_path.as_ref();
+        let bytes = fs::read(file_path)?;
+        let (lines, file_format) = file_support::decode(&bytes)?;
+
+        let mut it = EditorBuffer::new_empty(file_support::get_file_extension(file_path));
+        it.set_lines(lines);
+        it.maybe_file_info = Some(EditorBufferFileInfo {
+            file_path: file_path.to_string_lossy().to_string(),
+            file_format,
+        });
+
+        // Record the initial version, which is the one that was loaded.
+        history::mark_as_saved(&mut it);
+
+        Ok(it)
+    }
+
+    /// Save the buffer to the file that it was loaded from (or last saved to), preserving
+    /// its [FileFormat]. Returns an error if the buffer isn't associated w/ a file, in which
+    /// case use [save_file_as](EditorBuffer::save_file_as) instead.
+    pub fn save_file(&mut self) -> CommonResult<()> {
+        let Some(file_info) = self.maybe_file_info.as_ref() else {
+            return CommonError::new(
+                CommonErrorType::InvalidState,
+                "EditorBuffer is not associated w/ a file",
+            );
+        };
+        let file_path = PathBuf::from(&file_info.file_path);
+        let file_format = file_info.file_format;
+        self.save_file_as_impl(&file_path, file_format)
+    }
+
+    /// Save the buffer to `file_path`, which it is then associated w/. The [FileFormat] of
+    /// the file that the buffer was loaded from is preserved (new buffers use
+    /// [LineEnding::Lf] and a trailing newline).
+    pub fn save_file_as(&mut self, file_path: impl AsRef<Path>) -> CommonResult<()> {
+        let file_format = match self.maybe_file_info {
+            Some(ref file_info) => file_info.file_format,
+            None => FileFormat {
+                has_trailing_newline: true,
+                ..Default::default()
+            },
+        };
+        self.save_file_as_impl(file_path.as_ref(), file_format)
+    }
+
+    fn save_file_as_impl(
+        &mut self,
+        file_path: &Path,
+        file_format: FileFormat,
+    ) -> CommonResult<()> {
+        let content = file_support::encode(self.get_lines(), file_format);
+        file_support::write_atomically(file_path, &content)?;
+
+        self.maybe_file_info = Some(EditorBufferFileInfo {
+            file_path: file_path.to_string_lossy().to_string(),
+            file_format,
+        });
+        history::mark_as_saved(self);
+
+        Ok(())
+    }
+
+    pub fn get_maybe_file_info(&self) -> Option<&EditorBufferFileInfo> {
+        self.maybe_file_info.as_ref()
+    }
+
+    /// Returns `true` if the buffer has changed since it was loaded or last saved.
+    pub fn is_dirty(&self) -> bool { history::is_dirty(self) }
+}
+
+#[cfg(test)]
+mod file_support_tests {
+    use super::*;
+
+    fn get_lines(editor_buffer: &EditorBuffer) -> Vec<String> {
+        editor_buffer
+            .get_lines()
+            .iter()
+            .map(|it| it.string.clone())
+            .collect()
+    }
+
+    fn make_temp_file_path(file_name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "r3bl_tui_{}_{}",
+            std::process::id(),
+            file_name
+        ))
+    }
+
+    #[test]
+    fn test_decode_and_encode() {
+        let inputs = [
+            ("", vec![""], LineEnding::Lf, false, false),
+            ("a\nb", vec!["a", "b"], LineEnding::Lf, false, false),
+            ("a\nb\n", vec!["a", "b"], LineEnding::Lf, true, false),
+            ("a\n\n", vec!["a", ""], LineEnding::Lf, true, false),
+            ("a\r\nb\r\n", vec!["a", "b"], LineEnding::CrLf, true, false),
+            (
+                "\u{feff}a\r\nb",
+                vec!["a", "b"],
+                LineEnding::CrLf,
+                false,
+                true,
+            ),
+            // A lone `\r` is part of the line.
+            ("a\r", vec!["a\r"], LineEnding::Lf, false, false),
+        ];
+
+        for (content, lines, line_ending, has_trailing_newline, has_bom) in inputs {
+            let (actual_lines, file_format) =
+                file_support::decode(content.as_bytes()).unwrap();
+            assert_eq2!(actual_lines, lines);
+            assert_eq2!(
+                file_format,
+                FileFormat {
+                    line_ending,
+                    has_trailing_newline,
+                    has_bom,
+                }
+            );
+
+            let unicode_lines: Vec<UnicodeString> =
+                lines.iter().map(|it| UnicodeString::from(*it)).collect();
+            assert_eq2!(file_support::encode(&unicode_lines, file_format), content);
+        }
+
+        assert!(file_support::decode(&[0xff, 0xfe, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_open_edit_and_save() {
+        let file_path = make_temp_file_path("test_open_edit_and_save.md");
+        fs::write(&file_path, "\u{feff}# Title\r\nbody\r\n").unwrap();
+
+        let mut editor_buffer = EditorBuffer::open_file(&file_path).unwrap();
+        assert_eq2!(get_lines(&editor_buffer), vec!["# Title", "body"]);
+        assert_eq2!(editor_buffer.get_maybe_file_extension(), Some("md"));
+        assert!(!editor_buffer.is_dirty());
+
+        editor_buffer.set_lines(vec!["# Title".into(), "new body".into()]);
+        assert!(editor_buffer.is_dirty());
+
+        editor_buffer.save_file().unwrap();
+        assert!(!editor_buffer.is_dirty());
+        assert_eq2!(
+            fs::read_to_string(&file_path).unwrap(),
+            "\u{feff}# Title\r\nnew body\r\n"
+        );
+
+        // Save as a new file.
+        let new_file_path = make_temp_file_path("test_open_edit_and_save_new.md");
+        editor_buffer.save_file_as(&new_file_path).unwrap();
+        assert_eq2!(
+            editor_buffer
+                .get_maybe_file_info()
+                .map(|it| it.file_path.clone()),
+            Some(new_file_path.to_string_lossy().to_string())
+        );
+        assert_eq2!(
+            fs::read_to_string(&new_file_path).unwrap(),
+            "\u{feff}# Title\r\nnew body\r\n"
+        );
+
+        fs::remove_file(&file_path).unwrap();
+        fs::remove_file(&new_file_path).unwrap();
+    }
+
+    #[test]
+    fn test_save_without_file() {
+        let mut editor_buffer = EditorBuffer::new_empty(None);
+        assert!(editor_buffer.save_file().is_err());
+
+        let file_path = make_temp_file_path("test_save_without_file.txt");
+        editor_buffer.set_lines(vec!["a".into(), "b".into()]);
+        editor_buffer.save_file_as(&file_path).unwrap();
+        assert_eq2!(fs::read_to_string(&file_path).unwrap(), "a\nb\n");
+        assert!(!editor_buffer.is_dirty());
+
+        fs::remove_file(&file_path).unwrap();
+    }
+}
@@ -0,0 +1,591 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::cmp::Reverse;
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// A secondary caret in an [EditorBuffer], used for multi caret editing.
+///
+/// The primary caret is the one that is stored in the buffer itself (its
+/// `caret_display_position` and `selection_map`), and all the functions in
+/// [EditorEngineInternalApi] work w/ it. In order to apply an [EditorEvent] to every
+/// caret, [multi_caret_support::apply_to_all_carets] temporarily turns each secondary
+/// caret into the primary one.
+///
+/// 1. `position` is [scroll adjusted](CaretKind::ScrollAdjusted), since a secondary caret
+///    can be outside of the viewport.
+/// 2. Just like the primary caret, each secondary caret owns its own [SelectionMap].
+#[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default, Debug)]
+pub struct EditorCaret {
+    pub position: Position,
+    pub selection_map: SelectionMap,
+}
+
+/// What happens to the secondary carets of an [EditorBuffer] when an [EditorEvent] is
+/// applied to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultiCaretBehavior {
+    /// The event is applied at every caret.
+    ApplyToAllCarets,
+    /// The event is only applied at the primary caret.
+    KeepSecondaryCarets,
+    /// The secondary carets are removed, and the event is applied at the primary caret.
+    ClearSecondaryCarets,
+}
+
+pub mod multi_caret_support {
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    struct CaretEntry {
+        caret: EditorCaret,
+        is_primary: bool,
+    }
+
+    /// Run `operation` once for every caret (primary and secondary).
+    ///
+    /// 1. The carets are processed from the bottom-most one to the top-most one, so that
+    ///    the edits made at one caret don't move the carets that are yet to be processed.
+    /// 2. Before running `operation`, the caret is made the primary one. Afterwards, the
+    ///    rows that were changed are diffed, and all the other carets (and their
+    ///    selections) are moved to account for the text that was inserted or removed.
+    /// 3. Once all the carets are processed, the carets that have collided (ended up at
+    ///    the same position) are merged into one. If the primary caret is one of them,
+    ///    then it is kept.
+    /// 4. The viewport is scrolled (if needed) to show the primary caret.
+    pub fn apply_to_all_carets(
+        args: EditorArgsMut<'_>,
+        mut operation: impl FnMut(&mut EditorEngine, &mut EditorBuffer),
+    ) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let scroll_offset_before = editor_buffer.get_scroll_offset();
+
+        let mut carets = vec![CaretEntry {
+            caret: get_primary_caret(editor_buffer),
+            is_primary: true,
+        }];
+        carets.extend(editor_buffer.get_secondary_carets().iter().map(|caret| {
+            CaretEntry {
+                caret: caret.clone(),
+                is_primary: false,
+            }
+        }));
+        editor_buffer.clear_secondary_carets();
+
+        // Bottom-most caret first.
+        carets.sort_by_key(|it| Reverse(position_key(it.caret.position)));
+
+        for index in 0..carets.len() {
+            set_primary_caret(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                &carets[index].caret,
+            );
+            let window = EditWindow::new(editor_buffer, &carets[index].caret);
+
+            operation(editor_engine, editor_buffer);
+
+            carets[index].caret = get_primary_caret(editor_buffer);
+            if let Some(shift) = window.into_caret_shift(editor_buffer) {
+                for (other_index, other) in carets.iter_mut().enumerate() {
+                    if other_index != index {
+                        shift.apply(&mut other.caret);
+                    }
+                }
+            }
+        }
+
+        let (primary_caret, secondary_carets) = merge_collided_carets(carets);
+
+        // Restore the viewport, and make the primary caret the current one again.
+        let (_, _, scroll_offset, _) = editor_buffer.get_mut();
+        *scroll_offset = scroll_offset_before;
+        set_primary_caret(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            &primary_caret,
+        );
+        editor_buffer.set_secondary_carets(secondary_carets);
+    }
+
+    /// Add a caret on the line above the top-most caret ([CaretDirection::Up]), or on the
+    /// line below the bottom-most caret ([CaretDirection::Down]). It is placed in the same
+    /// col, or at the end of the line if it is shorter. The new caret becomes the primary
+    /// caret, so that the viewport follows it.
+    pub fn add_caret_vertically(args: EditorArgsMut<'_>, direction: CaretDirection) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let positions = get_all_carets(editor_buffer)
+            .into_iter()
+            .map(|it| it.position);
+        let maybe_position = match direction {
+            CaretDirection::Up => positions.min_by_key(|it| position_key(*it)),
+            CaretDirection::Down => positions.max_by_key(|it| position_key(*it)),
+            CaretDirection::Left | CaretDirection::Right => None,
+        };
+        let Some(position) = maybe_position else {
+            return;
+        };
+
+        let row_index = ch!(@to_usize position.row_index);
+        let new_row_index = match direction {
+            CaretDirection::Up if row_index > 0 => row_index - 1,
+            CaretDirection::Down if row_index + 1 < editor_buffer.get_lines().len() => {
+                row_index + 1
+            }
+            _ => return,
+        };
+        let Some(line) = editor_buffer.get_lines().get(new_row_index) else {
+            return;
+        };
+
+        // Don't end up in the middle of a wide grapheme cluster.
+        let new_col_index = {
+            let byte_offset =
+                byte_offset_at_display_col(&line.string, position.col_index);
+            display_col_at_byte_offset(&line.string, byte_offset)
+        };
+
+        add_primary_caret(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            EditorCaret {
+                position: position!(col_index: new_col_index, row_index: new_row_index),
+                selection_map: SelectionMap::default(),
+            },
+        );
+    }
+
+    /// Add a caret that selects the next occurrence of the text that is selected by the
+    /// primary caret. The search starts after the bottom-most caret, wraps around, and
+    /// skips occurrences that are already selected. The new caret becomes the primary
+    /// caret. Nothing happens if the primary caret's selection is empty or spans
+    /// multiple lines.
+    pub fn add_caret_at_next_occurrence(args: EditorArgsMut<'_>) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let selection_map = editor_buffer.get_selection_map();
+        if selection_map.map.len() != 1 {
+            return;
+        }
+        let Some((row_index, selection_range)) = selection_map.iter().next() else {
+            return;
+        };
+        let Some(line) = editor_buffer.get_lines().get(ch!(@to_usize *row_index)) else {
+            return;
+        };
+        let selected_text = line.clip_to_range(*selection_range);
+        let Some(regex) = SearchQuery::new(selected_text, SearchMode::PlainText, true)
+            .try_build_regex()
+        else {
+            return;
+        };
+
+        let all_carets = get_all_carets(editor_buffer);
+        let is_already_selected = |search_match: &SearchMatch| {
+            all_carets.iter().any(|caret| {
+                caret.selection_map.map.len() == 1
+                    && caret.selection_map.get(search_match.row_index)
+                        == Some(&search_match.range)
+            })
+        };
+        let Some(last_position) =
+            all_carets.iter().map(|it| position_key(it.position)).max()
+        else {
+            return;
+        };
+
+        let matches: Vec<SearchMatch> = editor_buffer
+            .get_lines()
+            .iter()
+            .enumerate()
+            .flat_map(|(row_index, line)| {
+                search_support::find_matches_in_line(&regex, ch!(row_index), line)
+            })
+            .filter(|it| !is_already_selected(it))
+            .collect();
+        let Some(next_match) = matches
+            .iter()
+            .find(|it| (it.row_index, it.range.start_display_col_index) >= last_position)
+            .or(matches.first())
+            .copied()
+        else {
+            return;
+        };
+
+        let mut selection_map = SelectionMap::default();
+        selection_map.insert(
+            next_match.row_index,
+            next_match.range,
+            CaretMovementDirection::Right,
+        );
+        add_primary_caret(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            EditorCaret {
+                position: position!(
+                    col_index: next_match.range.end_display_col_index,
+                    row_index: next_match.row_index
+                ),
+                selection_map,
+            },
+        );
+    }
+
+    /// Make `new_caret` the primary caret, and turn the current primary caret into a
+    /// secondary one. Nothing happens if there already is a caret at that position.
+    fn add_primary_caret(args: EditorArgsMut<'_>, new_caret: EditorCaret) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let all_carets = get_all_carets(editor_buffer);
+        if all_carets
+            .iter()
+            .any(|it| it.position == new_caret.position)
+        {
+            return;
+        }
+
+        let mut secondary_carets = editor_buffer.get_secondary_carets().clone();
+        secondary_carets.push(get_primary_caret(editor_buffer));
+        editor_buffer.set_secondary_carets(secondary_carets);
+
+        set_primary_caret(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            &new_caret,
+        );
+    }
+
+    /// Returns the primary caret, followed by the secondary carets.
+    fn get_all_carets(editor_buffer: &EditorBuffer) -> Vec<EditorCaret> {
+        let mut it = vec![get_primary_caret(editor_buffer)];
+        it.extend(editor_buffer.get_secondary_carets().iter().cloned());
+        it
+    }
+
+    fn get_primary_caret(editor_buffer: &EditorBuffer) -> EditorCaret {
+        EditorCaret {
+            position: editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+            selection_map: editor_buffer.get_selection_map().clone(),
+        }
+    }
+
+    fn set_primary_caret(args: EditorArgsMut<'_>, caret: &EditorCaret) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            caret.position,
+        );
+
+        let (_, _, _, selection_map) = editor_buffer.get_mut();
+        *selection_map = caret.selection_map.clone();
+    }
+
+    fn merge_collided_carets(
+        mut carets: Vec<CaretEntry>,
+    ) -> (EditorCaret, Vec<EditorCaret>) {
+        carets.sort_by_key(|it| position_key(it.caret.position));
+
+        let mut merged: Vec<CaretEntry> = vec![];
+        for entry in carets {
+            match merged.last_mut() {
+                Some(last) if last.caret.position == entry.caret.position => {
+                    if entry.is_primary {
+                        *last = entry;
+                    }
+                }
+                _ => merged.push(entry),
+            }
+        }
+
+        let primary_index = merged.iter().position(|it| it.is_primary).unwrap_or(0);
+        let primary_caret = merged.remove(primary_index).caret;
+        let secondary_carets = merged.into_iter().map(|it| it.caret).collect();
+        (primary_caret, secondary_carets)
+    }
+
+    fn position_key(position: Position) -> (ChUnit, ChUnit) {
+        (position.row_index, position.col_index)
+    }
+
+    /// The rows of the buffer that can be changed by an operation at a caret: the rows of
+    /// the caret and its selection, and one row above and below them (eg: backspace at
+    /// the start of a line, or delete at the end of a line). Everything outside of it is
+    /// expected to stay the same, except for being shifted up or down.
+    struct EditWindow {
+        first_row_index: usize,
+        end_row_index: usize,
+        old_line_count: usize,
+        old_lines: Vec<String>,
+    }
+
+    impl EditWindow {
+        fn new(editor_buffer: &EditorBuffer, caret: &EditorCaret) -> Self {
+            let lines = editor_buffer.get_lines();
+            let caret_row_index = ch!(@to_usize caret.position.row_index);
+
+            let selected_row_indices = caret.selection_map.get_ordered_indices();
+            let min_row_index = selected_row_indices
+                .first()
+                .map(|it| ch!(@to_usize *it))
+                .unwrap_or(caret_row_index)
+                .min(caret_row_index);
+            let max_row_index = selected_row_indices
+                .last()
+                .map(|it| ch!(@to_usize *it))
+                .unwrap_or(caret_row_index)
+                .max(caret_row_index);
+
+            let end_row_index = (max_row_index + 2).min(lines.len());
+            let first_row_index = min_row_index.saturating_sub(1).min(end_row_index);
+
+            Self {
+                first_row_index,
+                end_row_index,
+                old_line_count: lines.len(),
+                old_lines: lines[first_row_index..end_row_index]
+                    .iter()
+                    .map(|it| it.string.clone())
+                    .collect(),
+            }
+        }
+
+        /// Returns [None] if nothing has changed.
+        fn into_caret_shift(self, editor_buffer: &EditorBuffer) -> Option<CaretShift> {
+            let lines = editor_buffer.get_lines();
+            if self.first_row_index > lines.len() {
+                return None;
+            }
+            let row_delta = lines.len() as isize - self.old_line_count as isize;
+            let new_end_row_index = (self.end_row_index as isize + row_delta)
+                .clamp(self.first_row_index as isize, lines.len() as isize)
+                as usize;
+            let new_lines = &lines[self.first_row_index..new_end_row_index];
+
+            let span = EditSpan::compute(&self.old_lines, new_lines)?;
+
+            Some(CaretShift {
+                first_row_index: self.first_row_index,
+                end_row_index: self.end_row_index,
+                row_delta,
+                span,
+                old_lines: self.old_lines,
+                new_lines: new_lines.iter().map(|it| it.string.clone()).collect(),
+            })
+        }
+    }
+
+    /// Moves the carets to account for an edit that was made in an [EditWindow].
+    struct CaretShift {
+        first_row_index: usize,
+        end_row_index: usize,
+        row_delta: isize,
+        span: EditSpan,
+        old_lines: Vec<String>,
+        new_lines: Vec<String>,
+    }
+
+    impl CaretShift {
+        fn apply(&self, caret: &mut EditorCaret) {
+            caret.position = self.shift_position(caret.position);
+
+            let mut selection_map = SelectionMap {
+                maybe_previous_direction: caret.selection_map.maybe_previous_direction,
+                ..Default::default()
+            };
+            for (row_index, range) in caret.selection_map.iter() {
+                if let Some((row_index, range)) = self.shift_selection(*row_index, *range)
+                {
+                    selection_map.map.insert(row_index, range);
+                }
+            }
+            caret.selection_map = selection_map;
+        }
+
+        fn shift_position(&self, position: Position) -> Position {
+            let row_index = ch!(@to_usize position.row_index);
+
+            if row_index < self.first_row_index {
+                return position;
+            }
+
+            if row_index >= self.end_row_index {
+                return position!(
+                    col_index: position.col_index,
+                    row_index: self.shift_row_index(row_index)
+                );
+            }
+
+            // The position is inside the window, so convert it to a byte offset in the
+            // window's text, move it past the edit, and then convert it back.
+            let old_byte_offset = self.old_byte_offset(row_index, position.col_index);
+            self.position_at_new_byte_offset(self.shift_byte_offset(old_byte_offset))
+        }
+
+        /// Selections inside the window are dropped if they overlap the text that was
+        /// changed, since the text they selected is gone.
+        fn shift_selection(
+            &self,
+            row_index: RowIndex,
+            range: SelectionRange,
+        ) -> Option<(RowIndex, SelectionRange)> {
+            let row_index = ch!(@to_usize row_index);
+
+            if row_index < self.first_row_index {
+                return Some((ch!(row_index), range));
+            }
+
+            if row_index >= self.end_row_index {
+                return Some((ch!(self.shift_row_index(row_index)), range));
+            }
+
+            let start = self.old_byte_offset(row_index, range.start_display_col_index);
+            let end = self.old_byte_offset(row_index, range.end_display_col_index);
+            let overlaps_edit = start < self.removed_end() && end > self.span_start();
+            if overlaps_edit {
+                return None;
+            }
+
+            let new_start =
+                self.position_at_new_byte_offset(self.shift_byte_offset(start));
+            let new_end = self.position_at_new_byte_offset(self.shift_byte_offset(end));
+            if new_start.row_index != new_end.row_index {
+                return None;
+            }
+
+            Some((
+                new_start.row_index,
+                SelectionRange::new(new_start.col_index, new_end.col_index),
+            ))
+        }
+
+        fn shift_row_index(&self, row_index: usize) -> usize {
+            (row_index as isize + self.row_delta) as usize
+        }
+
+        /// The byte offset (in the window's text, before the edit) of the given position.
+        fn old_byte_offset(&self, row_index: usize, display_col_index: ChUnit) -> usize {
+            let row_index_in_window = row_index - self.first_row_index;
+            joined_byte_count(&self.old_lines[..row_index_in_window])
+                + byte_offset_at_display_col(
+                    &self.old_lines[row_index_in_window],
+                    display_col_index,
+                )
+        }
+
+        fn span_start(&self) -> usize {
+            joined_byte_count(&self.old_lines[..self.span.row_index])
+                + self.span.byte_offset
+        }
+
+        fn removed_end(&self) -> usize {
+            self.span_start() + self.span.removed_text.len()
+        }
+
+        /// Offsets before the edit stay the same, offsets after it are moved by the
+        /// difference in length, and offsets inside it end up after the inserted text.
+        fn shift_byte_offset(&self, old_byte_offset: usize) -> usize {
+            let span_start = self.span_start();
+            if old_byte_offset <= span_start {
+                old_byte_offset
+            } else if old_byte_offset >= self.removed_end() {
+                old_byte_offset - self.span.removed_text.len()
+                    + self.span.inserted_text.len()
+            } else {
+                span_start + self.span.inserted_text.len()
+            }
+        }
+
+        /// The position of the given byte offset (in the window's text, after the edit).
+        fn position_at_new_byte_offset(&self, new_byte_offset: usize) -> Position {
+            let mut remaining_byte_count = new_byte_offset;
+            for (index, line) in self.new_lines.iter().enumerate() {
+                let is_last_line = index + 1 == self.new_lines.len();
+                if remaining_byte_count <= line.len() || is_last_line {
+                    return position!(
+                        col_index: display_col_at_byte_offset(
+                            line,
+                            remaining_byte_count.min(line.len())
+                        ),
+                        row_index: self.first_row_index + index
+                    );
+                }
+                remaining_byte_count -= line.len() + 1;
+            }
+
+            position!(col_index: 0, row_index: self.first_row_index)
+        }
+    }
+
+    /// The number of bytes in the given lines, including a `\n` after each one.
+    fn joined_byte_count(lines: &[String]) -> usize {
+        lines.iter().map(|it| it.len() + 1).sum()
+    }
+
+    fn byte_offset_at_display_col(line: &str, display_col_index: ChUnit) -> usize {
+        let line = UnicodeString::from(line);
+        line.vec_segment
+            .iter()
+            .find(|seg| seg.display_col_offset >= display_col_index)
+            .map(|seg| seg.byte_offset)
+            .unwrap_or(line.byte_size)
+    }
+
+    fn display_col_at_byte_offset(line: &str, byte_offset: usize) -> ChUnit {
+        let line = UnicodeString::from(line);
+        line.vec_segment
+            .iter()
+            .find(|seg| seg.byte_offset >= byte_offset)
+            .map(|seg| seg.display_col_offset)
+            .unwrap_or(line.display_width)
+    }
+}
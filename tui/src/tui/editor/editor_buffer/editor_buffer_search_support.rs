@@ -0,0 +1,485 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use regex::{Captures, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// The query that is used to find (and replace) text in an [EditorBuffer]. It is stored
+/// in the buffer (see [set_search_query](EditorBuffer::set_search_query)) so that its
+/// matches can be highlighted when the buffer is rendered.
+///
+/// Matches never span multiple lines, and empty matches (eg: from the regex `a*`) are
+/// ignored.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize, Default)]
+pub struct SearchQuery {
+    pub pattern: String,
+    pub mode: SearchMode,
+    pub is_case_sensitive: bool,
+}
+
+/// How the [SearchQuery::pattern] is interpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize, Default)]
+pub enum SearchMode {
+    /// The pattern is matched literally.
+    #[default]
+    PlainText,
+    /// The pattern is a [regex](https://docs.rs/regex/latest/regex/#syntax). The
+    /// replacement text can refer to capture groups, eg: `$1` or `${name}`.
+    Regex,
+}
+
+/// A single match of a [SearchQuery] in a line of the [EditorBuffer].
+/// - `row_index` and `range` are scroll adjusted, and `range` is a display col index
+///   range (just like the [SelectionRange]s in the [SelectionMap]).
+/// - `byte_start` and `byte_end` are the byte offsets of the match in the line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchMatch {
+    pub row_index: RowIndex,
+    pub range: SelectionRange,
+    pub byte_start: usize,
+    pub byte_end: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchDirection {
+    Next,
+    Previous,
+}
+
+impl SearchQuery {
+    pub fn new(pattern: &str, mode: SearchMode, is_case_sensitive: bool) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            mode,
+            is_case_sensitive,
+        }
+    }
+
+    /// Returns [None] if the pattern is empty, or if it is not a valid regex.
+    pub fn try_build_regex(&self) -> Option<Regex> {
+        if self.pattern.is_empty() {
+            return None;
+        }
+
+        let pattern = match self.mode {
+            SearchMode::PlainText => regex::escape(&self.pattern),
+            SearchMode::Regex => self.pattern.clone(),
+        };
+
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.is_case_sensitive)
+            .build()
+            .ok()
+    }
+
+    /// Append the `replacement` for the match in `captures` to `dest`. Capture groups are
+    /// only expanded in [SearchMode::Regex].
+    fn expand_replacement(
+        &self,
+        captures: &Captures<'_>,
+        replacement: &str,
+        dest: &mut String,
+    ) {
+        match self.mode {
+            SearchMode::PlainText => dest.push_str(replacement),
+            SearchMode::Regex => captures.expand(replacement, dest),
+        }
+    }
+}
+
+pub mod search_support {
+    use super::*;
+
+    /// Find all the (non empty) matches of `regex` in the given `line`.
+    pub fn find_matches_in_line(
+        regex: &Regex,
+        row_index: RowIndex,
+        line: &UnicodeString,
+    ) -> Vec<SearchMatch> {
+        regex
+            .find_iter(&line.string)
+            .filter(|it| !it.is_empty())
+            .map(|it| SearchMatch {
+                row_index,
+                range: byte_range_to_display_col_range(line, it.start(), it.end()),
+                byte_start: it.start(),
+                byte_end: it.end(),
+            })
+            .collect()
+    }
+
+    /// Find all the matches of the buffer's [SearchQuery]. Returns an empty [Vec] if there
+    /// is no query, or if it is invalid.
+    pub fn find_all_matches(buffer: &EditorBuffer) -> Vec<SearchMatch> {
+        let Some(regex) = buffer
+            .get_maybe_search_query()
+            .and_then(|query| query.try_build_regex())
+        else {
+            return vec![];
+        };
+
+        buffer
+            .get_lines()
+            .iter()
+            .enumerate()
+            .flat_map(|(row_index, line)| {
+                find_matches_in_line(&regex, ch!(row_index), line)
+            })
+            .collect()
+    }
+
+    /// Returns the match that is currently selected, ie: the selection is exactly one
+    /// match and nothing else.
+    pub fn get_selected_match(buffer: &EditorBuffer) -> Option<SearchMatch> {
+        let selection_map = buffer.get_selection_map();
+        if selection_map.map.len() != 1 {
+            return None;
+        }
+
+        let (row_index, selection_range) = selection_map.iter().next()?;
+        let regex = buffer.get_maybe_search_query()?.try_build_regex()?;
+        let line = buffer.get_lines().get(ch!(@to_usize *row_index))?;
+
+        find_matches_in_line(&regex, *row_index, line)
+            .into_iter()
+            .find(|it| it.range == *selection_range)
+    }
+
+    /// Find the match that comes after (or before) the caret, wrapping around at the end
+    /// (or start) of the buffer. If a match is currently selected, then the search starts
+    /// after (or before) it.
+    pub fn find_match(
+        buffer: &EditorBuffer,
+        direction: SearchDirection,
+    ) -> Option<SearchMatch> {
+        let matches = find_all_matches(buffer);
+        let caret = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let maybe_selected_match = get_selected_match(buffer);
+
+        match direction {
+            SearchDirection::Next => {
+                let start = match maybe_selected_match {
+                    Some(it) => (it.row_index, it.range.end_display_col_index),
+                    None => (caret.row_index, caret.col_index),
+                };
+                matches
+                    .iter()
+                    .find(|it| (it.row_index, it.range.start_display_col_index) >= start)
+                    .or(matches.first())
+                    .copied()
+            }
+            SearchDirection::Previous => {
+                let end = match maybe_selected_match {
+                    Some(it) => (it.row_index, it.range.start_display_col_index),
+                    None => (caret.row_index, caret.col_index),
+                };
+                matches
+                    .iter()
+                    .rev()
+                    .find(|it| (it.row_index, it.range.end_display_col_index) <= end)
+                    .or(matches.last())
+                    .copied()
+            }
+        }
+    }
+
+    /// Select the next (or previous) match and move the caret to the end of it. Does
+    /// nothing if there are no matches.
+    pub fn select_match(args: EditorArgsMut<'_>, direction: SearchDirection) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if let Some(search_match) = find_match(editor_buffer, direction) {
+            select(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                search_match,
+            );
+        }
+    }
+
+    /// Replace the selected match w/ `replacement`, and then select the next match. If no
+    /// match is selected, then the next match is selected (without replacing anything).
+    /// This is the same as how most editors behave, so that the user can see what is
+    /// going to be replaced before it happens.
+    pub fn replace_selected_match(args: EditorArgsMut<'_>, replacement: &str) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if let Some(selected_match) = get_selected_match(editor_buffer) {
+            replace_match(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                selected_match,
+                replacement,
+            );
+        }
+
+        select_match(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            SearchDirection::Next,
+        );
+    }
+
+    /// Replace all the matches in the buffer w/ `replacement`. Returns the number of
+    /// matches that were replaced.
+    pub fn replace_all_matches(args: EditorArgsMut<'_>, replacement: &str) -> usize {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let Some(query) = editor_buffer.get_maybe_search_query().cloned() else {
+            return 0;
+        };
+        let Some(regex) = query.try_build_regex() else {
+            return 0;
+        };
+
+        // Compute all the new lines first, so that the buffer is only changed once.
+        let mut replacement_count = 0;
+        let mut new_lines: Vec<(usize, String)> = vec![];
+        for (row_index, line) in editor_buffer.get_lines().iter().enumerate() {
+            let mut new_line = String::new();
+            let mut last_byte_end = 0;
+            for captures in regex.captures_iter(&line.string) {
+                let Some(it) = captures.get(0) else { continue };
+                if it.is_empty() {
+                    continue;
+                }
+                new_line.push_str(&line.string[last_byte_end..it.start()]);
+                query.expand_replacement(&captures, replacement, &mut new_line);
+                last_byte_end = it.end();
+                replacement_count += 1;
+            }
+            if last_byte_end > 0 {
+                new_line.push_str(&line.string[last_byte_end..]);
+                new_lines.push((row_index, new_line));
+            }
+        }
+
+        if new_lines.is_empty() {
+            return 0;
+        }
+
+        editor_buffer.clear_selection();
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                for (row_index, new_line) in new_lines {
+                    lines[row_index] = new_line.into();
+                }
+            },
+        );
+
+        replacement_count
+    }
+
+    /// Replace the given match (which must be in the buffer) w/ `replacement`, and move
+    /// the caret to the end of the replaced text.
+    fn replace_match(
+        args: EditorArgsMut<'_>,
+        search_match: SearchMatch,
+        replacement: &str,
+    ) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let Some(query) = editor_buffer.get_maybe_search_query().cloned() else {
+            return;
+        };
+        let Some(regex) = query.try_build_regex() else {
+            return;
+        };
+        let row_index = ch!(@to_usize search_match.row_index);
+        let Some(line) = editor_buffer.get_lines().get(row_index) else {
+            return;
+        };
+
+        let (new_line, new_line_caret_col) = {
+            let Some(captures) = regex.captures_iter(&line.string).find(|it| {
+                it.get(0).map(|it| (it.start(), it.end()))
+                    == Some((search_match.byte_start, search_match.byte_end))
+            }) else {
+                return;
+            };
+            let mut it = line.string[..search_match.byte_start].to_string();
+            query.expand_replacement(&captures, replacement, &mut it);
+            let caret_col = ch!(UnicodeString::str_display_width(&it));
+            it.push_str(&line.string[search_match.byte_end..]);
+            (it, caret_col)
+        };
+
+        editor_buffer.clear_selection();
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                lines[row_index] = new_line.into();
+            },
+        );
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            position!(col_index: new_line_caret_col, row_index: search_match.row_index),
+        );
+    }
+
+    /// Replace the selection w/ the given match, and move the caret to the end of it.
+    fn select(args: EditorArgsMut<'_>, search_match: SearchMatch) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            position!(
+                col_index: search_match.range.end_display_col_index,
+                row_index: search_match.row_index
+            ),
+        );
+
+        let (_, _, _, selection_map) = editor_buffer.get_mut();
+        selection_map.clear();
+        selection_map.insert(
+            search_match.row_index,
+            search_match.range,
+            CaretMovementDirection::Right,
+        );
+    }
+
+    /// Convert the byte range of a match into a display col range. If the match starts or
+    /// ends in the middle of a grapheme cluster, then the entire grapheme cluster is
+    /// included.
+    fn byte_range_to_display_col_range(
+        line: &UnicodeString,
+        byte_start: usize,
+        byte_end: usize,
+    ) -> SelectionRange {
+        let start_display_col_index = line
+            .vec_segment
+            .iter()
+            .find(|seg| seg.byte_offset + seg.byte_size > byte_start)
+            .map(|seg| seg.display_col_offset)
+            .unwrap_or(line.display_width);
+
+        let end_display_col_index = line
+            .vec_segment
+            .iter()
+            .find(|seg| seg.byte_offset + seg.byte_size >= byte_end)
+            .map(|seg| seg.display_col_offset + seg.unicode_width)
+            .unwrap_or(line.display_width);
+
+        SelectionRange::new(start_display_col_index, end_display_col_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(query: &SearchQuery, line: &str) -> Vec<SelectionRange> {
+        let regex = query.try_build_regex().unwrap();
+        search_support::find_matches_in_line(&regex, ch!(0), &UnicodeString::from(line))
+            .into_iter()
+            .map(|it| it.range)
+            .collect()
+    }
+
+    #[test]
+    fn test_plain_text_is_not_a_regex() {
+        let query = SearchQuery::new("a.c", SearchMode::PlainText, true);
+        assert_eq2!(
+            find(&query, "abc a.c"),
+            vec![SelectionRange::new(ch!(4), ch!(7))]
+        );
+    }
+
+    #[test]
+    fn test_case_sensitivity() {
+        let query = SearchQuery::new("abc", SearchMode::PlainText, true);
+        assert_eq2!(find(&query, "ABC abc").len(), 1);
+
+        let query = SearchQuery::new("abc", SearchMode::PlainText, false);
+        assert_eq2!(find(&query, "ABC abc").len(), 2);
+    }
+
+    #[test]
+    fn test_regex_and_empty_matches() {
+        let query = SearchQuery::new("b+", SearchMode::Regex, true);
+        assert_eq2!(
+            find(&query, "abbcb"),
+            vec![
+                SelectionRange::new(ch!(1), ch!(3)),
+                SelectionRange::new(ch!(4), ch!(5)),
+            ]
+        );
+
+        // Empty matches are ignored.
+        let query = SearchQuery::new("x*", SearchMode::Regex, true);
+        assert_eq2!(find(&query, "abc"), vec![]);
+
+        // Invalid regex & empty pattern.
+        assert!(SearchQuery::new("(", SearchMode::Regex, true)
+            .try_build_regex()
+            .is_none());
+        assert!(SearchQuery::new("", SearchMode::PlainText, true)
+            .try_build_regex()
+            .is_none());
+    }
+
+    #[test]
+    fn test_display_col_range_with_wide_graphemes() {
+        // "😃" is 2 display cols wide.
+        let query = SearchQuery::new("b", SearchMode::PlainText, true);
+        assert_eq2!(
+            find(&query, "a😃b"),
+            vec![SelectionRange::new(ch!(3), ch!(4))]
+        );
+
+        let query = SearchQuery::new("😃", SearchMode::PlainText, true);
+        assert_eq2!(
+            find(&query, "a😃b"),
+            vec![SelectionRange::new(ch!(1), ch!(3))]
+        );
+    }
+}
@@ -15,7 +15,8 @@
  *   limitations under the License.
  */
 
-use std::fmt::{Debug, Formatter, Result};
+use std::{collections::HashMap,
+          fmt::{Debug, Formatter, Result}};
 
 use get_size::GetSize;
 use r3bl_rs_utils_core::*;
@@ -165,10 +166,28 @@ use crate::*;
 /// in the map represents a row of text in the buffer.
 /// - The row index is the key.
 /// - The value is the [SelectionRange].
+///
+/// ## `maybe_search_query`
+///
+/// The [SearchQuery] that is used by find and replace. Its matches are highlighted when
+/// the buffer is rendered.
+///
+/// ## `secondary_carets`
+///
+/// The carets (in addition to the primary caret, which is `caret_display_position` and
+/// `selection_map`) that are used for multi caret editing. Each [EditorCaret] owns its
+/// own selection. Please take a look at [multi_caret_support] for more details.
+///
+/// ## `line_markers`
+///
+/// The [LineMarker]s (supplied by the app) that are shown in the gutter. Key is the row
+/// index. Please take a look at [gutter_support] for more details.
 #[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default)]
 pub struct EditorBuffer {
     editor_content: EditorContent,
     pub(crate) history: EditorBufferHistory,
+    pub(crate) maybe_file_info: Option<EditorBufferFileInfo>,
+    pub(crate) kill_ring: KillRing,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default)]
@@ -178,43 +197,209 @@ pub struct EditorContent {
     scroll_offset: ScrollOffset,
     maybe_file_extension: Option<String>,
     selection_map: SelectionMap,
+    maybe_search_query: Option<SearchQuery>,
+    secondary_carets: Vec<EditorCaret>,
+    line_markers: HashMap<RowIndex, LineMarker>,
 }
 
+/// The undo / redo history of an [EditorBuffer].
+///
+/// Instead of keeping a copy of the entire [EditorContent] for every version, only the
+/// reversible edits ([EditorBufferEdit]) that turn one version into the next are stored.
+/// This means that the memory used by the history grows w/ the size of the edits, and
+/// not w/ the size of the document.
+///
+/// ```text
+///   version 0 ──edits[0]──▸ version 1 ──edits[1]──▸ version 2
+///                                           ▴
+///                                    current_index = 1
+/// ```
+///
+/// 1. `edits[i]` turns version `i` into version `i + 1`. Undo reverts the edit before
+///    `current_index` and redo re-applies the edit at `current_index`.
+/// 2. `maybe_snapshot` holds the lines (as plain [String]s) of the version at
+///    `current_index`. When a new version is pushed, these lines are compared w/ the lines
+///    in the buffer to find the span of text that was changed. It is [None] until the
+///    first version is pushed.
+/// 3. `max_depth` is the maximum number of edits that are kept. When it is exceeded, the
+///    oldest edits are dropped.
+/// 4. `maybe_saved_index` is the index of the version that was last loaded from or saved
+///    to a file (the initial version is considered saved). The buffer is dirty when the
+///    current version is not this one. It is [None] when that version has been dropped
+///    from the history (so the buffer is dirty until it is saved again).
 #[derive(Clone, PartialEq, Serialize, Deserialize, GetSize)]
 pub struct EditorBufferHistory {
-    versions: Vec<EditorContent>,
-    current_index: isize,
+    edits: Vec<EditorBufferEdit>,
+    current_index: usize,
+    maybe_snapshot: Option<HistorySnapshot>,
+    max_depth: usize,
+    maybe_saved_index: Option<usize>,
 }
 
+/// The default value for the maximum number of undo steps that are kept in the
+/// [EditorBufferHistory]. This can be changed using
+/// [set_history_max_depth](EditorBuffer::set_history_max_depth).
+pub const DEFAULT_HISTORY_MAX_DEPTH: usize = 1_000;
+
 impl Default for EditorBufferHistory {
     fn default() -> Self {
         Self {
-            versions: vec![],
-            current_index: -1,
+            edits: vec![],
+            current_index: 0,
+            maybe_snapshot: None,
+            max_depth: DEFAULT_HISTORY_MAX_DEPTH,
+            maybe_saved_index: Some(0),
         }
     }
 }
 
+/// The lines and caret state of the version at the current index of the
+/// [EditorBufferHistory].
+#[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default)]
+struct HistorySnapshot {
+    lines: Vec<String>,
+    caret_state: EditorCaretState,
+}
+
+/// The caret, scroll offset and selection of an [EditorBuffer]. These are saved before and
+/// after each [EditorBufferEdit]. Redo restores the ones from after the edit, and undo
+/// restores the ones from before it (except for the caret, which stays where it is).
+#[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default, Debug)]
+pub struct EditorCaretState {
+    pub caret_display_position: Position,
+    pub scroll_offset: ScrollOffset,
+    pub selection_map: SelectionMap,
+}
+
+/// A single reversible undo step in the [EditorBufferHistory].
+#[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Debug)]
+pub struct EditorBufferEdit {
+    pub span: EditSpan,
+    pub caret_before: EditorCaretState,
+    pub caret_after: EditorCaretState,
+    /// Consecutive typed characters are coalesced into a single edit, as long as this is
+    /// `true`. It is set to `false` when a whitespace character is typed, or when the
+    /// edit becomes the current version due to an undo or redo.
+    pub is_open_for_typing: bool,
+}
+
+/// The span of text that was changed by an [EditorBufferEdit].
+///
+/// The lines `row_index..row_index + removed_line_count` (before the edit) are replaced by
+/// the lines `row_index..row_index + inserted_line_count` (after the edit). When these
+/// lines are joined w/ `\n`, both versions share the first `byte_offset` bytes and the
+/// last `tail_byte_count` bytes. Only the text in between (`removed_text` and
+/// `inserted_text`) is stored.
+///
+/// ```text
+/// before: "abc␤def"  byte_offset: 2, tail_byte_count: 2, removed_text: "c␤d"
+/// after : "abXef"                                        inserted_text: "X"
+/// ```
+#[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Debug)]
+pub struct EditSpan {
+    pub row_index: usize,
+    pub removed_line_count: usize,
+    pub inserted_line_count: usize,
+    pub byte_offset: usize,
+    pub tail_byte_count: usize,
+    pub removed_text: String,
+    pub inserted_text: String,
+}
+
 pub mod history {
     use super::*;
 
-    pub fn convert_isize_to_usize(index: isize) -> usize {
-        index.try_into().unwrap_or(index as usize)
+    /// Record the changes made to the buffer since the last push as a single undo step.
+    /// If nothing has changed, only the caret state is remembered; this is used as the
+    /// "caret before" of the next undo step. The very first push records the initial
+    /// version of the buffer.
+    pub fn push(editor_buffer: &mut EditorBuffer) { push_impl(editor_buffer, None); }
+
+    /// Just like [push], except that the change (which is the result of typing
+    /// `character`) is coalesced w/ the previous undo step if that was also typing that
+    /// happened at the same caret position. Typing a whitespace character ends the
+    /// coalescing.
+    pub fn push_typed_char(editor_buffer: &mut EditorBuffer, character: char) {
+        push_impl(editor_buffer, Some(character));
     }
 
-    pub fn push(editor_buffer: &mut EditorBuffer) {
-        let content_copy = editor_buffer.editor_content.clone();
+    fn push_impl(editor_buffer: &mut EditorBuffer, maybe_typed_char: Option<char>) {
+        let caret_state_now = EditorCaretState::from(&editor_buffer.editor_content);
+        let lines = &editor_buffer.editor_content.lines;
+        let history = &mut editor_buffer.history;
+
+        // Initial version.
+        if history.maybe_snapshot.is_none() {
+            history.maybe_snapshot = Some(HistorySnapshot {
+                lines: lines.iter().map(|line| line.string.clone()).collect(),
+                caret_state: caret_state_now,
+            });
+            return;
+        }
+        let Some(snapshot) = history.maybe_snapshot.as_mut() else {
+            return;
+        };
+
+        // Nothing has changed, just remember the caret.
+        let Some(span) = EditSpan::compute(&snapshot.lines, lines) else {
+            snapshot.caret_state = caret_state_now;
+            return;
+        };
 
         // Delete the history from the current version index to the end.
-        if let Some(current_index) = editor_buffer.history.get_current_index() {
-            editor_buffer
-                .history
-                .versions
-                .truncate(convert_isize_to_usize(current_index + 1));
+        history.edits.truncate(history.current_index);
+        if let Some(saved_index) = history.maybe_saved_index {
+            if saved_index > history.current_index {
+                history.maybe_saved_index = None;
+            }
         }
 
+        let new_edit = EditorBufferEdit {
+            caret_before: snapshot.caret_state.clone(),
+            caret_after: caret_state_now.clone(),
+            is_open_for_typing: maybe_typed_char.is_some(),
+            span,
+        };
+
+        // Try and coalesce w/ the previous edit (this has to happen before the snapshot
+        // is updated, since the snapshot is needed to revert the previous edit).
+        let maybe_merged_edit = match history.edits.last() {
+            Some(last_edit) => try_coalesce(last_edit, &new_edit, &snapshot.lines, lines),
+            None => None,
+        };
+
+        // Update the snapshot to match the buffer.
+        let removed_rows = {
+            let start = new_edit.span.row_index;
+            start..start + new_edit.span.removed_line_count
+        };
+        let inserted_rows = {
+            let start = new_edit.span.row_index;
+            start..start + new_edit.span.inserted_line_count
+        };
+        snapshot.lines.splice(
+            removed_rows,
+            lines[inserted_rows].iter().map(|line| line.string.clone()),
+        );
+        snapshot.caret_state = caret_state_now;
+
         // Normal history insertion.
-        editor_buffer.history.push_content(content_copy);
+        match maybe_merged_edit {
+            Some(mut merged_edit) => {
+                close_for_typing_if_whitespace(&mut merged_edit, maybe_typed_char);
+                if let Some(last_edit) = history.edits.last_mut() {
+                    *last_edit = merged_edit;
+                }
+            }
+            None => {
+                let mut new_edit = new_edit;
+                close_for_typing_if_whitespace(&mut new_edit, maybe_typed_char);
+                history.edits.push(new_edit);
+                history.current_index += 1;
+            }
+        }
+
+        history.enforce_max_depth();
 
         if DEBUG_TUI_COPY_PASTE {
             log_debug(format!(
@@ -224,11 +409,86 @@ pub mod history {
         }
     }
 
+    fn close_for_typing_if_whitespace(
+        edit: &mut EditorBufferEdit,
+        maybe_typed_char: Option<char>,
+    ) {
+        if let Some(character) = maybe_typed_char {
+            if character.is_whitespace() {
+                edit.is_open_for_typing = false;
+            }
+        }
+    }
+
+    /// Returns a single edit that combines `last_edit` and `new_edit`, if both of them are
+    /// typing that happened one after the other on the same line. `snapshot_lines` are
+    /// the lines after `last_edit` was applied, and `lines` are the lines after
+    /// `new_edit` was applied.
+    fn try_coalesce(
+        last_edit: &EditorBufferEdit,
+        new_edit: &EditorBufferEdit,
+        snapshot_lines: &[String],
+        lines: &[UnicodeString],
+    ) -> Option<EditorBufferEdit> {
+        let is_typing = last_edit.is_open_for_typing && new_edit.is_open_for_typing;
+        let is_contiguous = last_edit.caret_after == new_edit.caret_before;
+        let is_same_line = last_edit.span.is_single_line()
+            && new_edit.span.is_single_line()
+            && last_edit.span.row_index == new_edit.span.row_index;
+        if !(is_typing && is_contiguous && is_same_line) {
+            return None;
+        }
+
+        let row_index = last_edit.span.row_index;
+        let line_before_last_edit = last_edit.span.revert(snapshot_lines)?;
+        let line_after_new_edit = lines.get(row_index..row_index + 1)?;
+
+        let mut merged_span =
+            EditSpan::compute(&line_before_last_edit, line_after_new_edit)?;
+        merged_span.row_index += row_index;
+
+        Some(EditorBufferEdit {
+            span: merged_span,
+            caret_before: last_edit.caret_before.clone(),
+            caret_after: new_edit.caret_after.clone(),
+            is_open_for_typing: true,
+        })
+    }
+
+    /// Revert the last edit. The scroll offset and selection from before the edit are
+    /// restored, but the caret stays where it is (unlike [redo], which moves it to where
+    /// it was after the edit).
     pub fn undo(editor_buffer: &mut EditorBuffer) {
+        // Make sure that the snapshot matches the buffer before reverting anything.
+        push(editor_buffer);
+
         let retain_caret_position = editor_buffer.editor_content.caret_display_position;
-        if let Some(content) = editor_buffer.history.previous_content() {
-            editor_buffer.editor_content = content;
-            editor_buffer.editor_content.caret_display_position = retain_caret_position;
+        let history = &mut editor_buffer.history;
+        if history.current_index == 0 {
+            return;
+        }
+
+        let edit = &history.edits[history.current_index - 1];
+        let caret_state = EditorCaretState {
+            caret_display_position: retain_caret_position,
+            ..edit.caret_before.clone()
+        };
+        if let Some(snapshot) = history.maybe_snapshot.as_mut() {
+            if let Some(lines_before_edit) = edit.span.revert(&snapshot.lines) {
+                let rows_after_edit = {
+                    let start = edit.span.row_index;
+                    start..start + edit.span.inserted_line_count
+                };
+                replace_rows(
+                    &mut editor_buffer.editor_content,
+                    snapshot,
+                    rows_after_edit,
+                    lines_before_edit,
+                    &caret_state,
+                );
+                history.current_index -= 1;
+                history.close_current_for_typing();
+            }
         }
 
         if DEBUG_TUI_COPY_PASTE {
@@ -237,8 +497,30 @@ pub mod history {
     }
 
     pub fn redo(editor_buffer: &mut EditorBuffer) {
-        if let Some(content) = editor_buffer.history.next_content() {
-            editor_buffer.editor_content = content;
+        // Any changes that were not pushed would be lost, so don't redo.
+        if history_is_out_of_sync(editor_buffer) {
+            return;
+        }
+
+        let history = &mut editor_buffer.history;
+        if let Some(edit) = history.edits.get(history.current_index) {
+            if let Some(snapshot) = history.maybe_snapshot.as_mut() {
+                if let Some(lines_after_edit) = edit.span.apply(&snapshot.lines) {
+                    let rows_before_edit = {
+                        let start = edit.span.row_index;
+                        start..start + edit.span.removed_line_count
+                    };
+                    replace_rows(
+                        &mut editor_buffer.editor_content,
+                        snapshot,
+                        rows_before_edit,
+                        lines_after_edit,
+                        &edit.caret_after,
+                    );
+                    history.current_index += 1;
+                    history.close_current_for_typing();
+                }
+            }
         }
 
         if DEBUG_TUI_COPY_PASTE {
@@ -246,102 +528,263 @@ pub mod history {
         }
     }
 
-    impl EditorBufferHistory {
-        pub(crate) fn is_empty(&self) -> bool { self.versions.is_empty() }
+    /// Remember the current version as the one that was saved to (or loaded from) a
+    /// file. Any changes that were not pushed are pushed first.
+    pub fn mark_as_saved(editor_buffer: &mut EditorBuffer) {
+        push(editor_buffer);
 
-        fn get_last_index(&self) -> Option<ChUnit> {
-            if self.is_empty() {
-                None
-            } else {
-                Some(ch!(self.versions.len()) - 1)
+        let history = &mut editor_buffer.history;
+        // Typing after the save must not be coalesced into the saved version.
+        history.close_current_for_typing();
+        history.maybe_saved_index = Some(history.current_index);
+    }
+
+    /// Returns `true` if the buffer has changed since it was last saved (or loaded). Undo
+    /// and redo back to the saved version makes the buffer clean again.
+    pub fn is_dirty(editor_buffer: &EditorBuffer) -> bool {
+        let history = &editor_buffer.history;
+        history_is_out_of_sync(editor_buffer)
+            || history.maybe_saved_index != Some(history.current_index)
+    }
+
+    fn history_is_out_of_sync(editor_buffer: &EditorBuffer) -> bool {
+        match editor_buffer.history.maybe_snapshot {
+            Some(ref snapshot) => {
+                EditSpan::compute(&snapshot.lines, &editor_buffer.editor_content.lines)
+                    .is_some()
             }
+            None => false,
         }
+    }
 
-        fn get_current_index(&self) -> Option<isize> {
-            if self.is_empty() {
-                None
-            } else {
-                Some(self.current_index)
+    /// Replace `rows` in both the buffer and the snapshot w/ `new_lines`, and restore the
+    /// caret state.
+    fn replace_rows(
+        editor_content: &mut EditorContent,
+        snapshot: &mut HistorySnapshot,
+        rows: std::ops::Range<usize>,
+        new_lines: Vec<String>,
+        caret_state: &EditorCaretState,
+    ) {
+        editor_content.lines.splice(
+            rows.clone(),
+            new_lines
+                .iter()
+                .map(|line| UnicodeString::from(line.as_str())),
+        );
+        snapshot.lines.splice(rows, new_lines);
+
+        editor_content.caret_display_position = caret_state.caret_display_position;
+        editor_content.scroll_offset = caret_state.scroll_offset;
+        editor_content.selection_map = caret_state.selection_map.clone();
+        snapshot.caret_state = caret_state.clone();
+    }
+
+    impl From<&EditorContent> for EditorCaretState {
+        fn from(editor_content: &EditorContent) -> Self {
+            Self {
+                caret_display_position: editor_content.caret_display_position,
+                scroll_offset: editor_content.scroll_offset,
+                selection_map: editor_content.selection_map.clone(),
             }
         }
+    }
 
-        fn increment_index(&mut self) {
-            // Don't do anything if is empty.
-            match self.get_last_index() {
-                Some(max_index) => {
-                    // Make sure it doesn't go past the end.
-                    if self.current_index == ch!(@to_isize max_index) {
-                        return;
-                    }
-                    // Increment index.
-                    self.current_index += 1;
-                }
-                _ => (),
+    impl EditorBufferHistory {
+        pub fn get_current_index(&self) -> usize { self.current_index }
+
+        pub fn get_edits(&self) -> &Vec<EditorBufferEdit> { &self.edits }
+
+        pub fn get_max_depth(&self) -> usize { self.max_depth }
+
+        pub(crate) fn set_max_depth(&mut self, max_depth: usize) {
+            self.max_depth = max_depth;
+            self.enforce_max_depth();
+        }
+
+        /// Drop the oldest edits until there are no more than `max_depth` of them.
+        fn enforce_max_depth(&mut self) {
+            if self.edits.len() <= self.max_depth {
+                return;
             }
+            let excess = self.edits.len() - self.max_depth;
+            self.edits.drain(..excess);
+            self.current_index = self.current_index.saturating_sub(excess);
+            self.maybe_saved_index = self
+                .maybe_saved_index
+                .and_then(|saved_index| saved_index.checked_sub(excess));
         }
 
-        fn decrement_index(&mut self) {
-            // Don't do anything if is empty.
-            match self.get_current_index() {
-                Some(current_index) => {
-                    // Make sure it doesn't go past the start.
-                    if current_index == 0 {
-                        return;
-                    }
-                    // Decrement index.
-                    self.current_index -= 1;
+        fn close_current_for_typing(&mut self) {
+            if self.current_index == 0 {
+                return;
+            }
+            if let Some(edit) = self.edits.get_mut(self.current_index - 1) {
+                edit.is_open_for_typing = false;
+            }
+        }
+    }
+
+    impl EditSpan {
+        /// Find the span of text that has to be changed in order to turn `old_lines`
+        /// into `new_lines`. Returns [None] if they are the same.
+        pub fn compute(
+            old_lines: &[String],
+            new_lines: &[UnicodeString],
+        ) -> Option<Self> {
+            let old_len = old_lines.len();
+            let new_len = new_lines.len();
+
+            // Lines that are the same at the start.
+            let mut prefix_line_count = 0;
+            while prefix_line_count < old_len
+                && prefix_line_count < new_len
+                && old_lines[prefix_line_count] == new_lines[prefix_line_count].string
+            {
+                prefix_line_count += 1;
+            }
+
+            if prefix_line_count == old_len && prefix_line_count == new_len {
+                return None;
+            }
+
+            // Lines that are the same at the end.
+            let mut suffix_line_count = 0;
+            while suffix_line_count < old_len - prefix_line_count
+                && suffix_line_count < new_len - prefix_line_count
+                && old_lines[old_len - 1 - suffix_line_count]
+                    == new_lines[new_len - 1 - suffix_line_count].string
+            {
+                suffix_line_count += 1;
+            }
+
+            // When lines are only inserted or only removed, then include a neighboring line
+            // in the span, so that the `\n` separators are part of the changed text.
+            let touches_no_lines_in_either = prefix_line_count + suffix_line_count
+                == old_len
+                || prefix_line_count + suffix_line_count == new_len;
+            if touches_no_lines_in_either {
+                if suffix_line_count > 0 {
+                    suffix_line_count -= 1;
+                } else {
+                    prefix_line_count = prefix_line_count.saturating_sub(1);
                 }
-                _ => (),
             }
+
+            let old_text = join_lines(
+                old_lines[prefix_line_count..old_len - suffix_line_count]
+                    .iter()
+                    .map(|line| line.as_str()),
+            );
+            let new_text = join_lines(
+                new_lines[prefix_line_count..new_len - suffix_line_count]
+                    .iter()
+                    .map(|line| line.string.as_str()),
+            );
+
+            let byte_offset = common_prefix_byte_count(&old_text, &new_text);
+            let tail_byte_count = common_suffix_byte_count(
+                &old_text,
+                &new_text,
+                old_text.len().min(new_text.len()) - byte_offset,
+            );
+
+            Some(Self {
+                row_index: prefix_line_count,
+                removed_line_count: old_len - prefix_line_count - suffix_line_count,
+                inserted_line_count: new_len - prefix_line_count - suffix_line_count,
+                removed_text: old_text[byte_offset..old_text.len() - tail_byte_count]
+                    .to_string(),
+                inserted_text: new_text[byte_offset..new_text.len() - tail_byte_count]
+                    .to_string(),
+                byte_offset,
+                tail_byte_count,
+            })
         }
 
-        fn push_content(&mut self, content: EditorContent) {
-            self.versions.push(content);
-            self.increment_index();
+        pub fn is_single_line(&self) -> bool {
+            self.removed_line_count == 1 && self.inserted_line_count == 1
         }
 
-        fn previous_content(&mut self) -> Option<EditorContent> {
-            if self.is_empty() {
-                None
-            } else {
-                // At start of history.
-                if self.current_index == -1 {
-                    return None;
-                }
+        /// Given the lines before this edit, return the lines that replace
+        /// `row_index..row_index + removed_line_count`.
+        pub fn apply(&self, lines_before: &[String]) -> Option<Vec<String>> {
+            self.splice(
+                lines_before,
+                self.removed_line_count,
+                &self.removed_text,
+                &self.inserted_text,
+                self.inserted_line_count,
+            )
+        }
 
-                // Decrement index.
-                self.decrement_index();
+        /// Given the lines after this edit, return the lines that replace
+        /// `row_index..row_index + inserted_line_count`.
+        pub fn revert(&self, lines_after: &[String]) -> Option<Vec<String>> {
+            self.splice(
+                lines_after,
+                self.inserted_line_count,
+                &self.inserted_text,
+                &self.removed_text,
+                self.removed_line_count,
+            )
+        }
 
-                // Return item at index.
-                let it = self
-                    .versions
-                    .get(convert_isize_to_usize(self.current_index))
-                    .cloned();
-                it
+        fn splice(
+            &self,
+            lines: &[String],
+            from_line_count: usize,
+            from_text: &str,
+            to_text: &str,
+            to_line_count: usize,
+        ) -> Option<Vec<String>> {
+            let rows = lines.get(self.row_index..self.row_index + from_line_count)?;
+            let text = join_lines(rows.iter().map(|line| line.as_str()));
+
+            let tail_start = text.len().checked_sub(self.tail_byte_count)?;
+            if text.get(self.byte_offset..tail_start)? != from_text {
+                return None;
             }
-        }
 
-        fn next_content(&mut self) -> Option<EditorContent> {
-            if self.is_empty() {
-                None
+            let new_text = format!(
+                "{}{}{}",
+                text.get(..self.byte_offset)?,
+                to_text,
+                text.get(tail_start..)?
+            );
+
+            if to_line_count == 0 {
+                Some(vec![])
             } else {
-                // At end of versions.
-                if let Some(max_index) = self.get_last_index() {
-                    let max_index = ch!(@to_isize max_index);
-                    if self.current_index == max_index {
-                        return None;
-                    }
-                }
+                Some(new_text.split('\n').map(String::from).collect())
+            }
+        }
+    }
 
-                // Increment index.
-                self.increment_index();
+    fn join_lines<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+        lines.collect::<Vec<&str>>().join("\n")
+    }
 
-                // Return item at index.
-                self.versions
-                    .get(convert_isize_to_usize(self.current_index))
-                    .cloned()
+    fn common_prefix_byte_count(lhs: &str, rhs: &str) -> usize {
+        let mut it = 0;
+        for (lhs_char, rhs_char) in lhs.chars().zip(rhs.chars()) {
+            if lhs_char != rhs_char {
+                break;
             }
+            it += lhs_char.len_utf8();
         }
+        it
+    }
+
+    fn common_suffix_byte_count(lhs: &str, rhs: &str, max_byte_count: usize) -> usize {
+        let mut it = 0;
+        for (lhs_char, rhs_char) in lhs.chars().rev().zip(rhs.chars().rev()) {
+            if lhs_char != rhs_char || it + lhs_char.len_utf8() > max_byte_count {
+                break;
+            }
+            it += lhs_char.len_utf8();
+        }
+        it
     }
 }
 
@@ -351,91 +794,160 @@ mod history_tests {
 
     use super::*;
 
+    fn set_lines(editor_buffer: &mut EditorBuffer, lines: &[&str]) {
+        editor_buffer.editor_content.lines =
+            lines.iter().map(|it| UnicodeString::from(*it)).collect();
+    }
+
+    fn get_lines(editor_buffer: &EditorBuffer) -> Vec<String> {
+        editor_buffer
+            .editor_content
+            .lines
+            .iter()
+            .map(|it| it.string.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_is_dirty() {
+        let mut editor_buffer = EditorBuffer::default();
+        set_lines(&mut editor_buffer, &["abc"]);
+        history::mark_as_saved(&mut editor_buffer);
+        assert!(!history::is_dirty(&editor_buffer));
+
+        // Changes that have not been pushed yet.
+        set_lines(&mut editor_buffer, &["abcd"]);
+        assert!(history::is_dirty(&editor_buffer));
+        history::push(&mut editor_buffer);
+        assert!(history::is_dirty(&editor_buffer));
+
+        // Undo back to the saved version, and redo.
+        history::undo(&mut editor_buffer);
+        assert!(!history::is_dirty(&editor_buffer));
+        history::redo(&mut editor_buffer);
+        assert!(history::is_dirty(&editor_buffer));
+
+        // Typing after a save is not coalesced into the saved version.
+        history::mark_as_saved(&mut editor_buffer);
+        assert!(!history::is_dirty(&editor_buffer));
+        set_lines(&mut editor_buffer, &["abcde"]);
+        history::push_typed_char(&mut editor_buffer, 'e');
+        assert!(history::is_dirty(&editor_buffer));
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["abcd"]);
+        assert!(!history::is_dirty(&editor_buffer));
+
+        // The saved version is dropped from the history by a new edit after undo.
+        history::undo(&mut editor_buffer);
+        assert!(history::is_dirty(&editor_buffer));
+        set_lines(&mut editor_buffer, &["xyz"]);
+        history::push(&mut editor_buffer);
+        assert_eq2!(editor_buffer.history.maybe_saved_index, None);
+        history::undo(&mut editor_buffer);
+        assert!(history::is_dirty(&editor_buffer));
+    }
+
     #[test]
     fn test_push_default() {
         let mut editor_buffer = EditorBuffer::default();
-        let content = editor_buffer.editor_content.clone();
+        assert!(editor_buffer.history.maybe_snapshot.is_none());
 
         history::push(&mut editor_buffer);
+        assert!(editor_buffer.history.maybe_snapshot.is_some());
         assert_eq2!(editor_buffer.history.current_index, 0);
-
-        let history_stack = editor_buffer.history.versions;
-        assert_eq2!(history_stack.len(), 1);
-        assert_eq2!(history_stack[0], content);
+        assert_eq2!(editor_buffer.history.edits.len(), 0);
     }
 
     #[test]
     fn test_push_with_contents() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        set_lines(&mut editor_buffer, &["abc"]);
         history::push(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 0);
 
-        let history_stack = editor_buffer.history.versions;
-        assert_eq2!(history_stack.len(), 1);
-        assert_eq2!(history_stack[0].lines.len(), 1);
-        assert_eq2!(history_stack[0].lines[0].string, "abc");
+        set_lines(&mut editor_buffer, &["abXc"]);
+        history::push(&mut editor_buffer);
+        assert_eq2!(editor_buffer.history.current_index, 1);
+
+        // Only the inserted text is stored, not a copy of the line.
+        let edit = &editor_buffer.history.edits[0];
+        assert_eq2!(edit.span.row_index, 0);
+        assert_eq2!(edit.span.byte_offset, 2);
+        assert_eq2!(edit.span.tail_byte_count, 1);
+        assert_eq2!(edit.span.removed_text, "");
+        assert_eq2!(edit.span.inserted_text, "X");
     }
 
     #[test]
-    fn test_push_and_drop_future_redos() {
+    fn test_push_without_changes_does_not_add_undo_step() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        set_lines(&mut editor_buffer, &["abc"]);
+        history::push(&mut editor_buffer);
+        history::push(&mut editor_buffer);
         history::push(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 0);
+        assert_eq2!(editor_buffer.history.edits.len(), 0);
+    }
+
+    #[test]
+    fn test_push_and_drop_future_redos() {
+        let mut editor_buffer = EditorBuffer::default();
+        set_lines(&mut editor_buffer, &["abc"]);
+        history::push(&mut editor_buffer);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")];
+        set_lines(&mut editor_buffer, &["def"]);
         history::push(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 1);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("ghi")];
+        set_lines(&mut editor_buffer, &["ghi"]);
         history::push(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 2);
 
         // Do two undos.
         history::undo(&mut editor_buffer);
         history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["abc"]);
 
         // Push new content. Should drop future redos.
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("xyz")];
+        set_lines(&mut editor_buffer, &["xyz"]);
         history::push(&mut editor_buffer);
 
-        let history = editor_buffer.history;
+        let history = &editor_buffer.history;
         assert_eq2!(history.current_index, 1);
+        assert_eq2!(history.edits.len(), 1);
 
-        let history_stack = history.versions;
-        assert_eq2!(history_stack.len(), 2);
-        assert_eq2!(history_stack[0].lines.len(), 1);
-        assert_eq2!(history_stack[0].lines[0].string, "abc");
-        assert_eq2!(history_stack[1].lines.len(), 1);
-        assert_eq2!(history_stack[1].lines[0].string, "xyz");
+        // Redo does nothing.
+        history::redo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["xyz"]);
+
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["abc"]);
     }
 
     #[test]
     fn test_single_undo() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        set_lines(&mut editor_buffer, &["abc"]);
         history::push(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 0);
 
         // Undo.
         history::undo(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 0);
+        assert_eq2!(get_lines(&editor_buffer), vec!["abc"]);
     }
 
     #[test]
     fn test_many_undo() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        set_lines(&mut editor_buffer, &["abc"]);
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")];
+        set_lines(&mut editor_buffer, &["abc", "def"]);
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 1);
         let copy_of_editor_content = editor_buffer.editor_content.clone();
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("ghi")];
+        set_lines(&mut editor_buffer, &["ghi", "def", "jkl"]);
         history::push(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 2);
 
@@ -444,24 +956,20 @@ mod history_tests {
         assert_eq2!(editor_buffer.history.current_index, 1);
         assert_eq2!(editor_buffer.editor_content, copy_of_editor_content);
 
-        let history_stack = editor_buffer.history.versions;
-        assert_eq2!(history_stack.len(), 3);
-        assert_eq2!(history_stack[0].lines.len(), 1);
-        assert_eq2!(history_stack[0].lines[0].string, "abc");
-        assert_eq2!(history_stack[1].lines.len(), 1);
-        assert_eq2!(history_stack[1].lines[0].string, "def");
-        assert_eq2!(history_stack[2].lines.len(), 1);
-        assert_eq2!(history_stack[2].lines[0].string, "ghi");
+        history::undo(&mut editor_buffer);
+        assert_eq2!(editor_buffer.history.current_index, 0);
+        assert_eq2!(get_lines(&editor_buffer), vec!["abc"]);
+
+        assert_eq2!(editor_buffer.history.edits.len(), 2);
     }
 
     #[test]
     fn test_multiple_undos() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        set_lines(&mut editor_buffer, &["abc"]);
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")];
+        set_lines(&mut editor_buffer, &["def"]);
         history::push(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 1);
 
@@ -471,16 +979,16 @@ mod history_tests {
         history::undo(&mut editor_buffer);
 
         assert_eq2!(editor_buffer.history.current_index, 0);
+        assert_eq2!(get_lines(&editor_buffer), vec!["abc"]);
     }
 
     #[test]
     fn test_undo_and_multiple_redos() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        set_lines(&mut editor_buffer, &["abc"]);
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")];
+        set_lines(&mut editor_buffer, &["def"]);
         history::push(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 1);
         let snapshot_content = editor_buffer.editor_content.clone();
@@ -498,13 +1006,186 @@ mod history_tests {
 
         // Redo.
         history::redo(&mut editor_buffer);
+        assert_eq2!(editor_buffer.history.current_index, 1);
+        assert_eq2!(editor_buffer.editor_content, snapshot_content);
+    }
+
+    #[test]
+    fn test_undo_redo_lines_inserted_and_removed() {
+        let mut editor_buffer = EditorBuffer::default();
+        set_lines(&mut editor_buffer, &["abc", "def"]);
+        history::push(&mut editor_buffer);
+
+        // Insert lines in the middle.
+        set_lines(&mut editor_buffer, &["abc", "x", "y", "def"]);
+        history::push(&mut editor_buffer);
+
+        // Remove lines from the start.
+        set_lines(&mut editor_buffer, &["y", "def"]);
+        history::push(&mut editor_buffer);
+
+        // Remove all the lines.
+        set_lines(&mut editor_buffer, &[]);
+        history::push(&mut editor_buffer);
+
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["y", "def"]);
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["abc", "x", "y", "def"]);
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["abc", "def"]);
+
+        history::redo(&mut editor_buffer);
+        history::redo(&mut editor_buffer);
+        history::redo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_undo_keeps_caret_and_redo_restores_caret_after_edit() {
+        let mut editor_buffer = EditorBuffer::default();
+        set_lines(&mut editor_buffer, &["abc"]);
+        editor_buffer.editor_content.caret_display_position =
+            position!(col_index: 1, row_index: 0);
+        history::push(&mut editor_buffer);
+
+        set_lines(&mut editor_buffer, &["aXbc"]);
+        editor_buffer.editor_content.caret_display_position =
+            position!(col_index: 2, row_index: 0);
+        history::push(&mut editor_buffer);
+
+        // The caret is moved away from the edit before the undo.
+        editor_buffer.editor_content.caret_display_position =
+            position!(col_index: 4, row_index: 0);
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["abc".to_string()]);
+        assert_eq2!(
+            editor_buffer.editor_content.caret_display_position,
+            position!(col_index: 4, row_index: 0)
+        );
+
+        history::redo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["aXbc".to_string()]);
+        assert_eq2!(
+            editor_buffer.editor_content.caret_display_position,
+            position!(col_index: 2, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_undo_restores_scroll_offset_and_selection_before_edit() {
+        let mut editor_buffer = EditorBuffer::default();
+        set_lines(&mut editor_buffer, &["abc"]);
+        editor_buffer.editor_content.scroll_offset =
+            position!(col_index: 1, row_index: 0);
+        editor_buffer
+            .editor_content
+            .selection_map
+            .map
+            .insert(ch!(0), SelectionRange::new(ch!(0), ch!(2)));
+        let selection_map_before_edit =
+            editor_buffer.editor_content.selection_map.clone();
+        history::push(&mut editor_buffer);
+
+        set_lines(&mut editor_buffer, &["c"]);
+        editor_buffer.editor_content.scroll_offset = ScrollOffset::default();
+        editor_buffer.editor_content.selection_map = SelectionMap::default();
+        history::push(&mut editor_buffer);
+
+        history::undo(&mut editor_buffer);
+        assert_eq2!(
+            editor_buffer.editor_content.scroll_offset,
+            position!(col_index: 1, row_index: 0)
+        );
+        assert_eq2!(
+            editor_buffer.editor_content.selection_map,
+            selection_map_before_edit
+        );
+    }
+
+    #[test]
+    fn test_typed_chars_are_coalesced() {
+        let mut editor_buffer = EditorBuffer::default();
+        set_lines(&mut editor_buffer, &[""]);
+        history::push(&mut editor_buffer);
+
+        let mut line = String::new();
+        for (col_index, character) in "hello world".chars().enumerate() {
+            line.push(character);
+            set_lines(&mut editor_buffer, &[line.as_str()]);
+            editor_buffer.editor_content.caret_display_position =
+                position!(col_index: col_index + 1, row_index: 0);
+            history::push_typed_char(&mut editor_buffer, character);
+        }
+
+        // "hello " and "world".
+        assert_eq2!(editor_buffer.history.edits.len(), 2);
+        assert_eq2!(editor_buffer.history.edits[0].span.inserted_text, "hello ");
+        assert_eq2!(editor_buffer.history.edits[1].span.inserted_text, "world");
+
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["hello "]);
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec![""]);
+    }
 
-        let history_stack = editor_buffer.history.versions;
-        assert_eq2!(history_stack.len(), 2);
-        assert_eq2!(history_stack[0].lines.len(), 1);
-        assert_eq2!(history_stack[0].lines[0].string, "abc");
-        assert_eq2!(history_stack[1].lines.len(), 1);
-        assert_eq2!(history_stack[1].lines[0].string, "def");
+    #[test]
+    fn test_typed_chars_are_not_coalesced_after_caret_moves() {
+        let mut editor_buffer = EditorBuffer::default();
+        set_lines(&mut editor_buffer, &[""]);
+        history::push(&mut editor_buffer);
+
+        set_lines(&mut editor_buffer, &["a"]);
+        editor_buffer.editor_content.caret_display_position =
+            position!(col_index: 1, row_index: 0);
+        history::push_typed_char(&mut editor_buffer, 'a');
+
+        // Move the caret to the start of the line & type.
+        editor_buffer.editor_content.caret_display_position =
+            position!(col_index: 0, row_index: 0);
+        history::push(&mut editor_buffer);
+        set_lines(&mut editor_buffer, &["ba"]);
+        editor_buffer.editor_content.caret_display_position =
+            position!(col_index: 1, row_index: 0);
+        history::push_typed_char(&mut editor_buffer, 'b');
+
+        assert_eq2!(editor_buffer.history.edits.len(), 2);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let mut editor_buffer = EditorBuffer::default();
+        editor_buffer.set_history_max_depth(2);
+        set_lines(&mut editor_buffer, &["a"]);
+        history::push(&mut editor_buffer);
+
+        for it in ["b", "c", "d", "e"] {
+            set_lines(&mut editor_buffer, &[it]);
+            history::push(&mut editor_buffer);
+        }
+
+        assert_eq2!(editor_buffer.history.edits.len(), 2);
+        assert_eq2!(editor_buffer.history.current_index, 2);
+
+        history::undo(&mut editor_buffer);
+        history::undo(&mut editor_buffer);
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["c"]);
+    }
+
+    #[test]
+    fn test_wide_graphemes() {
+        let mut editor_buffer = EditorBuffer::default();
+        set_lines(&mut editor_buffer, &["a😃b"]);
+        history::push(&mut editor_buffer);
+
+        set_lines(&mut editor_buffer, &["a😃🙏🏽b"]);
+        history::push(&mut editor_buffer);
+        assert_eq2!(editor_buffer.history.edits[0].span.inserted_text, "🙏🏽");
+
+        history::undo(&mut editor_buffer);
+        assert_eq2!(get_lines(&editor_buffer), vec!["a😃b"]);
+        assert_eq2!(editor_buffer.editor_content.lines[0].display_width, ch!(4));
     }
 }
 
@@ -585,6 +1266,8 @@ pub mod access_and_mutate {
             self.editor_content.caret_display_position = Position::default();
             // Reset scroll_offset.
             self.editor_content.scroll_offset = ScrollOffset::default();
+            // Reset secondary carets.
+            self.editor_content.secondary_carets.clear();
         }
 
         /// Returns the current caret position in two variants:
@@ -623,6 +1306,57 @@ pub mod access_and_mutate {
             self.editor_content.scroll_offset
         }
 
+        /// Move the caret to `caret_adj` (a scroll adjusted position) w/out an
+        /// [EditorEngine], eg: after the buffer is changed by the app. Since the size of the
+        /// viewport isn't known here, the buffer is only scrolled up or left (if needed),
+        /// and the viewport is scrolled to the caret when the editor is rendered next. Use
+        /// [EditorEngineInternalApi::move_caret_to] if an [EditorEngine] is available.
+        pub fn set_caret_scroll_adjusted(&mut self, caret_adj: Position) {
+            let EditorContent {
+                caret_display_position: caret,
+                scroll_offset,
+                ..
+            } = &mut self.editor_content;
+
+            if caret_adj.row_index >= scroll_offset.row_index {
+                caret.row_index = caret_adj.row_index - scroll_offset.row_index;
+            } else {
+                scroll_offset.row_index = caret_adj.row_index;
+                caret.row_index = ch!(0);
+            }
+
+            if caret_adj.col_index >= scroll_offset.col_index {
+                caret.col_index = caret_adj.col_index - scroll_offset.col_index;
+            } else {
+                scroll_offset.col_index = caret_adj.col_index;
+                caret.col_index = ch!(0);
+            }
+        }
+
+        /// Move the caret to the start of the line at `row_index` and scroll the buffer so
+        /// that this line is at the top of the viewport, eg: to jump to a heading. The
+        /// selection & any secondary carets are cleared. If the line is near the end of
+        /// the buffer, the scroll offset is adjusted when the editor is rendered next.
+        pub fn jump_to_row(&mut self, row_index: ChUnit) {
+            let max_row_index = ch!(self.get_lines().len()) - ch!(1);
+            let row_index = row_index.min(max_row_index);
+
+            let EditorContent {
+                caret_display_position: caret,
+                scroll_offset,
+                selection_map,
+                secondary_carets,
+                ..
+            } = &mut self.editor_content;
+
+            *scroll_offset = position!(col_index: 0, row_index: row_index);
+            *caret = Position::default();
+            selection_map.clear();
+            secondary_carets.clear();
+        }
+
+        pub fn get_kill_ring(&self) -> &KillRing { &self.kill_ring }
+
         /// Returns:
         /// 1. /* lines */ &mut `Vec<UnicodeString>`,
         /// 2. /* caret */ &mut Position,
@@ -657,6 +1391,72 @@ pub mod access_and_mutate {
         pub fn get_selection_map(&self) -> &SelectionMap {
             &self.editor_content.selection_map
         }
+
+        pub fn get_maybe_search_query(&self) -> Option<&SearchQuery> {
+            self.editor_content.maybe_search_query.as_ref()
+        }
+
+        /// Set the [SearchQuery] that is used by find and replace, or clear it by passing
+        /// [None].
+        pub fn set_search_query(&mut self, maybe_search_query: Option<SearchQuery>) {
+            self.editor_content.maybe_search_query = maybe_search_query;
+        }
+
+        pub fn get_secondary_carets(&self) -> &Vec<EditorCaret> {
+            &self.editor_content.secondary_carets
+        }
+
+        pub fn has_secondary_carets(&self) -> bool {
+            !self.editor_content.secondary_carets.is_empty()
+        }
+
+        pub fn set_secondary_carets(&mut self, secondary_carets: Vec<EditorCaret>) {
+            self.editor_content.secondary_carets = secondary_carets;
+        }
+
+        pub fn clear_secondary_carets(&mut self) {
+            self.editor_content.secondary_carets.clear();
+        }
+
+        pub fn get_line_marker(&self, row_index: RowIndex) -> Option<&LineMarker> {
+            self.editor_content.line_markers.get(&row_index)
+        }
+
+        pub fn get_line_markers(&self) -> &HashMap<RowIndex, LineMarker> {
+            &self.editor_content.line_markers
+        }
+
+        /// Replace all the [LineMarker]s that are shown in the gutter. Key is the row
+        /// index.
+        pub fn set_line_markers(&mut self, line_markers: HashMap<RowIndex, LineMarker>) {
+            self.editor_content.line_markers = line_markers;
+        }
+
+        /// Set (or clear, by passing [None]) the [LineMarker] of a single line.
+        pub fn set_line_marker(
+            &mut self,
+            row_index: RowIndex,
+            maybe_line_marker: Option<LineMarker>,
+        ) {
+            match maybe_line_marker {
+                Some(line_marker) => {
+                    self.editor_content
+                        .line_markers
+                        .insert(row_index, line_marker);
+                }
+                None => {
+                    self.editor_content.line_markers.remove(&row_index);
+                }
+            }
+        }
+
+        pub fn clear_line_markers(&mut self) { self.editor_content.line_markers.clear(); }
+
+        /// Set the maximum number of undo steps that are kept. The default is
+        /// [DEFAULT_HISTORY_MAX_DEPTH].
+        pub fn set_history_max_depth(&mut self, max_depth: usize) {
+            self.history.set_max_depth(max_depth);
+        }
     }
 }
 
@@ -669,10 +1469,14 @@ pub mod debug_format_helpers {
                 f,
                 "\nEditorBuffer [                                  \n \
                 ├ content: {0:?}                                     \n \
-                └ history: {1:?}                                     \n \
+                ├ history: {1:?}                                     \n \
+                ├ file_info: {2:?}                                   \n \
+                └ kill_ring: {3}                                     \n \
                 ]",
                 /* 0 */ self.editor_content,
                 /* 1 */ self.history,
+                /* 2 */ self.maybe_file_info,
+                /* 3 */ self.kill_ring.len(),
             }
         }
     }
@@ -684,6 +1488,9 @@ pub mod debug_format_helpers {
                 "\n\tEditorContent [                                  \n \
                 \t├ lines: {0}, size: {1}                            \n \
                 \t├ selection_map: {4}                               \n \
+                \t├ search_query: {6:?}                              \n \
+                \t├ secondary_carets: {7}                            \n \
+                \t├ line_markers: {8}                                \n \
                 \t└ ext: {2:?}, caret: {3:?}, scroll_offset: {5:?}   \n \
                 \t]",
                 /* 0 */ self.lines.len(),
@@ -692,6 +1499,9 @@ pub mod debug_format_helpers {
                 /* 3 */ self.caret_display_position,
                 /* 4 */ self.selection_map.to_formatted_string(),
                 /* 5 */ self.scroll_offset,
+                /* 6 */ self.maybe_search_query,
+                /* 7 */ self.secondary_carets.len(),
+                /* 8 */ self.line_markers.len(),
             }
         }
     }
@@ -701,12 +1511,14 @@ pub mod debug_format_helpers {
             write! {
                 f,
                 "\n\tEditorBufferHistory [                           \n \
-                \t├ stack: {0}, size: {1}                            \n \
-                \t└ index: {2}                                       \n \
+                \t├ edits: {0}, size: {1}                            \n \
+                \t└ index: {2}, max_depth: {3}, saved_index: {4:?}   \n \
                 \t]",
-                /* 0 */ self.versions.len(),
-                /* 1 */ self.versions.get_heap_size(),
-                /* 2 */ self.current_index
+                /* 0 */ self.edits.len(),
+                /* 1 */ self.edits.get_heap_size(),
+                /* 2 */ self.current_index,
+                /* 3 */ self.max_depth,
+                /* 4 */ self.maybe_saved_index
             }
         }
     }
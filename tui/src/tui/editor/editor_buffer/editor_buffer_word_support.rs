@@ -0,0 +1,353 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+pub enum WordDirection {
+    Left,
+    Right,
+}
+
+/// Word-wise caret movement, selection and deletion.
+///
+/// The word boundaries come from Unicode word segmentation ([UAX
+/// #29](https://www.unicode.org/reports/tr29/#Word_Boundaries)) of the line. Only the
+/// segments that contain alphanumeric characters (or `_`) are words, and the whitespace
+/// & punctuation in between them is skipped over. Since word boundaries are also grapheme
+/// cluster boundaries, the caret never ends up in the middle of a grapheme cluster.
+///
+/// ```text
+///   let foo_bar = "baz";
+///   ├─┤ ├─────┤    ├─┤
+///   word  word     word
+/// ```
+///
+/// 1. Moving right goes to the end of the next word (or the end of the line if there are
+///    no more words in it). At the end of a line, it goes to the start of the next line.
+/// 2. Moving left goes to the start of the previous word (or the start of the line). At
+///    the start of a line, it goes to the end of the previous line.
+/// 3. Deleting a word deletes the text between the caret and where it would move to. If
+///    that is on another line, the lines are joined instead (just like
+///    [EditorEvent::Backspace] and [EditorEvent::Delete]).
+pub mod word_support {
+    use super::*;
+
+    /// Returns the display col ranges of the words in the line.
+    pub fn find_word_ranges(line: &UnicodeString) -> Vec<SelectionRange> {
+        line.string
+            .split_word_bound_indices()
+            .filter(|(_, segment)| is_word(segment))
+            .filter_map(|(byte_offset, segment)| {
+                let start = find_display_col_at_byte_offset(line, byte_offset)?;
+                let end =
+                    find_display_col_at_byte_offset(line, byte_offset + segment.len())?;
+                Some(SelectionRange::new(start, end))
+            })
+            .collect()
+    }
+
+    /// Returns the range of the word that contains the display col (or that ends at it).
+    pub fn find_word_at(
+        line: &UnicodeString,
+        display_col: ChUnit,
+    ) -> Option<SelectionRange> {
+        let word_ranges = find_word_ranges(line);
+        word_ranges
+            .iter()
+            .find(|range| {
+                range.start_display_col_index <= display_col
+                    && display_col < range.end_display_col_index
+            })
+            .or_else(|| {
+                word_ranges
+                    .iter()
+                    .find(|range| range.end_display_col_index == display_col)
+            })
+            .copied()
+    }
+
+    /// Returns the (scroll adjusted) position that the caret moves to, or [None] if it
+    /// can't move any further.
+    pub fn find_word_boundary(
+        lines: &[UnicodeString],
+        caret_adj: Position,
+        direction: WordDirection,
+    ) -> Option<Position> {
+        let row_index = ch!(@to_usize caret_adj.row_index);
+        let line = lines.get(row_index)?;
+        let col_index = caret_adj.col_index;
+
+        match direction {
+            WordDirection::Left => {
+                if col_index == ch!(0) {
+                    let previous_line = lines.get(row_index.checked_sub(1)?)?;
+                    return Some(position!(
+                        col_index: previous_line.display_width,
+                        row_index: row_index - 1
+                    ));
+                }
+                let new_col_index = find_word_ranges(line)
+                    .iter()
+                    .rev()
+                    .find(|range| range.start_display_col_index < col_index)
+                    .map(|range| range.start_display_col_index)
+                    .unwrap_or(ch!(0));
+                Some(position!(col_index: new_col_index, row_index: row_index))
+            }
+            WordDirection::Right => {
+                if col_index >= line.display_width {
+                    if row_index + 1 >= lines.len() {
+                        return None;
+                    }
+                    return Some(position!(col_index: 0, row_index: row_index + 1));
+                }
+                let new_col_index = find_word_ranges(line)
+                    .iter()
+                    .find(|range| range.end_display_col_index > col_index)
+                    .map(|range| range.end_display_col_index)
+                    .unwrap_or(line.display_width);
+                Some(position!(col_index: new_col_index, row_index: row_index))
+            }
+        }
+    }
+
+    /// Move the caret to the next (or previous) word boundary. If `select_mode` is
+    /// enabled, the selection is extended to the new caret position, otherwise it is
+    /// cleared.
+    pub fn move_caret_by_word(
+        args: EditorArgsMut<'_>,
+        direction: WordDirection,
+        select_mode: SelectMode,
+    ) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if editor_buffer.is_empty() {
+            return;
+        }
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let Some(new_caret_adj) =
+            find_word_boundary(editor_buffer.get_lines(), caret_adj, direction)
+        else {
+            return;
+        };
+        if new_caret_adj.row_index != caret_adj.row_index
+            && editor_engine.config_options.multiline_mode == LineMode::SingleLine
+        {
+            return;
+        }
+
+        // This is only set if select_mode is enabled.
+        let maybe_previous_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            new_caret_adj,
+        );
+
+        // This is only set if select_mode is enabled.
+        let maybe_current_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        if new_caret_adj.row_index == caret_adj.row_index {
+            select_mode.handle_selection_single_line_caret_movement(
+                editor_buffer,
+                maybe_previous_caret_display_position,
+                maybe_current_caret_display_position,
+            );
+        } else {
+            select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+                editor_buffer,
+                maybe_previous_caret_display_position,
+                maybe_current_caret_display_position,
+            );
+        }
+    }
+
+    /// Delete the text between the caret and the next (or previous) word boundary. If
+    /// there is a selection, then it is deleted instead.
+    pub fn delete_word(args: EditorArgsMut<'_>, direction: WordDirection) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if editor_buffer.is_empty() {
+            return;
+        }
+
+        if editor_buffer.has_selection() {
+            EditorEngineInternalApi::delete_selected(
+                editor_buffer,
+                editor_engine,
+                match direction {
+                    WordDirection::Left => DeleteSelectionWith::Backspace,
+                    WordDirection::Right => DeleteSelectionWith::Delete,
+                },
+            );
+            return;
+        }
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let Some(word_boundary) =
+            find_word_boundary(editor_buffer.get_lines(), caret_adj, direction)
+        else {
+            return;
+        };
+
+        // Join the lines.
+        if word_boundary.row_index != caret_adj.row_index {
+            match direction {
+                WordDirection::Left => {
+                    EditorEngineInternalApi::backspace_at_caret(
+                        editor_buffer,
+                        editor_engine,
+                    );
+                }
+                WordDirection::Right => {
+                    EditorEngineInternalApi::delete_at_caret(
+                        editor_buffer,
+                        editor_engine,
+                    );
+                }
+            }
+            return;
+        }
+
+        let (start_col_index, end_col_index) = match direction {
+            WordDirection::Left => (word_boundary.col_index, caret_adj.col_index),
+            WordDirection::Right => (caret_adj.col_index, word_boundary.col_index),
+        };
+        let row_index = ch!(@to_usize caret_adj.row_index);
+        let Some(line) = editor_buffer.get_lines().get(row_index) else {
+            return;
+        };
+        let new_line = {
+            let mut it = line.clip_to_width(ch!(0), start_col_index).to_string();
+            it.push_str(line.clip_to_width(end_col_index, line.display_width));
+            it
+        };
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                lines[row_index] = new_line.into();
+            },
+        );
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            position!(col_index: start_col_index, row_index: caret_adj.row_index),
+        );
+    }
+
+    /// Select the word at the (scroll adjusted) position, and move the caret to the end of
+    /// it. If there is no word there, the caret is just moved to the position. This is
+    /// used for double clicks.
+    pub fn select_word_at(args: EditorArgsMut<'_>, position: Position) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if editor_buffer.is_empty() {
+            return;
+        }
+
+        let row_index = ch!(@to_usize position.row_index);
+        let Some(line) = editor_buffer.get_lines().get(row_index) else {
+            return;
+        };
+        let maybe_word_range = find_word_at(line, position.col_index);
+
+        editor_buffer.clear_selection();
+
+        let Some(word_range) = maybe_word_range else {
+            EditorEngineInternalApi::move_caret_to(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                position,
+            );
+            return;
+        };
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            position!(
+                col_index: word_range.start_display_col_index,
+                row_index: position.row_index
+            ),
+        );
+        EditorBufferApi::handle_selection_single_line_caret_movement(
+            editor_buffer,
+            position.row_index,
+            word_range.start_display_col_index,
+            word_range.end_display_col_index,
+        );
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            position!(
+                col_index: word_range.end_display_col_index,
+                row_index: position.row_index
+            ),
+        );
+    }
+
+    fn is_word(segment: &str) -> bool {
+        segment
+            .chars()
+            .any(|character| character.is_alphanumeric() || character == '_')
+    }
+
+    fn find_display_col_at_byte_offset(
+        line: &UnicodeString,
+        byte_offset: usize,
+    ) -> Option<ChUnit> {
+        if byte_offset == line.string.len() {
+            return Some(line.display_width);
+        }
+        line.vec_segment
+            .iter()
+            .find(|segment| segment.byte_offset == byte_offset)
+            .map(|segment| segment.display_col_offset)
+    }
+}
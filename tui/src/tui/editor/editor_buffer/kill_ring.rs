@@ -0,0 +1,93 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use get_size::GetSize;
+use serde::*;
+
+use crate::*;
+
+pub const DEFAULT_KILL_RING_MAX_LEN: usize = 16;
+
+/// The recent copies (and cuts) from an [EditorBuffer], most recent first. Unlike the
+/// clipboard (which only holds the latest one), older entries can be pasted too, eg: by
+/// showing them in an autocomplete dialog
+/// ([DialogEngineMode::ModalAutocomplete]):
+///
+/// 1. Use [get_completion_items](KillRing::get_completion_items) to fill
+///    [DialogBuffer::maybe_results] when the dialog is shown.
+/// 2. In the [OnDialogPressFn], pass the text in [DialogChoice::Yes] to
+///    [EditorBuffer::paste_text].
+///
+/// The `ex_editor` demo does this when <kbd>Ctrl+p</kbd> is pressed. If the [EditorEngine]
+/// is at hand, [EditorEvent::PasteFromKillRing] pastes an entry by its index instead.
+///
+/// Copying text that is already in the kill ring moves it to the front (instead of
+/// adding it again). Once there are `max_len` entries, the oldest ones are dropped.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+pub struct KillRing {
+    entries: Vec<String>,
+    max_len: usize,
+}
+
+impl Default for KillRing {
+    fn default() -> Self { Self::new(DEFAULT_KILL_RING_MAX_LEN) }
+}
+
+impl KillRing {
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            entries: vec![],
+            max_len,
+        }
+    }
+
+    pub fn push(&mut self, content: &str) {
+        if content.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry != content);
+        self.entries.insert(0, content.to_string());
+        self.entries.truncate(self.max_len);
+    }
+
+    pub fn get_entries(&self) -> &[String] { &self.entries }
+
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// The label of each item is the first line of the entry (w/ the number of lines as
+    /// the detail, if there are more), and the insert text is the whole entry.
+    pub fn get_completion_items(&self) -> Vec<CompletionItem> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let line_count = entry.lines().count();
+                let first_line = entry.lines().next().unwrap_or_default();
+                if line_count > 1 {
+                    CompletionItem::new(
+                        format!("{first_line} …"),
+                        Some(format!("{line_count} lines")),
+                        entry.clone(),
+                    )
+                } else {
+                    CompletionItem::new(first_line, None, entry.clone())
+                }
+            })
+            .collect()
+    }
+}
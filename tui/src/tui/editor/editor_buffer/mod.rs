@@ -17,11 +17,23 @@
 
 // Attach.
 pub mod editor_buffer_clipboard_support;
+pub mod editor_buffer_completion_support;
+pub mod editor_buffer_file_support;
+pub mod editor_buffer_multi_caret_support;
+pub mod editor_buffer_search_support;
 pub mod editor_buffer_selection_support;
 pub mod editor_buffer_struct;
+pub mod editor_buffer_word_support;
+pub mod kill_ring;
 pub mod selection_map;
 
 // Re-export.
+pub use editor_buffer_clipboard_support::*;
+pub use editor_buffer_file_support::*;
+pub use editor_buffer_multi_caret_support::*;
+pub use editor_buffer_search_support::*;
 pub use editor_buffer_selection_support::*;
 pub use editor_buffer_struct::*;
+pub use editor_buffer_word_support::*;
+pub use kill_ring::*;
 pub use selection_map::*;
@@ -40,13 +40,71 @@ pub enum EditorEvent {
     PageDown,
     PageUp,
     MoveCaret(CaretDirection),
+    /// Move the caret to the next (or previous) word boundary.
+    MoveCaretByWord(WordDirection),
+    /// Indent the selected lines, or insert spaces up to the next indentation stop.
+    /// Please take a look at [indent_support] for more details.
+    Indent,
+    /// Outdent the selected lines (or the caret line).
+    Outdent,
+    /// Re-align the Markdown table under the caret. Please take a look at
+    /// [table_support] for more details.
+    AlignTable,
+    /// Re-write the Markdown document in a canonical format. Please take a look at
+    /// [format_support] for more details.
+    FormatDocument,
+    /// Toggle the checkbox of the task (eg: `- [ ] todo`) on the caret line. Please take a
+    /// look at [task_list_support] for more details.
+    ToggleCheckbox,
+    /// Toggle the checkbox of the task at the (scroll adjusted) position, if the position
+    /// is on the checkbox, eg: on click.
+    ToggleCheckboxAt(Position),
+    /// Delete from the caret to the start of the previous word.
+    DeleteWordLeft,
+    /// Delete from the caret to the end of the next word.
+    DeleteWordRight,
+    /// Select the word at the (scroll adjusted) position, eg: on double click.
+    SelectWordAt(Position),
+    /// Move the caret to the (scroll adjusted) position & clear the selection, eg: on
+    /// click.
+    PlaceCaretAt(Position),
+    /// Select the text between the two (scroll adjusted) positions & move the caret to
+    /// `caret`, eg: on drag.
+    SelectRange {
+        anchor: Position,
+        caret: Position,
+    },
+    /// Scroll the viewport up by this many rows, without moving the caret (unless it
+    /// would end up outside of the viewport), eg: on mouse wheel.
+    ScrollUp(usize),
+    /// Scroll the viewport down by this many rows, without moving the caret (unless it
+    /// would end up outside of the viewport), eg: on mouse wheel.
+    ScrollDown(usize),
     Resize(Size),
     Select(SelectionScope),
     Copy,
     Paste,
+    /// Paste the entry of the [KillRing] at this index (the most recent one is at `0`),
+    /// eg: the one that the user picked in a dialog.
+    PasteFromKillRing(usize),
     Cut,
     Undo,
     Redo,
+    /// Set the [SearchQuery] that is used by find and replace (and highlight its
+    /// matches), or clear it w/ [None].
+    SetSearchQuery(Option<SearchQuery>),
+    FindNext,
+    FindPrevious,
+    /// Replace the selected match & select the next one.
+    Replace(String),
+    ReplaceAll(String),
+    /// Add a caret on the line above the top-most caret.
+    AddCaretAbove,
+    /// Add a caret on the line below the bottom-most caret.
+    AddCaretBelow,
+    /// Add a caret that selects the next occurrence of the selected text.
+    AddCaretAtNextOccurrence,
+    ClearSecondaryCarets,
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -59,6 +117,8 @@ pub enum SelectionScope {
     PageDown,
     Home,
     End,
+    WordLeft,
+    WordRight,
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, GetSize)]
@@ -102,6 +162,146 @@ impl TryFrom<InputEvent> for EditorEvent {
                     },
             }) => Ok(EditorEvent::Redo),
 
+            // Find events.
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::FunctionKey(FunctionKey::F3),
+            }) => Ok(EditorEvent::FindNext),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::FunctionKey(FunctionKey::F3),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::FindPrevious),
+
+            // Multi caret events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Up),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::AddCaretAbove),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Down),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::AddCaretBelow),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('d'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::AddCaretAtNextOccurrence),
+
+            // Table events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('t'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::AlignTable),
+
+            // Format events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('f'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::FormatDocument),
+
+            // Task list events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('x'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::ToggleCheckbox),
+
+            // Word events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Left),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::MoveCaretByWord(WordDirection::Left)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::MoveCaretByWord(WordDirection::Right)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Left),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionScope::WordLeft)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionScope::WordRight)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Backspace),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::DeleteWordLeft),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Delete),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::DeleteWordRight),
+
             // Selection events.
             InputEvent::Keyboard(KeyPress::WithModifiers {
                 key: Key::SpecialKey(SpecialKey::Right),
@@ -233,6 +433,25 @@ impl TryFrom<InputEvent> for EditorEvent {
 
             InputEvent::Resize(size) => Ok(EditorEvent::Resize(size)),
 
+            // Indent events.
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Tab),
+            }) => Ok(Self::Indent),
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::BackTab),
+            }) => Ok(Self::Outdent),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::BackTab | SpecialKey::Tab),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(Self::Outdent),
+
             InputEvent::Keyboard(KeyPress::Plain {
                 key: Key::Character(character),
             }) => Ok(Self::InsertChar(character)),
@@ -287,12 +506,103 @@ impl EditorEvent {
         );
     }
 
+    /// What happens to the secondary carets (if any) when this event is applied.
+    pub fn get_multi_caret_behavior(&self) -> MultiCaretBehavior {
+        match self {
+            EditorEvent::InsertChar(_)
+            | EditorEvent::InsertString(_)
+            | EditorEvent::InsertNewLine
+            | EditorEvent::Delete
+            | EditorEvent::Backspace
+            | EditorEvent::Paste
+            | EditorEvent::PasteFromKillRing(_)
+            | EditorEvent::MoveCaret(_)
+            | EditorEvent::MoveCaretByWord(_)
+            | EditorEvent::DeleteWordLeft
+            | EditorEvent::DeleteWordRight
+            | EditorEvent::Home
+            | EditorEvent::End => MultiCaretBehavior::ApplyToAllCarets,
+
+            EditorEvent::Resize(_)
+            | EditorEvent::ScrollUp(_)
+            | EditorEvent::ScrollDown(_)
+            | EditorEvent::Copy
+            | EditorEvent::SetSearchQuery(_)
+            | EditorEvent::AddCaretAbove
+            | EditorEvent::AddCaretBelow
+            | EditorEvent::AddCaretAtNextOccurrence
+            | EditorEvent::ClearSecondaryCarets => {
+                MultiCaretBehavior::KeepSecondaryCarets
+            }
+
+            _ => MultiCaretBehavior::ClearSecondaryCarets,
+        }
+    }
+
     pub fn apply_editor_event(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
         editor_event: EditorEvent,
     ) {
+        if editor_buffer.has_secondary_carets() {
+            match editor_event.get_multi_caret_behavior() {
+                MultiCaretBehavior::ApplyToAllCarets => {
+                    // The secondary carets are removed from the buffer while each caret
+                    // is processed, so this does not recurse any further.
+                    multi_caret_support::apply_to_all_carets(
+                        EditorArgsMut {
+                            editor_buffer,
+                            editor_engine,
+                        },
+                        |editor_engine, editor_buffer| {
+                            Self::apply_editor_event(
+                                editor_engine,
+                                editor_buffer,
+                                editor_event.clone(),
+                            )
+                        },
+                    );
+                    return;
+                }
+                MultiCaretBehavior::KeepSecondaryCarets => {}
+                MultiCaretBehavior::ClearSecondaryCarets => {
+                    editor_buffer.clear_secondary_carets();
+                }
+            }
+        }
+
         match editor_event {
+            EditorEvent::AddCaretAbove => {
+                multi_caret_support::add_caret_vertically(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    CaretDirection::Up,
+                );
+            }
+
+            EditorEvent::AddCaretBelow => {
+                multi_caret_support::add_caret_vertically(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    CaretDirection::Down,
+                );
+            }
+
+            EditorEvent::AddCaretAtNextOccurrence => {
+                multi_caret_support::add_caret_at_next_occurrence(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            EditorEvent::ClearSecondaryCarets => {
+                editor_buffer.clear_secondary_carets();
+            }
+
             EditorEvent::Undo => {
                 history::undo(editor_buffer);
             }
@@ -301,17 +611,112 @@ impl EditorEvent {
                 history::redo(editor_buffer);
             }
 
+            EditorEvent::SetSearchQuery(maybe_search_query) => {
+                editor_buffer.set_search_query(maybe_search_query);
+            }
+
+            EditorEvent::FindNext => {
+                search_support::select_match(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    SearchDirection::Next,
+                );
+            }
+
+            EditorEvent::FindPrevious => {
+                search_support::select_match(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    SearchDirection::Previous,
+                );
+            }
+
+            EditorEvent::Replace(replacement) => {
+                // The replacement is recorded as a single undo step.
+                history::push(editor_buffer);
+                search_support::replace_selected_match(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &replacement,
+                );
+                history::push(editor_buffer);
+            }
+
+            EditorEvent::ReplaceAll(replacement) => {
+                // All the replacements are recorded as a single undo step.
+                history::push(editor_buffer);
+                search_support::replace_all_matches(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &replacement,
+                );
+                history::push(editor_buffer);
+            }
+
             EditorEvent::InsertChar(character) => {
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
-                EditorEngineInternalApi::insert_str_at_caret(
+                bracket_support::insert_char_at_caret(
                     EditorArgsMut {
                         editor_buffer,
                         editor_engine,
                     },
-                    &String::from(character),
+                    character,
                 )
             }
 
+            EditorEvent::Indent => {
+                indent_support::indent(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            EditorEvent::Outdent => {
+                indent_support::outdent(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            EditorEvent::AlignTable => {
+                table_support::align_table_at_caret(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            EditorEvent::FormatDocument => {
+                format_support::format_document(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            EditorEvent::ToggleCheckbox => {
+                task_list_support::toggle_checkbox_at_caret(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            EditorEvent::ToggleCheckboxAt(position) => {
+                task_list_support::toggle_checkbox_at(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    position,
+                );
+            }
+
             EditorEvent::InsertNewLine => {
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
                 EditorEngineInternalApi::insert_new_line_at_caret(EditorArgsMut {
@@ -339,11 +744,12 @@ impl EditorEvent {
 
             EditorEvent::Backspace => {
                 if editor_buffer.get_selection_map().is_empty() {
-                    // There is no selection and we want to backspace a single character.
-                    EditorEngineInternalApi::backspace_at_caret(
+                    // There is no selection and we want to backspace a single character
+                    // (or an empty pair of brackets).
+                    bracket_support::backspace_at_caret(EditorArgsMut {
                         editor_buffer,
                         editor_engine,
-                    );
+                    });
                 } else {
                     // The text is selected and we want to delete the entire selected text.
                     EditorEngineInternalApi::delete_selected(
@@ -379,6 +785,90 @@ impl EditorEvent {
                 };
             }
 
+            EditorEvent::MoveCaretByWord(direction) => {
+                word_support::move_caret_by_word(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    direction,
+                    SelectMode::Disabled,
+                );
+            }
+
+            EditorEvent::DeleteWordLeft => {
+                word_support::delete_word(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    WordDirection::Left,
+                );
+            }
+
+            EditorEvent::DeleteWordRight => {
+                word_support::delete_word(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    WordDirection::Right,
+                );
+            }
+
+            EditorEvent::SelectWordAt(position) => {
+                word_support::select_word_at(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    position,
+                );
+            }
+
+            EditorEvent::PlaceCaretAt(position) => {
+                mouse_support::place_caret_at(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    position,
+                );
+            }
+
+            EditorEvent::SelectRange { anchor, caret } => {
+                mouse_support::select_range(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    anchor,
+                    caret,
+                );
+            }
+
+            EditorEvent::ScrollUp(row_count) => {
+                mouse_support::scroll_viewport(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    CaretDirection::Up,
+                    row_count,
+                );
+            }
+
+            EditorEvent::ScrollDown(row_count) => {
+                mouse_support::scroll_viewport(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    CaretDirection::Down,
+                    row_count,
+                );
+            }
+
             EditorEvent::InsertString(chunk) => {
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
                 EditorEngineInternalApi::insert_str_at_caret(
@@ -487,11 +977,32 @@ impl EditorEvent {
                         SelectMode::Enabled,
                     );
                 }
+                SelectionScope::WordLeft => {
+                    word_support::move_caret_by_word(
+                        EditorArgsMut {
+                            editor_buffer,
+                            editor_engine,
+                        },
+                        WordDirection::Left,
+                        SelectMode::Enabled,
+                    );
+                }
+                SelectionScope::WordRight => {
+                    word_support::move_caret_by_word(
+                        EditorArgsMut {
+                            editor_buffer,
+                            editor_engine,
+                        },
+                        WordDirection::Right,
+                        SelectMode::Enabled,
+                    );
+                }
             },
 
             EditorEvent::Cut => {
                 EditorEngineInternalApi::copy_editor_selection_to_clipboard(
                     editor_buffer,
+                    editor_engine,
                 );
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
             }
@@ -499,6 +1010,7 @@ impl EditorEvent {
             EditorEvent::Copy => {
                 EditorEngineInternalApi::copy_editor_selection_to_clipboard(
                     editor_buffer,
+                    editor_engine,
                 );
             }
 
@@ -511,6 +1023,17 @@ impl EditorEvent {
                     },
                 )
             }
+
+            EditorEvent::PasteFromKillRing(index) => {
+                Self::delete_text_if_selected(editor_engine, editor_buffer);
+                EditorEngineInternalApi::paste_kill_ring_entry_into_editor(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    index,
+                )
+            }
         };
     }
 
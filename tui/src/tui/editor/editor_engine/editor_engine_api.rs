@@ -34,6 +34,29 @@ impl EditorEngineApi {
         editor_engine: &mut EditorEngine,
         input_event: InputEvent,
     ) -> CommonResult<EditorEngineApplyEventResult> {
+        // Mouse events are translated here (instead of in [EditorEvent::try_from]), since
+        // this needs the layout of the viewport. Except for clicking a checkbox, they
+        // don't change the content, so they are allowed in read only mode too.
+        if let InputEvent::Mouse(mouse_input) = input_event {
+            let Some(editor_event) = mouse_support::to_editor_event(
+                editor_buffer,
+                editor_engine,
+                &mouse_input,
+            ) else {
+                return Ok(EditorEngineApplyEventResult::NotApplied);
+            };
+            let is_content_changed =
+                matches!(editor_event, EditorEvent::ToggleCheckboxAt(_));
+            if is_content_changed {
+                history::push(editor_buffer);
+            }
+            EditorEvent::apply_editor_event(editor_engine, editor_buffer, editor_event);
+            if is_content_changed {
+                history::push(editor_buffer);
+            }
+            return Ok(EditorEngineApplyEventResult::Applied);
+        }
+
         let editor_config = &editor_engine.config_options;
 
         if let EditMode::ReadOnly = editor_config.edit_mode {
@@ -62,15 +85,56 @@ impl EditorEngineApi {
                 KeyPress::Plain {
                     key: Key::SpecialKey(SpecialKey::PageDown),
                 },
+                KeyPress::Plain {
+                    key: Key::FunctionKey(FunctionKey::F3),
+                },
+                KeyPress::WithModifiers {
+                    key: Key::SpecialKey(SpecialKey::Left),
+                    mask: ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+                },
+                KeyPress::WithModifiers {
+                    key: Key::SpecialKey(SpecialKey::Right),
+                    mask: ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+                },
+                KeyPress::WithModifiers {
+                    key: Key::FunctionKey(FunctionKey::F3),
+                    mask: ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+                },
             ]) {
                 return Ok(EditorEngineApplyEventResult::NotApplied);
             }
         }
 
+        // Esc removes the secondary carets (if any). Otherwise it is left to the app.
+        if editor_buffer.has_secondary_carets()
+            && input_event.matches_keypress(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Esc),
+            })
+        {
+            EditorEvent::apply_editor_event(
+                editor_engine,
+                editor_buffer,
+                EditorEvent::ClearSecondaryCarets,
+            );
+            return Ok(EditorEngineApplyEventResult::Applied);
+        }
+
         if let Ok(editor_event) = EditorEvent::try_from(input_event) {
-            if editor_buffer.history.is_empty() {
-                history::push(editor_buffer);
-            }
+            // Record the initial version (if this is the first event), or the selection
+            // & scroll offset before the event is applied (so that undo can restore them).
+            history::push(editor_buffer);
 
             EditorEvent::apply_editor_event(
                 editor_engine,
@@ -79,8 +143,8 @@ impl EditorEngineApi {
             );
 
             match editor_event {
-                EditorEvent::InsertChar(_) => {
-                    history::push(editor_buffer);
+                EditorEvent::InsertChar(character) => {
+                    history::push_typed_char(editor_buffer, character);
                 }
                 EditorEvent::InsertString(_) => {
                     history::push(editor_buffer);
@@ -94,7 +158,25 @@ impl EditorEngineApi {
                 EditorEvent::Backspace => {
                     history::push(editor_buffer);
                 }
-                EditorEvent::Copy => {
+                EditorEvent::Indent => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::Outdent => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::AlignTable => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::FormatDocument => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::ToggleCheckbox => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::DeleteWordLeft => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::DeleteWordRight => {
                     history::push(editor_buffer);
                 }
                 EditorEvent::Paste => {
@@ -120,6 +202,40 @@ impl EditorEngineApi {
         throws_with_return!({
             editor_engine.current_box = current_box.into();
 
+            // The viewport width may have changed (the box was resized, or the gutter got
+            // wider or narrower), which changes the visual rows (or the horizontal scroll
+            // offset). The buffer may also have been changed w/out the engine (eg:
+            // EditorBuffer::paste_text), so the caret may not be in the viewport.
+            gutter_support::update_gutter_width(editor_buffer, editor_engine);
+            if soft_wrap_support::is_enabled(editor_engine) {
+                soft_wrap_support::validate_scroll(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            } else {
+                EditorEngineInternalApi::validate_scroll(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            // Only the blocks of the Markdown document that were touched by the edits since
+            // the last render are re-parsed & re-highlighted.
+            if matches!(
+                editor_engine.config_options.syntax_highlight,
+                SyntaxHighlightMode::Enable(_)
+            ) {
+                editor_engine.md_syn_hi_cache.update(
+                    editor_buffer.get_lines(),
+                    &editor_engine.current_box.get_computed_style(),
+                    Some((&editor_engine.syntax_set, &editor_engine.theme)),
+                );
+            }
+
+            // Which part of which line is painted on each row of the viewport.
+            let viewport_lines =
+                soft_wrap_support::layout_viewport(editor_buffer, editor_engine);
+
             // Create reusable args for render functions.
             let render_args = RenderArgs {
                 editor_buffer,
@@ -132,9 +248,32 @@ impl EditorEngineApi {
             } else {
                 let mut render_ops = render_ops!();
 
-                EditorEngineApi::render_content(&render_args, &mut render_ops);
-                EditorEngineApi::render_selection(&render_args, &mut render_ops);
-                EditorEngineApi::render_caret(&render_args, &mut render_ops);
+                gutter_support::render_gutter(
+                    render_args.editor_buffer,
+                    render_args.editor_engine,
+                    &viewport_lines,
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_content(
+                    &render_args,
+                    &viewport_lines,
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_selection(
+                    &render_args,
+                    &viewport_lines,
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_secondary_carets(
+                    &render_args,
+                    &viewport_lines,
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_caret(
+                    &render_args,
+                    &viewport_lines,
+                    &mut render_ops,
+                );
 
                 let mut render_pipeline = render_pipeline!();
                 render_pipeline.push(ZOrder::Normal, render_ops);
@@ -143,16 +282,16 @@ impl EditorEngineApi {
         })
     }
 
-    fn render_content(render_args: &RenderArgs<'_>, render_ops: &mut RenderOps) {
+    fn render_content(
+        render_args: &RenderArgs<'_>,
+        viewport_lines: &[ViewportLine],
+        render_ops: &mut RenderOps,
+    ) {
         let RenderArgs {
             editor_buffer,
             editor_engine,
             ..
         } = render_args;
-        let Size {
-            col_count: max_display_col_count,
-            row_count: max_display_row_count,
-        } = editor_engine.current_box.style_adjusted_bounds_size;
 
         let syntax_highlight_enabled = matches!(
             editor_engine.config_options.syntax_highlight,
@@ -162,74 +301,116 @@ impl EditorEngineApi {
         if !syntax_highlight_enabled {
             no_syn_hi_path::render_content(
                 editor_buffer,
-                max_display_row_count,
+                viewport_lines,
+                render_ops,
+                editor_engine,
+            );
+        } else {
+            // Render using syntect first.
+            syn_hi_syntect_path::render_content(
+                editor_buffer,
+                viewport_lines,
+                render_ops,
+                editor_engine,
+            );
+
+            // Any overrides can be applied here.
+            syn_hi_r3bl_path::render_content(
+                editor_buffer,
+                viewport_lines,
                 render_ops,
                 editor_engine,
-                max_display_col_count,
             );
-            return;
         }
 
-        // Render using syntect first.
-        syn_hi_syntect_path::render_content(
+        // Highlight the search matches on top of the content (the selection is painted on
+        // top of these).
+        search_match_path::render_content(
             editor_buffer,
-            max_display_row_count,
+            viewport_lines,
             render_ops,
             editor_engine,
-            max_display_col_count,
         );
 
-        // Any overrides can be applied here.
-        syn_hi_r3bl_path::render_content(
+        // Highlight the bracket at the caret and its matching bracket.
+        bracket_match_path::render_content(
             editor_buffer,
-            max_display_row_count,
+            viewport_lines,
             render_ops,
             editor_engine,
-            max_display_col_count,
         );
     }
 
     // BOOKM: Render selection
-    fn render_selection(render_args: &RenderArgs<'_>, render_ops: &mut RenderOps) {
+    fn render_selection(
+        render_args: &RenderArgs<'_>,
+        viewport_lines: &[ViewportLine],
+        render_ops: &mut RenderOps,
+    ) {
         let RenderArgs {
             editor_buffer,
             editor_engine,
             ..
         } = render_args;
 
-        for (row_index, range_of_display_col_indices) in
-            editor_buffer.get_selection_map().iter()
-        {
-            let row_index = *row_index;
-            let lines = editor_buffer.get_lines();
-
-            let scroll_offset = editor_buffer.get_scroll_offset();
-
-            if let Some(line) = lines.get(ch!(@to_usize *row_index)) {
-                // Take the scroll_offset into account when "slicing" the selection.
-                let selection = match range_of_display_col_indices
-                    .locate_scroll_offset_col(scroll_offset)
-                {
-                    ScrollOffsetColLocationInRange::Underflow => {
-                        let it = line.clip_to_range(*range_of_display_col_indices);
-                        if it.is_empty() {
-                            continue;
-                        };
-                        it
-                    }
-                    ScrollOffsetColLocationInRange::Overflow => {
-                        let scroll_offset_clipped_selection_range = SelectionRange {
-                            start_display_col_index: scroll_offset.col_index,
-                            ..*range_of_display_col_indices
-                        };
-                        let it =
-                            line.clip_to_range(scroll_offset_clipped_selection_range);
-                        if it.is_empty() {
-                            continue;
-                        };
-                        it
-                    }
-                };
+        // Each caret owns its selection.
+        EditorEngineApi::render_selection_map(
+            editor_buffer,
+            editor_engine,
+            editor_buffer.get_selection_map(),
+            viewport_lines,
+            render_ops,
+        );
+        for secondary_caret in editor_buffer.get_secondary_carets() {
+            EditorEngineApi::render_selection_map(
+                editor_buffer,
+                editor_engine,
+                &secondary_caret.selection_map,
+                viewport_lines,
+                render_ops,
+            );
+        }
+    }
+
+    /// A selected range in a line is painted on every visual row of the line that it
+    /// overlaps (there's only one visual row per line, unless the lines are soft wrapped).
+    fn render_selection_map(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        selection_map: &SelectionMap,
+        viewport_lines: &[ViewportLine],
+        render_ops: &mut RenderOps,
+    ) {
+        let lines = editor_buffer.get_lines();
+        let scroll_offset = editor_buffer.get_scroll_offset();
+
+        for (row_index, range_of_display_col_indices) in selection_map.iter() {
+            let Some(viewport_line) =
+                viewport_lines.iter().find(|it| it.row_index == *row_index)
+            else {
+                continue;
+            };
+            let Some(line) = lines.get(ch!(@to_usize *row_index)) else {
+                continue;
+            };
+
+            for visual_row in &viewport_line.visual_rows {
+                // Clip the selection to the part of the line that is in this visual row.
+                let start_col_index = range_of_display_col_indices
+                    .start_display_col_index
+                    .max(visual_row.range.start_display_col_index);
+                let end_col_index = range_of_display_col_indices
+                    .end_display_col_index
+                    .min(visual_row.range.end_display_col_index);
+                if start_col_index >= end_col_index {
+                    continue;
+                }
+
+                let selection = line
+                    .clip_to_range(SelectionRange::new(start_col_index, end_col_index));
+                if selection.is_empty() {
+                    continue;
+                }
 
                 call_if_true!(
                     DEBUG_TUI_COPY_PASTE,
@@ -241,27 +422,14 @@ impl EditorEngineApi {
                     ))
                 );
 
-                let position = {
-                    // Convert scroll adjusted to raw.
-                    let raw_row_index = {
-                        let row_scroll_offset = scroll_offset.row_index;
-                        row_index - row_scroll_offset
-                    };
-
-                    // Convert scroll adjusted to raw.
-                    let raw_col_index = {
-                        let col_scroll_offset = scroll_offset.col_index;
-                        range_of_display_col_indices.start_display_col_index
-                            - col_scroll_offset
-                    };
-
-                    let it =
-                        position!(col_index: raw_col_index, row_index: raw_row_index);
-                    it
-                };
+                // Convert scroll adjusted to raw.
+                let position = position!(
+                    col_index: start_col_index - visual_row.range.start_display_col_index,
+                    row_index: visual_row.raw_row_index
+                );
 
                 render_ops.push(RenderOp::MoveCursorPositionRelTo(
-                    editor_engine.current_box.style_adjusted_origin_pos,
+                    editor_engine.content_origin_pos(),
                     position,
                 ));
 
@@ -277,7 +445,71 @@ impl EditorEngineApi {
         }
     }
 
-    fn render_caret(render_args: &RenderArgs<'_>, render_ops: &mut RenderOps) {
+    /// The secondary carets (used for multi caret editing) are painted just like the
+    /// primary caret, except that the terminal cursor is not moved to them.
+    fn render_secondary_carets(
+        render_args: &RenderArgs<'_>,
+        viewport_lines: &[ViewportLine],
+        render_ops: &mut RenderOps,
+    ) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            has_focus,
+        } = render_args;
+
+        if !has_focus.does_id_have_focus(editor_engine.current_box.id) {
+            return;
+        }
+
+        let max_display_col_count = editor_engine.viewport_width();
+
+        for secondary_caret in editor_buffer.get_secondary_carets() {
+            let EditorCaret { position, .. } = secondary_caret;
+
+            // Skip the carets that are outside of the viewport.
+            let Some(raw_position) =
+                soft_wrap_support::find_viewport_position(viewport_lines, *position)
+            else {
+                continue;
+            };
+            let is_in_viewport = position.col_index
+                >= editor_buffer.get_scroll_offset().col_index
+                && raw_position.col_index < max_display_col_count;
+            if !is_in_viewport {
+                continue;
+            }
+
+            let str_at_caret: String = if let Some(UnicodeStringSegmentSliceResult {
+                unicode_string_seg: str_seg,
+                ..
+            }) = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize position.row_index))
+                .and_then(|line| line.get_string_at_display_col_index(position.col_index))
+            {
+                str_seg.string
+            } else {
+                DEFAULT_CURSOR_CHAR.into()
+            };
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.content_origin_pos(),
+                raw_position,
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                str_at_caret,
+                style! { attrib: [reverse] }.into(),
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    fn render_caret(
+        render_args: &RenderArgs<'_>,
+        viewport_lines: &[ViewportLine],
+        render_ops: &mut RenderOps,
+    ) {
         let RenderArgs {
             editor_buffer,
             editor_engine,
@@ -296,17 +528,25 @@ impl EditorEngineApi {
                 DEFAULT_CURSOR_CHAR.into()
             };
 
+            // The caret's raw position is in terms of lines, and not visual rows (which
+            // are different when lines are soft wrapped).
+            let caret_raw_position = soft_wrap_support::find_viewport_position(
+                viewport_lines,
+                editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+            )
+            .unwrap_or(editor_buffer.get_caret(CaretKind::Raw));
+
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
-                editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
+                editor_engine.content_origin_pos(),
+                caret_raw_position,
             ));
             render_ops.push(RenderOp::PaintTextWithAttributes(
                 str_at_caret,
                 style! { attrib: [reverse] }.into(),
             ));
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
-                editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
+                editor_engine.content_origin_pos(),
+                caret_raw_position,
             ));
             render_ops.push(RenderOp::ResetColor);
         }
@@ -366,49 +606,35 @@ pub enum EditorEngineApplyEventResult {
 mod syn_hi_r3bl_path {
     use super::*;
 
-    /// Try convert [Vec] of [US] to [MdDocument]:
-    /// - Step 1: Get the lines from the buffer using
-    ///           [editor_buffer.get_lines()](EditorBuffer::get_lines()).
-    /// - Step 2: Convert the lines into a [List] of [StyleUSSpanLine] using
-    ///           [try_parse_and_highlight()]. If this fails then take the path of no
-    ///           syntax highlighting else take the path of syntax highlighting.
+    /// Render the highlighted lines of the [MdDocument]:
+    /// - Step 1: The lines from the buffer are parsed into a [List] of [StyleUSSpanLine]
+    ///   (by [EditorEngineApi::render_engine] using [MdSynHiCache::update()], which only
+    ///   re-parses the blocks that changed).
+    /// - Step 2: Paint the highlighted lines that are in the viewport.
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
-        max_display_row_count: ChUnit,
+        viewport_lines: &[ViewportLine],
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
-        max_display_col_count: ChUnit,
     ) {
-        // Try to parse the Vec<US> into an MDDocument & render it.
-        try_render_content(
-            editor_buffer,
-            max_display_row_count,
-            render_ops,
-            editor_engine,
-            max_display_col_count,
-        )
-        .ok();
+        // Render the highlighted lines of the MdDocument.
+        try_render_content(editor_buffer, viewport_lines, render_ops, editor_engine).ok();
     }
 
     /// Path of syntax highlighting:
-    /// - Step 1: Iterate the `List<StyleUSSpanLine>` from: `ch!(@to_usize
-    ///           editor_buffer.get_scroll_offset().row_index)` to: `ch!(@to_usize
-    ///           max_display_row_count)`
-    /// - Step 2: For each, call `StyleUSSpanLine::clip()` which returns a `StyledTexts`
+    /// - Step 1: Iterate the `List<StyleUSSpanLine>` for each of the `viewport_lines` (the
+    ///   lines are highlighted as a whole, even if they are soft wrapped).
+    /// - Step 2: For each visual row, call `StyleUSSpanLine::clip()` which returns a
+    ///   `StyledTexts`
     /// - Step 3: Render the `StyledTexts` into `render_ops`
     fn try_render_content(
         editor_buffer: &&EditorBuffer,
-        max_display_row_count: ChUnit,
+        viewport_lines: &[ViewportLine],
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
-        max_display_col_count: ChUnit,
     ) -> CommonResult<()> {
         throws!({
-            let lines = try_parse_and_highlight(
-                editor_buffer.get_lines(),
-                &editor_engine.current_box.get_computed_style(),
-                Some((&editor_engine.syntax_set, &editor_engine.theme)),
-            )?;
+            let lines = editor_engine.md_syn_hi_cache.get_style_us_span_lines();
 
             call_if_true!(DEBUG_TUI_SYN_HI, {
                 log_debug(format!(
@@ -420,43 +646,32 @@ mod syn_hi_r3bl_path {
                 ));
             });
 
-            for (row_index, line) in lines
-                .iter()
-                .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-                .enumerate()
-            {
-                // Clip the content to max rows.
-                if ch!(row_index) > max_display_row_count {
+            for viewport_line in viewport_lines {
+                let Some(line) = lines.get(ch!(@to_usize viewport_line.row_index)) else {
                     break;
-                }
+                };
 
-                render_single_line(
-                    line,
-                    editor_buffer,
-                    editor_engine,
-                    row_index,
-                    max_display_col_count,
-                    render_ops,
-                );
+                for visual_row in &viewport_line.visual_rows {
+                    render_single_line(line, editor_engine, visual_row, render_ops);
+                }
             }
         });
     }
 
     fn render_single_line(
         line: &List<StyleUSSpan>,
-        editor_buffer: &&EditorBuffer,
         editor_engine: &&mut EditorEngine,
-        row_index: usize,
-        max_display_col_count: ChUnit,
+        visual_row: &VisualRow,
         render_ops: &mut RenderOps,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
-            editor_engine.current_box.style_adjusted_origin_pos,
-            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
+            editor_engine.content_origin_pos(),
+            position! { col_index: 0 , row_index: visual_row.raw_row_index },
         ));
-        let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let styled_texts: StyledTexts =
-            line.clip(scroll_offset_col, max_display_col_count);
+        let styled_texts: StyledTexts = line.clip(
+            visual_row.range.start_display_col_index,
+            visual_row.display_col_count(),
+        );
         styled_texts.render_into(render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
@@ -467,87 +682,84 @@ mod syn_hi_syntect_path {
 
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
-        max_display_row_count: ChUnit,
+        viewport_lines: &[ViewportLine],
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
-        max_display_col_count: ChUnit,
     ) {
-        // Paint each line in the buffer (skipping the scroll_offset.row).
-        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-            .enumerate()
-        {
-            // Clip the content to max rows.
-            if ch!(row_index) > max_display_row_count {
+        // Paint each line in the viewport.
+        for viewport_line in viewport_lines {
+            let Some(line) = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize viewport_line.row_index))
+            else {
                 break;
-            }
+            };
 
             render_single_line(
                 render_ops,
-                row_index,
+                viewport_line,
                 editor_engine,
                 editor_buffer,
                 line,
-                max_display_col_count,
             );
         }
     }
 
     fn render_single_line(
         render_ops: &mut RenderOps,
-        row_index: usize,
+        viewport_line: &ViewportLine,
         editor_engine: &&mut EditorEngine,
         editor_buffer: &&EditorBuffer,
         line: &UnicodeString,
-        max_display_col_count: ChUnit,
     ) {
-        render_ops.push(RenderOp::MoveCursorPositionRelTo(
-            editor_engine.current_box.style_adjusted_origin_pos,
-            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
-        ));
-
+        // The line is highlighted once, even if it is painted on many visual rows.
         let it =
             try_get_syntect_highlighted_line(editor_engine, editor_buffer, &line.string);
 
         match it {
             // If enabled, and we have a SyntaxReference then try and highlight the line.
             Some(syntect_highlighted_line) => {
-                render_line_with_syntect(
-                    syntect_highlighted_line,
-                    editor_buffer,
-                    max_display_col_count,
-                    render_ops,
-                );
+                let list: List<StyleUSSpan> =
+                    syntect_to_styled_text_conversion::from_syntect_to_tui(
+                        syntect_highlighted_line,
+                    );
+                for visual_row in &viewport_line.visual_rows {
+                    render_line_with_syntect(
+                        &list,
+                        visual_row,
+                        render_ops,
+                        editor_engine,
+                    );
+                }
             }
             // Otherwise, fallback.
             None => {
-                no_syn_hi_path::render_line_no_syntax_highlight(
-                    line,
-                    editor_buffer,
-                    max_display_col_count,
-                    render_ops,
-                    editor_engine,
-                );
+                for visual_row in &viewport_line.visual_rows {
+                    no_syn_hi_path::render_line_no_syntax_highlight(
+                        line,
+                        visual_row,
+                        render_ops,
+                        editor_engine,
+                    );
+                }
             }
         }
     }
 
     fn render_line_with_syntect(
-        syntect_highlighted_line: Vec<(syntect::highlighting::Style, &str)>,
-        editor_buffer: &&EditorBuffer,
-        max_display_col_count: ChUnit,
+        list: &List<StyleUSSpan>,
+        visual_row: &VisualRow,
         render_ops: &mut RenderOps,
+        editor_engine: &&mut EditorEngine,
     ) {
-        let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let list: List<StyleUSSpan> =
-            syntect_to_styled_text_conversion::from_syntect_to_tui(
-                syntect_highlighted_line,
-            );
-        let styled_texts: StyledTexts =
-            list.clip(scroll_offset_col, max_display_col_count);
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.content_origin_pos(),
+            position! { col_index: 0 , row_index: visual_row.raw_row_index },
+        ));
+        let styled_texts: StyledTexts = list.clip(
+            visual_row.range.start_display_col_index,
+            visual_row.display_col_count(),
+        );
         styled_texts.render_into(render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
@@ -577,80 +789,215 @@ mod no_syn_hi_path {
 
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
-        max_display_row_count: ChUnit,
+        viewport_lines: &[ViewportLine],
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
-        max_display_col_count: ChUnit,
     ) {
-        // Paint each line in the buffer (skipping the scroll_offset.row).
-        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-            .enumerate()
-        {
-            // Clip the content to max rows.
-            if ch!(row_index) > max_display_row_count {
+        // Paint each line in the viewport.
+        for viewport_line in viewport_lines {
+            let Some(line) = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize viewport_line.row_index))
+            else {
                 break;
-            }
+            };
 
-            render_single_line(
-                render_ops,
-                row_index,
-                editor_engine,
-                editor_buffer,
-                line,
-                max_display_col_count,
-            );
+            for visual_row in &viewport_line.visual_rows {
+                render_line_no_syntax_highlight(
+                    line,
+                    visual_row,
+                    render_ops,
+                    editor_engine,
+                );
+            }
         }
     }
 
-    fn render_single_line(
+    /// This is used as a fallback by other render paths.
+    pub fn render_line_no_syntax_highlight(
+        line: &UnicodeString,
+        visual_row: &VisualRow,
         render_ops: &mut RenderOps,
-        row_index: usize,
         editor_engine: &&mut EditorEngine,
-        editor_buffer: &&EditorBuffer,
-        line: &UnicodeString,
-        max_display_col_count: ChUnit,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
-            editor_engine.current_box.style_adjusted_origin_pos,
-            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
+            editor_engine.content_origin_pos(),
+            position! { col_index: 0 , row_index: visual_row.raw_row_index },
         ));
 
-        no_syn_hi_path::render_line_no_syntax_highlight(
+        // Clip the content [visual_row.range.start .. visual_row.range.end].
+        let truncated_line = line.clip_to_width(
+            visual_row.range.start_display_col_index,
+            visual_row.display_col_count(),
+        );
+
+        render_ops.push(RenderOp::ApplyColors(
+            editor_engine.current_box.get_computed_style(),
+        ));
+
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            truncated_line.into(),
+            editor_engine.current_box.get_computed_style(),
+        ));
+
+        render_ops.push(RenderOp::ResetColor);
+    }
+}
+
+mod search_match_path {
+    use super::*;
+
+    /// Paint the matches of the buffer's [SearchQuery] (if any) that are in the viewport,
+    /// using [get_search_match_style].
+    pub fn render_content(
+        editor_buffer: &&EditorBuffer,
+        viewport_lines: &[ViewportLine],
+        render_ops: &mut RenderOps,
+        editor_engine: &&mut EditorEngine,
+    ) {
+        let Some(regex) = editor_buffer
+            .get_maybe_search_query()
+            .and_then(|query| query.try_build_regex())
+        else {
+            return;
+        };
+
+        for viewport_line in viewport_lines {
+            let Some(line) = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize viewport_line.row_index))
+            else {
+                break;
+            };
+
+            let matches = search_support::find_matches_in_line(
+                &regex,
+                viewport_line.row_index,
+                line,
+            );
+
+            for search_match in matches {
+                for visual_row in &viewport_line.visual_rows {
+                    render_single_match(
+                        search_match,
+                        line,
+                        visual_row,
+                        render_ops,
+                        editor_engine,
+                    );
+                }
+            }
+        }
+    }
+
+    fn render_single_match(
+        search_match: SearchMatch,
+        line: &UnicodeString,
+        visual_row: &VisualRow,
+        render_ops: &mut RenderOps,
+        editor_engine: &&mut EditorEngine,
+    ) {
+        highlight_range_path::render_range(
+            search_match.range,
+            get_search_match_style(),
             line,
-            editor_buffer,
-            max_display_col_count,
+            visual_row,
             render_ops,
             editor_engine,
         );
     }
+}
 
-    /// This is used as a fallback by other render paths.
-    pub fn render_line_no_syntax_highlight(
-        line: &UnicodeString,
+mod bracket_match_path {
+    use super::*;
+
+    /// Paint the bracket at the caret and its matching bracket (if they are in the
+    /// viewport), using [get_matching_bracket_style].
+    pub fn render_content(
         editor_buffer: &&EditorBuffer,
-        max_display_col_count: ChUnit,
+        viewport_lines: &[ViewportLine],
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
     ) {
-        let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
+        let Some((bracket_position, matching_position)) =
+            bracket_support::find_matching_bracket(
+                editor_buffer.get_lines(),
+                editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+            )
+        else {
+            return;
+        };
 
-        // Clip the content [scroll_offset.col .. max cols].
-        let truncated_line =
-            line.clip_to_width(scroll_offset_col_index, max_display_col_count);
+        for position in [bracket_position, matching_position] {
+            let Some(viewport_line) = viewport_lines
+                .iter()
+                .find(|it| it.row_index == position.row_index)
+            else {
+                continue;
+            };
+            let Some(line) = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize position.row_index))
+            else {
+                continue;
+            };
 
-        render_ops.push(RenderOp::ApplyColors(
-            editor_engine.current_box.get_computed_style(),
-        ));
+            // All the brackets are 1 col wide.
+            let range =
+                SelectionRange::new(position.col_index, position.col_index + ch!(1));
+            for visual_row in &viewport_line.visual_rows {
+                highlight_range_path::render_range(
+                    range,
+                    get_matching_bracket_style(),
+                    line,
+                    visual_row,
+                    render_ops,
+                    editor_engine,
+                );
+            }
+        }
+    }
+}
 
-        render_ops.push(RenderOp::PaintTextWithAttributes(
-            truncated_line.into(),
-            editor_engine.current_box.get_computed_style(),
-        ));
+mod highlight_range_path {
+    use super::*;
+
+    /// Paint the range of the line (clipped to the visual row) on top of the content,
+    /// using the given style.
+    pub fn render_range(
+        range: SelectionRange,
+        style: Style,
+        line: &UnicodeString,
+        visual_row: &VisualRow,
+        render_ops: &mut RenderOps,
+        editor_engine: &&mut EditorEngine,
+    ) {
+        // Clip the range to the visual row [visual_row.range.start .. end].
+        let start_col_index = range
+            .start_display_col_index
+            .max(visual_row.range.start_display_col_index);
+        let end_col_index = range
+            .end_display_col_index
+            .min(visual_row.range.end_display_col_index);
+        if start_col_index >= end_col_index {
+            return;
+        }
+
+        let text =
+            line.clip_to_range(SelectionRange::new(start_col_index, end_col_index));
+        if text.is_empty() {
+            return;
+        }
 
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.content_origin_pos(),
+            position! {
+                col_index: start_col_index - visual_row.range.start_display_col_index,
+                row_index: visual_row.raw_row_index
+            },
+        ));
+        render_ops.push(RenderOp::ApplyColors(Some(style)));
+        render_ops.push(RenderOp::PaintTextWithAttributes(text.to_string(), None));
         render_ops.push(RenderOp::ResetColor);
     }
 }
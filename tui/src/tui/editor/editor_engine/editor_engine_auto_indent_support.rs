@@ -0,0 +1,126 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// What [EditorEvent::InsertNewLine] does, which is decided by
+/// [find_new_line_action](auto_indent_support::find_new_line_action).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NewLineAction {
+    /// Split the line at the caret, and insert the prefix at the start of the new line.
+    /// The caret ends up after the prefix.
+    InsertWithPrefix(String),
+    /// The caret is at the end of an empty list item, so the list is ended by removing
+    /// its bullet (instead of inserting a new line).
+    EndList,
+}
+
+/// Support for [AutoIndentMode], which carries over the indentation (and the Markdown
+/// list bullet) of the caret line when a new line is inserted, like the Markdown editor
+/// on GitHub.
+///
+/// ```text
+///   Caret line             New line
+///   ──────────────────     ────────────
+///   "    let a = 1;▴"  →  "    ▴"
+///   "- foo▴"           →  "- ▴"
+///   "  9. foo▴"        →  "  10. ▴"
+///   "- [x] foo▴"       →  "- [ ] ▴"
+///   "- [ ] ▴"          →  "▴"            (the list is ended, no new line)
+/// ```
+///
+/// 1. The list items are recognized by [parse_smart_list], so the same bullets &
+///    indentation that are highlighted as lists are continued.
+/// 2. The bullet is only carried over if the caret is after it. Otherwise (and for lines
+///    that aren't list items) only the leading whitespace before the caret is carried
+///    over.
+pub mod auto_indent_support {
+    use super::*;
+
+    pub fn find_new_line_action(
+        line: &UnicodeString,
+        caret_col_index: ChUnit,
+        auto_indent_mode: &AutoIndentMode,
+    ) -> NewLineAction {
+        let text_before_caret = line.clip_to_width(ch!(0), caret_col_index);
+        let leading_whitespace = get_leading_whitespace(text_before_caret);
+
+        match auto_indent_mode {
+            AutoIndentMode::Disable => NewLineAction::InsertWithPrefix(String::new()),
+            AutoIndentMode::Indent => NewLineAction::InsertWithPrefix(leading_whitespace),
+            AutoIndentMode::SmartList => {
+                match find_list_item_action(&line.string, caret_col_index) {
+                    Some(new_line_action) => new_line_action,
+                    None => NewLineAction::InsertWithPrefix(leading_whitespace),
+                }
+            }
+        }
+    }
+
+    /// Returns [None] if the line isn't a list item, or if the caret is before (or in
+    /// the middle of) its bullet.
+    fn find_list_item_action(
+        line: &str,
+        caret_col_index: ChUnit,
+    ) -> Option<NewLineAction> {
+        let (_, smart_list_ir) = parse_smart_list(line).ok()?;
+        let first_line = smart_list_ir.content_lines.first()?;
+
+        let bullet_width = smart_list_ir.indent + first_line.bullet_str.len();
+        if ch!(@to_usize caret_col_index) < bullet_width {
+            return None;
+        }
+
+        let content = first_line.content;
+        let checkbox = [CHECKED, UNCHECKED].into_iter().find(|checkbox| {
+            content.starts_with(&format!("{checkbox}{SPACE}"))
+                || content.trim_end() == *checkbox
+        });
+
+        let is_empty_item = match checkbox {
+            Some(checkbox) => content.trim_end() == checkbox,
+            None => content.trim().is_empty(),
+        };
+        if is_empty_item && ch!(@to_usize caret_col_index) >= line.trim_end().len() {
+            return Some(NewLineAction::EndList);
+        }
+
+        let mut prefix = SPACE.repeat(smart_list_ir.indent);
+        match smart_list_ir.bullet_kind {
+            BulletKind::Ordered(number) => {
+                prefix.push_str(&format!("{}{ORDERED_LIST_PARTIAL_PREFIX}", number + 1));
+            }
+            BulletKind::Unordered => prefix.push_str(UNORDERED_LIST_PREFIX),
+        }
+        // A new task is always unchecked.
+        if checkbox.is_some() {
+            prefix.push_str(UNCHECKED);
+            prefix.push_str(SPACE);
+        }
+
+        Some(NewLineAction::InsertWithPrefix(prefix))
+    }
+
+    fn get_leading_whitespace(text: &str) -> String {
+        text.chars()
+            .take_while(|character| *character == SPACE_CHAR || *character == '\t')
+            .collect()
+    }
+}
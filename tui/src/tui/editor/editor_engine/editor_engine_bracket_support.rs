@@ -0,0 +1,382 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// The brackets that are matched (and auto-paired) by the editor.
+pub const BRACKET_PAIRS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+/// The quotes that are auto-paired by the editor. Since they open & close with the same
+/// character, they aren't matched like [BRACKET_PAIRS] are.
+pub const QUOTE_CHARS: [char; 3] = ['"', '\'', '`'];
+
+/// Only this many lines above (or below) the caret are scanned for the matching bracket,
+/// so that the cost of painting the editor doesn't depend on the size of the buffer.
+pub const MAX_BRACKET_SCAN_LINE_COUNT: usize = 1_000;
+
+/// Whether the closing bracket (or quote) is inserted when an opening one is typed.
+/// Please take a look at [bracket_support] for more details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoPairMode {
+    Disable,
+    Enable,
+}
+
+/// What [EditorEvent::InsertChar] does, which is decided by
+/// [find_auto_pair_action](bracket_support::find_auto_pair_action).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AutoPairAction {
+    /// Insert the character as is.
+    Insert,
+    /// Insert the character & this closing character, and place the caret between them.
+    InsertPair(char),
+    /// The character is already to the right of the caret, so the caret is moved over it
+    /// (instead of inserting it again).
+    SkipOver,
+}
+
+/// Support for matching brackets, and for [AutoPairMode].
+///
+/// 1. The bracket at the caret (or the one to the left of it) is matched with its pair by
+///    counting the nested brackets of the same kind, across lines. Brackets in strings &
+///    comments are not treated differently.
+///
+///    ```text
+///      fn foo(bar: [u8; 2]) {▴
+///      ▲                    ▲
+///      └── matching bracket ┘
+///    ```
+///
+/// 2. When [AutoPairMode::Enable] is set, typing an opening bracket (or a quote) also
+///    inserts the closing one. This only happens if the character to the right of the
+///    caret is whitespace, a closing bracket, or the end of the line. Quotes aren't
+///    paired right after a word (eg: `don't`).
+/// 3. Typing a closing bracket (or a quote) when the same character is to the right of
+///    the caret moves the caret over it.
+/// 4. Backspace between an empty pair (eg: `(▴)`) deletes both of them.
+pub mod bracket_support {
+    use super::*;
+
+    /// Returns the (scroll adjusted) positions of the bracket at the caret (or the one to
+    /// the left of it) and its matching bracket, or [None] if there isn't one.
+    pub fn find_matching_bracket(
+        lines: &[UnicodeString],
+        caret_adj: Position,
+    ) -> Option<(Position, Position)> {
+        let line = lines.get(ch!(@to_usize caret_adj.row_index))?;
+
+        [
+            get_char_at(line, caret_adj.col_index),
+            get_char_before(line, caret_adj.col_index),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|(col_index, character)| {
+            let bracket_position =
+                position!(col_index: col_index, row_index: caret_adj.row_index);
+            let matching_position = if let Some(closing) = get_closing_bracket(character)
+            {
+                scan_for_matching_bracket(
+                    lines,
+                    bracket_position,
+                    (character, closing),
+                    ScanDirection::Forward,
+                )
+            } else if let Some(opening) = get_opening_bracket(character) {
+                scan_for_matching_bracket(
+                    lines,
+                    bracket_position,
+                    (character, opening),
+                    ScanDirection::Backward,
+                )
+            } else {
+                None
+            }?;
+            Some((bracket_position, matching_position))
+        })
+    }
+
+    pub fn find_auto_pair_action(
+        maybe_line: Option<&UnicodeString>,
+        caret_col_index: ChUnit,
+        character: char,
+        auto_pair_mode: &AutoPairMode,
+    ) -> AutoPairAction {
+        if *auto_pair_mode == AutoPairMode::Disable {
+            return AutoPairAction::Insert;
+        }
+
+        let maybe_char_before = maybe_line
+            .and_then(|line| get_char_before(line, caret_col_index))
+            .map(|(_, it)| it);
+        let maybe_char_after = maybe_line
+            .and_then(|line| get_char_at(line, caret_col_index))
+            .map(|(_, it)| it);
+
+        let is_closing_char =
+            get_opening_bracket(character).is_some() || QUOTE_CHARS.contains(&character);
+        if is_closing_char && maybe_char_after == Some(character) {
+            return AutoPairAction::SkipOver;
+        }
+
+        let can_insert_pair = match maybe_char_after {
+            None => true,
+            Some(char_after) => {
+                char_after.is_whitespace() || get_opening_bracket(char_after).is_some()
+            }
+        };
+        if !can_insert_pair {
+            return AutoPairAction::Insert;
+        }
+
+        if let Some(closing) = get_closing_bracket(character) {
+            return AutoPairAction::InsertPair(closing);
+        }
+
+        if QUOTE_CHARS.contains(&character) {
+            let is_after_word = maybe_char_before
+                .map(|char_before| char_before.is_alphanumeric() || char_before == '_')
+                .unwrap_or(false);
+            if !is_after_word {
+                return AutoPairAction::InsertPair(character);
+            }
+        }
+
+        AutoPairAction::Insert
+    }
+
+    /// Returns true if the caret is between an opening bracket (or a quote) and its
+    /// closing pair, eg: `(▴)`.
+    pub fn is_caret_in_empty_pair(line: &UnicodeString, caret_col_index: ChUnit) -> bool {
+        let (Some((_, char_before)), Some((_, char_after))) = (
+            get_char_before(line, caret_col_index),
+            get_char_at(line, caret_col_index),
+        ) else {
+            return false;
+        };
+
+        match get_closing_bracket(char_before) {
+            Some(closing) => closing == char_after,
+            None => QUOTE_CHARS.contains(&char_before) && char_before == char_after,
+        }
+    }
+
+    /// Insert the character at the caret, taking [AutoPairMode] into account.
+    pub fn insert_char_at_caret(args: EditorArgsMut<'_>, character: char) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let auto_pair_action = find_auto_pair_action(
+            editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize caret_adj.row_index)),
+            caret_adj.col_index,
+            character,
+            &editor_engine.config_options.auto_pair_mode,
+        );
+
+        match auto_pair_action {
+            AutoPairAction::Insert => {
+                EditorEngineInternalApi::insert_str_at_caret(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &String::from(character),
+                );
+            }
+            AutoPairAction::InsertPair(closing) => {
+                EditorEngineInternalApi::insert_str_at_caret(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &format!("{character}{closing}"),
+                );
+                // All the brackets & quotes are 1 col wide.
+                EditorEngineInternalApi::move_caret_to(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    position!(
+                        col_index: caret_adj.col_index + ch!(1),
+                        row_index: caret_adj.row_index
+                    ),
+                );
+            }
+            AutoPairAction::SkipOver => {
+                EditorEngineInternalApi::move_caret_to(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    position!(
+                        col_index: caret_adj.col_index + ch!(1),
+                        row_index: caret_adj.row_index
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Backspace a single character at the caret. If [AutoPairMode::Enable] is set and the
+    /// caret is in an empty pair, then the closing character is deleted too.
+    pub fn backspace_at_caret(args: EditorArgsMut<'_>) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let is_in_empty_pair = editor_engine.config_options.auto_pair_mode
+            == AutoPairMode::Enable
+            && editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize caret_adj.row_index))
+                .map(|line| is_caret_in_empty_pair(line, caret_adj.col_index))
+                .unwrap_or(false);
+
+        if is_in_empty_pair {
+            EditorEngineInternalApi::delete_at_caret(editor_buffer, editor_engine);
+        }
+        EditorEngineInternalApi::backspace_at_caret(editor_buffer, editor_engine);
+    }
+
+    fn get_closing_bracket(opening: char) -> Option<char> {
+        BRACKET_PAIRS
+            .iter()
+            .find(|(it, _)| *it == opening)
+            .map(|(_, closing)| *closing)
+    }
+
+    fn get_opening_bracket(closing: char) -> Option<char> {
+        BRACKET_PAIRS
+            .iter()
+            .find(|(_, it)| *it == closing)
+            .map(|(opening, _)| *opening)
+    }
+
+    /// Returns the display col & character of the grapheme cluster that starts at the
+    /// display col, if it is made up of a single character.
+    fn get_char_at(line: &UnicodeString, col_index: ChUnit) -> Option<(ChUnit, char)> {
+        let segment = line
+            .vec_segment
+            .iter()
+            .find(|segment| segment.display_col_offset == col_index)?;
+        Some((
+            segment.display_col_offset,
+            get_single_char(&segment.string)?,
+        ))
+    }
+
+    /// Returns the display col & character of the grapheme cluster that ends at the
+    /// display col, if it is made up of a single character.
+    fn get_char_before(
+        line: &UnicodeString,
+        col_index: ChUnit,
+    ) -> Option<(ChUnit, char)> {
+        let segment = line.vec_segment.iter().find(|segment| {
+            segment.display_col_offset + segment.unicode_width == col_index
+                && segment.unicode_width > ch!(0)
+        })?;
+        Some((
+            segment.display_col_offset,
+            get_single_char(&segment.string)?,
+        ))
+    }
+
+    fn get_single_char(string: &str) -> Option<char> {
+        let mut chars = string.chars();
+        match (chars.next(), chars.next()) {
+            (Some(character), None) => Some(character),
+            _ => None,
+        }
+    }
+
+    enum ScanDirection {
+        Forward,
+        Backward,
+    }
+
+    /// Scan from the bracket (excluding it) for its matching bracket, skipping over the
+    /// nested pairs of the same kind.
+    fn scan_for_matching_bracket(
+        lines: &[UnicodeString],
+        bracket_position: Position,
+        (bracket, matching_bracket): (char, char),
+        direction: ScanDirection,
+    ) -> Option<Position> {
+        let bracket_row_index = ch!(@to_usize bracket_position.row_index);
+        let row_indices: Vec<usize> = match direction {
+            ScanDirection::Forward => (bracket_row_index..lines.len())
+                .take(MAX_BRACKET_SCAN_LINE_COUNT)
+                .collect(),
+            ScanDirection::Backward => (0..=bracket_row_index)
+                .rev()
+                .take(MAX_BRACKET_SCAN_LINE_COUNT)
+                .collect(),
+        };
+
+        let mut depth = 0;
+        for row_index in row_indices {
+            let line = lines.get(row_index)?;
+            let mut segments: Vec<_> = line.vec_segment.iter().collect();
+            if let ScanDirection::Backward = direction {
+                segments.reverse();
+            }
+
+            for segment in segments {
+                if row_index == bracket_row_index {
+                    let is_past_bracket = match direction {
+                        ScanDirection::Forward => {
+                            segment.display_col_offset > bracket_position.col_index
+                        }
+                        ScanDirection::Backward => {
+                            segment.display_col_offset < bracket_position.col_index
+                        }
+                    };
+                    if !is_past_bracket {
+                        continue;
+                    }
+                }
+
+                match get_single_char(&segment.string) {
+                    Some(character) if character == bracket => depth += 1,
+                    Some(character) if character == matching_bracket => {
+                        if depth == 0 {
+                            return Some(position!(
+                                col_index: segment.display_col_offset,
+                                row_index: row_index
+                            ));
+                        }
+                        depth -= 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        None
+    }
+}
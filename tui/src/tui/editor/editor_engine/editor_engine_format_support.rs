@@ -0,0 +1,135 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Support for [EditorEvent::FormatDocument] (Ctrl+Alt+f), which re-writes the Markdown
+/// document in the editor buffer in a canonical format. Please take a look at
+/// [fn@convert_to_markdown] for more details.
+///
+/// 1. Only buffers w/ the Markdown file extension are formatted, and the buffer is left as
+///    is if any of its text can't be parsed, or if the parser skips over some of it (so
+///    that no text is lost). Please take a look at [format_support::format_lines] for more
+///    details.
+/// 2. The caret stays on the same row, and col (if the line is still long enough).
+pub mod format_support {
+    use super::*;
+
+    pub fn format_document(args: EditorArgsMut<'_>) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if editor_buffer.get_maybe_file_extension() != Some(DEFAULT_SYN_HI_FILE_EXT) {
+            return;
+        }
+
+        let Some(new_lines) = format_lines(editor_buffer.get_lines()) else {
+            return;
+        };
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        editor_buffer.clear_selection();
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                *lines = new_lines.into_iter().map(UnicodeString::from).collect();
+            },
+        );
+
+        let new_caret_adj = {
+            let lines = editor_buffer.get_lines();
+            let row_index = ch!(@to_usize caret_adj.row_index).min(lines.len() - 1);
+            let col_index = caret_adj.col_index.min(lines[row_index].display_width);
+            position!(col_index: col_index, row_index: ch!(row_index))
+        };
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            new_caret_adj,
+        );
+    }
+
+    /// Returns the formatted lines, or [None] if the lines are already formatted, or if
+    /// any of their text can't be parsed. The formatted lines are only used if:
+    /// 1. They have the same text as the `lines` (other than whitespace, and the bullets &
+    ///    markers that the canonical format replaces), since the parser can skip over
+    ///    some of the text (eg: an unclosed inline code span in a list item).
+    /// 2. Formatting them again doesn't change them.
+    pub fn format_lines(lines: &[UnicodeString]) -> Option<Vec<String>> {
+        let text = lines
+            .iter()
+            .map(|line| format!("{}{NEW_LINE}", line.string))
+            .collect::<String>();
+
+        let new_text = format_text(&text)?;
+        if get_significant_chars(&new_text) != get_significant_chars(&text)
+            || format_text(&new_text).as_ref() != Some(&new_text)
+        {
+            return None;
+        }
+
+        let new_lines = new_text.lines().map(String::from).collect::<Vec<_>>();
+        let is_unchanged = new_lines.len() == lines.len()
+            && new_lines
+                .iter()
+                .zip(lines)
+                .all(|(lhs, rhs)| *lhs == rhs.string);
+        if new_lines.is_empty() || is_unchanged {
+            return None;
+        }
+
+        Some(new_lines)
+    }
+
+    /// Returns the `text` in the canonical format, or [None] if any of it can't be parsed.
+    fn format_text(text: &str) -> Option<String> {
+        let (remainder, document) = parse_markdown(text).ok()?;
+        if !remainder.is_empty() {
+            return None;
+        }
+        Some(convert_to_markdown(&document))
+    }
+
+    /// Returns the characters of the `text` that the canonical format keeps as they are.
+    /// This skips whitespace, ordered list bullets (which are re-numbered), and the
+    /// characters that bullets, emphasis, thematic breaks & table delimiter rows are made
+    /// of (which are replaced).
+    fn get_significant_chars(text: &str) -> String {
+        text.split_whitespace()
+            .filter(|word| !is_ordered_list_bullet(word))
+            .flat_map(str::chars)
+            .filter(|it| !matches!(it, '-' | '*' | '+' | '_' | '~' | '|'))
+            .collect()
+    }
+
+    fn is_ordered_list_bullet(word: &str) -> bool {
+        match word.strip_suffix(PERIOD) {
+            Some(number) => {
+                !number.is_empty() && number.chars().all(|it| it.is_ascii_digit())
+            }
+            None => false,
+        }
+    }
+}
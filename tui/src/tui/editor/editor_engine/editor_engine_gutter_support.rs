@@ -0,0 +1,227 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::*;
+
+use crate::*;
+
+/// A marker that the app can show in the gutter next to a line, using
+/// [EditorBuffer::set_line_markers]. Please take a look at [gutter_support] for more
+/// details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+pub enum LineMarker {
+    /// The line has been modified (eg: since the file was loaded).
+    Modified,
+    Error,
+    Warning,
+}
+
+impl LineMarker {
+    pub fn get_glyph(&self) -> &'static str {
+        match self {
+            LineMarker::Modified => "▎",
+            LineMarker::Error => "●",
+            LineMarker::Warning => "▲",
+        }
+    }
+}
+
+/// Support for the gutter, which is painted to the left of the content when
+/// [EditorEngineConfig::gutter_mode] is not [GutterMode::Hidden].
+///
+/// ```text
+///   gutter_width
+///   ←──→
+///  ┌────┬──────────────────┐
+///  │● 9 │fn main() {       │
+///  │▎10 │  println!("hi"); │
+///  │ 11 │}                 │
+///  └────┴──────────────────┘
+///        ←─ viewport_width ─→
+/// ```
+///
+/// Each row of the gutter has a line marker col, the line number (right aligned), and a
+/// separator col.
+///
+/// 1. The gutter is as wide as the line number of the last line (plus the marker and
+///    separator cols), and its width is stored in [EditorEngine::gutter_width].
+/// 2. The content viewport ([EditorEngine::viewport_width] and
+///    [EditorEngine::content_origin_pos]) starts after the gutter, so the caret, scroll
+///    offset, and selection math do not have to account for it. Positions in the
+///    [EditorEngine::current_box] (eg: from mouse events) can be converted to positions in
+///    the content viewport using [to_content_position](gutter_support::to_content_position).
+/// 3. The [LineMarker]s are supplied by the app, since only the app knows what is
+///    modified, or has errors or warnings. They are keyed by (scroll adjusted) row index
+///    and are not moved when lines are inserted or removed, so the app should update them
+///    when the buffer changes.
+pub mod gutter_support {
+    use super::*;
+
+    const MARKER_COL_WIDTH: usize = 1;
+    const SEPARATOR_COL_WIDTH: usize = 1;
+
+    pub fn is_enabled(editor_engine: &EditorEngine) -> bool {
+        !matches!(editor_engine.config_options.gutter_mode, GutterMode::Hidden)
+    }
+
+    /// The gutter is hidden (its width is 0) if there would be no room left for the
+    /// content.
+    pub fn calc_gutter_width(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+    ) -> ChUnit {
+        if !is_enabled(editor_engine) {
+            return ch!(0);
+        }
+
+        let line_number_width =
+            ch!(@to_usize editor_buffer.len()).max(1).to_string().len();
+        let gutter_width =
+            ch!(MARKER_COL_WIDTH + line_number_width + SEPARATOR_COL_WIDTH);
+
+        if gutter_width
+            >= editor_engine
+                .current_box
+                .style_adjusted_bounds_size
+                .col_count
+        {
+            ch!(0)
+        } else {
+            gutter_width
+        }
+    }
+
+    /// Returns `true` if the width of the gutter (and so the viewport width) changed.
+    pub fn update_gutter_width(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> bool {
+        let gutter_width = calc_gutter_width(editor_buffer, editor_engine);
+        if gutter_width == editor_engine.gutter_width {
+            return false;
+        }
+        editor_engine.gutter_width = gutter_width;
+        true
+    }
+
+    /// Convert a position that is relative to the origin of the
+    /// [EditorEngine::current_box] into a position that is relative to the origin of the
+    /// content viewport. Returns [None] if the position is in the gutter.
+    pub fn to_content_position(
+        editor_engine: &EditorEngine,
+        position_in_box: Position,
+    ) -> Option<Position> {
+        if position_in_box.col_index < editor_engine.gutter_width {
+            return None;
+        }
+        Some(position!(
+            col_index: position_in_box.col_index - editor_engine.gutter_width,
+            row_index: position_in_box.row_index
+        ))
+    }
+
+    /// The line number is only painted on the first visual row of each line (there is
+    /// only one visual row per line, unless the lines are soft wrapped).
+    pub fn render_gutter(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        viewport_lines: &[ViewportLine],
+        render_ops: &mut RenderOps,
+    ) {
+        if editor_engine.gutter_width == ch!(0) {
+            return;
+        }
+
+        let line_number_width = ch!(@to_usize editor_engine.gutter_width)
+            - MARKER_COL_WIDTH
+            - SEPARATOR_COL_WIDTH;
+        let caret_row_index =
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+
+        for viewport_line in viewport_lines {
+            let Some(first_visual_row) = viewport_line.visual_rows.first() else {
+                continue;
+            };
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                position! { col_index: 0 , row_index: first_visual_row.raw_row_index },
+            ));
+
+            // Paint the marker.
+            match editor_buffer.get_line_marker(viewport_line.row_index) {
+                Some(line_marker) => {
+                    render_ops.push(RenderOp::ApplyColors(Some(get_line_marker_style(
+                        line_marker,
+                    ))));
+                    render_ops.push(RenderOp::PaintTextWithAttributes(
+                        line_marker.get_glyph().into(),
+                        None,
+                    ));
+                    render_ops.push(RenderOp::ResetColor);
+                }
+                None => {
+                    render_ops.push(RenderOp::PaintTextWithAttributes(
+                        " ".repeat(MARKER_COL_WIDTH),
+                        None,
+                    ));
+                }
+            }
+
+            // Paint the line number & separator.
+            let is_caret_row = viewport_line.row_index == caret_row_index;
+            let line_number = get_line_number(
+                &editor_engine.config_options.gutter_mode,
+                viewport_line.row_index,
+                caret_row_index,
+            );
+            render_ops.push(RenderOp::ApplyColors(Some(if is_caret_row {
+                get_gutter_current_line_number_style()
+            } else {
+                get_gutter_line_number_style()
+            })));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                format!(
+                    "{line_number:>line_number_width$}{separator}",
+                    separator = " ".repeat(SEPARATOR_COL_WIDTH)
+                ),
+                None,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    /// Line numbers start at 1. In [GutterMode::RelativeLineNumbers] the caret row shows
+    /// its absolute line number, and every other row shows its distance from the caret
+    /// row.
+    pub fn get_line_number(
+        gutter_mode: &GutterMode,
+        row_index: ChUnit,
+        caret_row_index: ChUnit,
+    ) -> usize {
+        let row_index = ch!(@to_usize row_index);
+        let caret_row_index = ch!(@to_usize caret_row_index);
+        match gutter_mode {
+            GutterMode::RelativeLineNumbers if row_index != caret_row_index => {
+                row_index.abs_diff(caret_row_index)
+            }
+            _ => row_index + 1,
+        }
+    }
+}
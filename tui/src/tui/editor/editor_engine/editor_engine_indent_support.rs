@@ -0,0 +1,218 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Support for [EditorEvent::Indent] & [EditorEvent::Outdent] (Tab & Shift+Tab), which
+/// use [EditorEngineConfig::indent_width] spaces per indentation level.
+///
+/// ```text
+///   Selection             Tab                     Shift+Tab
+///   ───────────────────   ─────────────────────   ─────────────────
+///   "fo▴o"                "fo  ▴o"                "fo▴o"
+///   "┃foo"   (2 lines)    "    ┃foo"              "foo"
+///   "┃  bar"              "      ┃bar"            "bar"
+/// ```
+///
+/// 1. Tab indents all the selected lines if the selection spans multiple lines.
+///    Otherwise, it replaces the selection (if any) with spaces up to the next
+///    indentation stop.
+/// 2. Shift+Tab outdents all the selected lines (or the caret line if there is no
+///    selection), by removing up to [EditorEngineConfig::indent_width] leading spaces (or
+///    a leading tab).
+/// 3. Empty lines are not indented, and a line that the selection only touches at its
+///    start (eg: after selecting whole lines w/ Shift+Down) is left as is.
+/// 4. The selection & caret are shifted along with the text.
+pub mod indent_support {
+    use super::*;
+
+    pub fn indent(args: EditorArgsMut<'_>) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if editor_buffer.get_selection_map().map.len() > 1 {
+            change_indentation(editor_buffer, editor_engine, IndentDirection::Indent);
+            return;
+        }
+
+        if editor_buffer.has_selection() {
+            EditorEngineInternalApi::delete_selected(
+                editor_buffer,
+                editor_engine,
+                DeleteSelectionWith::AnyKey,
+            );
+        }
+
+        let indent_width = get_indent_width(editor_engine);
+        let caret_col_index =
+            ch!(@to_usize editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index);
+        let space_count = indent_width - caret_col_index % indent_width;
+
+        EditorEngineInternalApi::insert_str_at_caret(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            &SPACE.repeat(space_count),
+        );
+    }
+
+    pub fn outdent(args: EditorArgsMut<'_>) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        change_indentation(editor_buffer, editor_engine, IndentDirection::Outdent);
+    }
+
+    /// An indent width of 0 is treated as 1.
+    pub fn get_indent_width(editor_engine: &EditorEngine) -> usize {
+        editor_engine.config_options.indent_width.max(1)
+    }
+
+    /// Returns the leading whitespace that is removed from the line when it is outdented.
+    pub fn find_outdent_prefix(line: &str, indent_width: usize) -> &str {
+        let space_count = line
+            .chars()
+            .take(indent_width)
+            .take_while(|character| *character == SPACE_CHAR)
+            .count();
+        if space_count == 0 && line.starts_with('\t') {
+            &line[..1]
+        } else {
+            &line[..space_count]
+        }
+    }
+
+    enum IndentDirection {
+        Indent,
+        Outdent,
+    }
+
+    /// Returns the rows that are selected (leaving out the ones where the selection is
+    /// empty), or the caret row if there is no selection.
+    fn get_rows_to_change(editor_buffer: &EditorBuffer) -> Vec<RowIndex> {
+        let selection_map = editor_buffer.get_selection_map();
+        if selection_map.is_empty() {
+            return vec![editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index];
+        }
+        selection_map
+            .get_ordered_indices()
+            .into_iter()
+            .filter(|row_index| {
+                selection_map.get(*row_index).is_some_and(|range| {
+                    range.start_display_col_index != range.end_display_col_index
+                })
+            })
+            .collect()
+    }
+
+    fn change_indentation(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        direction: IndentDirection,
+    ) {
+        if editor_buffer.is_empty() {
+            return;
+        }
+
+        let indent_width = get_indent_width(editor_engine);
+
+        // The new content of each changed row, and how many cols it is shifted by.
+        let changes: Vec<(RowIndex, String, ChUnit)> = get_rows_to_change(editor_buffer)
+            .into_iter()
+            .filter_map(|row_index| {
+                let line = editor_buffer.get_lines().get(ch!(@to_usize row_index))?;
+                match direction {
+                    IndentDirection::Indent => {
+                        if line.string.is_empty() {
+                            return None;
+                        }
+                        let new_line =
+                            format!("{}{}", SPACE.repeat(indent_width), line.string);
+                        Some((row_index, new_line, ch!(indent_width)))
+                    }
+                    IndentDirection::Outdent => {
+                        let prefix = find_outdent_prefix(&line.string, indent_width);
+                        if prefix.is_empty() {
+                            return None;
+                        }
+                        let new_line = line.string[prefix.len()..].to_string();
+                        let shift = UnicodeString::from(prefix).display_width;
+                        Some((row_index, new_line, shift))
+                    }
+                }
+            })
+            .collect();
+
+        if changes.is_empty() {
+            return;
+        }
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                for (row_index, new_line, _) in &changes {
+                    lines[ch!(@to_usize *row_index)] = new_line.clone().into();
+                }
+            },
+        );
+
+        // Shift the selection & the caret along with the text.
+        let shift_col_index = |col_index: ChUnit, shift: ChUnit| -> ChUnit {
+            match direction {
+                // Keep the start of the line selected.
+                IndentDirection::Indent if col_index == ch!(0) => col_index,
+                IndentDirection::Indent => col_index + shift,
+                IndentDirection::Outdent => col_index - shift,
+            }
+        };
+
+        let (_, _, _, selection_map) = editor_buffer.get_mut();
+        for (row_index, _, shift) in &changes {
+            if let Some(range) = selection_map.map.get_mut(row_index) {
+                range.start_display_col_index =
+                    shift_col_index(range.start_display_col_index, *shift);
+                range.end_display_col_index =
+                    shift_col_index(range.end_display_col_index, *shift);
+            }
+        }
+
+        let new_caret_col_index = changes
+            .iter()
+            .find(|(row_index, _, _)| *row_index == caret_adj.row_index)
+            .map(|(_, _, shift)| shift_col_index(caret_adj.col_index, *shift))
+            .unwrap_or(caret_adj.col_index);
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            position!(col_index: new_caret_col_index, row_index: caret_adj.row_index),
+        );
+    }
+}
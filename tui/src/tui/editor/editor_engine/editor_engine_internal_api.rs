@@ -21,7 +21,7 @@ use get_size::GetSize;
 use r3bl_rs_utils_core::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{editor_buffer_clipboard_support::clipboard_support, *};
+use crate::*;
 
 /// Functions that implement the editor engine.
 pub struct EditorEngineInternalApi;
@@ -91,6 +91,10 @@ impl EditorEngineInternalApi {
         caret_mut::to_end_of_line(buffer, engine, select_mode)
     }
 
+    pub fn move_caret_to(args: EditorArgsMut<'_>, caret_adj: Position) {
+        caret_mut::to_position(args, caret_adj)
+    }
+
     pub fn validate_scroll(args: EditorArgsMut) {
         scroll_editor_buffer::validate_scroll(args);
     }
@@ -143,13 +147,20 @@ impl EditorEngineInternalApi {
         content_mut::backspace_at_caret(buffer, engine)
     }
 
-    pub fn copy_editor_selection_to_clipboard(buffer: &EditorBuffer) {
-        clipboard_support::copy(buffer)
+    pub fn copy_editor_selection_to_clipboard(
+        buffer: &mut EditorBuffer,
+        engine: &EditorEngine,
+    ) {
+        clipboard_support::copy(buffer, &engine.clipboard)
     }
 
     pub fn paste_clipboard_content_into_editor(args: EditorArgsMut<'_>) {
         clipboard_support::paste(args)
     }
+
+    pub fn paste_kill_ring_entry_into_editor(args: EditorArgsMut<'_>, index: usize) {
+        clipboard_support::paste_from_kill_ring(args, index)
+    }
 }
 
 /// Helper macros just for this module.
@@ -375,6 +386,20 @@ mod caret_mut {
         empty_check_early_return!(editor_buffer, @None);
         multiline_disabled_check_early_return!(editor_engine, @None);
 
+        // Follow the visual rows (if there is one above the caret).
+        if soft_wrap_support::is_enabled(editor_engine)
+            && soft_wrap_support::move_caret_vertically(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                CaretDirection::Up,
+                select_mode,
+            )
+        {
+            return None;
+        }
+
         // This is only set if select_mode is enabled.
         let maybe_previous_caret_display_position =
             select_mode.get_caret_display_position(editor_buffer);
@@ -470,6 +495,20 @@ mod caret_mut {
         empty_check_early_return!(editor_buffer, @None);
         multiline_disabled_check_early_return!(editor_engine, @None);
 
+        // Follow the visual rows (if there is one below the caret).
+        if soft_wrap_support::is_enabled(editor_engine)
+            && soft_wrap_support::move_caret_vertically(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                CaretDirection::Down,
+                select_mode,
+            )
+        {
+            return None;
+        }
+
         // This is only set if select_mode is enabled.
         let maybe_previous_caret_display_position =
             select_mode.get_caret_display_position(editor_buffer);
@@ -547,6 +586,34 @@ mod caret_mut {
         None
     }
 
+    /// Move the caret to the given scroll adjusted position, and scroll the viewport if
+    /// the caret ends up outside of it. The selection is left as is, and the position is
+    /// expected to be inside the content.
+    pub fn to_position(args: EditorArgsMut<'_>, caret_adj: Position) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+
+        // Scroll up or left if the position is before the viewport. Scrolling down or
+        // right is taken care of by `validate_scroll`.
+        if caret_adj.row_index < scroll_offset.row_index {
+            scroll_offset.row_index = caret_adj.row_index;
+        }
+        if caret_adj.col_index < scroll_offset.col_index {
+            scroll_offset.col_index = caret_adj.col_index;
+        }
+        caret.row_index = caret_adj.row_index - scroll_offset.row_index;
+        caret.col_index = caret_adj.col_index - scroll_offset.col_index;
+
+        scroll_editor_buffer::validate_scroll(EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        });
+    }
+
     /// Depending on [SelectMode], this acts as a:
     /// - Convenience function for simply calling [left] repeatedly.
     /// - Convenience function for simply calling [scroll_editor_buffer::reset_caret_col].
@@ -1101,6 +1168,32 @@ mod content_mut {
             return;
         }
 
+        // Carry over the indentation (and the list bullet) of the caret line.
+        let new_line_action =
+            match content_get::line_at_caret_to_string(editor_buffer, editor_engine) {
+                Some(line) => auto_indent_support::find_new_line_action(
+                    &line,
+                    editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+                    &editor_engine.config_options.auto_indent_mode,
+                ),
+                None => NewLineAction::InsertWithPrefix(String::new()),
+            };
+        let prefix = match new_line_action {
+            NewLineAction::InsertWithPrefix(prefix) => prefix,
+            NewLineAction::EndList => {
+                let row_index = ch!(@to_usize editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index);
+                validate_editor_buffer_change::apply_change(
+                    editor_buffer,
+                    editor_engine,
+                    |lines, caret, scroll_offset| {
+                        lines[row_index] = String::new().into();
+                        scroll_editor_buffer::reset_caret_col(caret, scroll_offset);
+                    },
+                );
+                return;
+            }
+        };
+
         match caret_get::find_col(EditorArgs {
             editor_buffer,
             editor_engine,
@@ -1125,6 +1218,16 @@ mod content_mut {
             }
         }
 
+        if !prefix.is_empty() {
+            insert_str_at_caret(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                &prefix,
+            );
+        }
+
         mod inner {
             use super::*;
 
@@ -1488,6 +1591,10 @@ mod content_mut {
                 for row_index in vec_row_indices_to_remove {
                     lines.remove(ch!(@to_usize row_index));
                 }
+                // There is always at least one line, even if all the text was removed.
+                if lines.is_empty() {
+                    lines.push(UnicodeString::default());
+                }
 
                 // Restore caret position to start of selection range.
                 let maybe_new_position =
@@ -1660,6 +1767,24 @@ pub mod validate_editor_buffer_change {
             );
         }
 
+        // The gutter gets wider (or narrower) when the number of digits in the line count
+        // changes, which changes the viewport width.
+        let gutter_width_changed =
+            gutter_support::update_gutter_width(editor_buffer, editor_engine);
+
+        // There is no horizontal scrolling when lines are soft wrapped.
+        if soft_wrap_support::is_enabled(editor_engine) {
+            soft_wrap_support::validate_scroll(EditorArgsMut {
+                editor_engine,
+                editor_buffer,
+            });
+        } else if gutter_width_changed {
+            scroll_editor_buffer::validate_scroll(EditorArgsMut {
+                editor_engine,
+                editor_buffer,
+            });
+        }
+
         None
     }
 
@@ -2095,10 +2220,17 @@ mod scroll_editor_buffer {
             editor_buffer,
             editor_engine,
         });
-        validate_horizontal_scroll(EditorArgsMut {
-            editor_buffer,
-            editor_engine,
-        });
+        if soft_wrap_support::is_enabled(editor_engine) {
+            soft_wrap_support::validate_scroll(EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            });
+        } else {
+            validate_horizontal_scroll(EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            });
+        }
 
         /// Handle vertical scrolling (make sure caret is within viewport).
         ///
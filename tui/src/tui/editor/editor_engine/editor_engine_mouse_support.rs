@@ -0,0 +1,394 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::{Duration, Instant};
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Two clicks at the same position that are closer together than this are a double
+/// click.
+pub const DOUBLE_CLICK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The number of rows that the viewport is scrolled by for each notch of the mouse wheel.
+pub const MOUSE_WHEEL_ROW_COUNT: usize = 3;
+
+/// Support for [MouseInput] events in the editor.
+///
+/// 1. The position of a [MouseInput] is relative to the terminal. It is converted to a
+///    (scroll adjusted) position in the buffer using
+///    [to_buffer_position](mouse_support::to_buffer_position), which takes the origin of
+///    the [EditorEngine::current_box], the gutter, and soft wrapping into account.
+/// 2. The terminal does not report double clicks, so they are detected by the editor
+///    ([is_double_click](mouse_support::is_double_click)) using the time & position of
+///    the last click, which is stored in [EditorEngine::maybe_last_click].
+/// 3. [to_editor_event](mouse_support::to_editor_event) turns a [MouseInput] into an
+///    [EditorEvent]:
+///
+/// | Mouse input                 | Editor event                                        |
+/// | --------------------------- | --------------------------------------------------- |
+/// | Left click                  | [EditorEvent::PlaceCaretAt]                         |
+/// | Left click on a checkbox    | [EditorEvent::ToggleCheckboxAt] (unless read only)  |
+/// | Left double click           | [EditorEvent::SelectWordAt]                         |
+/// | Shift + left click          | [EditorEvent::SelectRange] (from the caret)         |
+/// | Left drag                   | [EditorEvent::SelectRange] (from the drag anchor)   |
+/// | Wheel up / down             | [EditorEvent::ScrollUp] / [EditorEvent::ScrollDown] |
+///
+/// The drag anchor is the position where the mouse went down, and it is stored in
+/// [EditorEngine::maybe_drag_anchor].
+pub mod mouse_support {
+    use super::*;
+
+    /// Returns [None] if the position is outside of the content viewport (eg: in the
+    /// gutter), or below the last line. The col is clipped to the end of the line, and
+    /// moved to the start of the grapheme cluster that it is in.
+    pub fn to_buffer_position(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        mouse_position: Position,
+    ) -> Option<Position> {
+        let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+        if mouse_position.col_index < origin_pos.col_index
+            || mouse_position.row_index < origin_pos.row_index
+        {
+            return None;
+        }
+        let position_in_box = position!(
+            col_index: mouse_position.col_index - origin_pos.col_index,
+            row_index: mouse_position.row_index - origin_pos.row_index
+        );
+
+        let raw_position =
+            gutter_support::to_content_position(editor_engine, position_in_box)?;
+        if raw_position.col_index >= editor_engine.viewport_width()
+            || raw_position.row_index >= editor_engine.viewport_height()
+        {
+            return None;
+        }
+
+        let viewport_lines =
+            soft_wrap_support::layout_viewport(editor_buffer, editor_engine);
+        let position =
+            soft_wrap_support::find_buffer_position(&viewport_lines, raw_position)?;
+
+        let line = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize position.row_index))?;
+        let col_index = match line.at_display_col_index(position.col_index) {
+            Some(segment) => segment.display_col_offset,
+            None => line.display_width,
+        };
+
+        Some(position!(col_index: col_index, row_index: position.row_index))
+    }
+
+    /// Returns [None] if the [MouseInput] is ignored by the editor, eg: a click outside of
+    /// the content, or a drag that didn't start in it.
+    pub fn to_editor_event(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        mouse_input: &MouseInput,
+    ) -> Option<EditorEvent> {
+        match mouse_input.kind {
+            MouseInputKind::MouseDown(Button::Left) => {
+                let is_double_click = is_double_click(editor_engine, mouse_input);
+                let position =
+                    to_buffer_position(editor_buffer, editor_engine, mouse_input.pos)?;
+
+                if is_double_click {
+                    return Some(EditorEvent::SelectWordAt(position));
+                }
+
+                if is_shift_pressed(mouse_input) {
+                    let anchor = match editor_engine.maybe_drag_anchor {
+                        Some(anchor) if editor_buffer.has_selection() => anchor,
+                        _ => editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+                    };
+                    editor_engine.maybe_drag_anchor = Some(anchor);
+                    return Some(EditorEvent::SelectRange {
+                        anchor,
+                        caret: position,
+                    });
+                }
+
+                let is_read_only =
+                    editor_engine.config_options.edit_mode == EditMode::ReadOnly;
+                if !is_read_only
+                    && task_list_support::is_checkbox_at(editor_buffer, position)
+                {
+                    editor_engine.maybe_drag_anchor = None;
+                    return Some(EditorEvent::ToggleCheckboxAt(position));
+                }
+
+                editor_engine.maybe_drag_anchor = Some(position);
+                Some(EditorEvent::PlaceCaretAt(position))
+            }
+            MouseInputKind::MouseDrag(Button::Left) => {
+                let anchor = editor_engine.maybe_drag_anchor?;
+                let position =
+                    to_buffer_position(editor_buffer, editor_engine, mouse_input.pos)?;
+                Some(EditorEvent::SelectRange {
+                    anchor,
+                    caret: position,
+                })
+            }
+            MouseInputKind::ScrollUp => {
+                Some(EditorEvent::ScrollUp(MOUSE_WHEEL_ROW_COUNT))
+            }
+            MouseInputKind::ScrollDown => {
+                Some(EditorEvent::ScrollDown(MOUSE_WHEEL_ROW_COUNT))
+            }
+            _ => None,
+        }
+    }
+
+    /// Move the caret to the (scroll adjusted) position, and clear the selection.
+    pub fn place_caret_at(args: EditorArgsMut<'_>, position: Position) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if editor_buffer.is_empty() {
+            return;
+        }
+
+        editor_buffer.clear_selection();
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            position,
+        );
+    }
+
+    /// Replace the selection w/ the text between the `anchor` and the `caret` (both are
+    /// scroll adjusted positions, and the `anchor` can be after the `caret`), and move the
+    /// caret there.
+    ///
+    /// ```text
+    ///   anchor
+    ///   ↓
+    ///  ┌───────────────┐
+    ///  │ab▓▓▓▓▓        │  <- from the anchor to the end of the line
+    ///  │▓▓▓▓▓▓▓▓▓      │  <- the whole line
+    ///  │▓▓▓▓cd         │  <- from the start of the line to the caret
+    ///  └───────────────┘
+    ///       ↑
+    ///       caret
+    /// ```
+    pub fn select_range(args: EditorArgsMut<'_>, anchor: Position, caret: Position) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if editor_buffer.is_empty() {
+            return;
+        }
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            caret,
+        );
+
+        let is_anchor_first =
+            (anchor.row_index, anchor.col_index) <= (caret.row_index, caret.col_index);
+        let (start, end) = if is_anchor_first {
+            (anchor, caret)
+        } else {
+            (caret, anchor)
+        };
+        let direction = match (is_anchor_first, anchor.row_index == caret.row_index) {
+            (true, true) => CaretMovementDirection::Right,
+            (false, true) => CaretMovementDirection::Left,
+            (true, false) => CaretMovementDirection::Down,
+            (false, false) => CaretMovementDirection::Up,
+        };
+
+        let mut new_selection_map = SelectionMap::default();
+        if start != end {
+            let lines = editor_buffer.get_lines();
+            for row_index in ch!(@to_usize start.row_index)..=ch!(@to_usize end.row_index)
+            {
+                let Some(line) = lines.get(row_index) else {
+                    break;
+                };
+                let row_index = ch!(row_index);
+                let start_display_col_index = if row_index == start.row_index {
+                    start.col_index
+                } else {
+                    ch!(0)
+                };
+                let end_display_col_index = if row_index == end.row_index {
+                    end.col_index
+                } else {
+                    line.display_width
+                };
+                new_selection_map.insert(
+                    row_index,
+                    SelectionRange::new(start_display_col_index, end_display_col_index),
+                    direction,
+                );
+            }
+        }
+
+        let (_, _, _, selection_map) = editor_buffer.get_mut();
+        *selection_map = new_selection_map;
+    }
+
+    /// Scroll the viewport up ([CaretDirection::Up]) or down ([CaretDirection::Down]) by
+    /// `row_count` lines, without moving the caret. If the caret would end up outside of
+    /// the viewport, then it is moved to the first (or last) line that is fully visible
+    /// (keeping its col if possible), since the caret is stored relative to the viewport.
+    /// The viewport can be scrolled down until the last line is at the top.
+    pub fn scroll_viewport(
+        args: EditorArgsMut<'_>,
+        direction: CaretDirection,
+        row_count: usize,
+    ) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if editor_buffer.is_empty() {
+            return;
+        }
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let max_scroll_row_index = editor_buffer.len() - ch!(1);
+        let new_scroll_row_index = match direction {
+            CaretDirection::Up => scroll_offset.row_index - ch!(row_count),
+            CaretDirection::Down => {
+                (scroll_offset.row_index + ch!(row_count)).min(max_scroll_row_index)
+            }
+            CaretDirection::Left | CaretDirection::Right => return,
+        };
+        if new_scroll_row_index == scroll_offset.row_index {
+            return;
+        }
+
+        {
+            let (_, caret, scroll_offset_mut, _) = editor_buffer.get_mut();
+            scroll_offset_mut.row_index = new_scroll_row_index;
+            caret.row_index =
+                caret_adj.row_index.max(new_scroll_row_index) - new_scroll_row_index;
+        }
+
+        // Find the lines that are fully visible in the (scrolled) viewport.
+        let viewport_lines =
+            soft_wrap_support::layout_viewport(editor_buffer, editor_engine);
+        let Some(first_row_index) = viewport_lines.first().map(|it| it.row_index) else {
+            return;
+        };
+        let last_row_index = viewport_lines
+            .iter()
+            .rev()
+            .find(|viewport_line| {
+                is_fully_visible(editor_buffer, editor_engine, viewport_line)
+            })
+            .map(|it| it.row_index)
+            .unwrap_or(first_row_index);
+
+        let new_row_index = caret_adj.row_index.clamp(first_row_index, last_row_index);
+        let new_col_index =
+            match editor_buffer.get_lines().get(ch!(@to_usize new_row_index)) {
+                Some(line) if new_row_index != caret_adj.row_index => {
+                    match line.at_display_col_index(caret_adj.col_index) {
+                        Some(segment) => segment.display_col_offset,
+                        None => line.display_width,
+                    }
+                }
+                _ => caret_adj.col_index,
+            };
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            position!(col_index: new_col_index, row_index: new_row_index),
+        );
+    }
+
+    /// Call this for every [MouseInput]. Returns `true` if it is the second click (of the
+    /// left button) at the same position within [DOUBLE_CLICK_TIMEOUT].
+    pub fn is_double_click(
+        editor_engine: &mut EditorEngine,
+        mouse_input: &MouseInput,
+    ) -> bool {
+        let MouseInputKind::MouseDown(Button::Left) = mouse_input.kind else {
+            return false;
+        };
+
+        let now = Instant::now();
+        let is_double_click = matches!(
+            editor_engine.maybe_last_click,
+            Some((position, instant))
+                if position == mouse_input.pos
+                    && now.duration_since(instant) < DOUBLE_CLICK_TIMEOUT
+        );
+
+        // A third click starts over (instead of being another double click).
+        editor_engine.maybe_last_click = if is_double_click {
+            None
+        } else {
+            Some((mouse_input.pos, now))
+        };
+
+        is_double_click
+    }
+
+    fn is_shift_pressed(mouse_input: &MouseInput) -> bool {
+        matches!(
+            mouse_input.maybe_modifier_keys,
+            Some(ModifierKeysMask {
+                shift_key_state: KeyState::Pressed,
+                ..
+            })
+        )
+    }
+
+    /// A line is not fully visible if some of its visual rows (when soft wrapped) are
+    /// below the viewport.
+    fn is_fully_visible(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        viewport_line: &ViewportLine,
+    ) -> bool {
+        if !soft_wrap_support::is_enabled(editor_engine) {
+            return true;
+        }
+        match editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize viewport_line.row_index))
+        {
+            Some(line) => {
+                soft_wrap_support::wrap_line(line, editor_engine.viewport_width()).len()
+                    == viewport_line.visual_rows.len()
+            }
+            None => true,
+        }
+    }
+}
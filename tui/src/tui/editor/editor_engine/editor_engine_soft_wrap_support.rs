@@ -0,0 +1,430 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// A line of the [EditorBuffer] that is (at least partially) visible in the viewport.
+/// - `row_index` is the scroll adjusted index of the line in the buffer.
+/// - `visual_rows` are the rows of the viewport that the line is painted on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewportLine {
+    pub row_index: ChUnit,
+    pub visual_rows: Vec<VisualRow>,
+}
+
+/// The part of a line (`range` is a display col index range, just like the
+/// [SelectionRange]s in the [SelectionMap]) that is painted on the row `raw_row_index` of
+/// the viewport.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VisualRow {
+    pub raw_row_index: ChUnit,
+    pub range: SelectionRange,
+}
+
+impl VisualRow {
+    pub fn display_col_count(&self) -> ChUnit {
+        self.range.end_display_col_index - self.range.start_display_col_index
+    }
+}
+
+/// Support for [WrapMode::SoftWrap].
+///
+/// The caret and the [SelectionMap] are still expressed in terms of lines (rows) and
+/// display cols, just like in [WrapMode::NoWrap]. The only differences are:
+/// 1. The horizontal scroll offset is always 0, so the (raw) caret col can be wider than
+///    the viewport.
+/// 2. The vertical scroll offset is the index of the first line that is visible, and the
+///    viewport is scrolled so that the visual row of the caret is visible.
+/// 3. Moving the caret up and down follows the visual rows, instead of the lines.
+///
+/// Rendering uses [layout_viewport](soft_wrap_support::layout_viewport) in both modes,
+/// to find out which part of which line is painted on each row of the viewport.
+pub mod soft_wrap_support {
+    use super::*;
+
+    pub fn is_enabled(editor_engine: &EditorEngine) -> bool {
+        matches!(editor_engine.config_options.wrap_mode, WrapMode::SoftWrap)
+            && editor_engine.viewport_width() > ch!(0)
+    }
+
+    /// Split the line into display col ranges, one for each visual row. Each range is at
+    /// most `viewport_width` wide, and wide grapheme clusters are never split across two
+    /// rows (a row is left short instead). An empty range is added at the end if the last
+    /// row is full (or if the line is empty), so that there is room for the caret at the
+    /// end of the line.
+    pub fn wrap_line(
+        line: &UnicodeString,
+        viewport_width: ChUnit,
+    ) -> Vec<SelectionRange> {
+        let line_display_width = line.display_width;
+
+        if viewport_width == ch!(0) {
+            return vec![SelectionRange::new(ch!(0), line_display_width)];
+        }
+
+        let mut it = vec![];
+
+        let mut start_display_col_index = ch!(0);
+        while start_display_col_index < line_display_width {
+            let display_col_count = {
+                let clipped_width = ch!(UnicodeString::str_display_width(
+                    line.clip_to_width(start_display_col_index, viewport_width)
+                ));
+                // A grapheme cluster that is wider than the viewport gets a row of its own.
+                if clipped_width == ch!(0) {
+                    line.at_display_col_index(start_display_col_index)
+                        .map(|segment| segment.unicode_width)
+                        .unwrap_or(ch!(1))
+                } else {
+                    clipped_width
+                }
+            };
+            it.push(SelectionRange::new(
+                start_display_col_index,
+                start_display_col_index + display_col_count,
+            ));
+            start_display_col_index += display_col_count;
+        }
+
+        let needs_row_for_caret = match it.last() {
+            Some(last) => {
+                last.end_display_col_index - last.start_display_col_index
+                    >= viewport_width
+            }
+            None => true,
+        };
+        if needs_row_for_caret {
+            it.push(SelectionRange::new(line_display_width, line_display_width));
+        }
+
+        it
+    }
+
+    /// Returns the index of the visual row (in the result of [wrap_line]) that holds the
+    /// given display col. A col at the boundary of two rows belongs to the second one.
+    pub fn find_visual_row_index(
+        ranges: &[SelectionRange],
+        display_col: ChUnit,
+    ) -> usize {
+        ranges
+            .iter()
+            .rposition(|range| range.start_display_col_index <= display_col)
+            .unwrap_or(0)
+    }
+
+    /// Find the lines that are visible in the viewport, and which part of them is painted
+    /// on which row of the viewport.
+    ///
+    /// - [WrapMode::NoWrap]: each line (starting at the scroll offset row) gets one visual
+    ///   row, which is the part of the line that is inside the horizontally scrolled
+    ///   viewport.
+    /// - [WrapMode::SoftWrap]: each line (starting at the scroll offset row) gets the
+    ///   visual rows from [wrap_line], until the viewport is full.
+    pub fn layout_viewport(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+    ) -> Vec<ViewportLine> {
+        let max_display_col_count = editor_engine.viewport_width();
+        let max_display_row_count = editor_engine.viewport_height();
+        let scroll_offset = editor_buffer.get_scroll_offset();
+
+        let lines = editor_buffer
+            .get_lines()
+            .iter()
+            .enumerate()
+            .skip(ch!(@to_usize scroll_offset.row_index));
+
+        let mut it = vec![];
+
+        if !is_enabled(editor_engine) {
+            for (raw_row_index, (row_index, _)) in lines.enumerate() {
+                // Clip the content to max rows.
+                if ch!(raw_row_index) > max_display_row_count {
+                    break;
+                }
+                it.push(ViewportLine {
+                    row_index: ch!(row_index),
+                    visual_rows: vec![VisualRow {
+                        raw_row_index: ch!(raw_row_index),
+                        range: SelectionRange::new(
+                            scroll_offset.col_index,
+                            scroll_offset.col_index + max_display_col_count,
+                        ),
+                    }],
+                });
+            }
+            return it;
+        }
+
+        let mut raw_row_index = ch!(0);
+        for (row_index, line) in lines {
+            if raw_row_index >= max_display_row_count {
+                break;
+            }
+            let mut visual_rows = vec![];
+            for range in wrap_line(line, max_display_col_count) {
+                if raw_row_index >= max_display_row_count {
+                    break;
+                }
+                visual_rows.push(VisualRow {
+                    raw_row_index,
+                    range,
+                });
+                raw_row_index += 1;
+            }
+            it.push(ViewportLine {
+                row_index: ch!(row_index),
+                visual_rows,
+            });
+        }
+
+        it
+    }
+
+    /// Convert a scroll adjusted position (in the buffer) to a position in the viewport,
+    /// using the result of [layout_viewport]. Returns [None] if the line isn't visible.
+    /// The returned col can be outside of the viewport (to the left or the right) in
+    /// [WrapMode::NoWrap].
+    pub fn find_viewport_position(
+        viewport_lines: &[ViewportLine],
+        position: Position,
+    ) -> Option<Position> {
+        let viewport_line = viewport_lines
+            .iter()
+            .find(|it| it.row_index == position.row_index)?;
+        let visual_row = viewport_line
+            .visual_rows
+            .iter()
+            .rev()
+            .find(|it| it.range.start_display_col_index <= position.col_index)
+            .or(viewport_line.visual_rows.first())?;
+        Some(position!(
+            col_index: position.col_index - visual_row.range.start_display_col_index,
+            row_index: visual_row.raw_row_index
+        ))
+    }
+
+    /// The opposite of [find_viewport_position]. Convert a position in the viewport to a
+    /// scroll adjusted position (in the buffer). Returns [None] if there is no line painted
+    /// on that row of the viewport. The returned col can be past the end of the line.
+    pub fn find_buffer_position(
+        viewport_lines: &[ViewportLine],
+        raw_position: Position,
+    ) -> Option<Position> {
+        viewport_lines.iter().find_map(|viewport_line| {
+            let visual_row = viewport_line
+                .visual_rows
+                .iter()
+                .find(|it| it.raw_row_index == raw_position.row_index)?;
+            Some(position!(
+                col_index: visual_row.range.start_display_col_index + raw_position.col_index,
+                row_index: viewport_line.row_index
+            ))
+        })
+    }
+
+    /// Move the caret up or down by one visual row, keeping it in the same col of the
+    /// viewport (if the row is long enough). Returns `false` if there is no visual row
+    /// above (or below) the caret, so that the caller can handle the top (or bottom) of
+    /// the buffer.
+    pub fn move_caret_vertically(
+        args: EditorArgsMut<'_>,
+        direction: CaretDirection,
+        select_mode: SelectMode,
+    ) -> bool {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let Some(new_caret_adj) =
+            find_caret_in_adjacent_visual_row(editor_buffer, editor_engine, direction)
+        else {
+            return false;
+        };
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+
+        // This is only set if select_mode is enabled.
+        let maybe_previous_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            new_caret_adj,
+        );
+
+        // This is only set if select_mode is enabled.
+        let maybe_current_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        // This is only runs if select_mode is enabled.
+        if new_caret_adj.row_index == caret_adj.row_index {
+            select_mode.handle_selection_single_line_caret_movement(
+                editor_buffer,
+                maybe_previous_caret_display_position,
+                maybe_current_caret_display_position,
+            );
+        } else {
+            select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+                editor_buffer,
+                maybe_previous_caret_display_position,
+                maybe_current_caret_display_position,
+            );
+        }
+
+        true
+    }
+
+    fn find_caret_in_adjacent_visual_row(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        direction: CaretDirection,
+    ) -> Option<Position> {
+        let viewport_width = editor_engine.viewport_width();
+        let lines = editor_buffer.get_lines();
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_index = ch!(@to_usize caret_adj.row_index);
+
+        let line = lines.get(row_index)?;
+        let ranges = wrap_line(line, viewport_width);
+        let visual_row_index = find_visual_row_index(&ranges, caret_adj.col_index);
+        let viewport_col_index =
+            caret_adj.col_index - ranges[visual_row_index].start_display_col_index;
+
+        let (new_row_index, new_line, new_ranges, new_visual_row_index) = match direction
+        {
+            CaretDirection::Up if visual_row_index > 0 => {
+                (row_index, line, ranges.clone(), visual_row_index - 1)
+            }
+            CaretDirection::Up if row_index > 0 => {
+                let new_line = &lines[row_index - 1];
+                let new_ranges = wrap_line(new_line, viewport_width);
+                let last_index = new_ranges.len() - 1;
+                (row_index - 1, new_line, new_ranges, last_index)
+            }
+            CaretDirection::Down if visual_row_index + 1 < ranges.len() => {
+                (row_index, line, ranges.clone(), visual_row_index + 1)
+            }
+            CaretDirection::Down if row_index + 1 < lines.len() => {
+                let new_line = &lines[row_index + 1];
+                (
+                    row_index + 1,
+                    new_line,
+                    wrap_line(new_line, viewport_width),
+                    0,
+                )
+            }
+            _ => return None,
+        };
+
+        // Keep the caret in the same viewport col, unless the row is shorter. The end of
+        // a row (that isn't the last one) is the start of the next one, so stop short of
+        // it.
+        let new_range = new_ranges[new_visual_row_index];
+        let is_last_visual_row = new_visual_row_index + 1 == new_ranges.len();
+        let desired_col_index = new_range.start_display_col_index + viewport_col_index;
+        let new_col_index = if !is_last_visual_row
+            && desired_col_index >= new_range.end_display_col_index
+        {
+            new_range.end_display_col_index - 1
+        } else {
+            desired_col_index.min(new_range.end_display_col_index)
+        };
+
+        Some(position!(
+            col_index: snap_to_start_of_grapheme_cluster(new_line, new_col_index),
+            row_index: new_row_index
+        ))
+    }
+
+    fn snap_to_start_of_grapheme_cluster(
+        line: &UnicodeString,
+        display_col: ChUnit,
+    ) -> ChUnit {
+        if display_col >= line.display_width {
+            return line.display_width;
+        }
+        line.vec_segment
+            .iter()
+            .rev()
+            .find(|segment| segment.display_col_offset <= display_col)
+            .map(|segment| segment.display_col_offset)
+            .unwrap_or(ch!(0))
+    }
+
+    /// Make sure that the visual row of the caret is in the viewport. The horizontal
+    /// scroll offset is reset to 0 (the caret col is adjusted to match), and the vertical
+    /// scroll offset is changed (if needed) one line at a time.
+    pub fn validate_scroll(args: EditorArgsMut<'_>) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = ch!(@to_usize editor_engine.viewport_height());
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let mut scroll_offset = editor_buffer.get_scroll_offset();
+        scroll_offset.col_index = ch!(0);
+
+        if caret_adj.row_index < scroll_offset.row_index {
+            // Caret is above viewport.
+            scroll_offset.row_index = caret_adj.row_index;
+        } else {
+            // Caret is in or below viewport.
+            let lines = editor_buffer.get_lines();
+            let caret_row_index = ch!(@to_usize caret_adj.row_index).min(lines.len());
+            let first_row_index =
+                ch!(@to_usize scroll_offset.row_index).min(caret_row_index);
+
+            let caret_visual_row_index_in_line = match lines.get(caret_row_index) {
+                Some(line) => find_visual_row_index(
+                    &wrap_line(line, viewport_width),
+                    caret_adj.col_index,
+                ),
+                None => 0,
+            };
+            let visual_row_counts: Vec<usize> = lines[first_row_index..caret_row_index]
+                .iter()
+                .map(|line| wrap_line(line, viewport_width).len())
+                .collect();
+
+            let mut caret_raw_row_index: usize =
+                visual_row_counts.iter().sum::<usize>() + caret_visual_row_index_in_line;
+            let mut scroll_row_index = first_row_index;
+            for visual_row_count in visual_row_counts {
+                if caret_raw_row_index < viewport_height {
+                    break;
+                }
+                caret_raw_row_index -= visual_row_count;
+                scroll_row_index += 1;
+            }
+            scroll_offset.row_index = ch!(scroll_row_index);
+        }
+
+        let (_, caret, scroll_offset_mut, _) = editor_buffer.get_mut();
+        *scroll_offset_mut = scroll_offset;
+        caret.row_index = caret_adj.row_index - scroll_offset.row_index;
+        caret.col_index = caret_adj.col_index;
+    }
+}
@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Instant};
 
 use r3bl_rs_utils_core::*;
 use serde::*;
@@ -44,6 +44,25 @@ pub struct EditorEngine {
     pub syntax_set: SyntaxSet,
     /// Syntax highlighting support. This is a very heavy object to create, re-use it.
     pub theme: Theme,
+    /// Set by [EditorEngineApi::render_engine](EditorEngineApi::render_engine) and when
+    /// the buffer changes. Please take a look at [gutter_support] for more details.
+    pub gutter_width: ChUnit,
+    /// The position & time of the last click, which is used to detect double clicks.
+    /// Please take a look at [mouse_support] for more details.
+    #[serde(skip)]
+    pub maybe_last_click: Option<(Position, Instant)>,
+    /// The (scroll adjusted) position where the mouse went down, which is where drag
+    /// selections start from.
+    #[serde(skip)]
+    pub maybe_drag_anchor: Option<Position>,
+    /// Used by [EditorEvent::Copy], [EditorEvent::Cut] & [EditorEvent::Paste]. Please take
+    /// a look at [SharedClipboard] for more details.
+    #[serde(skip)]
+    pub clipboard: SharedClipboard,
+    /// Set by [EditorEngineApi::render_engine](EditorEngineApi::render_engine). Please take
+    /// a look at [MdSynHiCache] for more details.
+    #[serde(skip)]
+    pub md_syn_hi_cache: MdSynHiCache,
 }
 
 impl Default for EditorEngine {
@@ -59,23 +78,45 @@ impl EditorEngine {
             config_options,
             syntax_set: SyntaxSet::load_defaults_newlines(),
             theme: try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme()),
+            gutter_width: ch!(0),
+            maybe_last_click: None,
+            maybe_drag_anchor: None,
+            clipboard: SharedClipboard::default(),
+            md_syn_hi_cache: MdSynHiCache::default(),
         }
     }
 
+    /// The width of the content viewport, which does not include the gutter.
     pub fn viewport_width(&self) -> ChUnit {
-        self.current_box.style_adjusted_bounds_size.col_count
+        self.current_box.style_adjusted_bounds_size.col_count - self.gutter_width
     }
 
     pub fn viewport_height(&self) -> ChUnit {
         self.current_box.style_adjusted_bounds_size.row_count
     }
+
+    /// The origin of the content viewport, which is to the right of the gutter.
+    pub fn content_origin_pos(&self) -> Position {
+        let mut it = self.current_box.style_adjusted_origin_pos;
+        it.col_index += self.gutter_width;
+        it
+    }
 }
 
+pub const DEFAULT_INDENT_WIDTH: usize = 4;
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EditorEngineConfig {
     pub multiline_mode: LineMode,
     pub syntax_highlight: SyntaxHighlightMode,
     pub edit_mode: EditMode,
+    pub wrap_mode: WrapMode,
+    pub gutter_mode: GutterMode,
+    pub auto_indent_mode: AutoIndentMode,
+    pub auto_pair_mode: AutoPairMode,
+    /// The number of spaces that [EditorEvent::Indent] & [EditorEvent::Outdent] add or
+    /// remove. Please take a look at [indent_support] for more details.
+    pub indent_width: usize,
 }
 
 mod editor_engine_config_options_impl {
@@ -89,6 +130,11 @@ mod editor_engine_config_options_impl {
                     DEFAULT_SYN_HI_FILE_EXT.to_string(),
                 ),
                 edit_mode: EditMode::ReadWrite,
+                wrap_mode: WrapMode::NoWrap,
+                gutter_mode: GutterMode::Hidden,
+                auto_indent_mode: AutoIndentMode::SmartList,
+                auto_pair_mode: AutoPairMode::Enable,
+                indent_width: DEFAULT_INDENT_WIDTH,
             }
         }
     }
@@ -106,6 +152,41 @@ pub enum LineMode {
     MultiLine,
 }
 
+/// How lines that are wider than the viewport are displayed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WrapMode {
+    /// The viewport is scrolled horizontally to show the rest of the line.
+    NoWrap,
+    /// Each line is laid out into as many visual rows (each as wide as the viewport) as
+    /// it needs, and there is no horizontal scrolling. Please take a look at
+    /// [soft_wrap_support] for more details.
+    SoftWrap,
+}
+
+/// What is shown in the gutter to the left of the content. Please take a look at
+/// [gutter_support] for more details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GutterMode {
+    Hidden,
+    /// Each line shows its line number (starting at 1) and its [LineMarker] (if any).
+    AbsoluteLineNumbers,
+    /// Each line shows its distance from the caret line (which shows its own line
+    /// number) and its [LineMarker] (if any).
+    RelativeLineNumbers,
+}
+
+/// What is carried over from the caret line when a new line is inserted. Please take a
+/// look at [auto_indent_support] for more details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoIndentMode {
+    Disable,
+    /// The leading whitespace (spaces & tabs) is carried over.
+    Indent,
+    /// The Markdown list bullet (`- `, `1. `, `- [ ] `) is carried over too. Ordered list
+    /// numbers are incremented, and pressing enter on an empty list item ends the list.
+    SmartList,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SyntaxHighlightMode {
     Disable,
@@ -0,0 +1,268 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::ops::Range;
+
+use constants::*;
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Support for [EditorEvent::AlignTable] (Ctrl+Alt+t), which re-aligns the Markdown table
+/// under the caret.
+///
+/// ```text
+///   Before                  After
+///   ─────────────────────   ─────────────────────────
+///   "|a|long header|"       "|  a  | long header |"
+///   "|:-:|--:|"             "| :-: | ----------: |"
+///   "|abc|1|"               "| abc |           1 |"
+/// ```
+///
+/// 1. The table is the block of lines around the caret that start & end w/ a `|`,
+///    starting at the line before the first delimiter row (eg: `|:---|---:|`). Please
+///    take a look at [mod@parse_block_table] for more details.
+/// 2. Each cell is padded to the display width of the widest cell in its column (so wide
+///    chars, like emoji, line up), based on the alignment of the column. Missing cells
+///    are added as empty ones.
+/// 3. The caret stays in the same cell.
+pub mod table_support {
+    use super::*;
+
+    pub fn align_table_at_caret(args: EditorArgsMut<'_>) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let caret_row_index = ch!(@to_usize caret_adj.row_index);
+        let lines = editor_buffer.get_lines();
+
+        let Some(row_range) = find_table_row_range(lines, caret_row_index) else {
+            return;
+        };
+        let table_lines = lines[row_range.clone()]
+            .iter()
+            .map(|line| line.string.as_str())
+            .collect::<Vec<_>>();
+        let Some(rows) = align_table_cells(&table_lines) else {
+            return;
+        };
+
+        let new_caret_col_index = {
+            let (cell_index, offset) =
+                find_caret_cell(&lines[caret_row_index], caret_adj.col_index);
+            let cells = &rows[caret_row_index - row_range.start];
+            // Past the last `|`, the caret goes to the end of the last cell.
+            let (cell_index, offset) = if cell_index < cells.len() {
+                (cell_index, offset)
+            } else {
+                (cells.len() - 1, usize::MAX)
+            };
+            let cell = &cells[cell_index];
+            let cell_start = cells[..cell_index]
+                .iter()
+                .map(|it| get_display_width(it) + TABLE_CELL_SEPARATOR.len())
+                .sum::<usize>()
+                + TABLE_ROW_PREFIX.len();
+            let padding_left = cell.len() - cell.trim_start().len();
+            cell_start + padding_left + offset.min(get_display_width(cell.trim()))
+        };
+
+        let new_lines = rows
+            .iter()
+            .map(|cells| join_table_cells(cells))
+            .collect::<Vec<_>>();
+        if new_lines
+            .iter()
+            .zip(table_lines)
+            .all(|(lhs, rhs)| lhs == rhs)
+        {
+            return;
+        }
+
+        editor_buffer.clear_selection();
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                for (row_index, new_line) in row_range.zip(new_lines) {
+                    lines[row_index] = new_line.into();
+                }
+            },
+        );
+
+        EditorEngineInternalApi::move_caret_to(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            position!(col_index: new_caret_col_index, row_index: caret_adj.row_index),
+        );
+    }
+
+    /// Returns the rows of the table that the row (at `row_index`) is in, if any.
+    pub fn find_table_row_range(
+        lines: &[UnicodeString],
+        row_index: usize,
+    ) -> Option<Range<usize>> {
+        let get_cells = |index: usize| -> Option<Vec<&str>> {
+            split_table_row(&lines.get(index)?.string)
+        };
+        let is_delimiter_row = |index: usize| -> bool {
+            get_cells(index).is_some_and(|cells| {
+                cells
+                    .iter()
+                    .all(|cell| parse_table_column_alignment(cell).is_some())
+            })
+        };
+
+        get_cells(row_index)?;
+
+        let mut start_row_index = row_index;
+        while start_row_index > 0 && get_cells(start_row_index - 1).is_some() {
+            start_row_index -= 1;
+        }
+        let mut end_row_index = row_index + 1;
+        while get_cells(end_row_index).is_some() {
+            end_row_index += 1;
+        }
+
+        let header_row_index = (start_row_index..end_row_index - 1)
+            .find(|index| is_delimiter_row(index + 1))?;
+        if header_row_index > row_index {
+            return None;
+        }
+        Some(header_row_index..end_row_index)
+    }
+
+    /// Returns the aligned lines of the table, or [None] if the lines aren't a table (eg:
+    /// the 2nd line isn't a delimiter row).
+    pub fn align_table(lines: &[&str]) -> Option<Vec<String>> {
+        let rows = align_table_cells(lines)?;
+        Some(rows.iter().map(|cells| join_table_cells(cells)).collect())
+    }
+
+    const TABLE_ROW_PREFIX: &str = "| ";
+    const TABLE_ROW_SUFFIX: &str = " |";
+    const TABLE_CELL_SEPARATOR: &str = " | ";
+    const MIN_TABLE_COLUMN_WIDTH: usize = 3;
+
+    fn get_display_width(text: &str) -> usize {
+        ch!(@to_usize UnicodeString::from(text).display_width)
+    }
+
+    fn join_table_cells(cells: &[String]) -> String {
+        format!(
+            "{TABLE_ROW_PREFIX}{}{TABLE_ROW_SUFFIX}",
+            cells.join(TABLE_CELL_SEPARATOR)
+        )
+    }
+
+    /// Returns the padded cells of each row (w/out the separators).
+    fn align_table_cells(lines: &[&str]) -> Option<Vec<Vec<String>>> {
+        let rows = lines
+            .iter()
+            .map(|line| split_table_row(line))
+            .collect::<Option<Vec<_>>>()?;
+        let alignments = rows
+            .get(1)?
+            .iter()
+            .map(|cell| parse_table_column_alignment(cell))
+            .collect::<Option<Vec<_>>>()?;
+        let column_count = rows.iter().map(Vec::len).max().unwrap_or_default();
+
+        let mut column_widths = vec![MIN_TABLE_COLUMN_WIDTH; column_count];
+        for row in rows.iter().take(1).chain(rows.iter().skip(2)) {
+            for (column_index, cell) in row.iter().enumerate() {
+                column_widths[column_index] =
+                    column_widths[column_index].max(get_display_width(cell.trim()));
+            }
+        }
+
+        let aligned_rows = rows
+            .iter()
+            .enumerate()
+            .map(|(row_index, row)| {
+                (0..column_count)
+                    .map(|column_index| {
+                        let alignment =
+                            alignments.get(column_index).copied().unwrap_or_default();
+                        let width = column_widths[column_index];
+                        if row_index == 1 {
+                            format_delimiter_cell(alignment, width)
+                        } else {
+                            let text = row.get(column_index).map_or("", |it| it.trim());
+                            pad_cell(text, alignment, width)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Some(aligned_rows)
+    }
+
+    fn format_delimiter_cell(alignment: TableColumnAlignment, width: usize) -> String {
+        let colon = TABLE_ALIGNMENT_CHAR.to_string();
+        let dashes = |count: usize| TABLE_DELIMITER_CHAR.to_string().repeat(count);
+        match alignment {
+            TableColumnAlignment::None => dashes(width),
+            TableColumnAlignment::Left => format!("{colon}{}", dashes(width - 1)),
+            TableColumnAlignment::Center => {
+                format!("{colon}{}{colon}", dashes(width - 2))
+            }
+            TableColumnAlignment::Right => format!("{}{colon}", dashes(width - 1)),
+        }
+    }
+
+    fn pad_cell(text: &str, alignment: TableColumnAlignment, width: usize) -> String {
+        let padding = width - get_display_width(text);
+        let (padding_left, padding_right) = match alignment {
+            TableColumnAlignment::None | TableColumnAlignment::Left => (0, padding),
+            TableColumnAlignment::Center => (padding / 2, padding - padding / 2),
+            TableColumnAlignment::Right => (padding, 0),
+        };
+        format!(
+            "{}{text}{}",
+            SPACE.repeat(padding_left),
+            SPACE.repeat(padding_right)
+        )
+    }
+
+    /// Returns the index of the cell that the caret is in, and the display col offset of
+    /// the caret from the start of the (trimmed) text of the cell.
+    fn find_caret_cell(line: &UnicodeString, caret_col_index: ChUnit) -> (usize, usize) {
+        let text_before_caret = line.clip_to_width(ch!(0), caret_col_index);
+
+        let mut separator_count = 0;
+        let mut cell_start = 0;
+        let mut is_escaped = false;
+        for (index, it) in text_before_caret.char_indices() {
+            if it == TABLE_SEPARATOR_CHAR && !is_escaped {
+                separator_count += 1;
+                cell_start = index + 1;
+            }
+            is_escaped = it == TABLE_ESCAPE_CHAR && !is_escaped;
+        }
+
+        let cell_index = separator_count.max(1) - 1;
+        let offset = get_display_width(text_before_caret[cell_start..].trim_start());
+        (cell_index, offset)
+    }
+}
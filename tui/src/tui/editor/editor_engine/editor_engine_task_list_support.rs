@@ -0,0 +1,115 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Support for [EditorEvent::ToggleCheckbox] (Ctrl+Alt+x) and
+/// [EditorEvent::ToggleCheckboxAt] (a click on a checkbox), which toggle the checkbox of a
+/// task in a Markdown list between `[ ]` and `[x]`.
+///
+/// ```text
+///   Before                  After
+///   ─────────────────────   ─────────────────────
+///   "- [ ] todo"            "- [x] todo"
+///   "  1. [x] done"         "  1. [ ] done"
+/// ```
+///
+/// 1. Only buffers w/ the Markdown file extension have tasks, which are found by parsing
+///    the whole document. Please take a look at [collect_tasks] for more details. This way
+///    a `- [ ]` in a code block isn't mistaken for a task.
+/// 2. Both checkboxes have the same width, so the caret doesn't move.
+pub mod task_list_support {
+    use super::*;
+
+    pub fn toggle_checkbox_at_caret(args: EditorArgsMut<'_>) {
+        let caret_adj = args.editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        toggle_checkbox_on_row(args, ch!(@to_usize caret_adj.row_index));
+    }
+
+    /// The (scroll adjusted) `position` must be on the checkbox, eg: where it was clicked.
+    pub fn toggle_checkbox_at(args: EditorArgsMut<'_>, position: Position) {
+        if is_checkbox_at(args.editor_buffer, position) {
+            toggle_checkbox_on_row(args, ch!(@to_usize position.row_index));
+        }
+    }
+
+    /// Returns the task on the line at `row_index`, if the line has one.
+    pub fn find_task_on_row(
+        editor_buffer: &EditorBuffer,
+        row_index: usize,
+    ) -> Option<MdTask> {
+        if editor_buffer.get_maybe_file_extension() != Some(DEFAULT_SYN_HI_FILE_EXT) {
+            return None;
+        }
+
+        let text = editor_buffer
+            .get_lines()
+            .iter()
+            .map(|line| format!("{}{NEW_LINE}", line.string))
+            .collect::<String>();
+
+        collect_tasks_from_markdown(&text)
+            .into_iter()
+            .find(|task| task.get_line_index() == row_index)
+    }
+
+    /// Whether the (scroll adjusted) `position` is on the checkbox of a task.
+    pub fn is_checkbox_at(editor_buffer: &EditorBuffer, position: Position) -> bool {
+        find_task_on_row(editor_buffer, ch!(@to_usize position.row_index)).is_some_and(
+            |task| {
+                let start_col_index = task.checkbox_span.start.col_index;
+                let end_col_index = task.checkbox_span.end.col_index;
+                position.col_index >= start_col_index
+                    && position.col_index < end_col_index
+            },
+        )
+    }
+
+    fn toggle_checkbox_on_row(args: EditorArgsMut<'_>, row_index: usize) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let Some(task) = find_task_on_row(editor_buffer, row_index) else {
+            return;
+        };
+
+        let new_line = {
+            let line = &editor_buffer.get_lines()[row_index];
+            let start_col_index = task.checkbox_span.start.col_index;
+            let end_col_index = task.checkbox_span.end.col_index;
+            let checkbox = if task.is_done { UNCHECKED } else { CHECKED };
+            format!(
+                "{}{checkbox}{}",
+                line.clip_to_width(ch!(0), start_col_index),
+                line.clip_to_width(end_col_index, line.display_width)
+            )
+        };
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                lines[row_index] = UnicodeString::from(new_line);
+            },
+        );
+    }
+}
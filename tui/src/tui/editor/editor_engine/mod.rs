@@ -17,10 +17,28 @@
 
 // Attach.
 pub mod editor_engine_api;
+pub mod editor_engine_auto_indent_support;
+pub mod editor_engine_bracket_support;
+pub mod editor_engine_format_support;
+pub mod editor_engine_gutter_support;
+pub mod editor_engine_indent_support;
 pub mod editor_engine_internal_api;
+pub mod editor_engine_mouse_support;
+pub mod editor_engine_soft_wrap_support;
 pub mod editor_engine_struct;
+pub mod editor_engine_table_support;
+pub mod editor_engine_task_list_support;
 
 // Re-export.
 pub use editor_engine_api::*;
+pub use editor_engine_auto_indent_support::*;
+pub use editor_engine_bracket_support::*;
+pub use editor_engine_format_support::*;
+pub use editor_engine_gutter_support::*;
+pub use editor_engine_indent_support::*;
 pub use editor_engine_internal_api::*;
+pub use editor_engine_mouse_support::*;
+pub use editor_engine_soft_wrap_support::*;
 pub use editor_engine_struct::*;
+pub use editor_engine_table_support::*;
+pub use editor_engine_task_list_support::*;
@@ -1550,6 +1550,2196 @@ mod test_editor_ops {
     }
 }
 
+#[cfg(test)]
+mod test_search_and_replace {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::get_lines, *};
+    use crate::*;
+
+    #[test]
+    fn editor_find_next_and_previous() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["abc abc".into(), "xabc".into()]);
+
+        let query = SearchQuery::new("abc", SearchMode::PlainText, true);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::SetSearchQuery(Some(query)),
+                EditorEvent::FindNext,
+            ],
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(3)))
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::FindNext, EditorEvent::FindNext],
+        );
+        assert_eq2!(buffer.get_selection_map().map.len(), 1);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(1)),
+            Some(&SelectionRange::new(ch!(1), ch!(4)))
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+
+        // Wrap around to the start of the buffer.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::FindNext],
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(3)))
+        );
+
+        // Wrap around to the end of the buffer.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::FindPrevious],
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(1)),
+            Some(&SelectionRange::new(ch!(1), ch!(4)))
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::FindPrevious],
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(4), ch!(7)))
+        );
+    }
+
+    #[test]
+    fn editor_find_in_read_only_mode() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                edit_mode: EditMode::ReadOnly,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        buffer.set_lines(vec!["ab ab".into()]);
+        buffer.set_search_query(Some(SearchQuery::new(
+            "ab",
+            SearchMode::PlainText,
+            true,
+        )));
+
+        let result = EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(keypress!(@fn FunctionKey::F3)),
+        )
+        .unwrap();
+        assert!(matches!(result, EditorEngineApplyEventResult::Applied));
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(2)))
+        );
+    }
+
+    #[test]
+    fn editor_replace() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["foo bar foo".into()]);
+
+        // Nothing is selected, so the first replace only selects the first match.
+        let query = SearchQuery::new("FOO", SearchMode::PlainText, false);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::SetSearchQuery(Some(query)),
+                EditorEvent::Replace("baz".into()),
+            ],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["foo bar foo"]);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(3)))
+        );
+
+        // Replace the selected match, and select the next one.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Replace("baz".into())],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["baz bar foo"]);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(8), ch!(11)))
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Replace("baz".into())],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["baz bar baz"]);
+        assert!(!buffer.has_selection());
+
+        // Each replace is a single undo step.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Undo],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["baz bar foo"]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Undo],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["foo bar foo"]);
+    }
+
+    #[test]
+    fn editor_replace_all() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["a1 b22".into(), "none".into(), "c333".into()]);
+
+        // Regex w/ capture groups.
+        let query = SearchQuery::new(r"([a-z])(\d+)", SearchMode::Regex, true);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::SetSearchQuery(Some(query)),
+                EditorEvent::ReplaceAll("$2$1".into()),
+            ],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["1a 22b", "none", "333c"]);
+
+        // All the replacements are undone (and redone) in a single step.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Undo],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["a1 b22", "none", "c333"]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Redo],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["1a 22b", "none", "333c"]);
+
+        // Case sensitive plain text, where `$` is not expanded.
+        let query = SearchQuery::new("none", SearchMode::PlainText, true);
+        buffer.set_lines(vec!["none NONE".into()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::SetSearchQuery(Some(query)),
+                EditorEvent::ReplaceAll("$1".into()),
+            ],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["$1 NONE"]);
+    }
+}
+
+#[cfg(test)]
+mod test_multi_caret {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::get_lines, *};
+    use crate::*;
+
+    fn get_secondary_caret_positions(buffer: &EditorBuffer) -> Vec<Position> {
+        buffer
+            .get_secondary_carets()
+            .iter()
+            .map(|it| it.position)
+            .collect()
+    }
+
+    #[test]
+    fn editor_add_carets_below_and_insert_char() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["abc".into(), "abc".into(), "abc".into()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::AddCaretBelow, EditorEvent::AddCaretBelow],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
+        assert_eq2!(
+            get_secondary_caret_positions(&buffer),
+            vec![
+                position!(col_index: 0, row_index: 0),
+                position!(col_index: 0, row_index: 1)
+            ]
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('x')],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["xabc", "xabc", "xabc"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 2)
+        );
+        assert_eq2!(
+            get_secondary_caret_positions(&buffer),
+            vec![
+                position!(col_index: 1, row_index: 0),
+                position!(col_index: 1, row_index: 1)
+            ]
+        );
+
+        // Events that aren't applied to all carets remove the secondary carets.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::PageUp],
+        );
+        assert!(!buffer.has_secondary_carets());
+    }
+
+    #[test]
+    fn editor_backspace_joins_lines_at_all_carets() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["a".into(), "b".into(), "c".into()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::AddCaretBelow,
+                EditorEvent::AddCaretBelow,
+                EditorEvent::Backspace,
+            ],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["abc"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert_eq2!(
+            get_secondary_caret_positions(&buffer),
+            vec![
+                position!(col_index: 0, row_index: 0),
+                position!(col_index: 1, row_index: 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn editor_carets_merge_on_collision() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["ab".into(), "c".into()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::AddCaretBelow,
+                EditorEvent::MoveCaret(CaretDirection::Up),
+            ],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert!(!buffer.has_secondary_carets());
+    }
+
+    #[test]
+    fn editor_add_caret_at_next_occurrence() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["ab ab".into()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionScope::OneCharRight),
+                EditorEvent::Select(SelectionScope::OneCharRight),
+                EditorEvent::AddCaretAtNextOccurrence,
+            ],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(3), ch!(5)))
+        );
+        assert_eq2!(
+            buffer.get_secondary_carets()[0].selection_map.get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(2)))
+        );
+
+        // Typing replaces the selection at each caret.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('x')],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["x x"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+        assert_eq2!(
+            get_secondary_caret_positions(&buffer),
+            vec![position!(col_index: 1, row_index: 0)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_soft_wrap {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    fn make_soft_wrap_engine() -> EditorEngine {
+        EditorEngine {
+            config_options: EditorEngineConfig {
+                wrap_mode: WrapMode::SoftWrap,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!( col_count: 5, row_count: 3 ),
+            )
+        }
+    }
+
+    #[test]
+    fn editor_soft_wrap_layout_viewport() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let engine = make_soft_wrap_engine();
+        buffer.set_lines(vec!["abcdefg".into(), "xy".into(), "z".into()]);
+
+        // R ┌─────┐
+        // 0 │abcde│ <- line 0
+        // 1 │fg   │ <- line 0
+        // 2 │xy   │ <- line 1
+        //   └─────┘
+        let viewport_lines = soft_wrap_support::layout_viewport(&buffer, &engine);
+        assert_eq2!(viewport_lines.len(), 2);
+        assert_eq2!(
+            viewport_lines[0].visual_rows,
+            vec![
+                VisualRow {
+                    raw_row_index: ch!(0),
+                    range: SelectionRange::new(ch!(0), ch!(5)),
+                },
+                VisualRow {
+                    raw_row_index: ch!(1),
+                    range: SelectionRange::new(ch!(5), ch!(7)),
+                },
+            ]
+        );
+        assert_eq2!(viewport_lines[1].row_index, ch!(1));
+        assert_eq2!(
+            viewport_lines[1].visual_rows,
+            vec![VisualRow {
+                raw_row_index: ch!(2),
+                range: SelectionRange::new(ch!(0), ch!(2)),
+            }]
+        );
+
+        assert_eq2!(
+            soft_wrap_support::find_viewport_position(
+                &viewport_lines,
+                position!(col_index: 6, row_index: 0)
+            ),
+            Some(position!(col_index: 1, row_index: 1))
+        );
+    }
+
+    #[test]
+    fn editor_soft_wrap_move_caret_up_and_down() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = make_soft_wrap_engine();
+        buffer.set_lines(vec!["abcdefghij".into(), "xy".into()]);
+
+        // Line 0 is laid out into 3 visual rows: "abcde", "fghij", and an empty one
+        // (for the caret at the end of the line).
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+            ],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 6, row_index: 0)
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 10, row_index: 0)
+        );
+
+        // The caret's visual row is past the bottom of the viewport, so scroll down.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Up),
+            ],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn editor_soft_wrap_no_horizontal_scroll() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = make_soft_wrap_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertString("abcdefgh".into())],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 8, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 8, row_index: 0)
+        );
+
+        // Select across the wrapped rows.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Select(SelectionScope::OneLineUp)],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(3), ch!(8)))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_gutter {
+    use std::collections::HashMap;
+
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    fn make_engine_with_gutter(gutter_mode: GutterMode) -> EditorEngine {
+        EditorEngine {
+            config_options: EditorEngineConfig {
+                gutter_mode,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        }
+    }
+
+    fn get_painted_text(render_ops: &RenderOps) -> Vec<String> {
+        render_ops
+            .list
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn editor_gutter_width() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = make_engine_with_gutter(GutterMode::AbsoluteLineNumbers);
+
+        // Marker col + 1 digit + separator col.
+        gutter_support::update_gutter_width(&buffer, &mut engine);
+        assert_eq2!(engine.gutter_width, ch!(3));
+        assert_eq2!(engine.viewport_width(), ch!(7));
+        assert_eq2!(
+            engine.content_origin_pos(),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // Marker col + 2 digits + separator col.
+        buffer.set_lines((1..=12).map(|it| it.to_string()).collect());
+        assert!(gutter_support::update_gutter_width(&buffer, &mut engine));
+        assert_eq2!(engine.gutter_width, ch!(4));
+        assert_eq2!(engine.viewport_width(), ch!(6));
+
+        // Positions in the gutter are not in the content viewport.
+        assert_eq2!(
+            gutter_support::to_content_position(
+                &engine,
+                position!(col_index: 3, row_index: 1)
+            ),
+            None
+        );
+        assert_eq2!(
+            gutter_support::to_content_position(
+                &engine,
+                position!(col_index: 5, row_index: 1)
+            ),
+            Some(position!(col_index: 1, row_index: 1))
+        );
+
+        // Hidden.
+        let mut engine = make_engine_with_gutter(GutterMode::Hidden);
+        assert!(!gutter_support::update_gutter_width(&buffer, &mut engine));
+        assert_eq2!(engine.gutter_width, ch!(0));
+        assert_eq2!(engine.viewport_width(), ch!(10));
+    }
+
+    #[test]
+    fn editor_gutter_line_numbers() {
+        let absolute = GutterMode::AbsoluteLineNumbers;
+        assert_eq2!(
+            gutter_support::get_line_number(&absolute, ch!(0), ch!(2)),
+            1
+        );
+        assert_eq2!(
+            gutter_support::get_line_number(&absolute, ch!(4), ch!(2)),
+            5
+        );
+
+        let relative = GutterMode::RelativeLineNumbers;
+        assert_eq2!(
+            gutter_support::get_line_number(&relative, ch!(0), ch!(2)),
+            2
+        );
+        assert_eq2!(
+            gutter_support::get_line_number(&relative, ch!(2), ch!(2)),
+            3
+        );
+        assert_eq2!(
+            gutter_support::get_line_number(&relative, ch!(4), ch!(2)),
+            2
+        );
+    }
+
+    #[test]
+    fn editor_gutter_render_markers() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = make_engine_with_gutter(GutterMode::AbsoluteLineNumbers);
+        buffer.set_lines(vec!["a".into(), "b".into(), "c".into()]);
+        buffer.set_line_markers(HashMap::from([
+            (ch!(0), LineMarker::Error),
+            (ch!(2), LineMarker::Modified),
+        ]));
+        buffer.set_line_marker(ch!(1), Some(LineMarker::Warning));
+        buffer.set_line_marker(ch!(2), None);
+        gutter_support::update_gutter_width(&buffer, &mut engine);
+
+        let viewport_lines = soft_wrap_support::layout_viewport(&buffer, &engine);
+        let mut render_ops = render_ops!();
+        gutter_support::render_gutter(&buffer, &engine, &viewport_lines, &mut render_ops);
+
+        assert_eq2!(
+            get_painted_text(&render_ops),
+            vec!["●", "1 ", "▲", "2 ", " ", "3 "]
+        );
+    }
+
+    #[test]
+    fn editor_gutter_is_excluded_from_viewport() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = make_engine_with_gutter(GutterMode::AbsoluteLineNumbers);
+
+        // The viewport is 7 cols wide (the gutter is 3 cols wide), so the content is
+        // scrolled horizontally.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertString("abcdefgh".into())],
+        );
+        assert_eq2!(engine.gutter_width, ch!(3));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 8, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 6, row_index: 0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_word_navigation {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::make_buffer, *};
+    use crate::*;
+
+    fn make_mouse_down(col_index: usize, row_index: usize) -> MouseInput {
+        MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: None,
+        }
+    }
+
+    #[test]
+    fn editor_find_word_ranges() {
+        let line = UnicodeString::from("let foo_bar = \"baz\";");
+        assert_eq2!(
+            word_support::find_word_ranges(&line),
+            vec![
+                SelectionRange::new(ch!(0), ch!(3)),
+                SelectionRange::new(ch!(4), ch!(11)),
+                SelectionRange::new(ch!(15), ch!(18)),
+            ]
+        );
+        assert_eq2!(
+            word_support::find_word_at(&line, ch!(6)),
+            Some(SelectionRange::new(ch!(4), ch!(11)))
+        );
+        assert_eq2!(
+            word_support::find_word_at(&line, ch!(11)),
+            Some(SelectionRange::new(ch!(4), ch!(11)))
+        );
+        assert_eq2!(word_support::find_word_at(&line, ch!(12)), None);
+    }
+
+    #[test]
+    fn editor_move_caret_by_word() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_buffer(&["ab cd", "ef"]);
+
+        // Right: end of each word, then the start of the next line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaretByWord(WordDirection::Right)],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaretByWord(WordDirection::Right)],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaretByWord(WordDirection::Right)],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        // Left: end of the previous line, then the start of each word.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaretByWord(WordDirection::Left)],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaretByWord(WordDirection::Left),
+                EditorEvent::MoveCaretByWord(WordDirection::Left),
+            ],
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn editor_select_by_word() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_buffer(&["ab cd"]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionScope::WordRight),
+                EditorEvent::Select(SelectionScope::WordRight),
+            ],
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(5)))
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Select(SelectionScope::WordLeft)],
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(3)))
+        );
+    }
+
+    #[test]
+    fn editor_delete_word() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_buffer(&["ab cd ef", "gh"]);
+
+        // Delete the word to the left of the caret.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::DeleteWordLeft,
+            ],
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "ab  ef");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // Delete the word to the right of the caret.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::DeleteWordRight],
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "ab ");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // At the end of the line, the next line is joined.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::DeleteWordRight],
+        );
+        assert_eq2!(buffer.len(), ch!(1));
+        assert_eq2!(buffer.get_lines()[0].string, "ab gh");
+    }
+
+    #[test]
+    fn editor_select_word_on_double_click() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_buffer(&["ab cd"]);
+
+        assert_eq2!(
+            mouse_support::to_buffer_position(
+                &buffer,
+                &engine,
+                position!(col_index: 4, row_index: 0)
+            ),
+            Some(position!(col_index: 4, row_index: 0))
+        );
+        assert_eq2!(
+            mouse_support::to_buffer_position(
+                &buffer,
+                &engine,
+                position!(col_index: 8, row_index: 0)
+            ),
+            Some(position!(col_index: 5, row_index: 0))
+        );
+        assert_eq2!(
+            mouse_support::to_buffer_position(
+                &buffer,
+                &engine,
+                position!(col_index: 0, row_index: 1)
+            ),
+            None
+        );
+
+        // The first click is not a double click, the second one is.
+        let mouse_down = make_mouse_down(4, 0);
+        assert!(!mouse_support::is_double_click(&mut engine, &mouse_down));
+        assert!(mouse_support::is_double_click(&mut engine, &mouse_down));
+        assert!(!mouse_support::is_double_click(&mut engine, &mouse_down));
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::SelectWordAt(
+                position!(col_index: 4, row_index: 0),
+            )],
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(3), ch!(5)))
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_mouse {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::make_buffer, *};
+    use crate::*;
+
+    fn make_mouse_input(
+        kind: MouseInputKind,
+        col_index: usize,
+        row_index: usize,
+    ) -> InputEvent {
+        InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind,
+            maybe_modifier_keys: None,
+        })
+    }
+
+    fn apply_input_event(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+        input_event: InputEvent,
+    ) -> EditorEngineApplyEventResult {
+        EditorEngineApi::apply_event(buffer, engine, input_event).unwrap()
+    }
+
+    #[test]
+    fn editor_mouse_click_places_caret() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_buffer(&["abc def", "a😀b"]);
+
+        let result = apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDown(Button::Left), 5, 0),
+        );
+        assert!(matches!(result, EditorEngineApplyEventResult::Applied));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+
+        // Clicking on the 2nd col of a wide grapheme cluster places the caret before it.
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDown(Button::Left), 2, 1),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
+
+        // Clicking past the end of the line places the caret at the end of it.
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDown(Button::Left), 8, 1),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+
+        // Clicking below the last line is ignored.
+        let result = apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDown(Button::Left), 0, 5),
+        );
+        assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+    }
+
+    #[test]
+    fn editor_mouse_click_accounts_for_origin_and_scroll_offset() {
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                gutter_mode: GutterMode::AbsoluteLineNumbers,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        engine.current_box.style_adjusted_origin_pos =
+            position!(col_index: 2, row_index: 3);
+        let mut buffer =
+            make_buffer(&(0..20).map(|it| format!("line {it}")).collect::<Vec<_>>());
+        gutter_support::update_gutter_width(&buffer, &mut engine);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ScrollDown(5)],
+        );
+
+        // The gutter is 4 cols wide, so col 2 + 4 + 1 is the 2nd col of the content.
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDown(Button::Left), 7, 4),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 6)
+        );
+
+        // Clicking in the gutter is ignored.
+        let result = apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDown(Button::Left), 3, 4),
+        );
+        assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+    }
+
+    #[test]
+    fn editor_mouse_drag_selects() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_buffer(&["abc def", "ghi"]);
+
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDown(Button::Left), 1, 0),
+        );
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDrag(Button::Left), 2, 1),
+        );
+        assert_eq2!(buffer.get_selection_map().map.len(), 2);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(1), ch!(7)))
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(1)),
+            Some(&SelectionRange::new(ch!(0), ch!(2)))
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+
+        // Drag back before the anchor.
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDrag(Button::Left), 0, 0),
+        );
+        assert_eq2!(buffer.get_selection_map().map.len(), 1);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(1)))
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Click clears the selection.
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::MouseDown(Button::Left), 2, 1),
+        );
+        assert!(!buffer.has_selection());
+
+        // Shift + click selects from the caret.
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            InputEvent::Mouse(MouseInput {
+                pos: position!(col_index: 4, row_index: 0),
+                kind: MouseInputKind::MouseDown(Button::Left),
+                maybe_modifier_keys: Some(ModifierKeysMask {
+                    shift_key_state: KeyState::Pressed,
+                    ctrl_key_state: KeyState::NotPressed,
+                    alt_key_state: KeyState::NotPressed,
+                }),
+            }),
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(4), ch!(7)))
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(1)),
+            Some(&SelectionRange::new(ch!(0), ch!(2)))
+        );
+    }
+
+    #[test]
+    fn editor_mouse_wheel_scrolls() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            make_buffer(&(0..20).map(|it| it.to_string()).collect::<Vec<_>>());
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+            ],
+        );
+
+        // The caret stays where it is.
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::ScrollDown, 0, 0),
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 3)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 5)
+        );
+
+        apply_input_event(
+            &mut engine,
+            &mut buffer,
+            make_mouse_input(MouseInputKind::ScrollUp, 0, 0),
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 5)
+        );
+
+        // The caret is moved to the first line in the viewport if it would be above it.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ScrollDown(100)],
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 19)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 19)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_auto_indent {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::*;
+
+    /// Press enter at the end of the line, and return the resulting lines.
+    fn insert_new_line_at_end(
+        engine: &mut EditorEngine,
+        line: &str,
+    ) -> (Vec<String>, Position) {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec![line.to_string()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::InsertNewLine],
+        );
+        (
+            buffer
+                .get_lines()
+                .iter()
+                .map(|it| it.string.clone())
+                .collect(),
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+        )
+    }
+
+    #[test]
+    fn editor_auto_indent_continues_lists() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        assert_eq2!(
+            insert_new_line_at_end(&mut engine, "- foo"),
+            (
+                vec!["- foo".to_string(), "- ".to_string()],
+                position!(col_index: 2, row_index: 1)
+            )
+        );
+        assert_eq2!(
+            insert_new_line_at_end(&mut engine, "  9. foo"),
+            (
+                vec!["  9. foo".to_string(), "  10. ".to_string()],
+                position!(col_index: 6, row_index: 1)
+            )
+        );
+        assert_eq2!(
+            insert_new_line_at_end(&mut engine, "- [x] done"),
+            (
+                vec!["- [x] done".to_string(), "- [ ] ".to_string()],
+                position!(col_index: 6, row_index: 1)
+            )
+        );
+    }
+
+    #[test]
+    fn editor_auto_indent_ends_list_on_empty_item() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        for line in ["- ", "1. ", "- [ ] "] {
+            assert_eq2!(
+                insert_new_line_at_end(&mut engine, line),
+                (vec!["".to_string()], position!(col_index: 0, row_index: 0))
+            );
+        }
+    }
+
+    #[test]
+    fn editor_auto_indent_preserves_indent() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        assert_eq2!(
+            insert_new_line_at_end(&mut engine, "    a = 1;"),
+            (
+                vec!["    a = 1;".to_string(), "    ".to_string()],
+                position!(col_index: 4, row_index: 1)
+            )
+        );
+
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                auto_indent_mode: AutoIndentMode::Disable,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        assert_eq2!(
+            insert_new_line_at_end(&mut engine, "- foo"),
+            (
+                vec!["- foo".to_string(), "".to_string()],
+                position!(col_index: 0, row_index: 1)
+            )
+        );
+    }
+
+    #[test]
+    fn editor_auto_indent_in_middle_of_list_item() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["- foobar".to_string()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::End,
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::InsertNewLine,
+            ],
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "- foo");
+        assert_eq2!(buffer.get_lines()[1].string, "- bar");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+
+        // The bullet isn't carried over if the caret is before it.
+        assert_eq2!(
+            auto_indent_support::find_new_line_action(
+                &UnicodeString::from("  - foo"),
+                ch!(1),
+                &AutoIndentMode::SmartList
+            ),
+            NewLineAction::InsertWithPrefix(" ".to_string())
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_clipboard {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::get_lines, *};
+    use crate::*;
+
+    #[test]
+    fn editor_copy_cut_paste() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["abc def".to_string()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionScope::WordRight),
+                EditorEvent::Copy,
+            ],
+        );
+        buffer.clear_selection();
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::Paste],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["abc defabc".to_string()]);
+        assert_eq2!(
+            engine
+                .clipboard
+                .try_to_get_content_from_clipboard()
+                .unwrap(),
+            "abc"
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Home,
+                EditorEvent::Select(SelectionScope::WordRight),
+                // "defabc" is a single word, so select " def" a char at a time.
+                EditorEvent::Select(SelectionScope::OneCharRight),
+                EditorEvent::Select(SelectionScope::OneCharRight),
+                EditorEvent::Select(SelectionScope::OneCharRight),
+                EditorEvent::Select(SelectionScope::OneCharRight),
+                EditorEvent::Cut,
+            ],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["abc".to_string()]);
+        assert_eq2!(
+            buffer.get_kill_ring().get_entries(),
+            &["abc def".to_string(), "abc".to_string()]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn editor_cut_all_the_text_leaves_an_empty_line() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["abc def".to_string()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Select(SelectionScope::End), EditorEvent::Cut],
+        );
+
+        assert_eq2!(get_lines(&buffer), vec!["".to_string()]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_kill_ring().get_entries(),
+            &["abc def".to_string()]
+        );
+    }
+
+    #[test]
+    fn editor_paste_from_kill_ring() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["ab cd".to_string()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionScope::WordRight),
+                EditorEvent::Copy,
+            ],
+        );
+        buffer.clear_selection();
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::End,
+                EditorEvent::Select(SelectionScope::WordLeft),
+                EditorEvent::Copy,
+            ],
+        );
+        buffer.clear_selection();
+        assert_eq2!(
+            buffer.get_kill_ring().get_entries(),
+            &["cd".to_string(), "ab".to_string()]
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::PasteFromKillRing(1)],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["ab cdab".to_string()]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+
+        // There is no such entry.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::PasteFromKillRing(2)],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["ab cdab".to_string()]);
+    }
+
+    #[test]
+    fn editor_paste_multiple_lines_is_not_auto_indented() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["x".to_string()]);
+        engine
+            .clipboard
+            .try_to_put_content_into_clipboard("- a\r\n- b")
+            .unwrap();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::Paste],
+        );
+
+        assert_eq2!(
+            get_lines(&buffer),
+            vec!["x- a".to_string(), "- b".to_string()]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn editor_buffer_paste_text_is_one_undo_step() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["ab".to_string()]);
+        buffer.set_caret_scroll_adjusted(position!(col_index: 1, row_index: 0));
+
+        buffer.paste_text("X\nY");
+        assert_eq2!(get_lines(&buffer), vec!["aX".to_string(), "Yb".to_string()]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
+
+        history::undo(&mut buffer);
+        assert_eq2!(get_lines(&buffer), vec!["ab".to_string()]);
+    }
+
+    #[test]
+    fn kill_ring_push() {
+        let mut kill_ring = KillRing::new(2);
+        kill_ring.push("a");
+        kill_ring.push("b");
+        kill_ring.push("");
+        kill_ring.push("a");
+        assert_eq2!(kill_ring.get_entries(), &["a".to_string(), "b".to_string()]);
+
+        kill_ring.push("c");
+        assert_eq2!(kill_ring.get_entries(), &["c".to_string(), "a".to_string()]);
+    }
+
+    #[test]
+    fn kill_ring_completion_items() {
+        let mut kill_ring = KillRing::default();
+        kill_ring.push("one\ntwo");
+        kill_ring.push("three");
+
+        assert_eq2!(
+            kill_ring.get_completion_items(),
+            vec![
+                CompletionItem::new("three", None, "three"),
+                CompletionItem::new("one …", Some("2 lines".to_string()), "one\ntwo"),
+            ]
+        );
+    }
+
+    #[test]
+    fn clipboard_services() {
+        let mut in_memory = InMemoryClipboard::default();
+        assert!(in_memory.try_to_get_content_from_clipboard().is_err());
+        in_memory.try_to_put_content_into_clipboard("a").unwrap();
+        assert_eq2!(in_memory.try_to_get_content_from_clipboard().unwrap(), "a");
+
+        // Clones share the same clipboard.
+        let clipboard = SharedClipboard::new(InMemoryClipboard::default());
+        clipboard
+            .clone()
+            .try_to_put_content_into_clipboard("b")
+            .unwrap();
+        assert_eq2!(clipboard.try_to_get_content_from_clipboard().unwrap(), "b");
+
+        for (content, base64) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("hello", "aGVsbG8="),
+        ] {
+            assert_eq2!(
+                Osc52Clipboard::make_escape_sequence(content),
+                format!("\x1b]52;c;{base64}\x07")
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_bracket_and_indent {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::get_lines, *};
+    use crate::*;
+
+    #[test]
+    fn editor_find_matching_bracket() {
+        let lines: Vec<UnicodeString> = ["fn a(b: [u8; 2]) {", "  (c)", "}"]
+            .into_iter()
+            .map(UnicodeString::from)
+            .collect();
+
+        // The bracket at the caret.
+        assert_eq2!(
+            bracket_support::find_matching_bracket(
+                &lines,
+                position!(col_index: 4, row_index: 0)
+            ),
+            Some((
+                position!(col_index: 4, row_index: 0),
+                position!(col_index: 15, row_index: 0)
+            ))
+        );
+
+        // The bracket to the left of the caret.
+        assert_eq2!(
+            bracket_support::find_matching_bracket(
+                &lines,
+                position!(col_index: 16, row_index: 0)
+            ),
+            Some((
+                position!(col_index: 15, row_index: 0),
+                position!(col_index: 4, row_index: 0)
+            ))
+        );
+
+        // Across lines, skipping over the brackets of another kind.
+        assert_eq2!(
+            bracket_support::find_matching_bracket(
+                &lines,
+                position!(col_index: 17, row_index: 0)
+            ),
+            Some((
+                position!(col_index: 17, row_index: 0),
+                position!(col_index: 0, row_index: 2)
+            ))
+        );
+
+        // No bracket at the caret.
+        assert_eq2!(
+            bracket_support::find_matching_bracket(
+                &lines,
+                position!(col_index: 0, row_index: 1)
+            ),
+            None
+        );
+
+        // Nested brackets of the same kind.
+        let lines = vec![UnicodeString::from("((a) b)")];
+        assert_eq2!(
+            bracket_support::find_matching_bracket(
+                &lines,
+                position!(col_index: 0, row_index: 0)
+            ),
+            Some((
+                position!(col_index: 0, row_index: 0),
+                position!(col_index: 6, row_index: 0)
+            ))
+        );
+    }
+
+    #[test]
+    fn editor_auto_pairs() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+
+        // Insert the pair, and skip over the closing bracket.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('('), EditorEvent::InsertChar('a')],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["(a)".to_string()]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar(')'), EditorEvent::InsertChar('"')],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["(a)\"\"".to_string()]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        // Backspace in an empty pair deletes both of them.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Backspace],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["(a)".to_string()]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // No pair before a word, or for a quote after a word.
+        let line = UnicodeString::from("don");
+        assert_eq2!(
+            bracket_support::find_auto_pair_action(
+                Some(&line),
+                ch!(0),
+                '(',
+                &AutoPairMode::Enable
+            ),
+            AutoPairAction::Insert
+        );
+        assert_eq2!(
+            bracket_support::find_auto_pair_action(
+                Some(&line),
+                ch!(3),
+                '\'',
+                &AutoPairMode::Enable
+            ),
+            AutoPairAction::Insert
+        );
+        assert_eq2!(
+            bracket_support::find_auto_pair_action(
+                Some(&line),
+                ch!(3),
+                '[',
+                &AutoPairMode::Enable
+            ),
+            AutoPairAction::InsertPair(']')
+        );
+
+        // Disabled.
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                auto_pair_mode: AutoPairMode::Disable,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('{'), EditorEvent::InsertChar('`')],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["{`".to_string()]);
+    }
+
+    #[test]
+    fn editor_indent_without_selection() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["ab".to_string()]);
+
+        // Insert spaces up to the next indentation stop.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::Indent,
+            ],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["a   b".to_string()]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        // Outdent the caret line.
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                indent_width: 2,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        buffer.set_lines(vec!["   foo".to_string(), "\tbar".to_string()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::Outdent],
+        );
+        assert_eq2!(
+            get_lines(&buffer),
+            vec![" foo".to_string(), "\tbar".to_string()]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Outdent,
+                EditorEvent::Outdent,
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::Outdent,
+            ],
+        );
+        assert_eq2!(
+            get_lines(&buffer),
+            vec!["foo".to_string(), "bar".to_string()]
+        );
+    }
+
+    #[test]
+    fn editor_indent_and_outdent_selected_lines() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["a".to_string(), "".to_string(), "b".to_string()]);
+
+        // Select the first 3 lines.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionScope::OneLineDown),
+                EditorEvent::Select(SelectionScope::OneLineDown),
+                EditorEvent::Select(SelectionScope::End),
+                EditorEvent::Indent,
+            ],
+        );
+        assert_eq2!(
+            get_lines(&buffer),
+            vec!["    a".to_string(), "".to_string(), "    b".to_string()]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 2)
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(2)),
+            Some(&SelectionRange::new(ch!(0), ch!(5)))
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Outdent],
+        );
+        assert_eq2!(
+            get_lines(&buffer),
+            vec!["a".to_string(), "".to_string(), "b".to_string()]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 2)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_table {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::get_lines, *};
+    use crate::*;
+
+    #[test]
+    fn editor_align_table() {
+        assert_eq2!(
+            table_support::align_table(&["|a|long header|", "|:-:|--:|", "|abc|1|"]),
+            Some(vec![
+                "|  a  | long header |".to_string(),
+                "| :-: | ----------: |".to_string(),
+                "| abc |           1 |".to_string(),
+            ])
+        );
+
+        // Missing cells, wide chars & escaped separators.
+        assert_eq2!(
+            table_support::align_table(&["| 😃 | b |", "|:-|-|", "|c|", r"|d \| e|f|"]),
+            Some(vec![
+                "| 😃     | b   |".to_string(),
+                "| :----- | --- |".to_string(),
+                "| c      |     |".to_string(),
+                r"| d \| e | f   |".to_string(),
+            ])
+        );
+
+        // No delimiter row.
+        assert_eq2!(table_support::align_table(&["|a|", "|b|"]), None);
+    }
+
+    #[test]
+    fn editor_find_table_row_range() {
+        let lines: Vec<UnicodeString> = ["foo", "|a|", "|-|", "|b|", "", "|c|"]
+            .into_iter()
+            .map(UnicodeString::from)
+            .collect();
+        assert_eq2!(table_support::find_table_row_range(&lines, 0), None);
+        assert_eq2!(table_support::find_table_row_range(&lines, 1), Some(1..4));
+        assert_eq2!(table_support::find_table_row_range(&lines, 3), Some(1..4));
+        assert_eq2!(table_support::find_table_row_range(&lines, 5), None);
+    }
+
+    #[test]
+    fn editor_align_table_at_caret() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec![
+            "foo".to_string(),
+            "|a|long header|".to_string(),
+            "|:-:|--:|".to_string(),
+            "|abc|1|".to_string(),
+        ]);
+
+        // The caret is between "a" & "bc".
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::Home,
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::AlignTable,
+            ],
+        );
+        assert_eq2!(
+            get_lines(&buffer),
+            vec![
+                "foo".to_string(),
+                "|  a  | long header |".to_string(),
+                "| :-: | ----------: |".to_string(),
+                "| abc |           1 |".to_string(),
+            ]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 3)
+        );
+
+        // Past the last `|`, the caret goes to the end of the last cell.
+        buffer.set_lines(vec!["|a|b|".to_string(), "|-|-|".to_string()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::AlignTable],
+        );
+        assert_eq2!(
+            get_lines(&buffer),
+            vec!["| a   | b   |".to_string(), "| --- | --- |".to_string()]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 9, row_index: 0)
+        );
+
+        // Not in a table (there is no delimiter row).
+        buffer.set_lines(vec!["|a|".to_string(), "foo".to_string()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::AlignTable],
+        );
+        assert_eq2!(
+            get_lines(&buffer),
+            vec!["|a|".to_string(), "foo".to_string()]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_format {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::get_lines, *};
+    use crate::*;
+
+    #[test]
+    fn editor_format_document() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec![
+            "__a__ _b_".to_string(),
+            "1. x".to_string(),
+            "1. y".to_string(),
+            "* * *".to_string(),
+        ]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::End,
+                EditorEvent::FormatDocument,
+            ],
+        );
+        assert_eq2!(
+            get_lines(&buffer),
+            vec![
+                "**a** *b*".to_string(),
+                "1. x".to_string(),
+                "2. y".to_string(),
+                "---".to_string(),
+            ]
+        );
+        // The caret stays on the same row, but the line is shorter now.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 3)
+        );
+    }
+
+    #[test]
+    fn editor_format_document_no_op() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Some of the text can't be parsed.
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["__a__".to_string(), "foo *bar".to_string()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::FormatDocument],
+        );
+        assert_eq2!(
+            get_lines(&buffer),
+            vec!["__a__".to_string(), "foo *bar".to_string()]
+        );
+
+        // Not a Markdown buffer.
+        let mut buffer = EditorBuffer::new_empty(Some("rs".to_owned()));
+        buffer.set_lines(vec!["__a__".to_string()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::FormatDocument],
+        );
+        assert_eq2!(get_lines(&buffer), vec!["__a__".to_string()]);
+
+        // Already formatted.
+        assert_eq2!(
+            format_support::format_lines(&[UnicodeString::from("**a**")]),
+            None
+        );
+    }
+
+    #[test]
+    fn editor_format_document_keeps_text() {
+        let format_lines = |lines: &[&str]| {
+            format_support::format_lines(
+                &lines
+                    .iter()
+                    .map(|it| UnicodeString::from(*it))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        // The parser skips over some of the text in these list items.
+        assert_eq2!(format_lines(&["- foo `bar"]), None);
+        assert_eq2!(format_lines(&["1. **😃"]), None);
+        assert_eq2!(format_lines(&["- ```rs"]), None);
+
+        // Formatting these again would lose the text of the last line.
+        assert_eq2!(format_lines(&["1. --:", "[[^1])"]), None);
+        assert_eq2!(format_lines(&["1. --:", "   [[^1])"]), None);
+
+        // The lazy continuation line is still lined up w/ the text after the bullet.
+        assert_eq2!(
+            format_lines(&["- a", "b"]),
+            Some(vec!["- a".to_string(), "  b".to_string()])
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_task_list {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::{get_lines, make_buffer},
+                *};
+    use crate::*;
+
+    fn make_task_list_buffer() -> EditorBuffer {
+        make_buffer(&["# Tasks", "- [ ] a", "  - [x] b", "```", "- [ ] c", "```"])
+    }
+
+    fn make_mouse_click(col_index: usize, row_index: usize) -> InputEvent {
+        InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: None,
+        })
+    }
+
+    fn make_ctrl_key(character: char, alt_key_state: KeyState) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character(character),
+            mask: ModifierKeysMask {
+                ctrl_key_state: KeyState::Pressed,
+                alt_key_state,
+                shift_key_state: KeyState::NotPressed,
+            },
+        })
+    }
+
+    #[test]
+    fn editor_toggle_checkbox_at_caret() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_task_list_buffer();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::End,
+                EditorEvent::ToggleCheckbox,
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::ToggleCheckbox,
+            ],
+        );
+        assert_eq2!(get_lines(&buffer)[1], "- [x] a");
+        assert_eq2!(get_lines(&buffer)[2], "  - [ ] b");
+        // The caret doesn't move.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 2)
+        );
+
+        // Lines that aren't tasks are left as is, eg: in a code block.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::ToggleCheckbox,
+            ],
+        );
+        assert_eq2!(get_lines(&buffer)[4], "- [ ] c");
+    }
+
+    #[test]
+    fn editor_toggle_checkbox_w_keyboard_and_undo() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_task_list_buffer();
+        buffer.set_caret_scroll_adjusted(position!(col_index: 0, row_index: 1));
+
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            make_ctrl_key('x', KeyState::Pressed),
+        )
+        .unwrap();
+        assert_eq2!(get_lines(&buffer)[1], "- [x] a");
+
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            make_ctrl_key('z', KeyState::NotPressed),
+        )
+        .unwrap();
+        assert_eq2!(get_lines(&buffer)[1], "- [ ] a");
+    }
+
+    #[test]
+    fn editor_toggle_checkbox_w_mouse_click() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_task_list_buffer();
+
+        // Clicking on the checkbox toggles it, w/out moving the caret.
+        let result = EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            make_mouse_click(4, 2),
+        )
+        .unwrap();
+        assert!(matches!(result, EditorEngineApplyEventResult::Applied));
+        assert_eq2!(get_lines(&buffer)[2], "  - [ ] b");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Clicking on the text of the task places the caret.
+        EditorEngineApi::apply_event(&mut buffer, &mut engine, make_mouse_click(6, 1))
+            .unwrap();
+        assert_eq2!(get_lines(&buffer)[1], "- [ ] a");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 6, row_index: 1)
+        );
+
+        // The checkbox can't be toggled in read only mode.
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                edit_mode: EditMode::ReadOnly,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        EditorEngineApi::apply_event(&mut buffer, &mut engine, make_mouse_click(2, 1))
+            .unwrap();
+        assert_eq2!(get_lines(&buffer)[1], "- [ ] a");
+        assert!(!task_list_support::is_checkbox_at(
+            &buffer,
+            position!(col_index: 1, row_index: 1)
+        ));
+        assert!(task_list_support::is_checkbox_at(
+            &buffer,
+            position!(col_index: 2, row_index: 1)
+        ));
+    }
+}
+
 pub mod mock_real_objects_for_editor {
     use std::fmt::Debug;
 
@@ -1586,10 +3776,28 @@ pub mod mock_real_objects_for_editor {
         let current_box: PartialFlexBox = (&flex_box).into();
         EditorEngine {
             current_box,
+            clipboard: SharedClipboard::new(InMemoryClipboard::default()),
             ..Default::default()
         }
     }
 
+    /// Returns a Markdown [EditorBuffer] w/ the `lines`.
+    pub fn make_buffer(lines: &[impl AsRef<str>]) -> EditorBuffer {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(lines.iter().map(|it| it.as_ref().to_string()).collect());
+        buffer
+    }
+
+    /// Returns the text of each line of the `buffer`.
+    pub fn get_lines(buffer: &EditorBuffer) -> Vec<String> {
+        buffer
+            .get_lines()
+            .iter()
+            .map(|it| it.string.clone())
+            .collect()
+    }
+
     pub fn make_editor_engine() -> EditorEngine {
         let flex_box = FlexBox {
             style_adjusted_bounds_size: size!( col_count: 10, row_count: 10 ),
@@ -1599,6 +3807,7 @@ pub mod mock_real_objects_for_editor {
         let current_box: PartialFlexBox = (&flex_box).into();
         EditorEngine {
             current_box,
+            clipboard: SharedClipboard::new(InMemoryClipboard::default()),
             ..Default::default()
         }
     }
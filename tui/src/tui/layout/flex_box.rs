@@ -87,6 +87,11 @@ pub struct FlexBox {
     pub requested_size_percent: RequestedSizePercent,
     pub insertion_pos_for_next_box: Option<Position>,
     pub maybe_computed_style: Option<Style>,
+    /// Space between the children along `dir` (includes the space added by
+    /// [JustifyContent::SpaceBetween]).
+    pub gap: ChUnit,
+    /// How the children are placed across `dir`.
+    pub align: AlignItems,
 }
 
 mod flex_box_impl {
@@ -117,6 +122,8 @@ mod flex_box_impl {
                     "maybe_computed_style",
                     format_option!(&self.maybe_computed_style),
                 )
+                .field("gap", &self.gap)
+                .field("align", &self.align)
                 .finish()
         }
     }
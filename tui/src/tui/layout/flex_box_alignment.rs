@@ -0,0 +1,113 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+/// Space around a [FlexBox](crate::FlexBox), taken out of the space that is allocated to it
+/// by its container (so that the box's siblings aren't moved). Unlike
+/// [Style::padding], each side can be different.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct Margin {
+    pub top: ChUnit,
+    pub right: ChUnit,
+    pub bottom: ChUnit,
+    pub left: ChUnit,
+}
+
+mod margin_impl {
+    use super::*;
+
+    impl Margin {
+        /// The same margin on all four sides.
+        pub fn all(value: ChUnit) -> Self {
+            Self {
+                top: value,
+                right: value,
+                bottom: value,
+                left: value,
+            }
+        }
+
+        /// Shrink the given `origin_pos` & `bounds_size` by this margin.
+        pub fn apply(&self, origin_pos: Position, bounds_size: Size) -> (Position, Size) {
+            let mut origin_pos = origin_pos;
+            origin_pos.col_index += self.left;
+            origin_pos.row_index += self.top;
+
+            let mut bounds_size = bounds_size;
+            bounds_size.col_count -= self.left + self.right;
+            bounds_size.row_count -= self.top + self.bottom;
+
+            (origin_pos, bounds_size)
+        }
+    }
+}
+
+/// How the children of a [FlexBox](crate::FlexBox) are placed across its
+/// [LayoutDirection](crate::LayoutDirection), similar to CSS `align-items`.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum AlignItems {
+    /// Children keep their requested size, and are placed at the top (or left).
+    #[default]
+    Start,
+    Center,
+    End,
+    /// Children fill the container, ignoring their requested size.
+    Stretch,
+}
+
+mod align_items_impl {
+    use super::*;
+
+    impl AlignItems {
+        /// Return the offset & size of a child w/ the `requested_size` inside the
+        /// container's `available` space.
+        pub fn align(
+            &self,
+            available: ChUnit,
+            requested_size: ChUnit,
+        ) -> (ChUnit, ChUnit) {
+            let free_space = available - requested_size;
+            match self {
+                AlignItems::Start => (ch!(0), requested_size),
+                AlignItems::Center => (free_space / 2, requested_size),
+                AlignItems::End => (free_space, requested_size),
+                AlignItems::Stretch => (ch!(0), available),
+            }
+        }
+    }
+}
+
+/// How the children of a [FlexBox](crate::FlexBox) are placed along its
+/// [LayoutDirection](crate::LayoutDirection), similar to CSS `justify-content`.
+///
+/// Anything other than [JustifyContent::Start] needs the sizes of all the children up
+/// front, which means that they have to be sized using
+/// [FlexBoxProps::maybe_child_size_constraints](crate::FlexBoxProps::maybe_child_size_constraints).
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum JustifyContent {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// The first child is placed at the start, the last one at the end, and the free
+    /// space is added to the gap between them.
+    SpaceBetween,
+}
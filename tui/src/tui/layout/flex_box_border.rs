@@ -0,0 +1,302 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// The box drawing characters that are used to paint a [Border].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum BorderStyle {
+    /// `┌─┐`
+    #[default]
+    Single,
+    /// `╔═╗`
+    Double,
+    /// `╭─╮`
+    Rounded,
+    /// `┏━┓`
+    Thick,
+    /// `+-+`, for terminals (or fonts) that can't display box drawing characters.
+    Ascii,
+}
+
+/// The characters that are used to paint the corners & sides of a [Border].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BorderGlyphs {
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    /// Where a horizontal line inside the border meets its left edge, eg: `├`.
+    pub left_junction: &'static str,
+    /// Where a horizontal line inside the border meets its right edge, eg: `┤`.
+    pub right_junction: &'static str,
+}
+
+mod border_style_impl {
+    use super::*;
+
+    impl BorderStyle {
+        pub fn get_glyphs(&self) -> BorderGlyphs {
+            let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] =
+                match self {
+                    BorderStyle::Single => ["┌", "┐", "└", "┘", "─", "│"],
+                    BorderStyle::Double => ["╔", "╗", "╚", "╝", "═", "║"],
+                    BorderStyle::Rounded => ["╭", "╮", "╰", "╯", "─", "│"],
+                    BorderStyle::Thick => ["┏", "┓", "┗", "┛", "━", "┃"],
+                    BorderStyle::Ascii => ["+", "+", "+", "+", "-", "|"],
+                };
+            let [left_junction, right_junction] = match self {
+                BorderStyle::Single | BorderStyle::Rounded => ["├", "┤"],
+                BorderStyle::Double => ["╠", "╣"],
+                BorderStyle::Thick => ["┣", "┫"],
+                BorderStyle::Ascii => ["+", "+"],
+            };
+            BorderGlyphs {
+                top_left,
+                top_right,
+                bottom_left,
+                bottom_right,
+                horizontal,
+                vertical,
+                left_junction,
+                right_junction,
+            }
+        }
+    }
+}
+
+/// A border around a [FlexBox], w/ an optional title (on its top edge) & footer (on its
+/// bottom edge). Set it using [FlexBoxProps::maybe_border].
+///
+/// The border is painted by the [Surface] when the box is added, and the box's content
+/// area ([FlexBox::style_adjusted_origin_pos] & [FlexBox::style_adjusted_bounds_size]) is
+/// shrunk by 1 cell on each side to make room for it (before the style's padding is
+/// applied). Just like padding, it doesn't affect where the box's children are placed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Border {
+    pub border_style: BorderStyle,
+    pub maybe_title: Option<String>,
+    pub maybe_footer: Option<String>,
+    /// Colors & attributes of the border, title & footer.
+    pub maybe_style: Option<Style>,
+    /// Used instead of `maybe_style` when the box has keyboard focus (based on
+    /// [Surface::maybe_focused_id]).
+    pub maybe_focused_style: Option<Style>,
+}
+
+mod border_impl {
+    use super::*;
+
+    impl Border {
+        /// Shrink the given `origin_pos` & `bounds_size` to the area inside the border.
+        pub fn apply(&self, origin_pos: Position, bounds_size: Size) -> (Position, Size) {
+            let mut origin_pos = origin_pos;
+            origin_pos += ch!(1);
+
+            let mut bounds_size = bounds_size;
+            bounds_size -= ch!(2);
+
+            (origin_pos, bounds_size)
+        }
+
+        /// Paint the border around the box at `origin_pos` w/ `bounds_size`. Only the
+        /// edges are painted, so the box's content is left alone. Nothing is painted if the
+        /// box is too small to have a border.
+        pub fn render(
+            &self,
+            origin_pos: Position,
+            bounds_size: Size,
+            has_focus: bool,
+        ) -> RenderOps {
+            let mut ops = render_ops!();
+            if bounds_size.col_count < ch!(2) || bounds_size.row_count < ch!(2) {
+                return ops;
+            }
+
+            let maybe_style = match (has_focus, self.maybe_focused_style) {
+                (true, Some(focused_style)) => Some(focused_style),
+                _ => self.maybe_style,
+            };
+            let glyphs = self.border_style.get_glyphs();
+            let inner_col_count = bounds_size.col_count - 2;
+            let last_col_index = origin_pos.col_index + bounds_size.col_count - 1;
+            let last_row_index = origin_pos.row_index + bounds_size.row_count - 1;
+
+            let mut paint = |col_index: ChUnit, row_index: ChUnit, text: String| {
+                ops.push(RenderOp::ResetColor);
+                ops.push(RenderOp::MoveCursorPositionAbs(position!(
+                    col_index: col_index,
+                    row_index: row_index
+                )));
+                ops.push(RenderOp::ApplyColors(maybe_style));
+                ops.push(RenderOp::PaintTextWithAttributes(text, maybe_style));
+            };
+
+            // Top edge, w/ the title.
+            paint(
+                origin_pos.col_index,
+                origin_pos.row_index,
+                format!(
+                    "{}{}{}",
+                    glyphs.top_left,
+                    make_edge(&self.maybe_title, inner_col_count, glyphs.horizontal),
+                    glyphs.top_right
+                ),
+            );
+
+            // Left & right edges.
+            for row_index in *origin_pos.row_index + 1..*last_row_index {
+                paint(
+                    origin_pos.col_index,
+                    ch!(row_index),
+                    glyphs.vertical.to_string(),
+                );
+                paint(last_col_index, ch!(row_index), glyphs.vertical.to_string());
+            }
+
+            // Bottom edge, w/ the footer.
+            paint(
+                origin_pos.col_index,
+                last_row_index,
+                format!(
+                    "{}{}{}",
+                    glyphs.bottom_left,
+                    make_edge(&self.maybe_footer, inner_col_count, glyphs.horizontal),
+                    glyphs.bottom_right
+                ),
+            );
+
+            ops
+        }
+    }
+
+    /// Return a horizontal edge that is `col_count` wide, w/ the (truncated) `maybe_text`
+    /// near its start, eg: `─ Title ─────`.
+    pub(super) fn make_edge(
+        maybe_text: &Option<String>,
+        col_count: ChUnit,
+        horizontal: &str,
+    ) -> String {
+        let Some(text) = maybe_text else {
+            return horizontal.repeat(ch!(@to_usize col_count));
+        };
+        if col_count < ch!(2) {
+            return horizontal.repeat(ch!(@to_usize col_count));
+        }
+
+        let label = UnicodeString::from(format!(" {text} "));
+        let label = label.truncate_end_to_fit_width(col_count - 1);
+        let label_width = UnicodeString::from(label).display_width;
+
+        format!(
+            "{horizontal}{label}{}",
+            horizontal.repeat(ch!(@to_usize col_count - 1 - label_width))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_edge() {
+        let title = Some("Title".to_string());
+        assert_eq!(border_impl::make_edge(&None, ch!(5), "─"), "─────");
+        assert_eq!(border_impl::make_edge(&title, ch!(10), "─"), "─ Title ──");
+        assert_eq!(border_impl::make_edge(&title, ch!(5), "-"), "- Tit");
+        assert_eq!(border_impl::make_edge(&title, ch!(1), "═"), "═");
+    }
+
+    #[test]
+    fn test_render_paints_only_the_edges() {
+        let border = Border {
+            border_style: BorderStyle::Ascii,
+            maybe_title: Some("Hi".to_string()),
+            ..Default::default()
+        };
+        let ops = border.render(
+            position!(col_index: 1, row_index: 1),
+            size!(col_count: 8, row_count: 3),
+            false,
+        );
+
+        let painted: Vec<(Position, String)> = ops
+            .iter()
+            .zip(ops.iter().skip(2))
+            .filter_map(|pair| match pair {
+                (
+                    RenderOp::MoveCursorPositionAbs(pos),
+                    RenderOp::PaintTextWithAttributes(text, _),
+                ) => Some((*pos, text.clone())),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            painted,
+            vec![
+                (
+                    position!(col_index: 1, row_index: 1),
+                    "+- Hi -+".to_string()
+                ),
+                (position!(col_index: 1, row_index: 2), "|".to_string()),
+                (position!(col_index: 8, row_index: 2), "|".to_string()),
+                (
+                    position!(col_index: 1, row_index: 3),
+                    "+------+".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_uses_focused_style() {
+        let style = Style {
+            dim: true,
+            ..Default::default()
+        };
+        let focused_style = Style {
+            bold: true,
+            ..Default::default()
+        };
+        let border = Border {
+            maybe_style: Some(style),
+            maybe_focused_style: Some(focused_style),
+            ..Default::default()
+        };
+        let size = size!(col_count: 4, row_count: 2);
+        let origin = position!(col_index: 0, row_index: 0);
+
+        let ops = border.render(origin, size, false);
+        assert!(ops.contains(&RenderOp::ApplyColors(Some(style))));
+
+        let ops = border.render(origin, size, true);
+        assert!(ops.contains(&RenderOp::ApplyColors(Some(focused_style))));
+        assert!(!ops.contains(&RenderOp::ApplyColors(Some(style))));
+
+        // Too small for a border.
+        let ops = border.render(origin, size!(col_count: 1, row_count: 5), false);
+        assert!(ops.is_empty());
+    }
+}
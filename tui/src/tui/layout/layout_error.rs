@@ -44,6 +44,8 @@ pub enum LayoutErrorType {
     ContainerBoxBoundsUndefined,
     BoxCursorPositionUndefined,
     ContentCursorPositionUndefined,
+    UnsatisfiableSizeConstraints,
+    MissingSizeConstraint,
 }
 
 /// Implement [`Error`] trait.
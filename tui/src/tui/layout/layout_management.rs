@@ -61,6 +61,21 @@ pub struct FlexBoxProps {
     pub dir: LayoutDirection,
     pub requested_size_percent: RequestedSizePercent,
     pub maybe_styles: Option<Vec<Style>>,
+    /// Sizes of this box's children along `dir`, one per child in the order they are
+    /// added. When this is `None` each child uses its own `requested_size_percent`. More
+    /// info in [SizeConstraint]. A box that isn't the root box can only have children if
+    /// this is set.
+    pub maybe_child_size_constraints: Option<Vec<SizeConstraint>>,
+    /// Space between this box's children along `dir`.
+    pub gap: ChUnit,
+    /// Space around this box, inside the space allocated to it by its container.
+    pub margin: Margin,
+    /// How this box's children are placed across `dir`.
+    pub align: AlignItems,
+    /// How this box's children are placed along `dir`.
+    pub justify: JustifyContent,
+    /// Border (w/ an optional title & footer) that is painted around this box.
+    pub maybe_border: Option<Border>,
 }
 
 /// Properties that are needed to create a [Surface].
@@ -17,16 +17,25 @@
 
 // Attach source files.
 pub mod flex_box;
+pub mod flex_box_alignment;
+pub mod flex_box_border;
 pub mod layout_error;
 pub mod layout_management;
+pub mod size_constraint;
 pub mod surface;
 
 // Re-export the public items.
 pub use flex_box::*;
+pub use flex_box_alignment::*;
+pub use flex_box_border::*;
 pub use layout_error::*;
 pub use layout_management::*;
+pub use size_constraint::*;
 pub use surface::*;
 
 // Tests.
 mod test_surface_2_col_complex;
 mod test_surface_2_col_simple;
+mod test_surface_alignment;
+mod test_surface_border;
+mod test_surface_size_constraints;
@@ -0,0 +1,306 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// How much space a child [FlexBox] takes up along its container's [LayoutDirection].
+///
+/// A container lists one of these per child in
+/// [FlexBoxProps::maybe_child_size_constraints], in the order that the children are
+/// added. They are all solved together (by [solve_size_constraints]) when the container
+/// is added to the [Surface], so that the container's space is distributed exactly, w/
+/// no stray columns or rows left over due to rounding.
+///
+/// Along the other axis the child is still sized using its own
+/// [FlexBoxProps::requested_size_percent].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum SizeConstraint {
+    /// Exactly this many cells, eg: a 1 row status bar, or a 30 column sidebar.
+    Fixed(ChUnit),
+    /// A percentage of the container's size.
+    Percent(Percent),
+    /// At least this many cells, growing into the remaining space like `Fraction(1)`.
+    Min(ChUnit),
+    /// At most this many cells, growing into the remaining space like `Fraction(1)`.
+    Max(ChUnit),
+    /// A share of the remaining space, weighted against the other growable constraints
+    /// (similar to CSS `flex-grow`). `Fraction(0)` never grows.
+    Fraction(u16),
+}
+
+mod size_constraint_impl {
+    use super::*;
+
+    impl SizeConstraint {
+        /// The space that has to be allocated before anything else is distributed.
+        pub(crate) fn base_size(&self) -> usize {
+            match self {
+                SizeConstraint::Fixed(size) | SizeConstraint::Min(size) => {
+                    ch!(@to_usize *size)
+                }
+                SizeConstraint::Percent(_)
+                | SizeConstraint::Max(_)
+                | SizeConstraint::Fraction(_) => 0,
+            }
+        }
+
+        /// The weight w/ which this constraint grows into the remaining space.
+        pub(crate) fn grow_weight(&self) -> usize {
+            match self {
+                SizeConstraint::Min(_) | SizeConstraint::Max(_) => 1,
+                SizeConstraint::Fraction(weight) => usize::from(*weight),
+                SizeConstraint::Fixed(_) | SizeConstraint::Percent(_) => 0,
+            }
+        }
+    }
+}
+
+/// Solve the given `constraints` against the `available` space, returning one size per
+/// constraint.
+///
+/// 1. [SizeConstraint::Fixed] & [SizeConstraint::Min] get their size, and all the
+///    [SizeConstraint::Percent]s share their part of `available` (using the largest
+///    remainder method so that `50%` + `50%` of `101` is `51` + `50`).
+/// 2. What's left over is split between the growable constraints by weight, and any
+///    [SizeConstraint::Max] that hits its limit gives its excess back to the others.
+///
+/// If there are no growable constraints then some space may remain unallocated. A
+/// [LayoutErrorType::UnsatisfiableSizeConstraints] error is returned if the
+/// percentages add up to more than 100, or if the sizes that have to be allocated don't
+/// fit in `available`.
+pub fn solve_size_constraints(
+    available: ChUnit,
+    constraints: &[SizeConstraint],
+) -> CommonResult<Vec<ChUnit>> {
+    let available = ch!(@to_usize available);
+
+    // Percentages.
+    let percent_weights: Vec<usize> = constraints
+        .iter()
+        .map(|constraint| match constraint {
+            SizeConstraint::Percent(percent) => usize::from(percent.value),
+            _ => 0,
+        })
+        .collect();
+    let total_percent: usize = percent_weights.iter().sum();
+    if total_percent > 100 {
+        LayoutError::new_err_with_msg(
+            LayoutErrorType::UnsatisfiableSizeConstraints,
+            format!("Percentages add up to {total_percent}%, which is more than 100%"),
+        )?
+    }
+    let percent_sizes = distribute_by_largest_remainder(
+        available * total_percent / 100,
+        &percent_weights,
+    );
+
+    // Sizes that have to be allocated.
+    let mut sizes: Vec<usize> = constraints
+        .iter()
+        .zip(percent_sizes)
+        .map(|(constraint, percent_size)| constraint.base_size() + percent_size)
+        .collect();
+    let required: usize = sizes.iter().sum();
+    if required > available {
+        LayoutError::new_err_with_msg(
+            LayoutErrorType::UnsatisfiableSizeConstraints,
+            format!("{required} cells are required, but only {available} are available"),
+        )?
+    }
+
+    // Distribute the remaining space.
+    let mut remaining = available - required;
+    let mut growable: Vec<usize> = (0..constraints.len())
+        .filter(|&index| constraints[index].grow_weight() > 0)
+        .collect();
+
+    while remaining > 0 && !growable.is_empty() {
+        let weights: Vec<usize> = growable
+            .iter()
+            .map(|&index| constraints[index].grow_weight())
+            .collect();
+        let shares = distribute_by_largest_remainder(remaining, &weights);
+
+        let capped: Vec<(usize, usize)> = growable
+            .iter()
+            .zip(&shares)
+            .filter_map(|(&index, &share)| match constraints[index] {
+                SizeConstraint::Max(max) => {
+                    let max = ch!(@to_usize max);
+                    (sizes[index] + share >= max).then_some((index, max))
+                }
+                _ => None,
+            })
+            .collect();
+
+        if capped.is_empty() {
+            for (&index, share) in growable.iter().zip(shares) {
+                sizes[index] += share;
+            }
+            remaining = 0;
+        } else {
+            // Cap these, and then distribute what's left between the others.
+            for &(index, max) in &capped {
+                remaining -= max - sizes[index];
+                sizes[index] = max;
+            }
+            growable.retain(|index| !capped.iter().any(|(it, _)| it == index));
+        }
+    }
+
+    Ok(sizes.into_iter().map(|size| ch!(size)).collect())
+}
+
+/// Split `total` proportionally to `weights`, handing out the cells lost to rounding one
+/// by one to the largest remainders (ties go to the earlier index).
+fn distribute_by_largest_remainder(total: usize, weights: &[usize]) -> Vec<usize> {
+    let weight_sum: usize = weights.iter().sum();
+    if weight_sum == 0 {
+        return vec![0; weights.len()];
+    }
+
+    let mut shares: Vec<usize> = weights
+        .iter()
+        .map(|weight| total * weight / weight_sum)
+        .collect();
+
+    let mut leftover = total - shares.iter().sum::<usize>();
+    let mut indices_by_remainder: Vec<usize> = (0..weights.len()).collect();
+    indices_by_remainder
+        .sort_by_key(|&index| std::cmp::Reverse(total * weights[index] % weight_sum));
+    for index in indices_by_remainder {
+        if leftover == 0 {
+            break;
+        }
+        shares[index] += 1;
+        leftover -= 1;
+    }
+
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(available: u16, constraints: &[SizeConstraint]) -> Vec<u16> {
+        solve_size_constraints(ch!(available), constraints)
+            .unwrap()
+            .into_iter()
+            .map(|size| size.value)
+            .collect()
+    }
+
+    #[test]
+    fn test_fixed_and_fraction() {
+        // 30 column sidebar, and the rest for the content.
+        let sizes = solve(
+            100,
+            &[SizeConstraint::Fixed(ch!(30)), SizeConstraint::Fraction(1)],
+        );
+        assert_eq!(sizes, vec![30, 70]);
+
+        // Content area & 1 row status bar.
+        let sizes = solve(
+            24,
+            &[SizeConstraint::Fraction(1), SizeConstraint::Fixed(ch!(1))],
+        );
+        assert_eq!(sizes, vec![23, 1]);
+    }
+
+    #[test]
+    fn test_percent_has_no_stray_cells() {
+        let sizes = solve(
+            101,
+            &[
+                SizeConstraint::Percent(percent!(50).unwrap()),
+                SizeConstraint::Percent(percent!(50).unwrap()),
+            ],
+        );
+        assert_eq!(sizes, vec![51, 50]);
+
+        let sizes = solve(
+            100,
+            &[
+                SizeConstraint::Percent(percent!(33).unwrap()),
+                SizeConstraint::Percent(percent!(33).unwrap()),
+                SizeConstraint::Percent(percent!(34).unwrap()),
+            ],
+        );
+        assert_eq!(sizes, vec![33, 33, 34]);
+    }
+
+    #[test]
+    fn test_fractions_are_distributed_exactly() {
+        let sizes = solve(
+            10,
+            &[
+                SizeConstraint::Fraction(1),
+                SizeConstraint::Fraction(1),
+                SizeConstraint::Fraction(1),
+            ],
+        );
+        assert_eq!(sizes, vec![4, 3, 3]);
+
+        let sizes = solve(
+            12,
+            &[SizeConstraint::Fraction(1), SizeConstraint::Fraction(2)],
+        );
+        assert_eq!(sizes, vec![4, 8]);
+    }
+
+    #[test]
+    fn test_min_and_max() {
+        let sizes = solve(
+            100,
+            &[SizeConstraint::Max(ch!(20)), SizeConstraint::Fraction(1)],
+        );
+        assert_eq!(sizes, vec![20, 80]);
+
+        let sizes = solve(
+            100,
+            &[SizeConstraint::Min(ch!(60)), SizeConstraint::Fraction(1)],
+        );
+        assert_eq!(sizes, vec![80, 20]);
+
+        // Nothing else to grow, so the remaining space is left unallocated.
+        let sizes = solve(100, &[SizeConstraint::Max(ch!(20))]);
+        assert_eq!(sizes, vec![20]);
+    }
+
+    #[test]
+    fn test_unsatisfiable() {
+        let result = solve_size_constraints(
+            ch!(20),
+            &[SizeConstraint::Fixed(ch!(15)), SizeConstraint::Min(ch!(10))],
+        );
+        assert!(result.is_err());
+
+        let result = solve_size_constraints(
+            ch!(20),
+            &[
+                SizeConstraint::Percent(percent!(60).unwrap()),
+                SizeConstraint::Percent(percent!(60).unwrap()),
+            ],
+        );
+        assert!(result.is_err());
+    }
+}
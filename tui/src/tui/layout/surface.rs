@@ -15,6 +15,8 @@
  *   limitations under the License.
  */
 
+use std::collections::VecDeque;
+
 use r3bl_rs_utils_core::*;
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +29,13 @@ pub struct Surface {
     pub origin_pos: Position,
     pub box_size: Size,
     pub stack_of_boxes: Vec<FlexBox>,
+    /// Sizes solved from [FlexBoxProps::maybe_child_size_constraints] that have not been
+    /// used up by children yet, one entry per box in `stack_of_boxes`.
+    pub stack_of_child_sizes: Vec<Option<VecDeque<ChUnit>>>,
+    /// The id of the box that has keyboard focus, which is set from [HasFocus::get_id] when
+    /// the `has_focus` arg is passed to [surface!]. Borders ([FlexBoxProps::maybe_border])
+    /// of this box are painted w/ their focused style.
+    pub maybe_focused_id: Option<FlexBoxId>,
     pub stylesheet: Stylesheet,
     pub render_pipeline: RenderPipeline,
 }
@@ -61,6 +70,17 @@ macro_rules! surface {
         }
     };
 
+    (
+        stylesheet: $arg_stylesheet : expr,
+        has_focus:  $arg_has_focus  : expr
+    ) => {
+        Surface {
+            stylesheet: $arg_stylesheet,
+            maybe_focused_id: $arg_has_focus.get_id(),
+            ..Default::default()
+        }
+    };
+
     (
         origin_pos: $arg_origin_pos : expr,
         box_size:   $arg_box_size   : expr,
@@ -133,6 +153,7 @@ impl LayoutManagement for Surface {
                 )?
             }
             self.stack_of_boxes.pop();
+            self.stack_of_child_sizes.pop();
         });
     }
 }
@@ -173,13 +194,16 @@ impl PerformPositioningAndSizing for Surface {
           LayoutErrorType::ErrorCalculatingNextBoxPos
         };
 
-        let new_pos: Position = current_insertion_pos + allocated_size;
-
-        // Adjust `new_pos` using Direction.
-        let new_pos: Position = match current_box.dir {
-            LayoutDirection::Vertical => new_pos * (0, 1),
-            LayoutDirection::Horizontal => new_pos * (1, 0),
-        };
+        // Move `new_pos` along the Direction, past the allocated size & the gap.
+        let mut new_pos: Position = current_insertion_pos;
+        match current_box.dir {
+            LayoutDirection::Vertical => {
+                new_pos.row_index += allocated_size.row_count + current_box.gap
+            }
+            LayoutDirection::Horizontal => {
+                new_pos.col_index += allocated_size.col_count + current_box.gap
+            }
+        }
 
         // Update the box_cursor_pos of the current layout.
         current_box.insertion_pos_for_next_box = new_pos.into();
@@ -194,6 +218,9 @@ impl PerformPositioningAndSizing for Surface {
             let container_box = self.current_box()?;
             let container_bounds = container_box.bounds_size;
 
+            let container_dir = container_box.dir;
+            let container_align = container_box.align;
+
             let maybe_cascaded_style: Option<Style> =
                 cascade_styles(container_box, &flex_box_props);
 
@@ -202,24 +229,67 @@ impl PerformPositioningAndSizing for Surface {
                 height_pc,
             } = flex_box_props.requested_size_percent;
 
-            let requested_size_allocation = size!(
+            let mut requested_size_allocation = size!(
               col_count: width_pc.calc_percentage(container_bounds.col_count),
               row_count: height_pc.calc_percentage(container_bounds.row_count)
             );
 
-            let origin_pos = unwrap_or_err! {
+            let insertion_pos = unwrap_or_err! {
               container_box.insertion_pos_for_next_box,
               LayoutErrorType::BoxCursorPositionUndefined
             };
 
+            // The container's size constraints (if any) override the percentage along
+            // the container's direction.
+            if let Some(solved_size) = self.take_next_child_size()? {
+                match container_dir {
+                    LayoutDirection::Horizontal => {
+                        requested_size_allocation.col_count = solved_size
+                    }
+                    LayoutDirection::Vertical => {
+                        requested_size_allocation.row_count = solved_size
+                    }
+                }
+            }
+
             self.update_insertion_pos_for_next_box(requested_size_allocation)?;
 
+            // Place the box across the container's direction, and then inside its margin.
+            let (origin_pos, bounds_size) = align_across_container(
+                container_dir,
+                container_align,
+                container_bounds,
+                insertion_pos,
+                requested_size_allocation,
+            );
+            let (origin_pos, bounds_size) =
+                flex_box_props.margin.apply(origin_pos, bounds_size);
+
+            let maybe_child_sizes = solve_child_sizes(&flex_box_props, bounds_size)?;
+
+            self.paint_border(&flex_box_props, origin_pos, bounds_size);
+
+            let (justify_offset, gap) = justify_children(
+                &flex_box_props,
+                bounds_size,
+                maybe_child_sizes.as_ref(),
+            )?;
+
+            // Children can only be inserted into a non-root box that has size
+            // constraints for them. Otherwise it is a leaf box.
+            let maybe_insertion_pos = maybe_child_sizes
+                .as_ref()
+                .map(|_| move_along_dir(flex_box_props.dir, origin_pos, justify_offset));
+
             self.stack_of_boxes.push(make_non_root_box_with_style(
                 flex_box_props,
                 origin_pos,
-                container_bounds,
+                bounds_size,
+                maybe_insertion_pos,
+                gap,
                 maybe_cascaded_style,
             ));
+            self.stack_of_child_sizes.push(maybe_child_sizes);
         });
     }
 
@@ -236,44 +306,198 @@ impl PerformPositioningAndSizing for Surface {
               row_count: height_pc.calc_percentage(self.box_size.row_count)
             );
 
+            let (origin_pos, bounds_size) =
+                flex_box_props.margin.apply(self.origin_pos, bounds_size);
+
+            let maybe_child_sizes = solve_child_sizes(&flex_box_props, bounds_size)?;
+
+            self.paint_border(&flex_box_props, origin_pos, bounds_size);
+
+            let (justify_offset, gap) = justify_children(
+                &flex_box_props,
+                bounds_size,
+                maybe_child_sizes.as_ref(),
+            )?;
+            let insertion_pos =
+                move_along_dir(flex_box_props.dir, origin_pos, justify_offset);
+
             self.stack_of_boxes.push(make_root_box_with_style(
                 flex_box_props,
-                self.origin_pos,
+                origin_pos,
                 bounds_size,
+                insertion_pos,
+                gap,
             ));
+            self.stack_of_child_sizes.push(maybe_child_sizes);
         });
     }
 }
 
+impl Surface {
+    /// Paint the [FlexBoxProps::maybe_border] (if any) of the box that is being added.
+    fn paint_border(
+        &mut self,
+        flex_box_props: &FlexBoxProps,
+        origin_pos: Position,
+        bounds_size: Size,
+    ) {
+        if let Some(ref border) = flex_box_props.maybe_border {
+            let has_focus = self.maybe_focused_id == Some(flex_box_props.id);
+            let render_ops = border.render(origin_pos, bounds_size, has_focus);
+            self.render_pipeline.push(ZOrder::Normal, render_ops);
+        }
+    }
+
+    /// If the current box has size constraints for its children, then return the size
+    /// solved for the next child. Returns a [LayoutErrorType::MissingSizeConstraint] error
+    /// if there are more children than constraints.
+    fn take_next_child_size(&mut self) -> CommonResult<Option<ChUnit>> {
+        let Some(Some(child_sizes)) = self.stack_of_child_sizes.last_mut() else {
+            return Ok(None);
+        };
+        match child_sizes.pop_front() {
+            Some(size) => Ok(Some(size)),
+            None => LayoutError::new_err_with_msg(
+                LayoutErrorType::MissingSizeConstraint,
+                LayoutError::format_msg_with_stack_len(
+                    &self.stack_of_boxes,
+                    "More children were added than there are size constraints",
+                ),
+            ),
+        }
+    }
+}
+
+/// Solve the [FlexBoxProps::maybe_child_size_constraints] (if any) along `dir`, against the
+/// given `bounds_size` of the box that is being added (minus the gaps between children).
+fn solve_child_sizes(
+    flex_box_props: &FlexBoxProps,
+    bounds_size: Size,
+) -> CommonResult<Option<VecDeque<ChUnit>>> {
+    let Some(ref constraints) = flex_box_props.maybe_child_size_constraints else {
+        return Ok(None);
+    };
+    let gap_count = constraints.len().saturating_sub(1);
+    let available = get_main_axis_size(flex_box_props.dir, bounds_size)
+        - flex_box_props.gap * ch!(gap_count);
+    let child_sizes = solve_size_constraints(available, constraints)?;
+    Ok(Some(VecDeque::from(child_sizes)))
+}
+
+/// Return where the first child goes (as an offset from the start of the box along `dir`),
+/// and the space between the children, using [FlexBoxProps::gap] &
+/// [FlexBoxProps::justify]. Justifying needs the solved `maybe_child_sizes`, otherwise a
+/// [LayoutErrorType::MissingSizeConstraint] error is returned.
+fn justify_children(
+    flex_box_props: &FlexBoxProps,
+    bounds_size: Size,
+    maybe_child_sizes: Option<&VecDeque<ChUnit>>,
+) -> CommonResult<(ChUnit, ChUnit)> {
+    let gap = flex_box_props.gap;
+    if flex_box_props.justify == JustifyContent::Start {
+        return Ok((ch!(0), gap));
+    }
+
+    let Some(child_sizes) = maybe_child_sizes else {
+        return LayoutError::new_err_with_msg(
+            LayoutErrorType::MissingSizeConstraint,
+            format!(
+                "{:?} needs maybe_child_size_constraints to be set",
+                flex_box_props.justify
+            ),
+        );
+    };
+
+    let child_count = child_sizes.len();
+    let gap_count = child_count.saturating_sub(1);
+    let used_space =
+        child_sizes.iter().fold(ch!(0), |acc, size| acc + *size) + gap * ch!(gap_count);
+    let free_space = get_main_axis_size(flex_box_props.dir, bounds_size) - used_space;
+
+    Ok(match flex_box_props.justify {
+        JustifyContent::Center => (free_space / 2, gap),
+        JustifyContent::End => (free_space, gap),
+        JustifyContent::SpaceBetween if gap_count > 0 => {
+            (ch!(0), gap + free_space / gap_count as ChUnitPrimitiveType)
+        }
+        _ => (ch!(0), gap),
+    })
+}
+
+/// Place a child w/ the `requested_size` at the `insertion_pos` of its container, across
+/// the container's `dir`, using the container's [AlignItems].
+fn align_across_container(
+    container_dir: LayoutDirection,
+    container_align: AlignItems,
+    container_bounds: Size,
+    insertion_pos: Position,
+    requested_size: Size,
+) -> (Position, Size) {
+    let mut origin_pos = insertion_pos;
+    let mut bounds_size = requested_size;
+    match container_dir {
+        LayoutDirection::Horizontal => {
+            let (offset, row_count) = container_align
+                .align(container_bounds.row_count, requested_size.row_count);
+            origin_pos.row_index += offset;
+            bounds_size.row_count = row_count;
+        }
+        LayoutDirection::Vertical => {
+            let (offset, col_count) = container_align
+                .align(container_bounds.col_count, requested_size.col_count);
+            origin_pos.col_index += offset;
+            bounds_size.col_count = col_count;
+        }
+    }
+    (origin_pos, bounds_size)
+}
+
+fn move_along_dir(dir: LayoutDirection, pos: Position, offset: ChUnit) -> Position {
+    let mut it = pos;
+    match dir {
+        LayoutDirection::Horizontal => it.col_index += offset,
+        LayoutDirection::Vertical => it.row_index += offset,
+    }
+    it
+}
+
+fn get_main_axis_size(dir: LayoutDirection, size: Size) -> ChUnit {
+    match dir {
+        LayoutDirection::Horizontal => size.col_count,
+        LayoutDirection::Vertical => size.row_count,
+    }
+}
+
 /// - If `is_root` is true:
 ///   - The `insertion_pos_for_next_box` is origin_pos + padding adjustment (from style)
 /// - If `is_root` is false:
 ///   - The `insertion_pos_for_next_box` is `None` non-root box; it needs to be calculated by
-///     `update_box_cursor_pos_for_next_box_insertion()`
+///     `update_box_cursor_pos_for_next_box_insertion()`. Unless the box has
+///     [FlexBoxProps::maybe_child_size_constraints], in which case it is where the first
+///     child goes (so that children can be nested in it).
 fn make_non_root_box_with_style(
     FlexBoxProps {
         id,
         dir,
-        requested_size_percent:
-            RequestedSizePercent {
-                width_pc,
-                height_pc,
-            },
-        maybe_styles: _,
+        requested_size_percent,
+        align,
+        maybe_border,
+        ..
     }: FlexBoxProps,
     origin_pos: Position,
-    container_bounds: Size,
+    bounds_size: Size,
+    maybe_insertion_pos_for_next_box: Option<Position>,
+    gap: ChUnit,
     maybe_cascaded_style: Option<Style>,
 ) -> FlexBox {
-    let bounds_size = size!(
-      col_count: width_pc.calc_percentage(container_bounds.col_count),
-      row_count: height_pc.calc_percentage(container_bounds.row_count)
+    // Adjust `bounds_size` & `origin` based on the border & the style's padding.
+    let (style_adjusted_origin_pos, style_adjusted_bounds_size) = adjust_with_style(
+        &maybe_cascaded_style,
+        maybe_border.as_ref(),
+        origin_pos,
+        bounds_size,
     );
 
-    // Adjust `bounds_size` & `origin` based on the style's padding.
-    let (style_adjusted_origin_pos, style_adjusted_bounds_size) =
-        adjust_with_style(&maybe_cascaded_style, origin_pos, bounds_size);
-
     FlexBox {
         id,
         dir,
@@ -281,12 +505,11 @@ fn make_non_root_box_with_style(
         bounds_size,
         style_adjusted_origin_pos,
         style_adjusted_bounds_size,
-        requested_size_percent: RequestedSizePercent {
-            width_pc,
-            height_pc,
-        },
+        requested_size_percent,
         maybe_computed_style: maybe_cascaded_style,
-        insertion_pos_for_next_box: None,
+        insertion_pos_for_next_box: maybe_insertion_pos_for_next_box,
+        gap,
+        align,
     }
 }
 
@@ -296,15 +519,24 @@ fn make_root_box_with_style(
         dir,
         requested_size_percent,
         maybe_styles,
+        align,
+        maybe_border,
+        ..
     }: FlexBoxProps,
     origin_pos: Position,
     bounds_size: Size,
+    insertion_pos_for_next_box: Position,
+    gap: ChUnit,
 ) -> FlexBox {
     let computed_style = Stylesheet::compute(&maybe_styles);
 
-    // Adjust `bounds_size` & `origin` based on the style's padding.
-    let (style_adjusted_origin_pos, style_adjusted_bounds_size) =
-        adjust_with_style(&computed_style, origin_pos, bounds_size);
+    // Adjust `bounds_size` & `origin` based on the border & the style's padding.
+    let (style_adjusted_origin_pos, style_adjusted_bounds_size) = adjust_with_style(
+        &computed_style,
+        maybe_border.as_ref(),
+        origin_pos,
+        bounds_size,
+    );
 
     FlexBox {
         id,
@@ -315,18 +547,25 @@ fn make_root_box_with_style(
         style_adjusted_bounds_size,
         requested_size_percent,
         maybe_computed_style: computed_style,
-        insertion_pos_for_next_box: Some(origin_pos),
+        insertion_pos_for_next_box: Some(insertion_pos_for_next_box),
+        gap,
+        align,
     }
 }
 
-/// Adjust `origin` & `bounds_size` based on the `maybe_style`'s padding.
+/// Adjust `origin` & `bounds_size` based on the `maybe_border` (if any) & then the
+/// `maybe_style`'s padding.
 fn adjust_with_style(
     maybe_computed_style: &Option<Style>,
+    maybe_border: Option<&Border>,
     origin_pos: Position,
     bounds_size: Size,
 ) -> (Position, Size) {
-    let mut style_adjusted_origin_pos = origin_pos;
-    let mut style_adjusted_bounds_size = bounds_size;
+    let (mut style_adjusted_origin_pos, mut style_adjusted_bounds_size) =
+        match maybe_border {
+            Some(border) => border.apply(origin_pos, bounds_size),
+            None => (origin_pos, bounds_size),
+        };
 
     if let Some(ref style) = maybe_computed_style {
         if let Some(padding) = style.padding {
@@ -0,0 +1,242 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    fn make_surface() -> CommonResult<Surface> {
+        let mut surface = Surface::default();
+        surface.surface_start(SurfaceProps {
+            pos: position!(col_index: 0, row_index: 0),
+            size: size!(col_count: 100, row_count: 10),
+        })?;
+        Ok(surface)
+    }
+
+    fn add_child(surface: &mut Surface, props: FlexBoxProps) -> CommonResult<FlexBox> {
+        surface.box_start(props)?;
+        let flex_box = *surface.stack_of_boxes.last().unwrap();
+        surface.box_end()?;
+        Ok(flex_box)
+    }
+
+    fn child_props(width_pc: u16, height_pc: u16) -> CommonResult<FlexBoxProps> {
+        Ok(FlexBoxProps {
+            requested_size_percent: requested_size_percent!(width: width_pc, height: height_pc),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_gap() -> CommonResult<()> {
+        throws!({
+            let mut surface = make_surface()?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width: 100, height: 100),
+              styles:                 [],
+              gap:                    ch!(2),
+              maybe_child_size_constraints: Some(vec![
+                SizeConstraint::Fraction(1),
+                SizeConstraint::Fraction(1),
+              ])
+            }
+
+            let left = add_child(&mut surface, child_props(100, 100)?)?;
+            assert_eq2!(left.origin_pos, position!(col_index: 0, row_index: 0));
+            assert_eq2!(left.bounds_size, size!(col_count: 49, row_count: 10));
+
+            let right = add_child(&mut surface, child_props(100, 100)?)?;
+            assert_eq2!(right.origin_pos, position!(col_index: 51, row_index: 0));
+            assert_eq2!(right.bounds_size, size!(col_count: 49, row_count: 10));
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_margin() -> CommonResult<()> {
+        throws!({
+            let mut surface = make_surface()?;
+
+            surface.box_start(FlexBoxProps {
+                dir: LayoutDirection::Horizontal,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                ..Default::default()
+            })?;
+
+            let left = add_child(
+                &mut surface,
+                FlexBoxProps {
+                    margin: Margin {
+                        top: ch!(1),
+                        right: ch!(2),
+                        bottom: ch!(1),
+                        left: ch!(2),
+                    },
+                    ..child_props(50, 100)?
+                },
+            )?;
+            assert_eq2!(left.origin_pos, position!(col_index: 2, row_index: 1));
+            assert_eq2!(left.bounds_size, size!(col_count: 46, row_count: 8));
+
+            // The margin doesn't move the next sibling.
+            let right = add_child(&mut surface, child_props(50, 100)?)?;
+            assert_eq2!(right.origin_pos, position!(col_index: 50, row_index: 0));
+
+            surface.box_end()?;
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_align() -> CommonResult<()> {
+        throws!({
+            let expected = [
+                (AlignItems::Start, 0, 5),
+                (AlignItems::Center, 2, 5),
+                (AlignItems::End, 5, 5),
+                (AlignItems::Stretch, 0, 10),
+            ];
+            for (align, row_index, row_count) in expected {
+                let mut surface = make_surface()?;
+
+                surface.box_start(FlexBoxProps {
+                    dir: LayoutDirection::Horizontal,
+                    requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                    align,
+                    ..Default::default()
+                })?;
+
+                let child = add_child(&mut surface, child_props(50, 50)?)?;
+                assert_eq2!(
+                    child.origin_pos,
+                    position!(col_index: 0, row_index: row_index)
+                );
+                assert_eq2!(
+                    child.bounds_size,
+                    size!(col_count: 50, row_count: row_count)
+                );
+
+                surface.box_end()?;
+                surface.surface_end()?;
+            }
+        });
+    }
+
+    #[test]
+    fn test_justify() -> CommonResult<()> {
+        throws!({
+            let expected = [
+                (JustifyContent::Start, 0, 20),
+                (JustifyContent::Center, 30, 50),
+                (JustifyContent::End, 60, 80),
+                (JustifyContent::SpaceBetween, 0, 80),
+            ];
+            for (justify, first_col_index, second_col_index) in expected {
+                let mut surface = make_surface()?;
+
+                surface.box_start(FlexBoxProps {
+                    dir: LayoutDirection::Horizontal,
+                    requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                    maybe_child_size_constraints: Some(vec![
+                        SizeConstraint::Fixed(ch!(20)),
+                        SizeConstraint::Fixed(ch!(20)),
+                    ]),
+                    justify,
+                    ..Default::default()
+                })?;
+
+                let first = add_child(&mut surface, child_props(100, 100)?)?;
+                assert_eq2!(*first.origin_pos.col_index, first_col_index);
+
+                let second = add_child(&mut surface, child_props(100, 100)?)?;
+                assert_eq2!(*second.origin_pos.col_index, second_col_index);
+
+                surface.box_end()?;
+                surface.surface_end()?;
+            }
+        });
+    }
+
+    #[test]
+    fn test_justify_nested_box() -> CommonResult<()> {
+        throws!({
+            let mut surface = make_surface()?;
+
+            surface.box_start(FlexBoxProps {
+                dir: LayoutDirection::Vertical,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                maybe_child_size_constraints: Some(vec![
+                    SizeConstraint::Fixed(ch!(2)),
+                    SizeConstraint::Fraction(1),
+                ]),
+                ..Default::default()
+            })?;
+
+            // A leaf box (w/out size constraints) can't have children.
+            let header = add_child(&mut surface, child_props(100, 100)?)?;
+            assert_eq2!(header.insertion_pos_for_next_box, None);
+
+            surface.box_start(FlexBoxProps {
+                dir: LayoutDirection::Horizontal,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                maybe_child_size_constraints: Some(vec![
+                    SizeConstraint::Fixed(ch!(20)),
+                    SizeConstraint::Fixed(ch!(20)),
+                ]),
+                gap: ch!(2),
+                justify: JustifyContent::Center,
+                ..Default::default()
+            })?;
+
+            let first = add_child(&mut surface, child_props(100, 100)?)?;
+            assert_eq2!(first.origin_pos, position!(col_index: 29, row_index: 2));
+            assert_eq2!(first.bounds_size, size!(col_count: 20, row_count: 8));
+
+            let second = add_child(&mut surface, child_props(100, 100)?)?;
+            assert_eq2!(second.origin_pos, position!(col_index: 51, row_index: 2));
+            assert_eq2!(second.bounds_size, size!(col_count: 20, row_count: 8));
+
+            surface.box_end()?;
+            surface.box_end()?;
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_justify_needs_size_constraints() -> CommonResult<()> {
+        throws!({
+            let mut surface = make_surface()?;
+
+            let result = surface.box_start(FlexBoxProps {
+                dir: LayoutDirection::Horizontal,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                justify: JustifyContent::Center,
+                ..Default::default()
+            });
+            assert!(result.is_err());
+        });
+    }
+}
@@ -0,0 +1,231 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    fn make_border() -> Border {
+        Border {
+            border_style: BorderStyle::Rounded,
+            maybe_title: Some("Editor".to_string()),
+            maybe_style: Some(Style {
+                dim: true,
+                ..Default::default()
+            }),
+            maybe_focused_style: Some(Style {
+                bold: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn get_painted_text(surface: &Surface) -> Vec<String> {
+        surface
+            .render_pipeline
+            .get_all_render_op_in(ZOrder::Normal)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_surface_border_shrinks_content_area() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count: 20, row_count: 10),
+            })?;
+
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(0),
+                dir: LayoutDirection::Horizontal,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                maybe_styles: Some(vec![Style {
+                    padding: Some(ch!(1)),
+                    ..Default::default()
+                }]),
+                maybe_border: Some(make_border()),
+                ..Default::default()
+            })?;
+
+            let flex_box = *surface.current_box()?;
+            assert_eq2!(flex_box.origin_pos, position!(col_index: 0, row_index: 0));
+            assert_eq2!(flex_box.bounds_size, size!(col_count: 20, row_count: 10));
+            // Border & then padding.
+            assert_eq2!(
+                flex_box.style_adjusted_origin_pos,
+                position!(col_index: 2, row_index: 2)
+            );
+            assert_eq2!(
+                flex_box.style_adjusted_bounds_size,
+                size!(col_count: 16, row_count: 6)
+            );
+
+            let painted_text = get_painted_text(&surface);
+            assert_eq2!(painted_text.first().unwrap(), "╭─ Editor ─────────╮");
+            assert_eq2!(painted_text.last().unwrap(), "╰──────────────────╯");
+            // Top & bottom edges, and 2 side edges for each of the 8 rows in between.
+            assert_eq2!(painted_text.len(), 2 + 8 * 2);
+
+            surface.box_end()?;
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_border_focused_style() -> CommonResult<()> {
+        throws!({
+            let border = make_border();
+            let focused_style = border.maybe_focused_style;
+            let style = border.maybe_style;
+
+            let mut surface = Surface {
+                maybe_focused_id: Some(FlexBoxId::from(1)),
+                ..Default::default()
+            };
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count: 20, row_count: 10),
+            })?;
+
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(0),
+                dir: LayoutDirection::Horizontal,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                ..Default::default()
+            })?;
+
+            // Not focused.
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(2),
+                requested_size_percent: requested_size_percent!(width: 50, height: 100),
+                maybe_border: Some(border.clone()),
+                ..Default::default()
+            })?;
+            surface.box_end()?;
+
+            // Focused.
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(1),
+                requested_size_percent: requested_size_percent!(width: 50, height: 100),
+                maybe_border: Some(border),
+                ..Default::default()
+            })?;
+            let flex_box = *surface.current_box()?;
+            assert_eq2!(
+                flex_box.style_adjusted_origin_pos,
+                position!(col_index: 11, row_index: 1)
+            );
+            surface.box_end()?;
+
+            surface.box_end()?;
+            surface.surface_end()?;
+
+            let render_ops = surface
+                .render_pipeline
+                .get_all_render_op_in(ZOrder::Normal)
+                .unwrap();
+            let (not_focused_ops, focused_ops) =
+                render_ops.split_at(render_ops.len() / 2);
+            assert!(not_focused_ops.contains(&RenderOp::ApplyColors(style)));
+            assert!(!not_focused_ops.contains(&RenderOp::ApplyColors(focused_style)));
+            assert!(focused_ops.contains(&RenderOp::ApplyColors(focused_style)));
+            assert!(!focused_ops.contains(&RenderOp::ApplyColors(style)));
+        });
+    }
+
+    #[test]
+    fn test_surface_border_color_follows_focus() -> CommonResult<()> {
+        let focused_color = TuiColor::Basic(ANSIBasicColor::Yellow);
+        let color = TuiColor::Basic(ANSIBasicColor::DarkGrey);
+        let border = Border {
+            maybe_style: Some(Style {
+                color_fg: Some(color),
+                ..Default::default()
+            }),
+            maybe_focused_style: Some(Style {
+                color_fg: Some(focused_color),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Lay out 2 boxes side by side, & return the fg color of the left & right border.
+        let get_border_colors =
+            |has_focus: &HasFocus| -> CommonResult<(TuiColor, TuiColor)> {
+                let mut surface = surface!(
+                    stylesheet: Stylesheet::default(),
+                    has_focus:  has_focus
+                );
+                surface.surface_start(SurfaceProps {
+                    pos: position!(col_index: 0, row_index: 0),
+                    size: size!(col_count: 20, row_count: 10),
+                })?;
+                surface.box_start(FlexBoxProps {
+                    id: FlexBoxId::from(0),
+                    dir: LayoutDirection::Horizontal,
+                    requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                    ..Default::default()
+                })?;
+                for id in [1, 2] {
+                    surface.box_start(FlexBoxProps {
+                        id: FlexBoxId::from(id),
+                        requested_size_percent: requested_size_percent!(width: 50, height: 100),
+                        maybe_border: Some(border.clone()),
+                        ..Default::default()
+                    })?;
+                    surface.box_end()?;
+                }
+                surface.box_end()?;
+                surface.surface_end()?;
+
+                let colors: Vec<TuiColor> = surface
+                    .render_pipeline
+                    .get_all_render_op_in(ZOrder::Normal)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|render_op| match render_op {
+                        RenderOp::ApplyColors(Some(style)) => style.color_fg,
+                        _ => None,
+                    })
+                    .collect();
+                Ok((colors[0], colors[colors.len() - 1]))
+            };
+
+        throws!({
+            let mut has_focus = HasFocus::default();
+            has_focus.set_id(FlexBoxId::from(1));
+            assert_eq2!(get_border_colors(&has_focus)?, (focused_color, color));
+
+            // Move the focus to the right box.
+            has_focus.set_id(FlexBoxId::from(2));
+            assert_eq2!(get_border_colors(&has_focus)?, (color, focused_color));
+
+            // Nothing has focus.
+            assert_eq2!(get_border_colors(&HasFocus::default())?, (color, color));
+        });
+    }
+}
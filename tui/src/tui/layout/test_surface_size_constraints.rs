@@ -0,0 +1,142 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    fn make_surface(size: Size) -> CommonResult<Surface> {
+        let mut surface = Surface::default();
+        surface.surface_start(SurfaceProps {
+            pos: position!(col_index: 0, row_index: 0),
+            size,
+        })?;
+        Ok(surface)
+    }
+
+    fn add_child(surface: &mut Surface, id: u8) -> CommonResult<FlexBox> {
+        surface.box_start(FlexBoxProps {
+            id: FlexBoxId::from(id),
+            dir: LayoutDirection::Vertical,
+            requested_size_percent: requested_size_percent!(width: 100, height: 100),
+            ..Default::default()
+        })?;
+        let flex_box = *surface.stack_of_boxes.last().unwrap();
+        surface.box_end()?;
+        Ok(flex_box)
+    }
+
+    #[test]
+    fn test_surface_fixed_status_bar() -> CommonResult<()> {
+        throws!({
+            let mut surface = make_surface(size!(col_count: 80, row_count: 24))?;
+
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(0),
+                dir: LayoutDirection::Vertical,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                maybe_child_size_constraints: Some(vec![
+                    SizeConstraint::Fraction(1),
+                    SizeConstraint::Fixed(ch!(1)),
+                ]),
+                ..Default::default()
+            })?;
+
+            let content = add_child(&mut surface, 1)?;
+            assert_eq2!(content.origin_pos, position!(col_index: 0, row_index: 0));
+            assert_eq2!(content.bounds_size, size!(col_count: 80, row_count: 23));
+
+            let status_bar = add_child(&mut surface, 2)?;
+            assert_eq2!(
+                status_bar.origin_pos,
+                position!(col_index: 0, row_index: 23)
+            );
+            assert_eq2!(status_bar.bounds_size, size!(col_count: 80, row_count: 1));
+
+            surface.box_end()?;
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_fixed_sidebar_fills_odd_width() -> CommonResult<()> {
+        throws!({
+            let mut surface = make_surface(size!(col_count: 101, row_count: 10))?;
+
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(0),
+                dir: LayoutDirection::Horizontal,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                maybe_child_size_constraints: Some(vec![
+                    SizeConstraint::Fixed(ch!(30)),
+                    SizeConstraint::Fraction(1),
+                ]),
+                ..Default::default()
+            })?;
+
+            let sidebar = add_child(&mut surface, 1)?;
+            assert_eq2!(sidebar.bounds_size, size!(col_count: 30, row_count: 10));
+
+            let content = add_child(&mut surface, 2)?;
+            assert_eq2!(content.origin_pos, position!(col_index: 30, row_index: 0));
+            assert_eq2!(content.bounds_size, size!(col_count: 71, row_count: 10));
+
+            surface.box_end()?;
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_unsatisfiable_size_constraints() -> CommonResult<()> {
+        throws!({
+            let mut surface = make_surface(size!(col_count: 20, row_count: 10))?;
+
+            let result = surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(0),
+                dir: LayoutDirection::Horizontal,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                maybe_child_size_constraints: Some(vec![
+                    SizeConstraint::Fixed(ch!(15)),
+                    SizeConstraint::Min(ch!(10)),
+                ]),
+                ..Default::default()
+            });
+            assert!(result.is_err());
+            assert!(surface.no_boxes_added());
+        });
+    }
+
+    #[test]
+    fn test_surface_missing_size_constraint() -> CommonResult<()> {
+        throws!({
+            let mut surface = make_surface(size!(col_count: 20, row_count: 10))?;
+
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(0),
+                dir: LayoutDirection::Horizontal,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                maybe_child_size_constraints: Some(vec![SizeConstraint::Fraction(1)]),
+                ..Default::default()
+            })?;
+
+            add_child(&mut surface, 1)?;
+            assert!(add_child(&mut surface, 2).is_err());
+        });
+    }
+}
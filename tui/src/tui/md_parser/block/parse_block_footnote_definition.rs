@@ -0,0 +1,82 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{bytes::complete::*, combinator::*, sequence::*, IResult};
+
+use crate::*;
+
+/// Sample inputs:
+/// Footnote definition:     "[^1]: Some *text*.\n"
+/// Empty footnote:          "[^note]:"
+///
+/// The label can't have any whitespace in it. The text after the `:` (including the spaces
+/// at its start) is parsed into fragments, and the rest of it (which can't be parsed into
+/// fragments) is kept as [MdLineFragment::Plain], just like [parse_block_markdown_text_all]
+/// does.
+#[rustfmt::skip]
+pub fn parse_block_footnote_definition(input: &str) -> IResult<&str, FootnoteDefinitionData> {
+    let (input, (label, text)) = terminated(
+        separated_pair(
+            /* label */ parse_element_footnote_reference,
+            /* separator */ tag(COLON),
+            /* text */ take_till(|it| it == '\n'),
+        ),
+        /* ends with (discarded) */ opt(tag(NEW_LINE)),
+    )(input)?;
+    let fragments = parse_block_markdown_text_all(text);
+    Ok((input, FootnoteDefinitionData { label, fragments }))
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_block_footnote_definition() {
+        assert_eq2!(
+            parse_block_footnote_definition("[^1]: Some *text*.\nfoo"),
+            Ok((
+                "foo",
+                FootnoteDefinitionData {
+                    label: "1",
+                    fragments: list![
+                        MdLineFragment::Plain(" Some "),
+                        MdLineFragment::Italic("text"),
+                        MdLineFragment::Plain("."),
+                    ]
+                }
+            ))
+        );
+        assert_eq2!(
+            parse_block_footnote_definition("[^note]:"),
+            Ok((
+                "",
+                FootnoteDefinitionData {
+                    label: "note",
+                    fragments: list![]
+                }
+            ))
+        );
+
+        for input in ["[1]: text", "[^1] text", "[^a b]: text"] {
+            assert!(parse_block_footnote_definition(input).is_err(), "{input}");
+        }
+    }
+}
@@ -0,0 +1,337 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{branch::*,
+          bytes::complete::*,
+          error::{Error, ErrorKind},
+          sequence::*,
+          Err as NomErr,
+          IResult};
+
+use crate::*;
+
+/// Sample inputs:
+/// YAML:                    "---\ntitle: Foo\ntags: [a, b]\n---\n"
+/// YAML w/ list items:      "---\ntags:\n  - a\n  - b\n---\n"
+/// TOML:                    "+++\ntitle = \"Foo\"\ntags = [\"a\", \"b\"]\n+++\n"
+///
+/// The front matter starts w/ a fence line (`---` or `+++`) and ends w/ the same fence
+/// line. It can only be at the start of the document, which is why [parse_markdown] only
+/// tries this parser for the first block. Each line between the fences must be blank, a
+/// comment, a key value pair, or part of the value of the key before it (eg: a list
+/// item). Otherwise this isn't front matter, eg: it is text between two thematic breaks.
+pub fn parse_block_front_matter(input: &str) -> IResult<&str, FrontMatterData> {
+    let (mut remainder, (fence, _)) = tuple((
+        alt((tag(FRONT_MATTER_YAML_FENCE), tag(FRONT_MATTER_TOML_FENCE))),
+        tag(NEW_LINE),
+    ))(input)?;
+    let kind = if fence == FRONT_MATTER_YAML_FENCE {
+        FrontMatterKind::Yaml
+    } else {
+        FrontMatterKind::Toml
+    };
+
+    let mut lines = List::new();
+    loop {
+        // There is no closing fence.
+        if remainder.is_empty() {
+            return Err(NomErr::Error(Error::new(input, ErrorKind::TakeUntil)));
+        }
+        let (line, rest) = match remainder.find(NEW_LINE) {
+            Some(index) => (&remainder[..index], &remainder[index + 1..]),
+            None => (remainder, &remainder[remainder.len()..]),
+        };
+        remainder = rest;
+        if line == fence {
+            break;
+        }
+        if !is_front_matter_line(kind, line) {
+            return Err(NomErr::Error(Error::new(input, ErrorKind::Verify)));
+        }
+        lines.push(line);
+    }
+
+    let entries = get_front_matter_entries(kind, &lines);
+    Ok((
+        remainder,
+        FrontMatterData {
+            kind,
+            lines,
+            entries,
+        },
+    ))
+}
+
+/// Splits a `key: value` (YAML) or `key = value` (TOML) line into the key, the separator
+/// (w/ the spaces around it) & the value, which add up to the line. Indented lines are
+/// not split, since they belong to the value of the key before them.
+pub fn split_front_matter_key_value(
+    kind: FrontMatterKind,
+    line: &str,
+) -> Option<(&str, &str, &str)> {
+    if line.starts_with(char::is_whitespace) || line.starts_with(FRONT_MATTER_COMMENT) {
+        return None;
+    }
+
+    // The index of the separator, & its length.
+    let (index, separator_len) = match kind {
+        FrontMatterKind::Yaml => {
+            if line.starts_with(FRONT_MATTER_YAML_LIST_ITEM) {
+                return None;
+            }
+            // The `:` must be followed by a space or the EOL, eg: `http://` isn't one.
+            let index = line
+                .match_indices(FRONT_MATTER_YAML_SEPARATOR)
+                .map(|(index, _)| index)
+                .find(|index| {
+                    let after = &line[index + FRONT_MATTER_YAML_SEPARATOR.len()..];
+                    after.is_empty() || after.starts_with(SPACE_CHAR)
+                })?;
+            (index, FRONT_MATTER_YAML_SEPARATOR.len())
+        }
+        FrontMatterKind::Toml => {
+            if line.starts_with(is_toml_value_char) {
+                return None;
+            }
+            let index = line.find(FRONT_MATTER_TOML_SEPARATOR)?;
+            (index, FRONT_MATTER_TOML_SEPARATOR.len())
+        }
+    };
+
+    let key = line[..index].trim_end();
+    if key.is_empty() {
+        return None;
+    }
+    let value = line[index + separator_len..].trim_start();
+    let separator = &line[key.len()..line.len() - value.len()];
+    Some((key, separator, value))
+}
+
+/// Splits a `- value` (YAML) list item line into the prefix (w/ the indent) & the value.
+pub fn split_front_matter_list_item(line: &str) -> Option<(&str, &str)> {
+    let content = line.trim_start();
+    let value = content.strip_prefix(FRONT_MATTER_YAML_LIST_ITEM)?;
+    if !value.starts_with(SPACE_CHAR) {
+        return None;
+    }
+    let value = value.trim_start();
+    Some((&line[..line.len() - value.len()], value))
+}
+
+fn is_toml_value_char(it: char) -> bool { matches!(it, '[' | ']' | '"' | '\'') }
+
+fn is_front_matter_line(kind: FrontMatterKind, line: &str) -> bool {
+    line.trim().is_empty()
+        || line.starts_with(char::is_whitespace)
+        || line.starts_with(FRONT_MATTER_COMMENT)
+        || split_front_matter_key_value(kind, line).is_some()
+        || match kind {
+            FrontMatterKind::Yaml => split_front_matter_list_item(line).is_some(),
+            FrontMatterKind::Toml => line.starts_with(is_toml_value_char),
+        }
+}
+
+fn get_front_matter_entries<'a>(
+    kind: FrontMatterKind,
+    lines: &[&'a str],
+) -> List<FrontMatterEntry<'a>> {
+    let mut acc = List::new();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        index += 1;
+        let Some((key, _, value)) = split_front_matter_key_value(kind, line) else {
+            continue;
+        };
+        let key = unquote(key);
+        let value = value.trim_end();
+
+        let value = if let Some(list_text) = value.strip_prefix('[') {
+            let mut items = List::new();
+            match list_text.strip_suffix(']') {
+                Some(list_text) => push_list_items(&mut items, list_text),
+                None => {
+                    // The array spans multiple lines.
+                    push_list_items(&mut items, list_text);
+                    while let Some(line) = lines.get(index) {
+                        index += 1;
+                        let line = line.trim();
+                        if let Some(line) = line.strip_suffix(']') {
+                            push_list_items(&mut items, line);
+                            break;
+                        }
+                        push_list_items(&mut items, line);
+                    }
+                }
+            }
+            FrontMatterValue::List(items)
+        } else if value.is_empty() && kind == FrontMatterKind::Yaml {
+            // The list items (if any) are in the lines after the key.
+            let mut items = List::new();
+            while let Some((_, item)) = lines
+                .get(index)
+                .and_then(|it| split_front_matter_list_item(it))
+            {
+                items.push(unquote(item.trim_end()));
+                index += 1;
+            }
+            if items.is_empty() {
+                FrontMatterValue::Text(value)
+            } else {
+                FrontMatterValue::List(items)
+            }
+        } else {
+            FrontMatterValue::Text(unquote(value))
+        };
+
+        acc.push(FrontMatterEntry { key, value });
+    }
+
+    acc
+}
+
+fn push_list_items<'a>(acc: &mut List<&'a str>, text: &'a str) {
+    for item in text.split(COMMA) {
+        let item = item.trim();
+        if !item.is_empty() {
+            acc.push(unquote(item));
+        }
+    }
+}
+
+fn unquote(text: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(it) = text
+            .strip_prefix(quote)
+            .and_then(|it| it.strip_suffix(quote))
+        {
+            return it;
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_block_front_matter_yaml() {
+        let input = [
+            "---",
+            "title: \"Foo: bar\"",
+            "tags: [a, 'b']",
+            "authors:",
+            "  - Jane",
+            "  - John",
+            "# A comment.",
+            "url: https://r3bl.com",
+            "---",
+            "# Heading",
+        ]
+        .join("\n");
+
+        let (remainder, front_matter) = parse_block_front_matter(&input).unwrap();
+        assert_eq2!(remainder, "# Heading");
+        assert_eq2!(front_matter.kind, FrontMatterKind::Yaml);
+        assert_eq2!(front_matter.lines.len(), 7);
+        assert_eq2!(
+            front_matter.entries,
+            list![
+                FrontMatterEntry {
+                    key: "title",
+                    value: FrontMatterValue::Text("Foo: bar"),
+                },
+                FrontMatterEntry {
+                    key: "tags",
+                    value: FrontMatterValue::List(list!["a", "b"]),
+                },
+                FrontMatterEntry {
+                    key: "authors",
+                    value: FrontMatterValue::List(list!["Jane", "John"]),
+                },
+                FrontMatterEntry {
+                    key: "url",
+                    value: FrontMatterValue::Text("https://r3bl.com"),
+                },
+            ]
+        );
+        assert_eq2!(front_matter.get_title(), Some("Foo: bar"));
+        assert_eq2!(front_matter.get_tags(), Some(list!["a", "b"]));
+        assert_eq2!(front_matter.get_authors(), Some(list!["Jane", "John"]));
+        assert_eq2!(front_matter.get_date(), None);
+    }
+
+    #[test]
+    fn test_parse_block_front_matter_toml() {
+        let input = [
+            "+++",
+            "title = \"Foo\"",
+            "date = 2023-09-20",
+            "tags = [",
+            "  \"a\",",
+            "  \"b\",",
+            "]",
+            "authors = \"Jane, John\"",
+            "+++",
+            "",
+        ]
+        .join("\n");
+
+        let (remainder, front_matter) = parse_block_front_matter(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(front_matter.kind, FrontMatterKind::Toml);
+        assert_eq2!(front_matter.get_title(), Some("Foo"));
+        assert_eq2!(front_matter.get_date(), Some("2023-09-20"));
+        assert_eq2!(front_matter.get_tags(), Some(list!["a", "b"]));
+        assert_eq2!(front_matter.get_authors(), Some(list!["Jane", "John"]));
+    }
+
+    #[test]
+    fn test_parse_block_front_matter_not_front_matter() {
+        // No closing fence.
+        assert!(parse_block_front_matter("---\ntitle: Foo\n").is_err());
+        // Text between two thematic breaks.
+        assert!(parse_block_front_matter("---\nSome text.\n---\n").is_err());
+        // The fences must match.
+        assert!(parse_block_front_matter("---\ntitle: Foo\n+++\n").is_err());
+        // The fence must be on its own line.
+        assert!(parse_block_front_matter("--- \ntitle: Foo\n--- \n").is_err());
+    }
+
+    #[test]
+    fn test_split_front_matter_key_value() {
+        assert_eq2!(
+            split_front_matter_key_value(FrontMatterKind::Yaml, "title:  Foo"),
+            Some(("title", ":  ", "Foo"))
+        );
+        assert_eq2!(
+            split_front_matter_key_value(FrontMatterKind::Toml, "title = \"Foo\""),
+            Some(("title", " = ", "\"Foo\""))
+        );
+        assert_eq2!(
+            split_front_matter_key_value(FrontMatterKind::Yaml, "  name: Foo"),
+            None
+        );
+        assert_eq2!(split_front_matter_list_item("  - a"), Some(("  - ", "a")));
+        assert_eq2!(split_front_matter_list_item("-a"), None);
+    }
+}
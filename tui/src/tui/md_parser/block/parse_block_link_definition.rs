@@ -0,0 +1,96 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{bytes::complete::*,
+          character::complete::*,
+          combinator::*,
+          sequence::*,
+          IResult};
+
+use crate::*;
+
+/// Sample inputs:
+/// Link definition:         `"[r3bl]: https://r3bl.com\n"`
+/// No EOL:                  `"[R3BL]:https://r3bl.com"`
+///
+/// The label can't start w/ `^`, since that is a footnote definition (please take a look
+/// at [fn@parse_block_footnote_definition]), and the URL (which is the rest of the line) can't
+/// have any whitespace in it. The URL is kept as is, so `<url>` keeps its angle brackets,
+/// which are removed when the links are resolved (please take a look at [MdReferences]).
+#[rustfmt::skip]
+pub fn parse_block_link_definition(input: &str) -> IResult<&str, LinkDefinitionData> {
+    let (input, (label, separator, url)) = terminated(
+        tuple((
+            /* label */ delimited(tag(LEFT_BRACKET), verify(is_not(RIGHT_BRACKET), is_link_label), tag(RIGHT_BRACKET)),
+            /* separator */ recognize(pair(tag(COLON), space0)),
+            /* url */ verify(take_till1(|it| it == '\n'), is_link_url),
+        )),
+        /* ends with (discarded) */ opt(tag(NEW_LINE)),
+    )(input)?;
+    Ok((input, LinkDefinitionData { label, separator, url }))
+}
+
+fn is_link_label(label: &str) -> bool {
+    !label.starts_with('^') && !label.contains('\n') && !label.trim().is_empty()
+}
+
+fn is_link_url(url: &str) -> bool { !url.contains(char::is_whitespace) }
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_block_link_definition() {
+        assert_eq2!(
+            parse_block_link_definition("[r3bl]:  https://r3bl.com\nfoo"),
+            Ok((
+                "foo",
+                LinkDefinitionData {
+                    label: "r3bl",
+                    separator: ":  ",
+                    url: "https://r3bl.com"
+                }
+            ))
+        );
+        assert_eq2!(
+            parse_block_link_definition("[R3BL]:https://r3bl.com"),
+            Ok((
+                "",
+                LinkDefinitionData {
+                    label: "R3BL",
+                    separator: ":",
+                    url: "https://r3bl.com"
+                }
+            ))
+        );
+
+        for input in [
+            "[^1]: https://r3bl.com",
+            "[r3bl]:",
+            "[r3bl]: a b",
+            "[r3bl]: https://r3bl.com ",
+            "[r3bl] https://r3bl.com",
+            "[r3bl](https://r3bl.com)",
+        ] {
+            assert!(parse_block_link_definition(input).is_err(), "{input}");
+        }
+    }
+}
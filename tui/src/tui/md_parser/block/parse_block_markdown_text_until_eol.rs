@@ -62,6 +62,21 @@ pub fn parse_block_markdown_text_opt_eol(
     parse_opt_eol(input, CheckboxParsePolicy::IgnoreCheckbox)
 }
 
+/// Parse a markdown text [FragmentsInOneLine] in the input (no EOL required). Any text that
+/// can't be parsed (eg: an unterminated `*`) is kept as [MdLineFragment::Plain], so that the
+/// whole input is used.
+pub fn parse_block_markdown_text_all(input: &str) -> MdLineFragments {
+    match parse_block_markdown_text_opt_eol(input) {
+        Ok((remainder, mut fragments)) => {
+            if !remainder.is_empty() {
+                fragments.push(MdLineFragment::Plain(remainder));
+            }
+            fragments
+        }
+        Err(_) => list![MdLineFragment::Plain(input)],
+    }
+}
+
 #[rustfmt::skip]
 fn parse_opt_eol(
     input: &str,
@@ -95,6 +110,15 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_block_markdown_text_all() {
+        assert_eq2!(parse_block_markdown_text_all(""), list![]);
+        assert_eq2!(
+            parse_block_markdown_text_all("foo *bar"),
+            list![MdLineFragment::Plain("foo "), MdLineFragment::Plain("*bar")]
+        );
+    }
+
     #[test]
     fn test_parse_block_markdown_text_with_eol() {
         assert_eq2!(parse_block_markdown_text_until_eol("\n"), Ok(("", list![])));
@@ -0,0 +1,159 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{branch::*, bytes::complete::*, combinator::*, multi::*, sequence::*, IResult};
+
+use crate::*;
+
+/// Sample inputs:
+/// One line:                "> foo\n"
+/// Multi line:              "> foo\n>\n> - bar\n"
+/// Nested:                  "> foo\n> > bar\n"
+///
+/// Each line must start w/ `> ` (or be just `>`), and the blockquote ends at the first
+/// line that doesn't. The rest of each line is parsed by [parse_markdown] on its own, so a
+/// block can't span multiple lines of a blockquote, except for nested blockquotes, which
+/// are merged together. Outputs the blocks in the blockquote.
+pub fn parse_block_quote(input: &str) -> IResult<&str, List<MdBlockElement>> {
+    let (remainder, lines) = many1(parse_block_quote_line)(input)?;
+
+    let mut acc = List::new();
+    for line in lines {
+        for block in parse_block_quote_line_content(line).items {
+            push_block(&mut acc, block);
+        }
+    }
+
+    Ok((remainder, acc))
+}
+
+/// Matches the `> ` prefix, and outputs the rest of the line (including the EOL, if any).
+#[rustfmt::skip]
+pub fn parse_block_quote_line(input: &str) -> IResult<&str, &str> {
+    preceded(
+        /* prefix - discarded */ tag(BLOCK_QUOTE),
+        /* output */
+        alt((
+            preceded(
+                tag(SPACE),
+                recognize(pair(take_till(|it| it == '\n'), opt(tag(NEW_LINE)))),
+            ),
+            tag(NEW_LINE),
+            eof,
+        )),
+    )(input)
+}
+
+/// Each line of the blockquote is turned into (at least) one block, so that the
+/// blockquote has as many lines as the text that it is parsed from.
+fn parse_block_quote_line_content(line: &str) -> List<MdBlockElement> {
+    if let Ok((remainder, blocks)) = parse_markdown(line) {
+        if remainder.is_empty() && !blocks.is_empty() {
+            return blocks;
+        }
+    }
+
+    // Eg: the last line w/out an EOL, or a line w/ an unterminated code block.
+    let text = line.strip_suffix(NEW_LINE).unwrap_or(line);
+    list![MdBlockElement::Text(parse_block_markdown_text_all(text))]
+}
+
+/// Consecutive nested blockquotes are merged into one (at every level of nesting).
+fn push_block<'a>(acc: &mut List<MdBlockElement<'a>>, block: MdBlockElement<'a>) {
+    let MdBlockElement::BlockQuote(blocks) = block else {
+        acc.push(block);
+        return;
+    };
+
+    if let Some(MdBlockElement::BlockQuote(last_blocks)) = acc.last_mut() {
+        for block in blocks.items {
+            push_block(last_blocks, block);
+        }
+        return;
+    }
+
+    acc.push(MdBlockElement::BlockQuote(blocks));
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{error::{Error, ErrorKind},
+              Err as NomErr};
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_block_quote() {
+        assert_eq2!(
+            parse_block_quote("> foo *bar*\n>\n> # baz\nqux\n"),
+            Ok((
+                "qux\n",
+                list![
+                    MdBlockElement::Text(list![
+                        MdLineFragment::Plain("foo "),
+                        MdLineFragment::Italic("bar"),
+                    ]),
+                    MdBlockElement::Text(list![]),
+                    MdBlockElement::Heading(HeadingData {
+                        level: HeadingLevel::Heading1,
+                        text: "baz",
+                    }),
+                ]
+            ))
+        );
+
+        // No EOL at the end.
+        assert_eq2!(
+            parse_block_quote("> foo"),
+            Ok((
+                "",
+                list![MdBlockElement::Text(list![MdLineFragment::Plain("foo")])]
+            ))
+        );
+
+        // The `>` must be followed by a space.
+        assert_eq2!(
+            parse_block_quote(">foo\n"),
+            Err(NomErr::Error(Error {
+                input: "foo\n",
+                code: ErrorKind::Eof
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_block_quote_nested() {
+        assert_eq2!(
+            parse_block_quote("> foo\n> > bar\n> > > baz\n> > qux\n"),
+            Ok((
+                "",
+                list![
+                    MdBlockElement::Text(list![MdLineFragment::Plain("foo")]),
+                    MdBlockElement::BlockQuote(list![
+                        MdBlockElement::Text(list![MdLineFragment::Plain("bar")]),
+                        MdBlockElement::BlockQuote(list![MdBlockElement::Text(list![
+                            MdLineFragment::Plain("baz")
+                        ])]),
+                        MdBlockElement::Text(list![MdLineFragment::Plain("qux")]),
+                    ]),
+                ]
+            ))
+        );
+    }
+}
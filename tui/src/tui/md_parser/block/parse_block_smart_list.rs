@@ -26,65 +26,279 @@ use nom::{branch::alt,
 
 use crate::*;
 
-/// Public API for parsing a smart list block in markdown.
-pub fn parse_block_smart_list(input: &str) -> IResult<&str, (Lines, BulletKind, usize)> {
-    let (remainder, smart_list_ir) = parse_smart_list(input)?;
-
-    let indent = smart_list_ir.indent;
-    let bullet_kind = smart_list_ir.bullet_kind;
-    let mut output_lines: Lines = List::with_capacity(smart_list_ir.content_lines.len());
-
-    for (index, line) in smart_list_ir.content_lines.iter().enumerate() {
-        // Parse the line as a markdown text. Take special care of checkboxes if they show up at the
-        // start of the line.
-        let (_, fragments_in_line) = {
-            let parse_checkbox_policy = {
-                let checked = format!("{}{}", CHECKED, SPACE);
-                let unchecked = format!("{}{}", UNCHECKED, SPACE);
-                if line.content.starts_with(&checked)
-                    || line.content.starts_with(&unchecked)
-                {
-                    CheckboxParsePolicy::ParseCheckbox
-                } else {
-                    CheckboxParsePolicy::IgnoreCheckbox
-                }
-            };
-            parse_block_markdown_text_opt_eol_with_checkbox_policy(
-                line.content,
-                parse_checkbox_policy,
-            )?
+/// Public API for parsing a smart list block in markdown. The list is made up of the items
+/// w/ the same indent (which has to be a multiple of [LIST_PREFIX_BASE_WIDTH]) & kind of
+/// bullet as the first one. Each item ends at the first line that isn't a part of it
+/// (please take a look at `parse_smart_list_item()` for what is).
+pub fn parse_block_smart_list(input: &str) -> IResult<&str, SmartListData> {
+    let (first_line, _) = split_first_line(input);
+    match parse_list_item_bullet(first_line) {
+        Some((indent, _, _)) if indent % LIST_PREFIX_BASE_WIDTH == 0 => {
+            parse_smart_list_items(input, indent)
+        }
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Fail,
+        ))),
+    }
+}
+
+/// Parses the items that start w/ `indent` spaces (which can be any number of spaces for
+/// a nested list), until a line w/ a different indent or kind of bullet.
+fn parse_smart_list_items(input: &str, indent: usize) -> IResult<&str, SmartListData> {
+    let mut items: List<SmartListItem> = List::new();
+    let mut input = input;
+
+    loop {
+        let (line, _) = split_first_line(input);
+        let is_next_item = match (parse_list_item_bullet(line), items.first()) {
+            (Some((it_indent, _, _)), _) if it_indent != indent => false,
+            (Some((_, _, bullet_kind)), Some(first_item)) => {
+                is_same_kind_of_list(&bullet_kind, &first_item.bullet_kind)
+            }
+            (Some(_), None) => true,
+            (None, _) => false,
         };
+        if !is_next_item {
+            break;
+        }
 
-        // Mark is first line or not (to show or hide bullet).
-        let is_first_line = index == 0;
+        let (remainder, item) = parse_smart_list_item(input, indent)?;
+        items.push(item);
+        input = remainder;
+    }
 
-        // Insert bullet marker before the line.
-        let mut it = match bullet_kind {
-            BulletKind::Ordered(number) => {
-                list![MdLineFragment::OrderedListBullet {
-                    indent,
-                    number,
-                    is_first_line
-                }]
+    if items.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Fail,
+        )));
+    }
+
+    Ok((input, SmartListData { indent, items }))
+}
+
+/// The first line of the `input` has the bullet of the item. The lines after it are a
+/// part of the item if they are:
+/// 1. Indented to line up w/ the text after the bullet (or more). These are lines of text,
+///    or code blocks (if the fences are lined up w/ the text too).
+/// 2. List items w/ more indent than this one, which are a nested list.
+/// 3. Blank lines, if they are followed by one of the above (eg: to separate the
+///    paragraphs of the item).
+/// 4. Lazy continuation lines, which are lines of text right after a line of text in the
+///    item, that aren't indented, and don't start some other block (eg: a heading).
+fn parse_smart_list_item(input: &str, indent: usize) -> IResult<&str, SmartListItem> {
+    let (first_line, mut input) = split_first_line(input);
+    let Some((_, bullet_str, bullet_kind)) = parse_list_item_bullet(first_line) else {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            first_line,
+            nom::error::ErrorKind::Fail,
+        )));
+    };
+    let content_indent = indent + bullet_str.len();
+
+    let mut blocks = list![MdBlockElement::Text(parse_smart_list_line(
+        &first_line[content_indent..]
+    ))];
+    let mut lazy_line_indices = list![];
+
+    while !input.is_empty() {
+        let (line, remainder) = split_first_line(input);
+
+        if line.trim().is_empty() {
+            let (blank_line_count, after_blank_lines) = skip_blank_lines(input);
+            let (next_line, _) = split_first_line(after_blank_lines);
+            if !is_part_of_item(next_line, indent, content_indent) {
+                break;
             }
-            BulletKind::Unordered => list![MdLineFragment::UnorderedListBullet {
-                indent,
-                is_first_line
-            }],
-        };
+            for _ in 0..blank_line_count {
+                blocks.push(MdBlockElement::Text(list![]));
+            }
+            input = after_blank_lines;
+            continue;
+        }
 
-        if fragments_in_line.is_empty() {
-            // If the line is empty, then we need to insert a blank line.
-            it.push(MdLineFragment::Plain(""));
+        if let Some((it_indent, _, _)) = parse_list_item_bullet(line) {
+            if it_indent <= indent {
+                break;
+            }
+            let (remainder, nested_list) = parse_smart_list_items(input, it_indent)?;
+            blocks.push(MdBlockElement::SmartList(nested_list));
+            input = remainder;
+            continue;
+        }
+
+        if count_spaces_at_start(line) >= content_indent {
+            let text = &line[content_indent..];
+            if text.starts_with(CODE_BLOCK_START_PARTIAL) {
+                if let Some((remainder, code_block_lines)) =
+                    parse_smart_list_code_block(input, content_indent)
+                {
+                    blocks.push(MdBlockElement::CodeBlock(code_block_lines));
+                    input = remainder;
+                    continue;
+                }
+            }
+            blocks.push(MdBlockElement::Text(parse_smart_list_line(text)));
+            input = remainder;
+            continue;
+        }
+
+        let is_after_text = matches!(
+            blocks.last(),
+            Some(MdBlockElement::Text(fragments))
+                if fragments.iter().any(|it| *it != MdLineFragment::Plain(""))
+        );
+        if is_after_text && is_lazy_continuation_line(input) {
+            lazy_line_indices.push(blocks.len());
+            blocks.push(MdBlockElement::Text(parse_block_markdown_text_all(line)));
+            input = remainder;
+            continue;
+        }
+
+        break;
+    }
+
+    Ok((
+        input,
+        SmartListItem {
+            bullet_kind,
+            bullet_str,
+            blocks,
+            lazy_line_indices,
+        },
+    ))
+}
+
+/// Take special care of checkboxes if they show up at the start of the line. An empty line
+/// has an empty [MdLineFragment::Plain].
+fn parse_smart_list_line(text: &str) -> MdLineFragments {
+    match parse_block_markdown_text_opt_eol_with_checkbox_policy(
+        text,
+        get_checkbox_parse_policy(text),
+    ) {
+        Ok((_, fragments)) if !fragments.is_empty() => fragments,
+        _ => list![MdLineFragment::Plain("")],
+    }
+}
+
+/// The fences & all the lines in between have to be indented by `content_indent` spaces
+/// (except for blank lines). Returns [None] if they aren't, or if there's no closing fence.
+fn parse_smart_list_code_block(
+    input: &str,
+    content_indent: usize,
+) -> Option<(&str, List<CodeBlockLine>)> {
+    let (first_line, mut input) = split_first_line(input);
+    let lang = &first_line[content_indent + CODE_BLOCK_START_PARTIAL.len()..];
+    let maybe_lang = if lang.is_empty() { None } else { Some(lang) };
+
+    let mut lines = vec![];
+    while !input.is_empty() {
+        let (line, remainder) = split_first_line(input);
+        let text = if count_spaces_at_start(line) >= content_indent {
+            &line[content_indent..]
+        } else if line.trim().is_empty() {
+            ""
         } else {
-            // Otherwise, we can just append the fragments.
-            it += fragments_in_line;
+            return None;
+        };
+        if text == CODE_BLOCK_END {
+            return Some((remainder, convert_into_code_block_lines(maybe_lang, lines)));
         }
+        lines.push(text);
+        input = remainder;
+    }
+
+    None
+}
 
-        output_lines.push(it);
+/// Returns the indent (spaces before the bullet), the bullet (eg: `- ` or `10. `) & the
+/// kind of bullet, if the line is a list item.
+pub fn parse_list_item_bullet(line: &str) -> Option<(usize, &str, BulletKind)> {
+    let indent = count_spaces_at_start(line);
+    let result: IResult<&str, &str> = recognize(alt((
+        tag(UNORDERED_LIST_PREFIX),
+        terminated(digit1, tag(ORDERED_LIST_PARTIAL_PREFIX)),
+    )))(&line[indent..]);
+    let (_, bullet_str) = result.ok()?;
+
+    let bullet_kind = match bullet_str.strip_suffix(ORDERED_LIST_PARTIAL_PREFIX) {
+        Some(number_str) => BulletKind::Ordered(number_str.parse::<usize>().ok()?),
+        None => BulletKind::Unordered,
+    };
+
+    Some((indent, bullet_str, bullet_kind))
+}
+
+fn is_same_kind_of_list(lhs: &BulletKind, rhs: &BulletKind) -> bool {
+    matches!(
+        (lhs, rhs),
+        (BulletKind::Ordered(_), BulletKind::Ordered(_))
+            | (BulletKind::Unordered, BulletKind::Unordered)
+    )
+}
+
+/// A line after blank lines is a part of the item if it's a nested list item, or if it is
+/// lined up w/ the text after the bullet.
+fn is_part_of_item(line: &str, indent: usize, content_indent: usize) -> bool {
+    match parse_list_item_bullet(line) {
+        Some((it_indent, _, _)) => it_indent > indent,
+        None => !line.trim().is_empty() && count_spaces_at_start(line) >= content_indent,
+    }
+}
+
+/// The first line of the `input` isn't a lazy continuation line if it starts any block
+/// other than [MdBlockElement::Text] (eg: a heading, a blockquote, a table, or a code
+/// fence).
+fn is_lazy_continuation_line(input: &str) -> bool {
+    if input.trim_start().starts_with(CODE_BLOCK_START_PARTIAL) {
+        return false;
     }
+    !matches!(
+        parse_block(input),
+        Ok((_, block)) if !matches!(block, MdBlockElement::Text(_))
+    )
+}
+
+/// Returns the number of blank lines at the start of the `input`, and the rest of it.
+fn skip_blank_lines(input: &str) -> (usize, &str) {
+    let mut count = 0;
+    let mut input = input;
+    while !input.is_empty() {
+        let (line, remainder) = split_first_line(input);
+        if !line.trim().is_empty() {
+            break;
+        }
+        count += 1;
+        input = remainder;
+    }
+    (count, input)
+}
+
+/// Returns the first line (w/out the EOL), and the rest of the `input` after the EOL.
+fn split_first_line(input: &str) -> (&str, &str) {
+    match input.find(NEW_LINE) {
+        Some(index) => (&input[..index], &input[index + 1..]),
+        // An empty slice at the end of the `input` (rather than a static `""`), so that the
+        // remainder stays a subslice of the `input` (which nom's `consumed` relies on).
+        None => (input, &input[input.len()..]),
+    }
+}
 
-    Ok((remainder, (output_lines, bullet_kind, indent)))
+fn count_spaces_at_start(line: &str) -> usize {
+    line.len() - line.trim_start_matches(SPACE_CHAR).len()
+}
+
+/// Checkboxes are only parsed if they show up at the start of the content of a line in a
+/// smart list.
+pub fn get_checkbox_parse_policy(content: &str) -> CheckboxParsePolicy {
+    let checked = format!("{}{}", CHECKED, SPACE);
+    let unchecked = format!("{}{}", UNCHECKED, SPACE);
+    if content.starts_with(&checked) || content.starts_with(&unchecked) {
+        CheckboxParsePolicy::ParseCheckbox
+    } else {
+        CheckboxParsePolicy::IgnoreCheckbox
+    }
 }
 
 /// Tests things that are final output (and not at the IR level).
@@ -94,6 +308,27 @@ mod tests_parse_block_smart_list {
 
     use super::*;
 
+    fn item<'a>(
+        bullet_str: &'a str,
+        blocks: List<MdBlockElement<'a>>,
+        lazy_line_indices: List<usize>,
+    ) -> SmartListItem<'a> {
+        let bullet_kind = match bullet_str.strip_suffix(ORDERED_LIST_PARTIAL_PREFIX) {
+            Some(number_str) => BulletKind::Ordered(number_str.parse().unwrap()),
+            None => BulletKind::Unordered,
+        };
+        SmartListItem {
+            bullet_kind,
+            bullet_str,
+            blocks,
+            lazy_line_indices,
+        }
+    }
+
+    fn text(it: &str) -> MdBlockElement {
+        MdBlockElement::Text(list![MdLineFragment::Plain(it)])
+    }
+
     #[test]
     fn test_with_unicode() {
         let input = "- straight 😃 foo bar baz\n";
@@ -103,185 +338,251 @@ mod tests_parse_block_smart_list {
         assert_eq2!(remainder, "");
         assert_eq2!(
             output,
-            &(
-                list![list![
-                    MdLineFragment::UnorderedListBullet {
-                        indent: 0,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Plain("straight 😃 foo bar baz"),
-                ],],
-                BulletKind::Unordered,
-                0
-            )
+            &SmartListData {
+                indent: 0,
+                items: list![item("- ", list![text("straight 😃 foo bar baz")], list![])],
+            }
         );
     }
 
     #[test]
     fn test_parse_block_smart_list_with_checkbox() {
-        // Valid unchecked.
-        {
-            let input = ["- [ ] todo"].join("\n");
-            let result = parse_block_smart_list(&input);
-            let remainder = result.as_ref().unwrap().0;
-            let (lines, _bullet_kind, _indent) = result.unwrap().1;
-            let first_line = lines.first().unwrap();
+        fn get_first_line(input: &str) -> MdBlockElement {
+            let (remainder, list_data) = parse_block_smart_list(input).unwrap();
             assert_eq2!(remainder, "");
-            assert_eq2!(
-                first_line,
-                &list![
-                    MdLineFragment::UnorderedListBullet {
-                        indent: 0,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Checkbox(false),
-                    MdLineFragment::Plain(" todo"),
-                ]
-            );
+            list_data.items[0].blocks[0].clone()
         }
 
+        // Valid unchecked.
+        assert_eq2!(
+            get_first_line("- [ ] todo"),
+            MdBlockElement::Text(list![
+                MdLineFragment::Checkbox(false),
+                MdLineFragment::Plain(" todo"),
+            ])
+        );
+
         // Valid checked.
-        {
-            let input = ["- [x] done"].join("\n");
-            let result = parse_block_smart_list(&input);
-            let remainder = result.as_ref().unwrap().0;
-            let (lines, _bullet_kind, _indent) = result.unwrap().1;
-            let first_line = lines.first().unwrap();
-            assert_eq2!(remainder, "");
-            assert_eq2!(
-                first_line,
-                &list![
-                    MdLineFragment::UnorderedListBullet {
-                        indent: 0,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Checkbox(true),
-                    MdLineFragment::Plain(" done"),
-                ]
-            );
-        }
+        assert_eq2!(
+            get_first_line("- [x] done"),
+            MdBlockElement::Text(list![
+                MdLineFragment::Checkbox(true),
+                MdLineFragment::Plain(" done"),
+            ])
+        );
 
         // Invalid unchecked.
-        {
-            let input = ["- [ ]todo"].join("\n");
-            let result = parse_block_smart_list(&input);
-            let remainder = result.as_ref().unwrap().0;
-            let (lines, _bullet_kind, _indent) = result.unwrap().1;
-            let first_line = lines.first().unwrap();
-            assert_eq2!(remainder, "");
-            assert_eq2!(
-                first_line,
-                &list![
-                    MdLineFragment::UnorderedListBullet {
-                        indent: 0,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Plain("[ ]"),
-                    MdLineFragment::Plain("todo"),
-                ]
-            );
-        }
+        assert_eq2!(
+            get_first_line("- [ ]todo"),
+            MdBlockElement::Text(list![
+                MdLineFragment::Plain("[ ]"),
+                MdLineFragment::Plain("todo"),
+            ])
+        );
 
         // Invalid checked.
-        {
-            let input = ["- [x]done"].join("\n");
-            let result = parse_block_smart_list(&input);
-            let remainder = result.as_ref().unwrap().0;
-            let (lines, _bullet_kind, _indent) = result.unwrap().1;
-            let first_line = lines.first().unwrap();
-            assert_eq2!(remainder, "");
-            assert_eq2!(
-                first_line,
-                &list![
-                    MdLineFragment::UnorderedListBullet {
-                        indent: 0,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Plain("[x]"),
-                    MdLineFragment::Plain("done"),
-                ]
-            );
-        }
+        assert_eq2!(
+            get_first_line("- [x]done"),
+            MdBlockElement::Text(list![
+                MdLineFragment::Plain("[x]"),
+                MdLineFragment::Plain("done"),
+            ])
+        );
     }
 
     #[test]
     fn test_valid_ul_list_1() {
         let input = "- foo\n  bar baz\n";
-        let expected = list! {
-            list![
-                MdLineFragment::UnorderedListBullet { indent: 0, is_first_line: true },
-                MdLineFragment::Plain("foo"),
-            ],
-            list![
-                MdLineFragment::UnorderedListBullet { indent: 0, is_first_line: false },
-                MdLineFragment::Plain("bar baz"),
-            ],
-        };
-        let result = parse_block_smart_list(input);
-        let remainder = result.as_ref().unwrap().0;
-        let (lines, _bullet_kind, _indent) = result.unwrap().1;
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
         assert_eq2!(remainder, "");
-        assert_eq2!(lines, expected);
+        assert_eq2!(
+            list_data.items,
+            list![item("- ", list![text("foo"), text("bar baz")], list![])]
+        );
     }
 
     #[test]
     fn test_valid_ul_list_2() {
         let input = "- foo\n  bar baz\n- foo1\n  bar1 baz1\n";
-        let expected = list! {
-            list![
-                MdLineFragment::UnorderedListBullet { indent: 0, is_first_line: true },
-                MdLineFragment::Plain("foo"),
-            ],
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            list_data.items,
             list![
-                MdLineFragment::UnorderedListBullet { indent: 0, is_first_line: false },
-                MdLineFragment::Plain("bar baz"),
-            ],
-        };
-        let result = parse_block_smart_list(input);
-        let remainder = result.as_ref().unwrap().0;
-        let (lines, _bullet_kind, _indent) = result.unwrap().1;
-        assert_eq2!(remainder, "- foo1\n  bar1 baz1\n");
-        assert_eq2!(lines, expected);
+                item("- ", list![text("foo"), text("bar baz")], list![]),
+                item("- ", list![text("foo1"), text("bar1 baz1")], list![]),
+            ]
+        );
     }
 
     #[test]
     fn test_valid_ol_list_1() {
         let input = "1. foo\n   bar baz\n";
-        let expected = list! {
-            list![
-                MdLineFragment::OrderedListBullet { indent: 0 , number: 1, is_first_line: true },
-                MdLineFragment::Plain("foo"),
-            ],
-            list![
-                MdLineFragment::OrderedListBullet { indent: 0 , number: 1, is_first_line: false },
-                MdLineFragment::Plain("bar baz"),
-            ],
-        };
-        let result = parse_block_smart_list(input);
-        let remainder = result.as_ref().unwrap().0;
-        let (lines, _bullet_kind, _indent) = result.unwrap().1;
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
         assert_eq2!(remainder, "");
-        assert_eq2!(lines, expected);
+        assert_eq2!(
+            list_data.items,
+            list![item("1. ", list![text("foo"), text("bar baz")], list![])]
+        );
     }
 
     #[test]
     fn test_valid_ol_list_2() {
         let input = "1. foo\n   bar baz\n1. foo\n   bar baz\n";
-        let expected = list! {
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            list_data.items,
             list![
-                MdLineFragment::OrderedListBullet { indent: 0 , number: 1, is_first_line: true },
-                MdLineFragment::Plain("foo"),
-            ],
+                item("1. ", list![text("foo"), text("bar baz")], list![]),
+                item("1. ", list![text("foo"), text("bar baz")], list![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_different_kind_of_bullet_starts_another_list() {
+        let input = "- foo\n1. bar\n";
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "1. bar\n");
+        assert_eq2!(list_data.items.len(), 1);
+    }
+
+    #[test]
+    fn test_nested_and_mixed_lists() {
+        let input = "- a\n  1. b\n     c\n  2. d\n- e\n";
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            list_data,
+            SmartListData {
+                indent: 0,
+                items: list![
+                    item(
+                        "- ",
+                        list![
+                            text("a"),
+                            MdBlockElement::SmartList(SmartListData {
+                                indent: 2,
+                                items: list![
+                                    item("1. ", list![text("b"), text("c")], list![]),
+                                    item("2. ", list![text("d")], list![]),
+                                ],
+                            }),
+                        ],
+                        list![]
+                    ),
+                    item("- ", list![text("e")], list![]),
+                ],
+            }
+        );
+
+        // An unordered list nested in an ordered one.
+        let input = "1. a\n   - b\n";
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            list_data.items[0].blocks[1],
+            MdBlockElement::SmartList(SmartListData {
+                indent: 3,
+                items: list![item("- ", list![text("b")], list![])],
+            })
+        );
+    }
+
+    #[test]
+    fn test_code_block_in_item() {
+        let input = "- a\n  ```rs\n  let a = 1;\n\n  ```\n- b\n";
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            list_data.items,
             list![
-                MdLineFragment::OrderedListBullet { indent: 0 , number: 1, is_first_line: false },
-                MdLineFragment::Plain("bar baz"),
-            ],
-        };
-        let result = parse_block_smart_list(input);
-        let remainder = result.as_ref().unwrap().0;
-        let (lines, _bullet_kind, _indent) = result.unwrap().1;
-        assert_eq2!(remainder, "1. foo\n   bar baz\n");
-        assert_eq2!(lines, expected);
+                item(
+                    "- ",
+                    list![
+                        text("a"),
+                        MdBlockElement::CodeBlock(convert_into_code_block_lines(
+                            Some("rs"),
+                            vec!["let a = 1;", ""]
+                        )),
+                    ],
+                    list![]
+                ),
+                item("- ", list![text("b")], list![]),
+            ]
+        );
+
+        // W/out the closing fence, the lines are just text.
+        let input = "- a\n  ```rs\n  let a = 1;\n";
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(list_data.items[0].blocks.len(), 3);
+        assert_eq2!(list_data.items[0].blocks[2], text("let a = 1;"));
+    }
+
+    #[test]
+    fn test_multi_paragraph_item() {
+        let input = "- a\n\n  b\n\nc\n";
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        // The blank line before `c` isn't a part of the list.
+        assert_eq2!(remainder, "\nc\n");
+        assert_eq2!(
+            list_data.items,
+            list![item(
+                "- ",
+                list![text("a"), MdBlockElement::Text(list![]), text("b")],
+                list![]
+            )]
+        );
+
+        // Blank lines between items end the list.
+        let input = "- a\n\n- b\n";
+        let (remainder, _) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "\n- b\n");
+    }
+
+    #[test]
+    fn test_lazy_continuation_line() {
+        let input = "- a\n  - b\nc\n# d\n";
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "# d\n");
+        // The lazy continuation line is a part of the innermost item.
+        assert_eq2!(
+            list_data.items[0].blocks[1],
+            MdBlockElement::SmartList(SmartListData {
+                indent: 2,
+                items: list![item("- ", list![text("b"), text("c")], list![1])],
+            })
+        );
+
+        // Not after a blank line.
+        let input = "- a\n\nc\n";
+        let (remainder, _) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "\nc\n");
+    }
+
+    #[test]
+    fn test_flatten() {
+        let input = "- a\n  - b\n    c\n  1. d\n\n     e\nf\n";
+        let (remainder, list_data) = parse_block_smart_list(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            list_data
+                .flatten()
+                .iter()
+                .map(|it| (it.first_line_prefix.as_str(), it.is_indented_in_source()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("─┤", true),
+                ("───┤", true),
+                (" │ │", true),
+                (" │1.│", true),
+                (" │  │", false),
+                (" │  │", true),
+                (" │  │", false),
+            ]
+        );
     }
 }
 
@@ -292,6 +593,25 @@ mod tests_parse_smart_lists_in_markdown {
 
     use super::*;
 
+    fn assert_pretty_printed_blocks(input: &str, expected_output: &[&str]) {
+        let result = parse_markdown(input);
+        let remainder = result.as_ref().unwrap().0;
+        let md_doc: MdDocument = result.unwrap().1;
+
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            md_doc
+                .items
+                .iter()
+                .map(|element| element.pretty_print_debug())
+                .collect::<Vec<_>>(),
+            expected_output
+                .iter()
+                .map(|it| it.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn test_parse_valid_md_ol_with_indent() {
         let input = [
@@ -302,6 +622,7 @@ mod tests_parse_smart_lists_in_markdown {
             "    3. ol3",
             "       ol3.1",
             "       ol3.2",
+            "",
             "end",
             "",
         ]
@@ -309,27 +630,12 @@ mod tests_parse_smart_lists_in_markdown {
 
         let expected_output = [
             "start",
-            "[  ┊1.│ol1┊  ]",
-            "[  ┊  2.│ol2┊ → ┊    │ol2.1┊  ]",
-            "[  ┊    3.│ol3┊ → ┊      │ol3.1┊ → ┊      │ol3.2┊  ]",
+            "[  ┊1.│ol1┊ → ┊  2.│ol2┊ → ┊    │ol2.1┊ → ┊    3.│ol3┊ → ┊      │ol3.1┊ → ┊      │ol3.2┊  ]",
+            "",
             "end",
         ];
 
-        let result = parse_markdown(input.as_str());
-        let remainder = result.as_ref().unwrap().0;
-        let md_doc: MdDocument = result.unwrap().1;
-
-        // md_doc.console_log_fg();
-        // remainder.console_log_bg();
-
-        assert_eq2!(remainder, "");
-        md_doc.items.iter().zip(expected_output.iter()).for_each(
-            |(element, test_str)| {
-                let lhs = element.pretty_print_debug();
-                let rhs = test_str.to_string();
-                assert_eq2!(lhs, rhs);
-            },
-        );
+        assert_pretty_printed_blocks(&input, &expected_output);
     }
 
     #[test]
@@ -342,6 +648,7 @@ mod tests_parse_smart_lists_in_markdown {
             "    - ul3",
             "      ul3.1",
             "      ul3.2",
+            "",
             "end",
             "",
         ]
@@ -349,32 +656,17 @@ mod tests_parse_smart_lists_in_markdown {
 
         let expected_output = [
             "start",
-            "[  ┊─┤ul1┊  ]",
-            "[  ┊───┤ul2┊ → ┊   │ul2.1┊  ]",
-            "[  ┊─────┤ul3┊ → ┊     │ul3.1┊ → ┊     │ul3.2┊  ]",
+            "[  ┊─┤ul1┊ → ┊───┤ul2┊ → ┊ │ │ul2.1┊ → ┊─────┤ul3┊ → ┊ │ │ │ul3.1┊ → ┊ │ │ │ul3.2┊  ]",
+            "",
             "end",
         ];
 
-        let result = parse_markdown(input.as_str());
-        let remainder = result.as_ref().unwrap().0;
-        let md_doc: MdDocument = result.unwrap().1;
-
-        // md_doc.console_log_fg();
-        // remainder.console_log_bg();
-
-        assert_eq2!(remainder, "");
-        md_doc.items.iter().zip(expected_output.iter()).for_each(
-            |(element, test_str)| {
-                let lhs = element.pretty_print_debug();
-                let rhs = test_str.to_string();
-                assert_eq2!(lhs, rhs);
-            },
-        );
+        assert_pretty_printed_blocks(&input, &expected_output);
     }
 
     #[test]
     fn test_parse_valid_md_multiline_no_indent() {
-        let input = vec![
+        let input = [
             "start",
             "- ul1",
             "- ul2",
@@ -391,6 +683,7 @@ mod tests_parse_smart_lists_in_markdown {
             "   ol3.2",
             "- [ ] todo",
             "- [x] done",
+            "",
             "end",
             "",
         ]
@@ -398,33 +691,16 @@ mod tests_parse_smart_lists_in_markdown {
 
         let expected_output = [
             "start",
-            "[  ┊─┤ul1┊  ]",
-            "[  ┊─┤ul2┊ → ┊ │ul2.1┊  ]",
+            "[  ┊─┤ul1┊ → ┊─┤ul2┊ → ┊ │ul2.1┊  ]",
             "  ",
             "[  ┊─┤ul3┊ → ┊ │ul3.1┊ → ┊ │ul3.2┊  ]",
-            "[  ┊1.│ol1┊  ]",
-            "[  ┊2.│ol2┊ → ┊  │ol2.1┊  ]",
-            "[  ┊3.│ol3┊ → ┊  │ol3.1┊ → ┊  │ol3.2┊  ]",
-            "[  ┊─┤[ ] todo┊  ]",
-            "[  ┊─┤[x] done┊  ]",
+            "[  ┊1.│ol1┊ → ┊2.│ol2┊ → ┊  │ol2.1┊ → ┊3.│ol3┊ → ┊  │ol3.1┊ → ┊  │ol3.2┊  ]",
+            "[  ┊─┤[ ] todo┊ → ┊─┤[x] done┊  ]",
+            "",
             "end",
         ];
 
-        let result = parse_markdown(input.as_str());
-        let remainder = result.as_ref().unwrap().0;
-        let md_doc: MdDocument = result.unwrap().1;
-
-        md_doc.console_log_fg();
-        remainder.console_log_bg();
-
-        assert_eq2!(remainder, "");
-        md_doc.items.iter().zip(expected_output.iter()).for_each(
-            |(element, test_str)| {
-                let lhs = element.pretty_print_debug();
-                let rhs = test_str.to_string();
-                assert_eq2!(lhs, rhs);
-            },
-        );
+        assert_pretty_printed_blocks(&input, &expected_output);
     }
 
     #[test]
@@ -437,6 +713,7 @@ mod tests_parse_smart_lists_in_markdown {
             "2. ol2",
             "- [ ] todo",
             "- [x] done",
+            "",
             "end",
             "",
         ]
@@ -444,30 +721,32 @@ mod tests_parse_smart_lists_in_markdown {
 
         let expected_output = [
             "start",
-            "[  ┊─┤ul1┊  ]",
-            "[  ┊─┤ul2┊  ]",
-            "[  ┊1.│ol1┊  ]",
-            "[  ┊2.│ol2┊  ]",
-            "[  ┊─┤[ ] todo┊  ]",
-            "[  ┊─┤[x] done┊  ]",
+            "[  ┊─┤ul1┊ → ┊─┤ul2┊  ]",
+            "[  ┊1.│ol1┊ → ┊2.│ol2┊  ]",
+            "[  ┊─┤[ ] todo┊ → ┊─┤[x] done┊  ]",
+            "",
             "end",
         ];
 
-        let result = parse_markdown(input.as_str());
-        let remainder = result.as_ref().unwrap().0;
-        let md_doc: MdDocument = result.unwrap().1;
+        assert_pretty_printed_blocks(&input, &expected_output);
+    }
 
-        // md_doc.console_log_fg();
-        // remainder.console_log_bg();
+    #[test]
+    fn test_parse_md_list_w_code_block_then_unindented_line() {
+        // "b" follows a code fence, not a line of text, so it is not a lazy continuation line.
+        let input = ["- a", "  ```rs", "  let a = 1;", "  ```", "b", ""].join("\n");
 
-        assert_eq2!(remainder, "");
-        md_doc.items.iter().zip(expected_output.iter()).for_each(
-            |(element, test_str)| {
-                let lhs = element.pretty_print_debug();
-                let rhs = test_str.to_string();
-                assert_eq2!(lhs, rhs);
-            },
-        );
+        let expected_output =
+            ["[  ┊─┤a┊ → ┊ │code block, line count: 3, lang: rs┊  ]", "b"];
+
+        assert_pretty_printed_blocks(&input, &expected_output);
+    }
+
+    #[test]
+    fn test_parse_md_list_wo_trailing_new_line() {
+        assert_pretty_printed_blocks("- a", &["[  ┊─┤a┊  ]"]);
+        assert_pretty_printed_blocks("1. ", &["[  ┊1.│┊  ]"]);
+        assert_pretty_printed_blocks("# h\n- [ ] task", &["# h", "[  ┊─┤[ ] task┊  ]"]);
     }
 }
 
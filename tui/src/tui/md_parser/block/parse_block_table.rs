@@ -0,0 +1,271 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{bytes::complete::*,
+          combinator::*,
+          error::{Error, ErrorKind},
+          multi::*,
+          sequence::*,
+          Err as NomErr,
+          IResult};
+
+use crate::*;
+
+/// Sample inputs:
+/// Header & body:           "| a | b |\n|:---|---:|\n| 1 | 2 |\n"
+/// Header only, no EOL:     "| a | b |\n|---|---|"
+///
+/// Each row must start & end w/ a `|` (a `|` inside a cell must be escaped as `\|`), and
+/// the delimiter row must have as many cells as the header row. The table ends at the
+/// first line that isn't a row.
+pub fn parse_block_table(input: &str) -> IResult<&str, TableData> {
+    let (remainder, header_row) = parse_table_row(input)?;
+    let (remainder, (delimiter_row, alignments)) = parse_table_delimiter_row(remainder)?;
+    if alignments.len() != header_row.len() {
+        return Err(NomErr::Error(Error::new(input, ErrorKind::Verify)));
+    }
+    let (remainder, body_rows) = many0(parse_table_row)(remainder)?;
+
+    Ok((
+        remainder,
+        TableData {
+            header_row,
+            delimiter_row,
+            alignments,
+            body_rows: List::from(body_rows),
+        },
+    ))
+}
+
+/// Splits a row (eg: `| a | b \| c |`) into the text of its cells (eg: `" a "` and
+/// `" b \| c "`). Returns [None] if the row doesn't start & end w/ a `|`.
+pub fn split_table_row(line: &str) -> Option<Vec<&str>> {
+    let inner = line.strip_prefix(TABLE_SEPARATOR)?;
+
+    let mut cells = vec![];
+    let mut cell_start = 0;
+    let mut is_escaped = false;
+    for (index, it) in inner.char_indices() {
+        if it == TABLE_SEPARATOR_CHAR && !is_escaped {
+            cells.push(&inner[cell_start..index]);
+            cell_start = index + 1;
+        }
+        is_escaped = it == TABLE_ESCAPE_CHAR && !is_escaped;
+    }
+
+    // Nothing can follow the last `|`.
+    if cells.is_empty() || cell_start != inner.len() {
+        return None;
+    }
+    Some(cells)
+}
+
+/// Sample inputs: `---`, ` :--- `, `:---:`, `---:`.
+pub fn parse_table_column_alignment(cell: &str) -> Option<TableColumnAlignment> {
+    let text = cell.trim();
+    let (is_left, text) = match text.strip_prefix(TABLE_ALIGNMENT_CHAR) {
+        Some(it) => (true, it),
+        None => (false, text),
+    };
+    let (is_right, text) = match text.strip_suffix(TABLE_ALIGNMENT_CHAR) {
+        Some(it) => (true, it),
+        None => (false, text),
+    };
+
+    if text.is_empty() || !text.chars().all(|it| it == TABLE_DELIMITER_CHAR) {
+        return None;
+    }
+
+    Some(match (is_left, is_right) {
+        (false, false) => TableColumnAlignment::None,
+        (true, false) => TableColumnAlignment::Left,
+        (true, true) => TableColumnAlignment::Center,
+        (false, true) => TableColumnAlignment::Right,
+    })
+}
+
+/// Matches a line that can be split into cells, and outputs it (w/out the EOL).
+#[rustfmt::skip]
+fn parse_table_line(input: &str) -> IResult<&str, &str> {
+    terminated(
+        /* output */
+        verify(
+            take_till(|it| it == '\n'),
+            |it: &str| split_table_row(it).is_some()
+        ),
+        /* ends with (discarded) */ opt(tag(NEW_LINE)),
+    )(input)
+}
+
+fn parse_table_row(input: &str) -> IResult<&str, TableRow> {
+    map(parse_table_line, |line| {
+        let cells = split_table_row(line)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|text| TableCell {
+                text,
+                fragments: parse_block_markdown_text_all(text.trim()),
+            })
+            .collect::<Vec<_>>();
+        List::from(cells)
+    })(input)
+}
+
+fn parse_table_delimiter_row(
+    input: &str,
+) -> IResult<&str, (&str, List<TableColumnAlignment>)> {
+    map_opt(parse_table_line, |line| {
+        let alignments = split_table_row(line)?
+            .into_iter()
+            .map(parse_table_column_alignment)
+            .collect::<Option<Vec<_>>>()?;
+        Some((line, List::from(alignments)))
+    })(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_split_table_row() {
+        assert_eq2!(split_table_row("| a | b |"), Some(vec![" a ", " b "]));
+        assert_eq2!(split_table_row("|a|"), Some(vec!["a"]));
+        assert_eq2!(split_table_row("||"), Some(vec![""]));
+        assert_eq2!(
+            split_table_row(r"| a \| b | c |"),
+            Some(vec![r" a \| b ", " c "])
+        );
+        assert_eq2!(split_table_row("| a | b"), None);
+        assert_eq2!(split_table_row("a | b |"), None);
+        assert_eq2!(split_table_row(r"| a \|"), None);
+        assert_eq2!(split_table_row("|"), None);
+        assert_eq2!(split_table_row(""), None);
+    }
+
+    #[test]
+    fn test_parse_table_column_alignment() {
+        assert_eq2!(
+            parse_table_column_alignment("---"),
+            Some(TableColumnAlignment::None)
+        );
+        assert_eq2!(
+            parse_table_column_alignment(" :--- "),
+            Some(TableColumnAlignment::Left)
+        );
+        assert_eq2!(
+            parse_table_column_alignment(":-:"),
+            Some(TableColumnAlignment::Center)
+        );
+        assert_eq2!(
+            parse_table_column_alignment("-:"),
+            Some(TableColumnAlignment::Right)
+        );
+        for input in ["", ":", "::", " ", "-a-", ":-: :"] {
+            assert_eq2!(parse_table_column_alignment(input), None);
+        }
+    }
+
+    #[test]
+    fn test_parse_block_table() {
+        assert_eq2!(
+            parse_block_table("| a | *b* |\n|:---|---:|\n| 1 | `2` |\n|3||\nfoo\n"),
+            Ok((
+                "foo\n",
+                TableData {
+                    header_row: list![
+                        TableCell {
+                            text: " a ",
+                            fragments: list![MdLineFragment::Plain("a")],
+                        },
+                        TableCell {
+                            text: " *b* ",
+                            fragments: list![MdLineFragment::Italic("b")],
+                        },
+                    ],
+                    delimiter_row: "|:---|---:|",
+                    alignments: list![
+                        TableColumnAlignment::Left,
+                        TableColumnAlignment::Right
+                    ],
+                    body_rows: list![
+                        list![
+                            TableCell {
+                                text: " 1 ",
+                                fragments: list![MdLineFragment::Plain("1")],
+                            },
+                            TableCell {
+                                text: " `2` ",
+                                fragments: list![MdLineFragment::InlineCode("2")],
+                            },
+                        ],
+                        list![
+                            TableCell {
+                                text: "3",
+                                fragments: list![MdLineFragment::Plain("3")],
+                            },
+                            TableCell {
+                                text: "",
+                                fragments: list![],
+                            },
+                        ],
+                    ],
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_block_table_header_only() {
+        assert_eq2!(
+            parse_block_table("|a|\n|-|"),
+            Ok((
+                "",
+                TableData {
+                    header_row: list![TableCell {
+                        text: "a",
+                        fragments: list![MdLineFragment::Plain("a")],
+                    }],
+                    delimiter_row: "|-|",
+                    alignments: list![TableColumnAlignment::None],
+                    body_rows: list![],
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_block_table_invalid() {
+        // No delimiter row.
+        assert!(parse_block_table("| a | b |\n| 1 | 2 |\n").is_err());
+
+        // The delimiter row has fewer cells than the header row.
+        assert_eq2!(
+            parse_block_table("| a | b |\n|---|\n"),
+            Err(NomErr::Error(Error {
+                input: "| a | b |\n|---|\n",
+                code: ErrorKind::Verify
+            }))
+        );
+
+        // The header row doesn't end w/ a `|`.
+        assert!(parse_block_table("| a | b\n|---|---|\n").is_err());
+    }
+}
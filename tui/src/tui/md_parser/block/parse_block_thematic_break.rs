@@ -0,0 +1,87 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{bytes::complete::*, combinator::*, sequence::*, IResult};
+
+use crate::*;
+
+/// Sample inputs:
+/// Dashes:                  "---\n"
+/// Asterisks w/ spaces:     "* * *\n"
+/// Underscores, no EOL:     "_____"
+///
+/// The line must only contain (at least [MIN_THEMATIC_BREAK_CHAR_COUNT] of) one of the
+/// [THEMATIC_BREAK_CHARS], and spaces. Outputs the line (w/out the EOL).
+#[rustfmt::skip]
+pub fn parse_block_thematic_break(input: &str) -> IResult<&str, &str> {
+    terminated(
+        /* output */ verify(take_till(|it| it == '\n'), is_thematic_break),
+        /* ends with (discarded) */ opt(tag(NEW_LINE)),
+    )(input)
+}
+
+fn is_thematic_break(line: &str) -> bool {
+    let indent = line.len() - line.trim_start_matches(SPACE_CHAR).len();
+    if indent > MAX_THEMATIC_BREAK_INDENT {
+        return false;
+    }
+
+    let mut chars = line.chars().filter(|it| *it != SPACE_CHAR);
+    let Some(first_char) = chars.next() else {
+        return false;
+    };
+    if !THEMATIC_BREAK_CHARS.contains(&first_char) {
+        return false;
+    }
+
+    let mut count = 1;
+    for it in chars {
+        if it != first_char {
+            return false;
+        }
+        count += 1;
+    }
+    count >= MIN_THEMATIC_BREAK_CHAR_COUNT
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{error::{Error, ErrorKind},
+              Err as NomErr};
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_block_thematic_break() {
+        assert_eq2!(parse_block_thematic_break("---\nfoo"), Ok(("foo", "---")));
+        assert_eq2!(parse_block_thematic_break("* * *\n"), Ok(("", "* * *")));
+        assert_eq2!(parse_block_thematic_break("_____"), Ok(("", "_____")));
+        assert_eq2!(parse_block_thematic_break("   ***\n"), Ok(("", "   ***")));
+
+        for input in ["--\n", "-*-\n", "--- foo\n", "    ---\n", "\n", ""] {
+            assert_eq2!(
+                parse_block_thematic_break(input),
+                Err(NomErr::Error(Error {
+                    input,
+                    code: ErrorKind::Verify
+                }))
+            );
+        }
+    }
+}
@@ -0,0 +1,708 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module is responsible for converting a [MdDocument] into word wrapped text w/ ANSI
+//! escape sequences (and w/out the Markdown syntax), eg: to print it to a terminal that
+//! isn't in raw mode, or to pipe it to a pager. The styles are the same ones that are used
+//! to highlight Markdown in the editor component.
+
+use crossterm::style::{Attribute, Stylize};
+use r3bl_ansi_color::{global_color_support, ColorSupport};
+use r3bl_rs_utils_core::*;
+
+use crate::{constants::*, *};
+
+/// Lines are never wrapped to fewer display cols than this.
+pub const MIN_ANSI_TEXT_DISPLAY_WIDTH: usize = 10;
+
+/// Converts the document into lines that fit in `max_display_width` cols (except for code
+/// blocks & tables, which are never wrapped), joined w/ `\n`. If the terminal doesn't
+/// support colors, the output is plain text.
+pub fn convert_to_ansi_text(document: &MdDocument, max_display_width: usize) -> String {
+    convert_to_style_us_span_lines(document, max_display_width)
+        .iter()
+        .map(convert_line_to_ansi_text)
+        .collect::<Vec<_>>()
+        .join(NEW_LINE)
+}
+
+/// Same as [fn@convert_to_ansi_text], but the lines are not converted into text.
+/// - Consecutive lines of text are joined into one paragraph before they are wrapped.
+/// - Each item of a smart list is wrapped separately, and its bullet is repeated on each
+///   line (as the continuation bullet).
+pub fn convert_to_style_us_span_lines(
+    document: &MdDocument,
+    max_display_width: usize,
+) -> StyleUSSpanLines {
+    let references = MdReferences::from_document(document);
+    convert_blocks(
+        document,
+        max_display_width.max(MIN_ANSI_TEXT_DISPLAY_WIDTH),
+        &references,
+    )
+}
+
+/// The spans that link to a URL (eg: links in the document) are wrapped in [OSC 8] escape
+/// sequences, so that they can be clicked in the terminals that support them.
+///
+/// [OSC 8]: https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda
+pub fn convert_line_to_ansi_text(line: &StyleUSSpanLine) -> String {
+    if let ColorSupport::NoColor = global_color_support::detect() {
+        return line.get_plain_text();
+    }
+
+    let mut acc = String::new();
+    for StyleUSSpan {
+        style,
+        text,
+        maybe_hyperlink_url,
+    } in line.iter()
+    {
+        let mut it = text.string.as_str().stylize();
+        if let Some(color) = style.color_fg {
+            it = it.with(to_crossterm_color(color));
+        }
+        if let Some(color) = style.color_bg {
+            it = it.on(to_crossterm_color(color));
+        }
+        for (is_set, attribute) in [
+            (style.bold, Attribute::Bold),
+            (style.italic, Attribute::Italic),
+            (style.dim, Attribute::Dim),
+            (style.underline, Attribute::Underlined),
+            (style.reverse, Attribute::Reverse),
+            (style.hidden, Attribute::Hidden),
+            (style.strikethrough, Attribute::CrossedOut),
+        ] {
+            if is_set {
+                it = it.attribute(attribute);
+            }
+        }
+        match maybe_hyperlink_url {
+            Some(url) => acc.push_str(&format!(
+                "{OSC_8_START}{url}{OSC_8_END}{it}{OSC_8_START}{OSC_8_END}"
+            )),
+            None => acc.push_str(&it.to_string()),
+        }
+    }
+    acc
+}
+
+fn convert_blocks(
+    blocks: &[MdBlockElement],
+    max_display_width: usize,
+    references: &MdReferences,
+) -> StyleUSSpanLines {
+    let mut acc = StyleUSSpanLines::default();
+    let mut paragraph = StyleUSSpanLine::default();
+    let no_prefix = StyleUSSpanLine::default();
+
+    for block in blocks {
+        if let MdBlockElement::Text(fragments) = block {
+            if !fragments.is_empty() {
+                if !paragraph.is_empty() {
+                    paragraph +=
+                        StyleUSSpan::new(get_foreground_style(), US::from(SPACE));
+                }
+                paragraph += convert_fragments(fragments, references);
+                continue;
+            }
+        }
+        if !paragraph.is_empty() {
+            acc += wrap_line(&paragraph, max_display_width, &no_prefix, &no_prefix);
+            paragraph = StyleUSSpanLine::default();
+        }
+        acc += convert_block(block, max_display_width, references);
+    }
+
+    if !paragraph.is_empty() {
+        acc += wrap_line(&paragraph, max_display_width, &no_prefix, &no_prefix);
+    }
+    acc
+}
+
+fn convert_block(
+    block: &MdBlockElement,
+    max_display_width: usize,
+    references: &MdReferences,
+) -> StyleUSSpanLines {
+    let no_prefix = StyleUSSpanLine::default();
+
+    match block {
+        MdBlockElement::Title(title) => wrap_line(
+            &StyleUSSpanLine::from_kvp(TITLE, title, &None),
+            max_display_width,
+            &no_prefix,
+            &no_prefix,
+        ),
+        MdBlockElement::Date(date) => wrap_line(
+            &StyleUSSpanLine::from_kvp(DATE, date, &None),
+            max_display_width,
+            &no_prefix,
+            &no_prefix,
+        ),
+        MdBlockElement::Tags(tags) => wrap_line(
+            &StyleUSSpanLine::from_csvp(TAGS, tags, &None),
+            max_display_width,
+            &no_prefix,
+            &no_prefix,
+        ),
+        MdBlockElement::Authors(authors) => wrap_line(
+            &StyleUSSpanLine::from_csvp(AUTHORS, authors, &None),
+            max_display_width,
+            &no_prefix,
+            &no_prefix,
+        ),
+        MdBlockElement::Heading(heading_data) => {
+            // Leave out the `#` prefix.
+            let mut line = StyleUSSpanLine::from_heading_data(heading_data, &None);
+            line.items.remove(0);
+            wrap_line(&line, max_display_width, &no_prefix, &no_prefix)
+        }
+        MdBlockElement::Text(fragments) => wrap_line(
+            &convert_fragments(fragments, references),
+            max_display_width,
+            &no_prefix,
+            &no_prefix,
+        ),
+        MdBlockElement::SmartList(list_data) => {
+            let mut acc = StyleUSSpanLines::default();
+            for it in list_data.flatten() {
+                let first_line_prefix = convert_list_prefix(&it.first_line_prefix);
+                let rest_line_prefix = convert_list_prefix(&it.rest_line_prefix);
+                match it.block {
+                    MdBlockElement::Text(fragments) if fragments.is_empty() => {
+                        acc += StyleUSSpanLine::default();
+                    }
+                    MdBlockElement::Text(fragments) => {
+                        acc += wrap_line(
+                            &convert_fragments(fragments, references),
+                            max_display_width,
+                            &first_line_prefix,
+                            &rest_line_prefix,
+                        );
+                    }
+                    block => {
+                        let prefix_width =
+                            ch!(@to_usize first_line_prefix.display_width());
+                        for (index, line) in convert_block(
+                            block,
+                            max_display_width.saturating_sub(prefix_width),
+                            references,
+                        )
+                        .items
+                        .into_iter()
+                        .enumerate()
+                        {
+                            let mut acc_line = if index == 0 {
+                                first_line_prefix.clone()
+                            } else {
+                                rest_line_prefix.clone()
+                            };
+                            acc_line += line;
+                            acc += acc_line;
+                        }
+                    }
+                }
+            }
+            acc
+        }
+        MdBlockElement::CodeBlock(code_block_lines) => {
+            let mut acc = StyleUSSpanLines::default();
+            for code_block_line in code_block_lines.iter() {
+                if let CodeBlockLineContent::Text(text) = code_block_line.content {
+                    acc += list![StyleUSSpan::new(
+                        get_code_block_content_style(),
+                        US::from(text)
+                    )];
+                }
+            }
+            acc
+        }
+        MdBlockElement::BlockQuote(blocks) => {
+            let prefix = list![StyleUSSpan::new(
+                get_block_quote_marker_style(),
+                US::from(BLOCK_QUOTE_DISPLAY)
+            )];
+            let prefix_width = ch!(@to_usize prefix.display_width());
+            let mut acc = StyleUSSpanLines::default();
+            for line in convert_blocks(
+                blocks,
+                max_display_width.saturating_sub(prefix_width),
+                references,
+            )
+            .items
+            {
+                let mut acc_line = prefix.clone();
+                acc_line += line;
+                acc += acc_line;
+            }
+            acc
+        }
+        MdBlockElement::ThematicBreak(_) => list![list![StyleUSSpan::new(
+            get_thematic_break_style(),
+            US::from(THEMATIC_BREAK_DISPLAY.repeat(max_display_width))
+        )]],
+        MdBlockElement::Table(table_data) => convert_table(table_data, references),
+        // The links are resolved (please take a look at [convert_fragments]), so the
+        // definitions are left out.
+        MdBlockElement::LinkDefinition(_) => StyleUSSpanLines::default(),
+        MdBlockElement::FootnoteDefinition(FootnoteDefinitionData {
+            label,
+            fragments,
+        }) => {
+            let first_line_prefix = list![
+                convert_footnote_reference(label, references),
+                StyleUSSpan::new(get_foreground_style(), US::from(SPACE))
+            ];
+            let rest_line_prefix = list![StyleUSSpan::new(
+                get_foreground_style(),
+                US::from(SPACE.repeat(ch!(@to_usize first_line_prefix.display_width())))
+            )];
+            wrap_line(
+                &convert_fragments(fragments, references),
+                max_display_width,
+                &first_line_prefix,
+                &rest_line_prefix,
+            )
+        }
+        // Same as the metadata blocks above, the rest of the keys are left out.
+        MdBlockElement::FrontMatter(front_matter) => {
+            let mut acc = StyleUSSpanLines::default();
+            let mut lines = vec![];
+            lines.extend(
+                front_matter
+                    .get_title()
+                    .map(|it| StyleUSSpanLine::from_kvp(TITLE, it, &None)),
+            );
+            lines.extend(
+                front_matter
+                    .get_tags()
+                    .map(|it| StyleUSSpanLine::from_csvp(TAGS, &it, &None)),
+            );
+            lines.extend(
+                front_matter
+                    .get_authors()
+                    .map(|it| StyleUSSpanLine::from_csvp(AUTHORS, &it, &None)),
+            );
+            lines.extend(
+                front_matter
+                    .get_date()
+                    .map(|it| StyleUSSpanLine::from_kvp(DATE, it, &None)),
+            );
+            for line in lines {
+                acc += wrap_line(&line, max_display_width, &no_prefix, &no_prefix);
+            }
+            acc
+        }
+    }
+}
+
+/// The fragments w/out the Markdown syntax. The links are resolved against the
+/// `references`, and their text links to their URL.
+fn convert_fragments(
+    fragments: &[MdLineFragment],
+    references: &MdReferences,
+) -> StyleUSSpanLine {
+    let mut acc = StyleUSSpanLine::default();
+    for fragment in fragments {
+        let span = |style: Style, text: &str| StyleUSSpan::new(style, US::from(text));
+        match fragment {
+            MdLineFragment::Plain(text) => acc += span(get_foreground_style(), text),
+            MdLineFragment::Bold(text) => acc += span(get_bold_style(), text),
+            MdLineFragment::Italic(text) => acc += span(get_italic_style(), text),
+            MdLineFragment::BoldItalic(text) => {
+                acc += span(get_bold_italic_style(), text)
+            }
+            MdLineFragment::Strikethrough(text) => {
+                acc += span(get_strikethrough_style(), text)
+            }
+            MdLineFragment::InlineCode(text) => {
+                acc += span(get_inline_code_style(), text)
+            }
+            MdLineFragment::Link(HyperlinkData { text, url })
+            | MdLineFragment::Image(HyperlinkData { text, url }) => {
+                acc += convert_link(text, url);
+            }
+            MdLineFragment::ReferenceLink(link_data) => {
+                match references.get_reference_link_url(link_data) {
+                    Some(url) => acc += convert_link(link_data.text, url),
+                    None => acc += span(get_foreground_style(), link_data.text),
+                }
+            }
+            MdLineFragment::Autolink(url) => {
+                acc += span(get_link_url_style(), url).with_hyperlink_url(url)
+            }
+            MdLineFragment::FootnoteReference(label) => {
+                acc += convert_footnote_reference(label, references)
+            }
+            MdLineFragment::Checkbox(_) => {
+                acc += StyleUSSpan::from_fragment(fragment, &None);
+            }
+        }
+    }
+    acc
+}
+
+fn convert_link(text: &str, url: &str) -> StyleUSSpanLine {
+    list![
+        StyleUSSpan::new(get_link_text_style(), US::from(text)).with_hyperlink_url(url),
+        StyleUSSpan::new(get_foreground_style(), US::from(SPACE)),
+        StyleUSSpan::new(
+            get_link_url_style(),
+            US::from(format!("{LEFT_PARENTHESIS}{url}{RIGHT_PARENTHESIS}")),
+        )
+        .with_hyperlink_url(url),
+    ]
+}
+
+/// The footnotes are displayed as their number, eg: `[1]`, or as their label if they aren't
+/// defined.
+fn convert_footnote_reference(label: &str, references: &MdReferences) -> StyleUSSpan {
+    let text = match references.get_footnote_number(label) {
+        Some(number) => format!("{LEFT_BRACKET}{number}{RIGHT_BRACKET}"),
+        None => format!("{FOOTNOTE_PREFIX}{label}{RIGHT_BRACKET}"),
+    };
+    StyleUSSpan::new(get_link_text_style(), US::from(text))
+}
+
+/// The prefix (bullet or guides) of a line in a smart list is followed by a space, so that
+/// it isn't right next to the text.
+fn convert_list_prefix(prefix: &str) -> StyleUSSpanLine {
+    list![
+        StyleUSSpan::new(get_list_bullet_style(), US::from(prefix)),
+        StyleUSSpan::new(get_foreground_style(), US::from(SPACE)),
+    ]
+}
+
+/// Each cell is padded to the display width of the widest cell in its column.
+fn convert_table(table_data: &TableData, references: &MdReferences) -> StyleUSSpanLines {
+    let header_row = table_data
+        .header_row
+        .iter()
+        .map(|cell| {
+            let mut it = convert_fragments(&cell.fragments, references);
+            it.add_style(get_table_header_style());
+            it
+        })
+        .collect::<Vec<_>>();
+    let body_rows = table_data
+        .body_rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| convert_fragments(&cell.fragments, references))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let column_count = table_data.alignments.len();
+    let mut column_widths = vec![0; column_count];
+    for row in std::iter::once(&header_row).chain(body_rows.iter()) {
+        for (column_index, cell) in row.iter().enumerate().take(column_count) {
+            column_widths[column_index] =
+                column_widths[column_index].max(ch!(@to_usize cell.display_width()));
+        }
+    }
+
+    let separator = StyleUSSpan::new(
+        get_table_separator_style(),
+        US::from(TABLE_SEPARATOR_DISPLAY),
+    );
+    let convert_row = |row: &[StyleUSSpanLine]| -> StyleUSSpanLine {
+        let mut acc = StyleUSSpanLine::default();
+        for (column_index, width) in column_widths.iter().enumerate() {
+            if column_index > 0 {
+                acc += separator.clone();
+            }
+            let cell = row.get(column_index).cloned().unwrap_or_default();
+            let padding = width - ch!(@to_usize cell.display_width());
+            let (padding_left, padding_right) = match table_data.alignments[column_index]
+            {
+                TableColumnAlignment::None | TableColumnAlignment::Left => (0, padding),
+                TableColumnAlignment::Center => (padding / 2, padding - padding / 2),
+                TableColumnAlignment::Right => (padding, 0),
+            };
+            if padding_left > 0 {
+                acc += StyleUSSpan::new(
+                    get_foreground_style(),
+                    US::from(SPACE.repeat(padding_left)),
+                );
+            }
+            acc += cell;
+            if padding_right > 0 {
+                acc += StyleUSSpan::new(
+                    get_foreground_style(),
+                    US::from(SPACE.repeat(padding_right)),
+                );
+            }
+        }
+        acc
+    };
+
+    let mut acc = StyleUSSpanLines::default();
+    acc += convert_row(&header_row);
+    acc += list![StyleUSSpan::new(
+        get_table_separator_style(),
+        US::from(
+            column_widths
+                .iter()
+                .map(|width| TABLE_DELIMITER_DISPLAY.repeat(*width))
+                .collect::<Vec<_>>()
+                .join(TABLE_DELIMITER_SEPARATOR_DISPLAY)
+        )
+    )];
+    for row in body_rows.iter() {
+        acc += convert_row(row);
+    }
+    acc
+}
+
+/// A word is made up of the spans (or parts of spans) between two spaces. It also has the
+/// style of the space before it (if any).
+struct Word {
+    maybe_space_style: Option<Style>,
+    line: StyleUSSpanLine,
+}
+
+fn split_into_words(line: &StyleUSSpanLine) -> Vec<Word> {
+    let mut acc = vec![];
+    let mut maybe_space_style = None;
+    let mut current_word = StyleUSSpanLine::default();
+
+    for span in line.iter() {
+        let text = span.text.string.as_str();
+        let mut chunk_start = 0;
+        for (index, it) in text.char_indices() {
+            if !it.is_whitespace() {
+                continue;
+            }
+            if chunk_start < index {
+                current_word += get_sub_span(span, &text[chunk_start..index]);
+            }
+            if !current_word.is_empty() {
+                acc.push(Word {
+                    maybe_space_style,
+                    line: std::mem::take(&mut current_word),
+                });
+            }
+            maybe_space_style = Some(span.style);
+            chunk_start = index + it.len_utf8();
+        }
+        if chunk_start < text.len() {
+            current_word += get_sub_span(span, &text[chunk_start..]);
+        }
+    }
+
+    if !current_word.is_empty() {
+        acc.push(Word {
+            maybe_space_style,
+            line: current_word,
+        });
+    }
+    acc
+}
+
+/// Same as the span, but w/ only a part of its text.
+fn get_sub_span(span: &StyleUSSpan, text: &str) -> StyleUSSpan {
+    StyleUSSpan {
+        style: span.style,
+        text: US::from(text),
+        maybe_hyperlink_url: span.maybe_hyperlink_url.clone(),
+    }
+}
+
+/// Consecutive spaces are collapsed into one, and the spaces at the start & end of the
+/// line are removed. A word that doesn't fit on a line by itself is split up.
+fn wrap_line(
+    line: &StyleUSSpanLine,
+    max_display_width: usize,
+    first_line_prefix: &StyleUSSpanLine,
+    rest_line_prefix: &StyleUSSpanLine,
+) -> StyleUSSpanLines {
+    let get_available_width = |prefix: &StyleUSSpanLine| {
+        max_display_width
+            .saturating_sub(ch!(@to_usize prefix.display_width()))
+            .max(1)
+    };
+
+    let mut acc = StyleUSSpanLines::default();
+    let mut acc_line = first_line_prefix.clone();
+    let mut available_width = get_available_width(first_line_prefix);
+    let mut used_width = 0;
+
+    let mut start_new_line = |acc_line: &mut StyleUSSpanLine| {
+        acc += std::mem::replace(acc_line, rest_line_prefix.clone());
+    };
+
+    for Word {
+        maybe_space_style,
+        line: word,
+    } in split_into_words(line)
+    {
+        let word_width = ch!(@to_usize word.display_width());
+        let space_width = usize::from(used_width > 0 && maybe_space_style.is_some());
+
+        if used_width > 0 && used_width + space_width + word_width > available_width {
+            start_new_line(&mut acc_line);
+            available_width = get_available_width(rest_line_prefix);
+            used_width = 0;
+        } else if let Some(style) = maybe_space_style.filter(|_| space_width > 0) {
+            acc_line += StyleUSSpan::new(style, US::from(SPACE));
+            used_width += space_width;
+        }
+
+        if used_width + word_width <= available_width {
+            acc_line += word;
+            used_width += word_width;
+            continue;
+        }
+
+        // Split up the word that is too wide, one grapheme cluster at a time.
+        for span in word.iter() {
+            for segment in span.text.vec_segment.iter() {
+                let segment_width = ch!(@to_usize segment.unicode_width);
+                if used_width > 0 && used_width + segment_width > available_width {
+                    start_new_line(&mut acc_line);
+                    available_width = get_available_width(rest_line_prefix);
+                    used_width = 0;
+                }
+                acc_line += get_sub_span(span, &segment.string);
+                used_width += segment_width;
+            }
+        }
+    }
+
+    acc += acc_line;
+    acc
+}
+
+#[cfg(test)]
+mod to_ansi_text_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    fn get_plain_text_lines(input: &str, max_display_width: usize) -> Vec<String> {
+        let (_, document) = parse_markdown(input).unwrap();
+        convert_to_style_us_span_lines(&document, max_display_width)
+            .iter()
+            .map(|line| line.get_plain_text())
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_paragraph() {
+        let input = "Some **bold** text that\nis *long* enough to wrap.\n\nNext.\n";
+        assert_eq2!(
+            get_plain_text_lines(input, 15),
+            vec![
+                "Some bold text",
+                "that is long",
+                "enough to wrap.",
+                "",
+                "Next.",
+            ]
+        );
+
+        // A word that is wider than the line is split up.
+        assert_eq2!(
+            get_plain_text_lines("0123456789abcdef ok\n", 10),
+            vec!["0123456789", "abcdef ok"]
+        );
+    }
+
+    #[test]
+    fn test_styles() {
+        let (_, document) = parse_markdown("a **b** `c`\n").unwrap();
+        let lines = convert_to_style_us_span_lines(&document, 80);
+        assert_eq2!(lines.len(), 1);
+        assert_eq2!(
+            lines[0].items,
+            vec![
+                StyleUSSpan::new(get_foreground_style(), US::from("a")),
+                StyleUSSpan::new(get_foreground_style(), US::from(" ")),
+                StyleUSSpan::new(get_bold_style(), US::from("b")),
+                StyleUSSpan::new(get_foreground_style(), US::from(" ")),
+                StyleUSSpan::new(get_inline_code_style(), US::from("c")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_quote_and_table() {
+        let input = [
+            "- one two three four",
+            "> quoted text here",
+            "| a | bb |",
+            "|---|:-:|",
+            "| ccc | d |",
+            "",
+        ]
+        .join("\n");
+        assert_eq2!(
+            get_plain_text_lines(&input, 12),
+            vec![
+                "─┤ one two",
+                " │ three",
+                " │ four",
+                "│ quoted",
+                "│ text here",
+                "a   │ bb",
+                "────┼───",
+                "ccc │ d ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_convert_to_ansi_text() {
+        let (_, document) = parse_markdown("# Foo\nbar\n").unwrap();
+        let ansi_text = convert_to_ansi_text(&document, 80);
+        assert_eq2!(ansi_text.lines().count(), 2);
+        assert!(ansi_text.contains("bar"));
+    }
+
+    #[test]
+    fn test_links_and_footnotes() {
+        let input = [
+            "See [r3bl][], [^n] & https://r3bl.com.",
+            "[^n]: A note.",
+            "[R3BL]: https://r3bl.com",
+            "",
+        ]
+        .join("\n");
+        assert_eq2!(
+            get_plain_text_lines(&input, 80),
+            vec![
+                "See r3bl (https://r3bl.com), [1] & https://r3bl.com.",
+                "[1] A note.",
+            ]
+        );
+
+        // The links are wrapped in OSC 8 escape sequences.
+        let (_, document) = parse_markdown(&input).unwrap();
+        let lines = convert_to_style_us_span_lines(&document, 80);
+        let url = Some("https://r3bl.com".to_string());
+        assert_eq2!(lines[0][2].maybe_hyperlink_url, url);
+        assert_eq2!(lines[0].last().unwrap().maybe_hyperlink_url, None);
+        if !matches!(global_color_support::detect(), ColorSupport::NoColor) {
+            assert!(convert_line_to_ansi_text(&lines[0])
+                .contains(&format!("{OSC_8_START}https://r3bl.com{OSC_8_END}")));
+        }
+    }
+}
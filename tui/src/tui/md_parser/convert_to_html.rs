@@ -0,0 +1,523 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module is responsible for converting a [MdDocument] into a standalone HTML document.
+
+use crate::{constants::*, *};
+
+/// Converts the document into a standalone HTML document. The metadata (`@title`, `@tags`,
+/// `@authors` & `@date`, or the same keys in the front matter) is emitted as tags in the
+/// `<head>`, and all the other blocks are emitted in the `<body>`.
+pub fn convert_to_html(document: &MdDocument) -> String {
+    let mut head = vec![r#"<meta charset="utf-8">"#.to_string()];
+    for block in document.iter() {
+        match block {
+            MdBlockElement::Title(title) => head.push(get_title_html(title)),
+            MdBlockElement::Tags(tags) => head.push(get_tags_html(tags)),
+            MdBlockElement::Authors(authors) => head.push(get_authors_html(authors)),
+            MdBlockElement::Date(date) => head.push(get_date_html(date)),
+            MdBlockElement::FrontMatter(front_matter) => {
+                head.extend(front_matter.get_title().map(get_title_html));
+                head.extend(front_matter.get_tags().map(|it| get_tags_html(&it)));
+                head.extend(front_matter.get_authors().map(|it| get_authors_html(&it)));
+                head.extend(front_matter.get_date().map(get_date_html));
+            }
+            _ => {}
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n{}\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        head.join(NEW_LINE),
+        {
+            let references = MdReferences::from_document(document);
+            let mut body = convert_blocks_to_html(document, &references);
+            if !references.footnotes.is_empty() {
+                body.push_str(NEW_LINE);
+                body.push_str(&convert_footnotes_to_html(&references));
+            }
+            body
+        }
+    )
+}
+
+fn get_title_html(title: &str) -> String {
+    format!("<title>{}</title>", escape_html(title))
+}
+
+fn get_tags_html(tags: &[&str]) -> String {
+    format!(
+        r#"<meta name="keywords" content="{}">"#,
+        escape_html(&tags.join(", "))
+    )
+}
+
+fn get_authors_html(authors: &[&str]) -> String {
+    format!(
+        r#"<meta name="author" content="{}">"#,
+        escape_html(&authors.join(", "))
+    )
+}
+
+fn get_date_html(date: &str) -> String {
+    format!(r#"<meta name="date" content="{}">"#, escape_html(date))
+}
+
+/// Converts the blocks (except for the metadata) into HTML elements, w/out the
+/// surrounding document.
+/// - Consecutive lines of text are joined into one paragraph.
+/// - The lists that are nested in the items of a smart list are nested in their `<li>`.
+/// - The link & footnote definitions are left out, since the references to them are
+///   resolved against the `references` (please take a look at [convert_footnotes_to_html]).
+pub fn convert_blocks_to_html(
+    blocks: &[MdBlockElement],
+    references: &MdReferences,
+) -> String {
+    let mut acc = vec![];
+    let mut paragraph_lines = vec![];
+
+    for block in blocks {
+        if let MdBlockElement::Text(fragments) = block {
+            if !fragments.is_empty() {
+                paragraph_lines.push(convert_fragments_to_html(fragments, references));
+                continue;
+            }
+        }
+        if !paragraph_lines.is_empty() {
+            acc.push(format!("<p>{}</p>", paragraph_lines.join(NEW_LINE)));
+            paragraph_lines.clear();
+        }
+
+        match block {
+            MdBlockElement::Heading(heading_data) => {
+                let level = usize::from(heading_data.level).min(MAX_HEADING_LEVEL);
+                acc.push(format!(
+                    "<h{level}>{}</h{level}>",
+                    escape_html(heading_data.text)
+                ));
+            }
+            MdBlockElement::CodeBlock(code_block_lines) => {
+                let lang_attribute = code_block_lines
+                    .first()
+                    .and_then(|it| it.language)
+                    .map(|lang| format!(r#" class="language-{}""#, escape_html(lang)))
+                    .unwrap_or_default();
+                let code = code_block_lines
+                    .iter()
+                    .filter_map(|it| match it.content {
+                        CodeBlockLineContent::Text(text) => Some(escape_html(text)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(NEW_LINE);
+                acc.push(format!("<pre><code{lang_attribute}>{code}</code></pre>"));
+            }
+            MdBlockElement::BlockQuote(blocks) => {
+                acc.push(format!(
+                    "<blockquote>\n{}\n</blockquote>",
+                    convert_blocks_to_html(blocks, references)
+                ));
+            }
+            MdBlockElement::ThematicBreak(_) => acc.push("<hr>".to_string()),
+            MdBlockElement::Table(table_data) => {
+                acc.push(convert_table_to_html(table_data, references));
+            }
+            MdBlockElement::SmartList(list_data) => {
+                acc.push(convert_smart_list_to_html(list_data, references));
+            }
+            // Empty lines, and the metadata (which goes in the head).
+            MdBlockElement::Text(_)
+            | MdBlockElement::Title(_)
+            | MdBlockElement::Date(_)
+            | MdBlockElement::Tags(_)
+            | MdBlockElement::Authors(_)
+            | MdBlockElement::FrontMatter(_)
+            | MdBlockElement::LinkDefinition(_)
+            | MdBlockElement::FootnoteDefinition(_) => {}
+        }
+    }
+
+    if !paragraph_lines.is_empty() {
+        acc.push(format!("<p>{}</p>", paragraph_lines.join(NEW_LINE)));
+    }
+
+    acc.join(NEW_LINE)
+}
+
+/// The footnotes are emitted as an ordered list (in the order of their numbers), which the
+/// footnote references link to.
+pub fn convert_footnotes_to_html(references: &MdReferences) -> String {
+    let mut acc = vec![
+        r#"<section class="footnotes">"#.to_string(),
+        "<ol>".to_string(),
+    ];
+    for FootnoteDefinitionData { label, fragments } in references.footnotes.iter() {
+        acc.push(format!(
+            r#"<li id="fn-{}">{}</li>"#,
+            escape_html(label),
+            convert_fragments_to_html(fragments, references).trim_start()
+        ));
+    }
+    acc.push("</ol>".to_string());
+    acc.push("</section>".to_string());
+    acc.join(NEW_LINE)
+}
+
+/// The fragments of a line of text.
+pub fn convert_fragments_to_html(
+    fragments: &[MdLineFragment],
+    references: &MdReferences,
+) -> String {
+    fragments
+        .iter()
+        .map(|it| convert_fragment_to_html(it, references))
+        .collect()
+}
+
+/// The reference links & footnote references that aren't defined in the `references` are
+/// emitted as text.
+pub fn convert_fragment_to_html(
+    fragment: &MdLineFragment,
+    references: &MdReferences,
+) -> String {
+    match fragment {
+        MdLineFragment::Plain(text) => escape_html(text),
+        MdLineFragment::Bold(text) => format!("<strong>{}</strong>", escape_html(text)),
+        MdLineFragment::Italic(text) => format!("<em>{}</em>", escape_html(text)),
+        MdLineFragment::BoldItalic(text) => {
+            format!("<strong><em>{}</em></strong>", escape_html(text))
+        }
+        MdLineFragment::Strikethrough(text) => {
+            format!("<del>{}</del>", escape_html(text))
+        }
+        MdLineFragment::InlineCode(text) => format!("<code>{}</code>", escape_html(text)),
+        MdLineFragment::Link(HyperlinkData { text, url }) => format!(
+            r#"<a href="{}">{}</a>"#,
+            escape_html(url),
+            escape_html(text)
+        ),
+        MdLineFragment::Image(HyperlinkData { text, url }) => format!(
+            r#"<img src="{}" alt="{}">"#,
+            escape_html(url),
+            escape_html(text)
+        ),
+        MdLineFragment::Checkbox(is_checked) => {
+            if *is_checked {
+                r#"<input type="checkbox" disabled checked>"#.to_string()
+            } else {
+                r#"<input type="checkbox" disabled>"#.to_string()
+            }
+        }
+        MdLineFragment::ReferenceLink(link_data) => {
+            match references.get_reference_link_url(link_data) {
+                Some(url) => format!(
+                    r#"<a href="{}">{}</a>"#,
+                    escape_html(url),
+                    escape_html(link_data.text)
+                ),
+                None => escape_html(&convert_fragment_to_markdown(fragment)),
+            }
+        }
+        MdLineFragment::Autolink(url) => {
+            format!(r#"<a href="{}">{}</a>"#, escape_html(url), escape_html(url))
+        }
+        MdLineFragment::FootnoteReference(label) => {
+            match references.get_footnote_number(label) {
+                Some(number) => format!(
+                    r##"<sup><a href="#fn-{label}" id="fnref-{label}">{number}</a></sup>"##,
+                    label = escape_html(label)
+                ),
+                None => escape_html(&convert_fragment_to_markdown(fragment)),
+            }
+        }
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut acc = String::with_capacity(text.len());
+    for it in text.chars() {
+        match it {
+            '&' => acc.push_str("&amp;"),
+            '<' => acc.push_str("&lt;"),
+            '>' => acc.push_str("&gt;"),
+            '"' => acc.push_str("&quot;"),
+            '\'' => acc.push_str("&#39;"),
+            _ => acc.push(it),
+        }
+    }
+    acc
+}
+
+/// The lines of text at the start of an item go right in its `<li>`, and the rest of its
+/// blocks (eg: nested lists) go after them. The items w/ blank lines in them are "loose",
+/// so all their blocks are converted like the blocks of the document (eg: into paragraphs).
+fn convert_smart_list_to_html(
+    list_data: &SmartListData,
+    references: &MdReferences,
+) -> String {
+    let (tag, start_attribute) = match list_data.items.first().map(|it| &it.bullet_kind) {
+        Some(BulletKind::Ordered(number)) if *number != 1 => {
+            ("ol", format!(r#" start="{number}""#))
+        }
+        Some(BulletKind::Ordered(_)) => ("ol", String::new()),
+        _ => ("ul", String::new()),
+    };
+
+    let mut acc = vec![format!("<{tag}{start_attribute}>")];
+    for (index, item) in list_data.items.iter().enumerate() {
+        if index > 0 {
+            acc.push("</li>".to_string());
+        }
+
+        let is_loose = item.blocks.iter().any(
+            |it| matches!(it, MdBlockElement::Text(fragments) if fragments.is_empty()),
+        );
+        let text_line_count = if is_loose {
+            0
+        } else {
+            item.blocks
+                .iter()
+                .take_while(|it| matches!(it, MdBlockElement::Text(_)))
+                .count()
+        };
+        let (text_lines, rest_blocks) = item.blocks.split_at(text_line_count);
+
+        let content = text_lines
+            .iter()
+            .filter_map(|it| match it {
+                MdBlockElement::Text(fragments) => {
+                    Some(convert_fragments_to_html(fragments, references))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(NEW_LINE);
+        acc.push(format!("<li>{content}"));
+        if !rest_blocks.is_empty() {
+            acc.push(convert_blocks_to_html(rest_blocks, references));
+        }
+    }
+    acc.push(format!("</li></{tag}>"));
+
+    acc.join(NEW_LINE)
+}
+
+fn convert_table_to_html(table_data: &TableData, references: &MdReferences) -> String {
+    let convert_row = |row: &TableRow, cell_tag: &str| -> String {
+        let cells = row
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let style_attribute = match table_data
+                    .alignments
+                    .get(index)
+                    .copied()
+                    .unwrap_or_default()
+                {
+                    TableColumnAlignment::None => "",
+                    TableColumnAlignment::Left => r#" style="text-align: left""#,
+                    TableColumnAlignment::Center => r#" style="text-align: center""#,
+                    TableColumnAlignment::Right => r#" style="text-align: right""#,
+                };
+                format!(
+                    "<{cell_tag}{style_attribute}>{}</{cell_tag}>",
+                    convert_fragments_to_html(&cell.fragments, references)
+                )
+            })
+            .collect::<String>();
+        format!("<tr>{cells}</tr>")
+    };
+
+    let mut acc = vec!["<table>".to_string(), "<thead>".to_string()];
+    acc.push(convert_row(&table_data.header_row, "th"));
+    acc.push("</thead>".to_string());
+    if !table_data.body_rows.is_empty() {
+        acc.push("<tbody>".to_string());
+        for row in table_data.body_rows.iter() {
+            acc.push(convert_row(row, "td"));
+        }
+        acc.push("</tbody>".to_string());
+    }
+    acc.push("</table>".to_string());
+    acc.join(NEW_LINE)
+}
+
+#[cfg(test)]
+mod to_html_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_fragment_to_html() {
+        let references = MdReferences::default();
+        assert_eq2!(
+            convert_fragment_to_html(&MdLineFragment::Plain("a < b & c"), &references),
+            "a &lt; b &amp; c"
+        );
+        assert_eq2!(
+            convert_fragment_to_html(&MdLineFragment::BoldItalic("foo"), &references),
+            "<strong><em>foo</em></strong>"
+        );
+        assert_eq2!(
+            convert_fragment_to_html(
+                &MdLineFragment::Link(HyperlinkData::new(
+                    "r3bl",
+                    "https://r3bl.com?a=\"b\""
+                )),
+                &references
+            ),
+            r#"<a href="https://r3bl.com?a=&quot;b&quot;">r3bl</a>"#
+        );
+        assert_eq2!(
+            convert_fragment_to_html(&MdLineFragment::Checkbox(true), &references),
+            r#"<input type="checkbox" disabled checked>"#
+        );
+        assert_eq2!(
+            convert_fragment_to_html(
+                &MdLineFragment::ReferenceLink(ReferenceLinkData::new("r3bl", "")),
+                &references
+            ),
+            "[r3bl][]"
+        );
+    }
+
+    #[test]
+    fn test_document_to_html() {
+        let input = [
+            "@title: Foo & bar",
+            "@tags: tag1, tag2",
+            "# Heading",
+            "some *text*",
+            "more text",
+            "",
+            "- a",
+            "  - b",
+            "- c",
+            "3. d",
+            "> quote",
+            "---",
+            "| a | b |",
+            "|:--|--:|",
+            "| 1 | 2 |",
+            "```rs",
+            "let a = 1 < 2;",
+            "```",
+            "",
+        ]
+        .join("\n");
+        let (_, document) = parse_markdown(&input).unwrap();
+
+        let html = convert_to_html(&document);
+
+        let expected_head = [
+            "<head>",
+            r#"<meta charset="utf-8">"#,
+            "<title>Foo &amp; bar</title>",
+            r#"<meta name="keywords" content="tag1, tag2">"#,
+            "</head>",
+        ]
+        .join("\n");
+        assert!(html.starts_with("<!DOCTYPE html>\n<html>\n"));
+        assert!(html.contains(&expected_head));
+
+        let expected_body = [
+            "<body>",
+            "<h1>Heading</h1>",
+            "<p>some <em>text</em>\nmore text</p>",
+            "<ul>",
+            "<li>a",
+            "<ul>",
+            "<li>b",
+            "</li></ul>",
+            "</li>",
+            "<li>c",
+            "</li></ul>",
+            r#"<ol start="3">"#,
+            "<li>d",
+            "</li></ol>",
+            "<blockquote>\n<p>quote</p>\n</blockquote>",
+            "<hr>",
+            "<table>",
+            "<thead>",
+            r#"<tr><th style="text-align: left">a</th><th style="text-align: right">b</th></tr>"#,
+            "</thead>",
+            "<tbody>",
+            r#"<tr><td style="text-align: left">1</td><td style="text-align: right">2</td></tr>"#,
+            "</tbody>",
+            "</table>",
+            r#"<pre><code class="language-rs">let a = 1 &lt; 2;</code></pre>"#,
+            "</body>",
+        ]
+        .join("\n");
+        assert!(html.contains(&expected_body), "{html}");
+    }
+
+    #[test]
+    fn test_front_matter_to_html() {
+        let input = ["---", "title: Foo", "tags: [a, b]", "---", "text", ""].join("\n");
+        let (_, document) = parse_markdown(&input).unwrap();
+
+        let html = convert_to_html(&document);
+
+        let expected_head = [
+            r#"<meta charset="utf-8">"#,
+            "<title>Foo</title>",
+            r#"<meta name="keywords" content="a, b">"#,
+            "</head>",
+            "<body>",
+            "<p>text</p>",
+            "</body>",
+        ]
+        .join("\n");
+        assert!(html.contains(&expected_head), "{html}");
+    }
+
+    #[test]
+    fn test_links_and_footnotes_to_html() {
+        let input = [
+            "See [r3bl][R3BL][^a], https://r3bl.com[^b].",
+            "[^b]: Note *b*.",
+            "[^a]: Note a.",
+            "[r3bl]: https://r3bl.com",
+            "",
+        ]
+        .join("\n");
+        let (_, document) = parse_markdown(&input).unwrap();
+
+        let html = convert_to_html(&document);
+
+        let expected_body = [
+            "<body>",
+            concat!(
+                r#"<p>See <a href="https://r3bl.com">r3bl</a>"#,
+                r##"<sup><a href="#fn-a" id="fnref-a">1</a></sup>, "##,
+                r#"<a href="https://r3bl.com">https://r3bl.com</a>"#,
+                r##"<sup><a href="#fn-b" id="fnref-b">2</a></sup>.</p>"##,
+            ),
+            r#"<section class="footnotes">"#,
+            "<ol>",
+            r#"<li id="fn-a">Note a.</li>"#,
+            r#"<li id="fn-b">Note <em>b</em>.</li>"#,
+            "</ol>",
+            "</section>",
+            "</body>",
+        ]
+        .join("\n");
+        assert!(html.contains(&expected_body), "{html}");
+    }
+}
@@ -0,0 +1,383 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module is responsible for converting a [MdDocument] back into Markdown text, in a
+//! canonical format. This is what the editor component uses to format a document.
+
+use crate::{constants::*, *};
+
+/// Converts the document back into Markdown text (w/ an EOL at the end of each line). This
+/// doesn't always round trip: the text that [parse_markdown] skips over (eg: an unclosed
+/// inline code span in a list item) isn't in the document, and a lazy continuation line
+/// may be parsed differently once it is lined up w/ its item. Please take a look at
+/// [format_support::format_lines] to see how the editor component checks for this. The
+/// other differences from the source text are that:
+/// - Unordered list items use the `- ` bullet, and the items of each ordered list are
+///   numbered one after another (starting from the number of its first item). The lines of
+///   each item (including lazy continuation lines) are lined up w/ the text after its
+///   bullet.
+/// - Bold, italic, bold italic & strikethrough use the `**`, `*`, `***` & `~~` markers.
+/// - Thematic breaks are `---`, and tables are aligned (please take a look at
+///   [table_support::align_table] for more details).
+pub fn convert_to_markdown(document: &MdDocument) -> String {
+    convert_blocks_to_markdown_lines(document)
+        .iter()
+        .map(|line| format!("{line}{NEW_LINE}"))
+        .collect()
+}
+
+/// Converts the blocks into lines of Markdown text (w/out the EOL).
+pub fn convert_blocks_to_markdown_lines(blocks: &[MdBlockElement]) -> Vec<String> {
+    let mut acc = vec![];
+
+    for block in blocks {
+        match block {
+            MdBlockElement::Title(title) => {
+                acc.push(format!("{TITLE}{COLON}{SPACE}{title}"))
+            }
+            MdBlockElement::Date(date) => acc.push(format!("{DATE}{COLON}{SPACE}{date}")),
+            MdBlockElement::Tags(tags) => acc.push(format!(
+                "{TAGS}{COLON}{SPACE}{}",
+                tags.join(&format!("{COMMA}{SPACE}"))
+            )),
+            MdBlockElement::Authors(authors) => acc.push(format!(
+                "{AUTHORS}{COLON}{SPACE}{}",
+                authors.join(&format!("{COMMA}{SPACE}"))
+            )),
+            MdBlockElement::Heading(HeadingData { level, text }) => {
+                let level: usize = (*level).into();
+                acc.push(format!(
+                    "{}{SPACE}{text}",
+                    HEADING_CHAR.to_string().repeat(level)
+                ));
+            }
+            MdBlockElement::Text(fragments) => {
+                acc.push(convert_fragments_to_markdown(fragments))
+            }
+            MdBlockElement::SmartList(list_data) => {
+                acc.extend(convert_smart_list_to_markdown_lines(list_data))
+            }
+            MdBlockElement::CodeBlock(lines) => {
+                for line in lines.iter() {
+                    acc.push(match line.content {
+                        CodeBlockLineContent::StartTag => format!(
+                            "{CODE_BLOCK_START_PARTIAL}{}",
+                            line.language.unwrap_or_default()
+                        ),
+                        CodeBlockLineContent::EndTag => CODE_BLOCK_END.to_string(),
+                        CodeBlockLineContent::Text(text) => text.to_string(),
+                    });
+                }
+            }
+            MdBlockElement::BlockQuote(blocks) => {
+                for line in convert_blocks_to_markdown_lines(blocks) {
+                    acc.push(if line.is_empty() {
+                        BLOCK_QUOTE.to_string()
+                    } else {
+                        format!("{BLOCK_QUOTE_PREFIX}{line}")
+                    });
+                }
+            }
+            MdBlockElement::ThematicBreak(_) => acc.push(
+                THEMATIC_BREAK_CHARS[0]
+                    .to_string()
+                    .repeat(MIN_THEMATIC_BREAK_CHAR_COUNT),
+            ),
+            MdBlockElement::Table(table_data) => {
+                acc.extend(convert_table_to_markdown_lines(table_data))
+            }
+            MdBlockElement::LinkDefinition(LinkDefinitionData { label, url, .. }) => acc
+                .push(format!(
+                    "{LEFT_BRACKET}{label}{RIGHT_BRACKET}{COLON}{SPACE}{url}"
+                )),
+            MdBlockElement::FootnoteDefinition(FootnoteDefinitionData {
+                label,
+                fragments,
+            }) => {
+                let prefix = format!("{FOOTNOTE_PREFIX}{label}{RIGHT_BRACKET}{COLON}");
+                acc.push(
+                    match convert_fragments_to_markdown(fragments).trim_start() {
+                        "" => prefix,
+                        text => format!("{prefix}{SPACE}{text}"),
+                    },
+                );
+            }
+            // The lines are kept as is, since the formatting of the values depends on the
+            // kind of the front matter.
+            MdBlockElement::FrontMatter(front_matter) => {
+                let fence = match front_matter.kind {
+                    FrontMatterKind::Yaml => FRONT_MATTER_YAML_FENCE,
+                    FrontMatterKind::Toml => FRONT_MATTER_TOML_FENCE,
+                };
+                acc.push(fence.to_string());
+                acc.extend(front_matter.lines.iter().map(|it| it.to_string()));
+                acc.push(fence.to_string());
+            }
+        }
+    }
+
+    acc
+}
+
+pub fn convert_fragments_to_markdown(fragments: &[MdLineFragment]) -> String {
+    fragments.iter().map(convert_fragment_to_markdown).collect()
+}
+
+pub fn convert_fragment_to_markdown(fragment: &MdLineFragment) -> String {
+    match fragment {
+        MdLineFragment::Plain(text) => text.to_string(),
+        MdLineFragment::Bold(text) => format!("{BOLD_1}{text}{BOLD_1}"),
+        MdLineFragment::Italic(text) => format!("{ITALIC_1}{text}{ITALIC_1}"),
+        MdLineFragment::BoldItalic(text) => format!("{BITALIC_1}{text}{BITALIC_1}"),
+        MdLineFragment::Strikethrough(text) => {
+            format!("{STRIKETHROUGH}{text}{STRIKETHROUGH}")
+        }
+        MdLineFragment::InlineCode(text) => format!("{BACK_TICK}{text}{BACK_TICK}"),
+        MdLineFragment::Link(HyperlinkData { text, url }) => format!(
+            "{LEFT_BRACKET}{text}{RIGHT_BRACKET}{LEFT_PARENTHESIS}{url}{RIGHT_PARENTHESIS}"
+        ),
+        MdLineFragment::Image(HyperlinkData { text, url }) => format!(
+            "{LEFT_IMAGE}{text}{RIGHT_IMAGE}{LEFT_PARENTHESIS}{url}{RIGHT_PARENTHESIS}"
+        ),
+        MdLineFragment::Checkbox(true) => CHECKED.to_string(),
+        MdLineFragment::Checkbox(false) => UNCHECKED.to_string(),
+        MdLineFragment::ReferenceLink(ReferenceLinkData { text, label }) => {
+            format!("{LEFT_BRACKET}{text}{RIGHT_BRACKET}{LEFT_BRACKET}{label}{RIGHT_BRACKET}")
+        }
+        MdLineFragment::Autolink(url) => url.to_string(),
+        MdLineFragment::FootnoteReference(label) => {
+            format!("{FOOTNOTE_PREFIX}{label}{RIGHT_BRACKET}")
+        }
+    }
+}
+
+/// The items of an ordered list are numbered after the first one. The lists that are
+/// nested in an item keep their own indent.
+fn convert_smart_list_to_markdown_lines(list_data: &SmartListData) -> Vec<String> {
+    let mut acc = vec![];
+
+    let maybe_first_number = match list_data.items.first().map(|it| &it.bullet_kind) {
+        Some(BulletKind::Ordered(number)) => Some(*number),
+        _ => None,
+    };
+
+    for (item_index, item) in list_data.items.iter().enumerate() {
+        let bullet = match maybe_first_number {
+            Some(number) => {
+                format!("{}{ORDERED_LIST_PARTIAL_PREFIX}", number + item_index)
+            }
+            None => UNORDERED_LIST_PREFIX.to_string(),
+        };
+        let first_line_prefix = format!("{}{bullet}", SPACE.repeat(list_data.indent));
+        let rest_line_prefix = SPACE.repeat(list_data.indent + bullet.len());
+
+        for (block_index, block) in item.blocks.iter().enumerate() {
+            if let MdBlockElement::SmartList(nested_list_data) = block {
+                acc.extend(convert_smart_list_to_markdown_lines(nested_list_data));
+                continue;
+            }
+            for (line_index, line) in
+                convert_blocks_to_markdown_lines(std::slice::from_ref(block))
+                    .into_iter()
+                    .enumerate()
+            {
+                if block_index == 0 && line_index == 0 {
+                    acc.push(format!("{first_line_prefix}{line}"));
+                } else if line.is_empty() {
+                    acc.push(line);
+                } else {
+                    acc.push(format!("{rest_line_prefix}{line}"));
+                }
+            }
+        }
+    }
+
+    acc
+}
+
+fn convert_table_to_markdown_lines(table_data: &TableData) -> Vec<String> {
+    let convert_row = |row: &TableRow| {
+        let cells = row
+            .iter()
+            .map(|cell| convert_fragments_to_markdown(&cell.fragments))
+            .collect::<Vec<_>>();
+        format!(
+            "{TABLE_SEPARATOR}{SPACE}{}{SPACE}{TABLE_SEPARATOR}",
+            cells.join(" | ")
+        )
+    };
+
+    let mut lines = vec![
+        convert_row(&table_data.header_row),
+        table_data.delimiter_row.to_string(),
+    ];
+    lines.extend(table_data.body_rows.iter().map(convert_row));
+
+    let table_lines = lines.iter().map(String::as_str).collect::<Vec<_>>();
+    table_support::align_table(&table_lines).unwrap_or(lines)
+}
+
+#[cfg(test)]
+mod to_markdown_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    fn format_markdown(input: &str) -> String {
+        let (remainder, document) = parse_markdown(input).unwrap();
+        assert_eq2!(remainder, "");
+        convert_to_markdown(&document)
+    }
+
+    #[test]
+    fn test_fragment_to_markdown() {
+        assert_eq2!(
+            convert_fragments_to_markdown(&[
+                MdLineFragment::Plain("a "),
+                MdLineFragment::Bold("b"),
+                MdLineFragment::Italic("i"),
+                MdLineFragment::BoldItalic("bi"),
+                MdLineFragment::Strikethrough("s"),
+                MdLineFragment::InlineCode("c"),
+                MdLineFragment::Link(HyperlinkData::new("l", "u")),
+                MdLineFragment::Image(HyperlinkData::new("m", "v")),
+            ]),
+            "a **b***i****bi***~~s~~`c`[l](u)![m](v)"
+        );
+        assert_eq2!(
+            convert_fragments_to_markdown(&[
+                MdLineFragment::ReferenceLink(ReferenceLinkData::new("l", "r")),
+                MdLineFragment::Plain(" "),
+                MdLineFragment::Autolink("https://r3bl.com"),
+                MdLineFragment::FootnoteReference("1"),
+            ]),
+            "[l][r] https://r3bl.com[^1]"
+        );
+    }
+
+    #[test]
+    fn test_document_to_markdown() {
+        let input = [
+            "@title: Title",
+            "@tags: a, b",
+            "# Heading",
+            "__bold__ _italic_ ___both___",
+            "3. three",
+            "  - nested",
+            "    - [x] done",
+            "9. nine",
+            "   more",
+            "10. ten",
+            "",
+            "5. five",
+            "> quote",
+            ">",
+            "> > __nested__",
+            "***",
+            "|a|b|",
+            "|---|:-:|",
+            "|1|2|",
+            "```rs",
+            "let a = 1;",
+            "```",
+            "",
+        ]
+        .join("\n");
+
+        let expected = [
+            "@title: Title",
+            "@tags: a, b",
+            "# Heading",
+            "**bold** *italic* ***both***",
+            "3. three",
+            "  - nested",
+            "    - [x] done",
+            "4. nine",
+            "   more",
+            "5. ten",
+            "",
+            "5. five",
+            "> quote",
+            ">",
+            "> > **nested**",
+            "---",
+            "| a   |  b  |",
+            "| --- | :-: |",
+            "| 1   |  2  |",
+            "```rs",
+            "let a = 1;",
+            "```",
+            "",
+        ]
+        .join("\n");
+
+        let output = format_markdown(&input);
+        assert_eq2!(output, expected);
+
+        // Formatting is idempotent.
+        assert_eq2!(format_markdown(&output), output);
+    }
+
+    #[test]
+    fn test_nested_list_to_markdown() {
+        let input = [
+            "- a",
+            "lazy",
+            "",
+            "  b",
+            "  ```rs",
+            "  let a = 1;",
+            "  ```",
+            "  1. c",
+            "  1. d",
+            "",
+        ]
+        .join("\n");
+
+        let expected = [
+            "- a",
+            "  lazy",
+            "",
+            "  b",
+            "  ```rs",
+            "  let a = 1;",
+            "  ```",
+            "  1. c",
+            "  2. d",
+            "",
+        ]
+        .join("\n");
+
+        let output = format_markdown(&input);
+        assert_eq2!(output, expected);
+        assert_eq2!(format_markdown(&output), output);
+    }
+
+    #[test]
+    fn test_definitions_to_markdown() {
+        let input = ["[^1]:  __Note__.", "[R3BL]:https://r3bl.com", ""].join("\n");
+        let expected = ["[^1]: **Note**.", "[R3BL]: https://r3bl.com", ""].join("\n");
+        assert_eq2!(format_markdown(&input), expected);
+    }
+
+    #[test]
+    fn test_front_matter_to_markdown() {
+        let input = ["+++", "title =  \"Foo\"", "+++", "__bold__", ""].join("\n");
+        let expected = ["+++", "title =  \"Foo\"", "+++", "**bold**", ""].join("\n");
+        assert_eq2!(format_markdown(&input), expected);
+    }
+}
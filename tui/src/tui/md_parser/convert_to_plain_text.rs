@@ -66,17 +66,50 @@ impl<'a> PrettyPrintDebug for MdBlockElement<'a> {
             MdBlockElement::Tags(tags) => format!("tags: {}", tags.join(", ")),
             MdBlockElement::Date(date) => format!("title: {}", date),
             MdBlockElement::Authors(authors) => format!("tags: {}", authors.join(", ")),
-            MdBlockElement::SmartList((list_lines, _bullet_kind, _indent)) => format!(
+            MdBlockElement::SmartList(list_data) => format!(
                 "[  {}  ]",
-                list_lines
+                list_data
+                    .flatten()
                     .iter()
-                    .map(|fragments_in_one_line| format!(
-                        "┊{}┊",
-                        fragments_in_one_line.pretty_print_debug()
+                    .map(|it| format!(
+                        "┊{}{}┊",
+                        if it.is_indented_in_source() {
+                            it.first_line_prefix.as_str()
+                        } else {
+                            ""
+                        },
+                        it.block.pretty_print_debug()
                     ))
                     .collect::<Vec<String>>()
                     .join(" → ")
             ),
+            MdBlockElement::BlockQuote(blocks) => format!(
+                "[  {}  ]",
+                blocks
+                    .iter()
+                    .map(|block| format!(
+                        "{BLOCK_QUOTE_PREFIX}{}",
+                        block.pretty_print_debug()
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(" → ")
+            ),
+            MdBlockElement::ThematicBreak(text) => text.to_string(),
+            MdBlockElement::LinkDefinition(LinkDefinitionData { label, url, .. }) => {
+                format!("link definition: {label} → {url}")
+            }
+            MdBlockElement::FootnoteDefinition(FootnoteDefinitionData {
+                label,
+                fragments,
+            }) => format!("footnote: {label} → {}", fragments.pretty_print_debug()),
+            MdBlockElement::FrontMatter(front_matter) => {
+                format!("front matter, key count: {}", front_matter.entries.len())
+            }
+            MdBlockElement::Table(table_data) => {
+                let column_count = table_data.alignments.len();
+                let row_count = table_data.body_rows.len();
+                format!("table, column count: {column_count}, row count: {row_count}")
+            }
         }
     }
 }
@@ -119,19 +152,20 @@ impl PrettyPrintDebug for MdLineFragment<'_> {
             MdLineFragment::Bold(text) => format!("{BOLD_1}{text}{BOLD_1}"),
             MdLineFragment::Italic(text) => format!("{ITALIC_1}{text}{ITALIC_1}"),
             MdLineFragment::BoldItalic(text) => format!("{BITALIC_1}{text}{BITALIC_1}"),
+            MdLineFragment::Strikethrough(text) => {
+                format!("{STRIKETHROUGH}{text}{STRIKETHROUGH}")
+            }
             MdLineFragment::InlineCode(text) => format!("{BACK_TICK}{text}{BACK_TICK}"),
             MdLineFragment::Checkbox(is_checked) => {
                 (if *is_checked { CHECKED } else { UNCHECKED }).to_string()
             }
-            MdLineFragment::OrderedListBullet {
-                indent,
-                number,
-                is_first_line,
-            } => generate_ordered_list_item_bullet(indent, number, is_first_line),
-            MdLineFragment::UnorderedListBullet {
-                indent,
-                is_first_line,
-            } => generate_unordered_list_item_bullet(indent, is_first_line),
+            MdLineFragment::ReferenceLink(ReferenceLinkData { text, label }) => format!(
+                "{LEFT_BRACKET}{text}{RIGHT_BRACKET}{LEFT_BRACKET}{label}{RIGHT_BRACKET}"
+            ),
+            MdLineFragment::Autolink(url) => url.to_string(),
+            MdLineFragment::FootnoteReference(label) => {
+                format!("{FOOTNOTE_PREFIX}{label}{RIGHT_BRACKET}")
+            }
         };
         it
     }
@@ -217,6 +251,10 @@ mod to_plain_text_tests {
             MdLineFragment::InlineCode("Hello World").pretty_print_debug(),
             "`Hello World`"
         );
+        assert_eq2!(
+            MdLineFragment::Strikethrough("Hello World").pretty_print_debug(),
+            "~~Hello World~~"
+        );
         assert_eq2!(MdLineFragment::Checkbox(true).pretty_print_debug(), "[x]");
         assert_eq2!(MdLineFragment::Checkbox(false).pretty_print_debug(), "[ ]");
     }
@@ -0,0 +1,164 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module is responsible for extracting the outline (table of contents) of a
+//! Markdown document from its [MdBlockElement::Heading]s, eg: to navigate a long document
+//! in the editor component by its headings.
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+impl MdOutline {
+    /// The headings in blockquotes aren't a part of the outline. The line index of each
+    /// heading comes from the [SourceSpan] of its block.
+    pub fn from_spanned_document(document: &[MdSpannedBlock]) -> Self {
+        let entries = document
+            .iter()
+            .filter_map(|spanned_block| match &spanned_block.block {
+                MdBlockElement::Heading(HeadingData { level, text }) => {
+                    Some(MdOutlineEntry {
+                        level: *level,
+                        text: text.to_string(),
+                        line_index: ch!(@to_usize spanned_block.span.start.row_index),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Parses the `input` (eg: the lines of the editor component joined w/ `\n`) w/
+    /// [parse_markdown_with_spans]. The outline is empty if it can't be parsed.
+    pub fn from_markdown(input: &str) -> Self {
+        match parse_markdown_with_spans(input) {
+            Ok((_, document)) => Self::from_spanned_document(&document),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Same as [MdOutline::from_markdown], but for the lines of an [EditorBuffer], which
+    /// are joined (each w/ a `\n` at the end) the same way as in [try_parse_and_highlight].
+    pub fn from_lines(lines: &[US]) -> Self {
+        let mut text = String::new();
+        for line in lines {
+            text.push_str(&line.string);
+            text.push('\n');
+        }
+        Self::from_markdown(&text)
+    }
+
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Returns the index of the entry for the section that the line is in, which is the
+    /// last heading at (or before) the line. Returns [None] for the lines before the first
+    /// heading.
+    pub fn get_section_entry_index(&self, line_index: usize) -> Option<usize> {
+        self.entries
+            .partition_point(|it| it.line_index <= line_index)
+            .checked_sub(1)
+    }
+}
+
+#[cfg(test)]
+mod tests_extract_outline {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_outline_from_markdown() {
+        let input = [
+            "---",
+            "title: Foo",
+            "---",
+            "intro",
+            "# One",
+            "text",
+            "## Two *a*",
+            "> # Quoted",
+            "```",
+            "# Not a heading",
+            "```",
+            "### Three",
+            "",
+        ]
+        .join("\n");
+
+        let outline = MdOutline::from_markdown(&input);
+
+        assert_eq2!(
+            outline.entries,
+            vec![
+                MdOutlineEntry {
+                    level: HeadingLevel::Heading1,
+                    text: "One".to_string(),
+                    line_index: 4,
+                },
+                MdOutlineEntry {
+                    level: HeadingLevel::Heading2,
+                    text: "Two *a*".to_string(),
+                    line_index: 6,
+                },
+                MdOutlineEntry {
+                    level: HeadingLevel::Heading3,
+                    text: "Three".to_string(),
+                    line_index: 11,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outline_from_markdown_wo_trailing_new_line() {
+        let outline = MdOutline::from_markdown("# h\n- a");
+        assert_eq2!(
+            outline.entries,
+            vec![MdOutlineEntry {
+                level: HeadingLevel::Heading1,
+                text: "h".to_string(),
+                line_index: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_get_section_entry_index() {
+        let outline = MdOutline::from_markdown("intro\n# One\ntext\n## Two\n");
+        assert_eq2!(outline.len(), 2);
+        assert_eq2!(outline.get_section_entry_index(0), None);
+        assert_eq2!(outline.get_section_entry_index(1), Some(0));
+        assert_eq2!(outline.get_section_entry_index(2), Some(0));
+        assert_eq2!(outline.get_section_entry_index(3), Some(1));
+        assert_eq2!(outline.get_section_entry_index(100), Some(1));
+
+        assert!(MdOutline::from_markdown("no headings\n").is_empty());
+
+        let lines = ["# One", "", "## Two"].map(US::from);
+        assert_eq2!(
+            MdOutline::from_lines(&lines)
+                .entries
+                .iter()
+                .map(|it| it.line_index)
+                .collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+    }
+}
@@ -0,0 +1,356 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module is responsible for extracting the tasks (the lines in lists that start w/ a
+//! checkbox, eg: `- [ ] todo`) from a Markdown document, and summarizing how many of them
+//! are done in each section of the document. The editor component uses this to toggle the
+//! checkbox on the caret line (please take a look at [task_list_support]).
+
+use std::{fs,
+          path::{Path, PathBuf}};
+
+use constants::*;
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+impl MdTask {
+    /// The index of the line that the task is on, in the source text.
+    pub fn get_line_index(&self) -> usize {
+        ch!(@to_usize self.checkbox_span.start.row_index)
+    }
+}
+
+impl MdTaskProgress {
+    pub fn from_tasks<'a>(tasks: impl IntoIterator<Item = &'a MdTask>) -> Self {
+        let mut it = Self::default();
+        for task in tasks {
+            it.total_count += 1;
+            if task.is_done {
+                it.done_count += 1;
+            }
+        }
+        it
+    }
+
+    pub fn is_complete(&self) -> bool { self.done_count == self.total_count }
+}
+
+/// Returns the tasks in the `document` (including the ones in blockquotes), in the same
+/// order as they are in it. Only the lines of an item that start w/ a checkbox are tasks,
+/// and lazy continuation lines never are.
+pub fn collect_tasks(document: &[MdSpannedBlock]) -> Vec<MdTask> {
+    let mut acc = vec![];
+    collect_tasks_into(document, &mut acc);
+    acc
+}
+
+/// Parses the `input` w/ [parse_markdown_with_spans], and returns its tasks. There are no
+/// tasks if it can't be parsed.
+pub fn collect_tasks_from_markdown(input: &str) -> Vec<MdTask> {
+    match parse_markdown_with_spans(input) {
+        Ok((_, document)) => collect_tasks(&document),
+        Err(_) => vec![],
+    }
+}
+
+/// Returns the progress of the tasks in each section of the `document`. A section is made
+/// up of the lines from its heading up to the next heading (of any level), so the tasks
+/// under a sub heading are only counted in the section of the sub heading.
+///
+/// There's an entry for each heading (even if its section has no tasks), which is
+/// preceded by an entry w/out a heading for the tasks before the first heading (if there
+/// are any).
+pub fn get_task_progress_by_section(
+    document: &[MdSpannedBlock],
+) -> Vec<MdSectionTaskProgress> {
+    let outline = MdOutline::from_spanned_document(document);
+    let tasks = collect_tasks(document);
+
+    let mut acc = vec![];
+
+    let tasks_before_first_heading = tasks.iter().filter(|task| {
+        outline
+            .get_section_entry_index(task.get_line_index())
+            .is_none()
+    });
+    let progress = MdTaskProgress::from_tasks(tasks_before_first_heading);
+    if progress.total_count > 0 {
+        acc.push(MdSectionTaskProgress {
+            maybe_heading: None,
+            progress,
+        });
+    }
+
+    for (entry_index, entry) in outline.entries.iter().enumerate() {
+        let tasks_in_section = tasks.iter().filter(|task| {
+            outline.get_section_entry_index(task.get_line_index()) == Some(entry_index)
+        });
+        acc.push(MdSectionTaskProgress {
+            maybe_heading: Some(entry.clone()),
+            progress: MdTaskProgress::from_tasks(tasks_in_section),
+        });
+    }
+
+    acc
+}
+
+/// Returns the open (not done) tasks in all the Markdown files (w/ the `md` extension) in
+/// the `dir_path` folder & its sub folders, sorted by file path (and then by line). Hidden
+/// files & folders (whose names start w/ `.`) are skipped, and so are files that aren't
+/// valid UTF-8.
+pub fn collect_open_tasks_in_dir(
+    dir_path: impl AsRef<Path>,
+) -> CommonResult<Vec<MdFileTask>> {
+    let mut file_paths = vec![];
+    collect_markdown_file_paths(dir_path.as_ref(), &mut file_paths)?;
+    file_paths.sort();
+
+    let mut acc = vec![];
+    for file_path in file_paths {
+        let bytes = fs::read(&file_path)?;
+        let Ok((lines, _)) = file_support::decode(&bytes) else {
+            continue;
+        };
+        let text = lines
+            .iter()
+            .map(|line| format!("{line}{NEW_LINE}"))
+            .collect::<String>();
+
+        for task in collect_tasks_from_markdown(&text) {
+            if !task.is_done {
+                acc.push(MdFileTask {
+                    file_path: file_path.clone(),
+                    task,
+                });
+            }
+        }
+    }
+
+    Ok(acc)
+}
+
+fn collect_tasks_into(document: &[MdSpannedBlock], acc: &mut Vec<MdTask>) {
+    for spanned_block in document {
+        match &spanned_block.block {
+            MdBlockElement::SmartList(list_data) => {
+                collect_smart_list_tasks(list_data, &spanned_block.fragment_spans, acc);
+            }
+            MdBlockElement::BlockQuote(_) => {
+                collect_tasks_into(&spanned_block.child_blocks, acc);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The `fragment_spans` has one line per line of the list, in the same order as
+/// [SmartListData::flatten]. The first span of a line is its bullet (or indent), and the
+/// second one is the checkbox (if the line has one).
+fn collect_smart_list_tasks(
+    list_data: &SmartListData,
+    fragment_spans: &[List<SourceSpan>],
+    acc: &mut Vec<MdTask>,
+) {
+    let mut lines = fragment_spans.iter();
+    for it in list_data.flatten() {
+        match it.block {
+            MdBlockElement::CodeBlock(code_block_lines) => {
+                lines.by_ref().take(code_block_lines.len()).for_each(drop);
+            }
+            MdBlockElement::Text(fragments) => {
+                let Some(spans) = lines.next() else {
+                    break;
+                };
+                if it.is_lazy {
+                    continue;
+                }
+                let (Some(MdLineFragment::Checkbox(is_done)), Some(checkbox_span)) =
+                    (fragments.first(), spans.get(1))
+                else {
+                    continue;
+                };
+                acc.push(MdTask {
+                    is_done: *is_done,
+                    text: convert_fragments_to_markdown(&fragments[1..])
+                        .trim_start()
+                        .to_string(),
+                    checkbox_span: *checkbox_span,
+                });
+            }
+            _ => {
+                if lines.next().is_none() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn collect_markdown_file_paths(
+    dir_path: &Path,
+    acc: &mut Vec<PathBuf>,
+) -> CommonResult<()> {
+    for dir_entry in fs::read_dir(dir_path)? {
+        let path = dir_entry?.path();
+        let is_hidden = path
+            .file_name()
+            .is_some_and(|it| it.to_string_lossy().starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_markdown_file_paths(&path, acc)?;
+        } else if file_support::get_file_extension(&path).as_deref()
+            == Some(DEFAULT_SYN_HI_FILE_EXT)
+        {
+            acc.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_extract_tasks {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    const INPUT: &str = "\
+- [ ] before
+# One
+- [x] done `a`
+- [ ] todo
+  - [x] nested
+    more text [ ] not a task
+- no checkbox
+```
+- [ ] in a code block
+```
+## Two
+> - [ ] quoted
+# Three
+";
+
+    fn get_summary(tasks: &[MdTask]) -> Vec<(bool, &str, usize, usize)> {
+        tasks
+            .iter()
+            .map(|it| {
+                (
+                    it.is_done,
+                    it.text.as_str(),
+                    it.get_line_index(),
+                    ch!(@to_usize it.checkbox_span.start.col_index),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_tasks() {
+        let tasks = collect_tasks_from_markdown(INPUT);
+        assert_eq2!(
+            get_summary(&tasks),
+            vec![
+                (false, "before", 0, 2),
+                (true, "done `a`", 2, 2),
+                (false, "todo", 3, 2),
+                (true, "nested", 4, 4),
+                (false, "quoted", 11, 4),
+            ]
+        );
+        assert_eq2!(tasks[1].checkbox_span.get_text(INPUT), "[x]");
+        assert_eq2!(tasks[3].checkbox_span.get_text(INPUT), "[x]");
+    }
+
+    #[test]
+    fn test_collect_tasks_wo_trailing_new_line() {
+        let input = "# h\n- [ ] task";
+        let tasks = collect_tasks_from_markdown(input);
+        assert_eq2!(get_summary(&tasks), vec![(false, "task", 1, 2)]);
+        assert_eq2!(tasks[0].checkbox_span.get_text(input), "[ ]");
+    }
+
+    #[test]
+    fn test_get_task_progress_by_section() {
+        let (_, document) = parse_markdown_with_spans(INPUT).unwrap();
+        let sections = get_task_progress_by_section(&document);
+
+        let summary = sections
+            .iter()
+            .map(|it| {
+                (
+                    it.maybe_heading
+                        .as_ref()
+                        .map(|heading| heading.text.as_str()),
+                    it.progress.done_count,
+                    it.progress.total_count,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            summary,
+            vec![
+                (None, 0, 1),
+                (Some("One"), 2, 3),
+                (Some("Two"), 0, 1),
+                (Some("Three"), 0, 0),
+            ]
+        );
+        assert!(sections[3].progress.is_complete());
+    }
+
+    #[test]
+    fn test_collect_open_tasks_in_dir() {
+        let dir_path = std::env::temp_dir().join(format!(
+            "r3bl_tui_{}_{}",
+            std::process::id(),
+            "collect_open_tasks_in_dir"
+        ));
+        fs::create_dir_all(dir_path.join("sub")).unwrap();
+        fs::create_dir_all(dir_path.join(".hidden")).unwrap();
+        fs::write(dir_path.join("b.md"), "- [x] done\r\n- [ ] b1\r\n").unwrap();
+        fs::write(dir_path.join("sub/a.md"), "# A\n\n- [ ] a1\n").unwrap();
+        fs::write(dir_path.join("c.txt"), "- [ ] not markdown\n").unwrap();
+        fs::write(dir_path.join(".hidden/d.md"), "- [ ] hidden\n").unwrap();
+        fs::write(dir_path.join("e.md"), [0xff_u8, 0xfe]).unwrap();
+
+        let tasks = collect_open_tasks_in_dir(&dir_path).unwrap();
+        fs::remove_dir_all(&dir_path).unwrap();
+
+        let summary = tasks
+            .iter()
+            .map(|it| {
+                (
+                    it.file_path.strip_prefix(&dir_path).unwrap().to_path_buf(),
+                    it.task.text.as_str(),
+                    it.task.get_line_index(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            summary,
+            vec![
+                (PathBuf::from("b.md"), "b1", 1),
+                (PathBuf::from("sub").join("a.md"), "a1", 2),
+            ]
+        );
+
+        assert!(collect_open_tasks_in_dir(dir_path.join("missing")).is_err());
+    }
+}
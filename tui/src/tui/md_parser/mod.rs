@@ -33,23 +33,48 @@
 //! 3. All the parsers related to parsing metadata specific for R3BL applications which are not
 //!    standard Markdown can be found in [parse_metadata_kv] and [parse_metadata_kcsv].
 //! 4. All the parsers that are related to parsing the main "blocks" of Markdown, such as order
-//!    lists, unordered lists, code blocks, text blocks, heading blocks, can be found [block].
+//!    lists, unordered lists, code blocks, text blocks, heading blocks, blockquotes, thematic
+//!    breaks, tables, can be found [block].
 //! 5. All the parsers that are related to parsing a single line of Markdown text, such as links,
-//!    bold, italic, etc. can be found [parse_element].
+//!    bold, italic, strikethrough, etc. can be found [parse_element].
+//! 6. A parsed [MdDocument] can be exported to a standalone HTML document w/
+//!    [fn@convert_to_html], or to word wrapped text w/ ANSI escape sequences w/
+//!    [fn@convert_to_ansi_text].
+//! 7. The parser [parse_markdown_with_spans] also returns the [SourceSpan] of every block &
+//!    fragment, and a parsed [MdDocument] can be written back to canonical Markdown w/
+//!    [fn@convert_to_markdown].
+//! 8. The reference links & footnotes in a parsed [MdDocument] are resolved against the link
+//!    & footnote definitions in it w/ [MdReferences].
+//! 9. The outline (table of contents) of a document is made up of its headings, w/
+//!    [MdOutline].
+//! 10. The tasks (list items w/ checkboxes) in a document, and how many of them are done in
+//!     each section, are collected w/ [collect_tasks] & [get_task_progress_by_section].
 
 // External use.
 pub mod block;
+pub mod convert_to_ansi_text;
+pub mod convert_to_html;
+pub mod convert_to_markdown;
 pub mod convert_to_plain_text;
+pub mod extract_outline;
+pub mod extract_tasks;
 pub mod parse_element;
 pub mod parse_metadata_kcsv;
 pub mod parse_metadata_kv;
 pub mod parser;
+pub mod parser_with_spans;
+pub mod resolve_references;
 pub mod types;
 
 pub use block::*;
+pub use convert_to_ansi_text::*;
+pub use convert_to_html::*;
+pub use convert_to_markdown::*;
 pub use convert_to_plain_text::*;
+pub use extract_tasks::*;
 pub use parse_element::*;
 pub use parse_metadata_kcsv::*;
 pub use parse_metadata_kv::*;
 pub use parser::*;
+pub use parser_with_spans::*;
 pub use types::*;
@@ -54,6 +54,11 @@ pub fn parse_element_italic(input: &str) -> IResult<&str, &str> {
     ))(input)
 }
 
+#[rustfmt::skip]
+pub fn parse_element_strikethrough(input: &str) -> IResult<&str, &str> {
+    delimited(/* start */ tag(STRIKETHROUGH), /* output */ is_not(STRIKETHROUGH), /* end */ tag(STRIKETHROUGH))(input)
+}
+
 #[rustfmt::skip]
 pub fn parse_element_code(input: &str) -> IResult<&str, &str> {
     delimited(/* start */ tag(BACK_TICK), /* output */ is_not(BACK_TICK), /* end */ tag(BACK_TICK))(input)
@@ -68,6 +73,45 @@ pub fn parse_element_link(input: &str) -> IResult<&str, HyperlinkData> {
     Ok((input, HyperlinkData::from(output)))
 }
 
+/// Sample inputs: `[text][label]`, and `[text][]` (where the label is the text).
+#[rustfmt::skip]
+pub fn parse_element_reference_link(input: &str) -> IResult<&str, ReferenceLinkData> {
+    let (input, (text, maybe_label)) = pair(
+        delimited(/* start */ tag(LEFT_BRACKET), /* output */ is_not(RIGHT_BRACKET), /* end */ tag(RIGHT_BRACKET)),
+        delimited(/* start */ tag(LEFT_BRACKET), /* output */ opt(is_not(RIGHT_BRACKET)), /* end */ tag(RIGHT_BRACKET)),
+    )(input)?;
+    Ok((input, ReferenceLinkData::new(text, maybe_label.unwrap_or_default())))
+}
+
+/// Sample inputs: `[^1]`, `[^note]`. The label can't have any whitespace in it.
+#[rustfmt::skip]
+pub fn parse_element_footnote_reference(input: &str) -> IResult<&str, &str> {
+    delimited(
+        /* start */ tag(FOOTNOTE_PREFIX),
+        /* output */ verify(is_not(RIGHT_BRACKET), |it: &str| !it.contains(char::is_whitespace)),
+        /* end */ tag(RIGHT_BRACKET),
+    )(input)
+}
+
+/// Sample inputs: `https://r3bl.com`, `http://r3bl.com/foo?bar=1`. The URL ends at the first
+/// whitespace (or `<`, `[`, `]`, eg: `https://r3bl.com[^1]`), and any [AUTOLINK_TRAILING_PUNCTUATION] at its end is left out, eg:
+/// the period in `see https://r3bl.com.`.
+pub fn parse_element_autolink(input: &str) -> IResult<&str, &str> {
+    let (_, (prefix, rest)) = pair(
+        alt((tag(AUTOLINK_PREFIXES[0]), tag(AUTOLINK_PREFIXES[1]))),
+        take_till1(|it: char| it.is_whitespace() || matches!(it, '<' | '[' | ']')),
+    )(input)?;
+    let rest = rest.trim_end_matches(&AUTOLINK_TRAILING_PUNCTUATION[..]);
+    if rest.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let url_len = prefix.len() + rest.len();
+    Ok((&input[url_len..], &input[..url_len]))
+}
+
 /// Checkboxes are tricky since they begin with "[" which is also used for hyperlinks and images.
 /// So some extra hint is need from the code calling this parser to let it know whether to parse
 /// a checkbox into plain text, or into a boolean.
@@ -118,9 +162,12 @@ pub fn parse_element_plaintext(input: &str) -> IResult<&str, &str> {
                         tag(BOLD_2),
                         tag(ITALIC_1),
                         tag(ITALIC_2),
+                        tag(STRIKETHROUGH),
                         tag(BACK_TICK),
                         tag(LEFT_BRACKET),
                         tag(LEFT_IMAGE),
+                        tag(AUTOLINK_PREFIXES[0]),
+                        tag(AUTOLINK_PREFIXES[1]),
                         tag(NEW_LINE),
                     ))
                 ),
@@ -142,9 +189,13 @@ pub fn parse_element_markdown_inline(
             map(parse_element_italic, MdLineFragment::Italic),
             map(parse_element_bold, MdLineFragment::Bold),
             map(parse_element_bold_italic, MdLineFragment::BoldItalic),
+            map(parse_element_strikethrough, MdLineFragment::Strikethrough),
             map(parse_element_code, MdLineFragment::InlineCode),
             map(parse_element_image, MdLineFragment::Image),
+            map(parse_element_footnote_reference, MdLineFragment::FootnoteReference),
             map(parse_element_link, MdLineFragment::Link),
+            map(parse_element_reference_link, MdLineFragment::ReferenceLink),
+            map(parse_element_autolink, MdLineFragment::Autolink),
             map(parse_element_checkbox_into_str, MdLineFragment::Plain),
             map(parse_element_plaintext, MdLineFragment::Plain),
         ))(input),
@@ -152,9 +203,13 @@ pub fn parse_element_markdown_inline(
             map(parse_element_italic, MdLineFragment::Italic),
             map(parse_element_bold, MdLineFragment::Bold),
             map(parse_element_bold_italic, MdLineFragment::BoldItalic),
+            map(parse_element_strikethrough, MdLineFragment::Strikethrough),
             map(parse_element_code, MdLineFragment::InlineCode),
             map(parse_element_image, MdLineFragment::Image),
+            map(parse_element_footnote_reference, MdLineFragment::FootnoteReference),
             map(parse_element_link, MdLineFragment::Link),
+            map(parse_element_reference_link, MdLineFragment::ReferenceLink),
+            map(parse_element_autolink, MdLineFragment::Autolink),
             map(parse_element_checkbox_into_bool, MdLineFragment::Checkbox),
             map(parse_element_plaintext, MdLineFragment::Plain),
         ))(input)
@@ -310,6 +365,30 @@ mod tests_parse_element {
         );
     }
 
+    #[test]
+    fn test_parse_element_strikethrough() {
+        assert_eq2!(
+            parse_element_strikethrough("~~here is strikethrough~~ foo"),
+            Ok((" foo", "here is strikethrough"))
+        );
+
+        assert_eq2!(
+            parse_element_strikethrough("~~here is strikethrough"),
+            Err(NomErr::Error(Error {
+                input: "",
+                code: ErrorKind::Tag
+            }))
+        );
+
+        assert_eq2!(
+            parse_element_strikethrough("~here is not strikethrough~"),
+            Err(NomErr::Error(Error {
+                input: "~here is not strikethrough~",
+                code: ErrorKind::Tag
+            }))
+        );
+    }
+
     #[test]
     fn test_parse_element_bold() {
         assert_eq2!(
@@ -441,6 +520,63 @@ mod tests_parse_element {
         );
     }
 
+    #[test]
+    fn test_parse_element_reference_link() {
+        assert_eq2!(
+            parse_element_reference_link("[title][ref] foo"),
+            Ok((" foo", ReferenceLinkData::new("title", "ref")))
+        );
+        assert_eq2!(
+            parse_element_reference_link("[title][]"),
+            Ok(("", ReferenceLinkData::new("title", "")))
+        );
+        assert_eq2!(
+            parse_element_reference_link("[title](https://www.example.com)"),
+            Err(NomErr::Error(Error {
+                input: "(https://www.example.com)",
+                code: ErrorKind::Tag
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_element_footnote_reference() {
+        assert_eq2!(
+            parse_element_footnote_reference("[^1] foo"),
+            Ok((" foo", "1"))
+        );
+        assert_eq2!(
+            parse_element_footnote_reference("[^a b]"),
+            Err(NomErr::Error(Error {
+                input: "a b]",
+                code: ErrorKind::Verify
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_element_autolink() {
+        assert_eq2!(
+            parse_element_autolink("https://r3bl.com/a?b=1 foo"),
+            Ok((" foo", "https://r3bl.com/a?b=1"))
+        );
+        assert_eq2!(
+            parse_element_autolink("http://r3bl.com."),
+            Ok((".", "http://r3bl.com"))
+        );
+        assert_eq2!(
+            parse_element_autolink("https://."),
+            Err(NomErr::Error(Error {
+                input: "https://.",
+                code: ErrorKind::Verify
+            }))
+        );
+        assert_eq2!(
+            parse_element_plaintext("see https://r3bl.com"),
+            Ok(("https://r3bl.com", "see "))
+        );
+    }
+
     #[test]
     fn test_parse_element_image() {
         assert_eq2!(
@@ -594,6 +730,20 @@ mod tests_parse_element {
             ),
             Ok(("", MdLineFragment::InlineCode("here is code")))
         );
+        assert_eq2!(
+            parse_element_markdown_inline(
+                "~~here is strikethrough~~",
+                CheckboxParsePolicy::IgnoreCheckbox
+            ),
+            Ok(("", MdLineFragment::Strikethrough("here is strikethrough")))
+        );
+        assert_eq2!(
+            parse_element_markdown_inline(
+                "here is ~plaintext~ ~~but",
+                CheckboxParsePolicy::IgnoreCheckbox
+            ),
+            Ok(("~~but", MdLineFragment::Plain("here is ~plaintext~ ")))
+        );
         assert_eq2!(
             parse_element_markdown_inline(
                 "[title](https://www.example.com)",
@@ -607,6 +757,27 @@ mod tests_parse_element {
                 )))
             ))
         );
+        assert_eq2!(
+            parse_element_markdown_inline(
+                "[title][ref]",
+                CheckboxParsePolicy::IgnoreCheckbox
+            ),
+            Ok((
+                "",
+                MdLineFragment::ReferenceLink(ReferenceLinkData::new("title", "ref"))
+            ))
+        );
+        assert_eq2!(
+            parse_element_markdown_inline("[^1]", CheckboxParsePolicy::IgnoreCheckbox),
+            Ok(("", MdLineFragment::FootnoteReference("1")))
+        );
+        assert_eq2!(
+            parse_element_markdown_inline(
+                "https://r3bl.com, foo",
+                CheckboxParsePolicy::IgnoreCheckbox
+            ),
+            Ok((", foo", MdLineFragment::Autolink("https://r3bl.com")))
+        );
         assert_eq2!(
             parse_element_markdown_inline(
                 "![alt text](image.jpg)",
@@ -35,8 +35,43 @@ use crate::{constants::*, tui::misc_types::list_of::List, *};
 /// 5. Code block (which contains string slices of the language & code). The parsers in
 ///    [mod@parse_block_code] file handle this.
 /// 6. line (which contains a [MdLineFragments]). The parsers in [parse_element] file handle this.
-#[rustfmt::skip]
+/// 7. Blockquote (which contains the [MdBlockElement]s that are parsed from its lines). The
+///    parsers in [mod@parse_block_quote] file handle this.
+/// 8. Thematic break (which contains the line). The parsers in
+///    [mod@parse_block_thematic_break] file handle this.
+/// 9. Table (which contains a [TableData]). The parsers in [mod@parse_block_table] file
+///    handle this.
+/// 10. Front matter (which contains a [FrontMatterData]), only at the start of the input.
+///     The parsers in [mod@parse_block_front_matter] file handle this.
+/// 11. Link definition (which contains a [LinkDefinitionData]) & footnote definition (which
+///     contains a [FootnoteDefinitionData]). The parsers in
+///     [mod@parse_block_link_definition] & [mod@parse_block_footnote_definition] files
+///     handle this. Please take a look at [MdReferences] to resolve the references to them.
 pub fn parse_markdown(input: &str) -> IResult<&str, MdDocument> {
+    let (input, maybe_first_block) = opt(parse_first_block)(input)?;
+    let (input, output) = many0(parse_block)(input)?;
+    let mut it = List::with_capacity(output.len() + 1);
+    if let Some(first_block) = maybe_first_block {
+        it += first_block;
+    }
+    it += output;
+    Ok((input, it))
+}
+
+/// Same as [parse_block], except that it also parses the front matter, which can only be
+/// the first block of the input.
+pub fn parse_first_block(input: &str) -> IResult<&str, MdBlockElement> {
+    alt((
+        map(parse_block_front_matter, MdBlockElement::FrontMatter),
+        parse_block,
+    ))(input)
+}
+
+/// Parses a single [MdBlockElement] from the start of the input. This is used by
+/// [parse_markdown] (and [crate::parse_markdown_with_spans]) to parse the blocks one after
+/// another.
+#[rustfmt::skip]
+pub fn parse_block(input: &str) -> IResult<&str, MdBlockElement> {
     // key: TAGS, value: CSV parser.
     fn parse_tags_list(input: &str) -> IResult<&str, List<&str>>
     {
@@ -61,21 +96,22 @@ pub fn parse_markdown(input: &str) -> IResult<&str, MdDocument> {
         parse_kv_opt_eol(DATE, input)
     }
 
-    let (input, output) = many0(
-        // NOTE: The ordering of the parsers below matters.
-        alt((
-            map(parse_title_value,                   MdBlockElement::Title),
-            map(parse_tags_list,                     MdBlockElement::Tags),
-            map(parse_authors_list,                  MdBlockElement::Authors),
-            map(parse_date_value,                    MdBlockElement::Date),
-            map(parse_block_heading_opt_eol,         MdBlockElement::Heading),
-            map(parse_block_smart_list,              MdBlockElement::SmartList),
-            map(parse_block_code,                    MdBlockElement::CodeBlock),
-            map(parse_block_markdown_text_until_eol, MdBlockElement::Text),
-        )),
-    )(input)?;
-    let it = List::from(output);
-    Ok((input, it))
+    // NOTE: The ordering of the parsers below matters.
+    alt((
+        map(parse_title_value,                   MdBlockElement::Title),
+        map(parse_tags_list,                     MdBlockElement::Tags),
+        map(parse_authors_list,                  MdBlockElement::Authors),
+        map(parse_date_value,                    MdBlockElement::Date),
+        map(parse_block_quote,                   MdBlockElement::BlockQuote),
+        map(parse_block_thematic_break,          MdBlockElement::ThematicBreak),
+        map(parse_block_table,                   MdBlockElement::Table),
+        map(parse_block_footnote_definition,     MdBlockElement::FootnoteDefinition),
+        map(parse_block_link_definition,         MdBlockElement::LinkDefinition),
+        map(parse_block_heading_opt_eol,         MdBlockElement::Heading),
+        map(parse_block_smart_list,              MdBlockElement::SmartList),
+        map(parse_block_code,                    MdBlockElement::CodeBlock),
+        map(parse_block_markdown_text_until_eol, MdBlockElement::Text),
+    ))(input)
 }
 
 #[cfg(test)]
@@ -118,11 +154,22 @@ mod tests {
             "2. ol2",
             "- [ ] todo",
             "- [x] done",
+            "",
             "end",
             "",
         ]
         .join("\n");
         let (remainder, vec_block) = parse_markdown(&input).unwrap();
+        let item = |bullet_str, fragments| SmartListItem {
+            bullet_kind: match bullet_str {
+                "1. " => BulletKind::Ordered(1),
+                "2. " => BulletKind::Ordered(2),
+                _ => BulletKind::Unordered,
+            },
+            bullet_str,
+            blocks: list![MdBlockElement::Text(fragments)],
+            lazy_line_indices: list![],
+        };
         let expected_vec = vec![
             MdBlockElement::Title("Something"),
             MdBlockElement::Tags(list!["tag1", "tag2", "tag3"]),
@@ -170,81 +217,45 @@ mod tests {
                     "foobar.singularize('phenomena') # returns 'phenomenon'",
                 ],
             )),
-            MdBlockElement::SmartList((
-                list![list![
-                    MdLineFragment::UnorderedListBullet {
-                        indent: 0,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Plain("ul1"),
-                ],],
-                BulletKind::Unordered,
-                0,
-            )),
-            MdBlockElement::SmartList((
-                list![list![
-                    MdLineFragment::UnorderedListBullet {
-                        indent: 0,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Plain("ul2"),
-                ],],
-                BulletKind::Unordered,
-                0,
-            )),
-            MdBlockElement::SmartList((
-                list![list![
-                    MdLineFragment::OrderedListBullet {
-                        indent: 0,
-                        number: 1,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Plain("ol1"),
-                ],],
-                BulletKind::Ordered(1),
-                0,
-            )),
-            MdBlockElement::SmartList((
-                list![list![
-                    MdLineFragment::OrderedListBullet {
-                        indent: 0,
-                        number: 2,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Plain("ol2"),
-                ],],
-                BulletKind::Ordered(2),
-                0,
-            )),
-            MdBlockElement::SmartList((
-                list![list![
-                    MdLineFragment::UnorderedListBullet {
-                        indent: 0,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Checkbox(false),
-                    MdLineFragment::Plain(" todo"),
-                ],],
-                BulletKind::Unordered,
-                0,
-            )),
-            MdBlockElement::SmartList((
-                list![list![
-                    MdLineFragment::UnorderedListBullet {
-                        indent: 0,
-                        is_first_line: true
-                    },
-                    MdLineFragment::Checkbox(true),
-                    MdLineFragment::Plain(" done"),
-                ],],
-                BulletKind::Unordered,
-                0,
-            )),
+            MdBlockElement::SmartList(SmartListData {
+                indent: 0,
+                items: list![
+                    item("- ", list![MdLineFragment::Plain("ul1")]),
+                    item("- ", list![MdLineFragment::Plain("ul2")]),
+                ],
+            }),
+            MdBlockElement::SmartList(SmartListData {
+                indent: 0,
+                items: list![
+                    item("1. ", list![MdLineFragment::Plain("ol1")]),
+                    item("2. ", list![MdLineFragment::Plain("ol2")]),
+                ],
+            }),
+            MdBlockElement::SmartList(SmartListData {
+                indent: 0,
+                items: list![
+                    item(
+                        "- ",
+                        list![
+                            MdLineFragment::Checkbox(false),
+                            MdLineFragment::Plain(" todo"),
+                        ]
+                    ),
+                    item(
+                        "- ",
+                        list![
+                            MdLineFragment::Checkbox(true),
+                            MdLineFragment::Plain(" done"),
+                        ]
+                    ),
+                ],
+            }),
+            MdBlockElement::Text(list![]), // Empty line.
             MdBlockElement::Text(list![MdLineFragment::Plain("end")]),
         ];
 
         // Print a few of the last items.
-        for block in vec_block.iter().skip(vec_block.len() - 7) {
+        for block in vec_block.iter().skip(vec_block.len() - 5) {
             println!(
                 "{0} {1}",
                 "█ → ".magenta().bold(),
@@ -253,6 +264,7 @@ mod tests {
         }
 
         assert_eq2!(remainder, "");
+        assert_eq2!(vec_block.len(), expected_vec.len());
 
         vec_block
             .iter()
@@ -260,6 +272,129 @@ mod tests {
             .for_each(|(lhs, rhs)| assert_eq2!(lhs, rhs));
     }
 
+    #[test]
+    fn test_parse_markdown_block_quote_thematic_break_strikethrough() {
+        let input = [
+            "> quote",
+            "> > nested ~~quote~~",
+            "---",
+            "- item",
+            "* * *",
+            "~~gone~~ here",
+            "",
+        ]
+        .join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            blocks,
+            list![
+                MdBlockElement::BlockQuote(list![
+                    MdBlockElement::Text(list![MdLineFragment::Plain("quote")]),
+                    MdBlockElement::BlockQuote(list![MdBlockElement::Text(list![
+                        MdLineFragment::Plain("nested "),
+                        MdLineFragment::Strikethrough("quote"),
+                    ])]),
+                ]),
+                MdBlockElement::ThematicBreak("---"),
+                MdBlockElement::SmartList(SmartListData {
+                    indent: 0,
+                    items: list![SmartListItem {
+                        bullet_kind: BulletKind::Unordered,
+                        bullet_str: "- ",
+                        blocks: list![MdBlockElement::Text(list![
+                            MdLineFragment::Plain("item")
+                        ])],
+                        lazy_line_indices: list![],
+                    }],
+                }),
+                MdBlockElement::ThematicBreak("* * *"),
+                MdBlockElement::Text(list![
+                    MdLineFragment::Strikethrough("gone"),
+                    MdLineFragment::Plain(" here"),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_markdown_table() {
+        let input =
+            ["# Table", "| a | b |", "|---|:-:|", "| 1 | 2 |", "| no", ""].join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 3);
+        assert_eq2!(
+            blocks[1],
+            MdBlockElement::Table(TableData {
+                header_row: list![
+                    TableCell {
+                        text: " a ",
+                        fragments: list![MdLineFragment::Plain("a")],
+                    },
+                    TableCell {
+                        text: " b ",
+                        fragments: list![MdLineFragment::Plain("b")],
+                    },
+                ],
+                delimiter_row: "|---|:-:|",
+                alignments: list![
+                    TableColumnAlignment::None,
+                    TableColumnAlignment::Center
+                ],
+                body_rows: list![list![
+                    TableCell {
+                        text: " 1 ",
+                        fragments: list![MdLineFragment::Plain("1")],
+                    },
+                    TableCell {
+                        text: " 2 ",
+                        fragments: list![MdLineFragment::Plain("2")],
+                    },
+                ]],
+            })
+        );
+        assert_eq2!(
+            blocks[2],
+            MdBlockElement::Text(list![MdLineFragment::Plain("| no")])
+        );
+    }
+
+    #[test]
+    fn test_parse_markdown_front_matter() {
+        let input = [
+            "---",
+            "title: Foo",
+            "tags: a, b",
+            "---",
+            "# Heading",
+            "---",
+            "",
+        ]
+        .join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 3);
+        let MdBlockElement::FrontMatter(front_matter) = &blocks[0] else {
+            panic!("expected front matter, got: {:?}", blocks[0]);
+        };
+        assert_eq2!(front_matter.get_title(), Some("Foo"));
+        assert_eq2!(front_matter.get_tags(), Some(list!["a", "b"]));
+        assert_eq2!(blocks[2], MdBlockElement::ThematicBreak("---"));
+
+        // Front matter is only recognized at the start of the document.
+        let input = ["# Heading", "---", "title: Foo", "---", ""].join("\n");
+        let (_, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(blocks.len(), 4);
+        assert_eq2!(blocks[1], MdBlockElement::ThematicBreak("---"));
+
+        // Text between two thematic breaks isn't front matter.
+        let input = ["---", "Some text.", "---", ""].join("\n");
+        let (_, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(blocks.len(), 3);
+        assert_eq2!(blocks[0], MdBlockElement::ThematicBreak("---"));
+    }
+
     #[test]
     fn test_markdown_invalid() {
         let input = [
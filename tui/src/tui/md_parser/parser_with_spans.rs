@@ -0,0 +1,450 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module is responsible for parsing a Markdown document into blocks that know where
+//! they (and everything in them) came from in the source text.
+
+use constants::*;
+use nom::{combinator::*, multi::*, IResult};
+
+use crate::{md_parser::parse_element::parse_element_markdown_inline, *};
+
+/// Same as [parse_markdown], except that each block is paired w/ its [SourceSpan], and the
+/// spans of its fragments & (for blockquotes) of the blocks in it. Each block is exactly
+/// what [parse_markdown] produces for it. All the spans are relative to the `input`.
+pub fn parse_markdown_with_spans(input: &str) -> IResult<&str, MdSpannedDocument> {
+    let (remainder, maybe_first_block) = opt(consumed(parse_first_block))(input)?;
+    let (remainder, output) = parse_blocks_with_spans(input, remainder)?;
+    let mut it = List::with_capacity(output.len() + 1);
+    if let Some((text, block)) = maybe_first_block {
+        it += convert_to_spanned_block(input, text, block);
+    }
+    it += output;
+    Ok((remainder, it))
+}
+
+fn parse_blocks_with_spans<'a>(
+    source: &'a str,
+    input: &'a str,
+) -> IResult<&'a str, MdSpannedDocument<'a>> {
+    let (remainder, output) = many0(consumed(parse_block))(input)?;
+    let it = output
+        .into_iter()
+        .map(|(text, block)| convert_to_spanned_block(source, text, block))
+        .collect::<Vec<_>>();
+    Ok((remainder, List::from(it)))
+}
+
+fn convert_to_spanned_block<'a>(
+    source: &'a str,
+    text: &'a str,
+    block: MdBlockElement<'a>,
+) -> MdSpannedBlock<'a> {
+    let text_without_eol = text.strip_suffix(NEW_LINE).unwrap_or(text);
+
+    let (fragment_spans, child_blocks) = match &block {
+        MdBlockElement::Text(_) => {
+            let (_, spans) = get_fragment_spans(
+                source,
+                text_without_eol,
+                CheckboxParsePolicy::IgnoreCheckbox,
+            );
+            (list![spans], list![])
+        }
+        MdBlockElement::SmartList(list_data) => (
+            get_smart_list_fragment_spans(source, text, list_data),
+            list![],
+        ),
+        MdBlockElement::Table(_) => {
+            (get_table_fragment_spans(source, text_without_eol), list![])
+        }
+        MdBlockElement::BlockQuote(_) => {
+            (list![], get_block_quote_child_blocks(source, text))
+        }
+        _ => (list![], list![]),
+    };
+
+    MdSpannedBlock {
+        block,
+        span: SourceSpan::from_slice(source, text_without_eol),
+        fragment_spans,
+        child_blocks,
+    }
+}
+
+/// Returns the spans of the fragments that the `input` is parsed into, along w/ the
+/// remainder that can't be parsed.
+fn get_fragment_spans<'a>(
+    source: &str,
+    input: &'a str,
+    checkbox_policy: CheckboxParsePolicy,
+) -> (&'a str, List<SourceSpan>) {
+    match many0(consumed(|it: &'a str| {
+        parse_element_markdown_inline(it, checkbox_policy)
+    }))(input)
+    {
+        Ok((remainder, output)) => {
+            let spans = output
+                .into_iter()
+                .map(|(text, _)| SourceSpan::from_slice(source, text))
+                .collect::<Vec<_>>();
+            (remainder, List::from(spans))
+        }
+        Err(_) => (input, list![]),
+    }
+}
+
+/// Same as [get_fragment_spans], except that the remainder is kept as a fragment too, just
+/// like [parse_block_markdown_text_all] does.
+fn get_all_fragment_spans(source: &str, input: &str) -> List<SourceSpan> {
+    let (remainder, mut spans) =
+        get_fragment_spans(source, input, CheckboxParsePolicy::IgnoreCheckbox);
+    if !remainder.is_empty() {
+        spans.push(SourceSpan::from_slice(source, remainder));
+    }
+    spans
+}
+
+/// The lines are in the same order as the blocks of [SmartListData::flatten].
+fn get_smart_list_fragment_spans(
+    source: &str,
+    text: &str,
+    list_data: &SmartListData,
+) -> List<List<SourceSpan>> {
+    let mut lines = text.split(NEW_LINE);
+    let mut acc = List::new();
+    for it in list_data.flatten() {
+        match it.block {
+            // Code blocks don't have any fragments.
+            MdBlockElement::CodeBlock(code_block_lines) => {
+                for _ in lines.by_ref().take(code_block_lines.len()) {
+                    acc.push(list![]);
+                }
+            }
+            _ => {
+                let Some(line) = lines.next() else {
+                    break;
+                };
+                if !it.is_indented_in_source() {
+                    acc.push(if it.is_lazy {
+                        get_all_fragment_spans(source, line)
+                    } else {
+                        list![]
+                    });
+                    continue;
+                }
+
+                // The indent (along w/ the bullet on the first line of an item) is the
+                // first fragment in each line.
+                let content = &line[it.rest_line_prefix.chars().count()..];
+                let content_start_byte_index =
+                    SourceSpan::from_slice(source, content).start_byte_index;
+                let mut spans = list![SourceSpan::from_byte_range(
+                    source,
+                    content_start_byte_index - (line.len() - content.len()),
+                    content_start_byte_index
+                )];
+
+                let (_, content_spans) = get_fragment_spans(
+                    source,
+                    content,
+                    get_checkbox_parse_policy(content),
+                );
+                if content_spans.is_empty() {
+                    // An empty line has an empty fragment.
+                    spans.push(SourceSpan::from_byte_range(
+                        source,
+                        content_start_byte_index,
+                        content_start_byte_index,
+                    ));
+                } else {
+                    spans += content_spans;
+                }
+
+                acc.push(spans);
+            }
+        }
+    }
+    acc
+}
+
+fn get_table_fragment_spans(source: &str, text: &str) -> List<List<SourceSpan>> {
+    let mut acc = List::new();
+    for (row_index, line) in text.split(NEW_LINE).enumerate() {
+        let mut it = List::new();
+        // The delimiter row doesn't have any fragments.
+        if row_index != 1 {
+            for cell in split_table_row(line).unwrap_or_default() {
+                it += get_all_fragment_spans(source, cell.trim());
+            }
+        }
+        acc.push(it);
+    }
+    acc
+}
+
+/// This mirrors what [parse_block_quote] does, so that the child blocks are the same as the
+/// blocks in the blockquote.
+fn get_block_quote_child_blocks<'a>(
+    source: &'a str,
+    text: &'a str,
+) -> List<MdSpannedBlock<'a>> {
+    let Ok((_, lines)) = many1(parse_block_quote_line)(text) else {
+        return list![];
+    };
+
+    let mut acc = List::new();
+    for line in lines {
+        for spanned_block in get_block_quote_line_content(source, line).items {
+            push_spanned_block(&mut acc, spanned_block);
+        }
+    }
+    acc
+}
+
+fn get_block_quote_line_content<'a>(
+    source: &'a str,
+    line: &'a str,
+) -> List<MdSpannedBlock<'a>> {
+    if let Ok((remainder, spanned_blocks)) = parse_blocks_with_spans(source, line) {
+        if remainder.is_empty() && !spanned_blocks.is_empty() {
+            return spanned_blocks;
+        }
+    }
+
+    let text = line.strip_suffix(NEW_LINE).unwrap_or(line);
+    list![MdSpannedBlock {
+        block: MdBlockElement::Text(parse_block_markdown_text_all(text)),
+        span: SourceSpan::from_slice(source, text),
+        fragment_spans: list![get_all_fragment_spans(source, text)],
+        child_blocks: list![],
+    }]
+}
+
+/// Consecutive nested blockquotes are merged into one (at every level of nesting).
+fn push_spanned_block<'a>(
+    acc: &mut List<MdSpannedBlock<'a>>,
+    spanned_block: MdSpannedBlock<'a>,
+) {
+    let is_block_quote = matches!(spanned_block.block, MdBlockElement::BlockQuote(_));
+    if let Some(last) = acc.last_mut() {
+        if is_block_quote && matches!(last.block, MdBlockElement::BlockQuote(_)) {
+            last.span = SourceSpan {
+                end_byte_index: spanned_block.span.end_byte_index,
+                end: spanned_block.span.end,
+                ..last.span
+            };
+            for child_block in spanned_block.child_blocks.items {
+                push_spanned_block(&mut last.child_blocks, child_block);
+            }
+            let blocks = last
+                .child_blocks
+                .iter()
+                .map(|it| it.block.clone())
+                .collect::<Vec<_>>();
+            last.block = MdBlockElement::BlockQuote(List::from(blocks));
+            return;
+        }
+    }
+
+    acc.push(spanned_block);
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    fn get_texts<'a>(source: &'a str, spans: &[SourceSpan]) -> Vec<&'a str> {
+        spans.iter().map(|it| it.get_text(source)).collect()
+    }
+
+    #[test]
+    fn test_parse_markdown_with_spans_same_blocks() {
+        let input = [
+            "@title: Something",
+            "# Heading",
+            "> quote",
+            "> > nested",
+            "- [x] *done*",
+            "  more",
+            "| a | b |",
+            "|---|---|",
+            "```rs",
+            "let a = 1;",
+            "```",
+            "end",
+            "",
+        ]
+        .join("\n");
+        let (remainder, spanned_blocks) = parse_markdown_with_spans(&input).unwrap();
+        let (_, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            List::from(
+                spanned_blocks
+                    .iter()
+                    .map(|it| it.block.clone())
+                    .collect::<Vec<_>>()
+            ),
+            blocks
+        );
+        assert_eq2!(
+            spanned_blocks
+                .iter()
+                .map(|it| it.span.get_text(&input))
+                .collect::<Vec<_>>(),
+            vec![
+                "@title: Something",
+                "# Heading",
+                "> quote\n> > nested",
+                "- [x] *done*\n  more",
+                "| a | b |\n|---|---|",
+                "```rs\nlet a = 1;\n```",
+                "end",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_markdown_with_spans_smart_list() {
+        let input = "# h\n- [x] *a* b\n  c\n";
+        let (_, spanned_blocks) = parse_markdown_with_spans(input).unwrap();
+        assert_eq2!(spanned_blocks.len(), 2);
+
+        let heading = &spanned_blocks[0];
+        assert_eq2!(heading.span.start, position!(col_index: 0, row_index: 0));
+        assert_eq2!(heading.span.end, position!(col_index: 3, row_index: 0));
+        assert_eq2!(heading.fragment_spans.len(), 0);
+
+        let smart_list = &spanned_blocks[1];
+        assert_eq2!(smart_list.span.start, position!(col_index: 0, row_index: 1));
+        assert_eq2!(smart_list.span.end, position!(col_index: 3, row_index: 2));
+        assert_eq2!(smart_list.fragment_spans.len(), 2);
+        assert_eq2!(
+            get_texts(input, &smart_list.fragment_spans[0]),
+            vec!["- ", "[x]", " ", "*a*", " b"]
+        );
+        assert_eq2!(
+            get_texts(input, &smart_list.fragment_spans[1]),
+            vec!["  ", "c"]
+        );
+        assert_eq2!(
+            smart_list.fragment_spans[1][1].start,
+            position!(col_index: 2, row_index: 2)
+        );
+
+        // The number of fragments matches the block (w/ the bullet).
+        let MdBlockElement::SmartList(list_data) = &smart_list.block else {
+            panic!("Expected a smart list");
+        };
+        for (line_index, it) in list_data.flatten().iter().enumerate() {
+            let MdBlockElement::Text(fragments) = it.block else {
+                panic!("Expected a line of text");
+            };
+            assert_eq2!(
+                fragments.len() + 1,
+                smart_list.fragment_spans[line_index].len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_markdown_with_spans_smart_list_wo_trailing_new_line() {
+        for input in ["- a", "1. ", "# h\n- [ ] task"] {
+            let (remainder, spanned_blocks) = parse_markdown_with_spans(input).unwrap();
+            let (_, blocks) = parse_markdown(input).unwrap();
+            assert_eq2!(remainder, "");
+            assert_eq2!(spanned_blocks.len(), blocks.len());
+            let smart_list = &spanned_blocks[spanned_blocks.len() - 1];
+            assert_eq2!(
+                smart_list.span.get_text(input),
+                input.lines().last().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_markdown_with_spans_front_matter_wo_trailing_new_line() {
+        let input = "---\ntitle: Foo\n---";
+        let (remainder, spanned_blocks) = parse_markdown_with_spans(input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(spanned_blocks.len(), 1);
+        assert_eq2!(spanned_blocks[0].span.get_text(input), input);
+    }
+
+    #[test]
+    fn test_parse_markdown_with_spans_nested_smart_list() {
+        let input = "- a\n\n  ```\n  b\n  ```\n  1. c\nd\n";
+        let (_, spanned_blocks) = parse_markdown_with_spans(input).unwrap();
+        assert_eq2!(spanned_blocks.len(), 1);
+
+        let fragment_spans = &spanned_blocks[0].fragment_spans;
+        assert_eq2!(fragment_spans.len(), 7);
+        assert_eq2!(get_texts(input, &fragment_spans[0]), vec!["- ", "a"]);
+        assert_eq2!(fragment_spans[1].len(), 0);
+        assert_eq2!(fragment_spans[2].len(), 0);
+        assert_eq2!(fragment_spans[4].len(), 0);
+        assert_eq2!(get_texts(input, &fragment_spans[5]), vec!["  1. ", "c"]);
+        assert_eq2!(get_texts(input, &fragment_spans[6]), vec!["d"]);
+    }
+
+    #[test]
+    fn test_parse_markdown_with_spans_block_quote() {
+        let input = "> a\n> > b\n> > c *d\n";
+        let (_, spanned_blocks) = parse_markdown_with_spans(input).unwrap();
+        assert_eq2!(spanned_blocks.len(), 1);
+
+        let block_quote = &spanned_blocks[0];
+        assert_eq2!(block_quote.child_blocks.len(), 2);
+        assert_eq2!(block_quote.child_blocks[0].span.get_text(input), "a");
+
+        let nested = &block_quote.child_blocks[1];
+        assert_eq2!(nested.span.get_text(input), "> b\n> > c *d");
+        assert_eq2!(nested.span.start, position!(col_index: 2, row_index: 1));
+        assert_eq2!(
+            nested
+                .child_blocks
+                .iter()
+                .map(|it| get_texts(input, &it.fragment_spans[0]))
+                .collect::<Vec<_>>(),
+            vec![vec!["b"], vec!["c ", "*d"]]
+        );
+    }
+
+    #[test]
+    fn test_parse_markdown_with_spans_table() {
+        let input = "| a | *b* |\n|---|---|\n| 1 | 😀 |\n";
+        let (_, spanned_blocks) = parse_markdown_with_spans(input).unwrap();
+        assert_eq2!(spanned_blocks.len(), 1);
+
+        let fragment_spans = &spanned_blocks[0].fragment_spans;
+        assert_eq2!(
+            fragment_spans
+                .iter()
+                .map(|it| get_texts(input, it))
+                .collect::<Vec<_>>(),
+            vec![vec!["a", "*b*"], vec![], vec!["1", "😀"]]
+        );
+        // Display width, not byte index.
+        assert_eq2!(
+            fragment_spans[2][1].end,
+            position!(col_index: 8, row_index: 2)
+        );
+    }
+}
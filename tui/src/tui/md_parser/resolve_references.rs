@@ -0,0 +1,198 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module is responsible for resolving the [MdLineFragment::ReferenceLink]s &
+//! [MdLineFragment::FootnoteReference]s in a [MdDocument] against the
+//! [MdBlockElement::LinkDefinition]s & [MdBlockElement::FootnoteDefinition]s in it, which
+//! can be anywhere in the document (eg: at the end).
+
+use crate::*;
+
+impl Default for MdReferences<'_> {
+    fn default() -> Self {
+        Self {
+            link_urls: Default::default(),
+            footnotes: List::new(),
+        }
+    }
+}
+
+impl<'a> MdReferences<'a> {
+    pub fn from_document(document: &[MdBlockElement<'a>]) -> Self {
+        let mut footnote_definitions = vec![];
+        let mut footnote_reference_labels = vec![];
+        let mut acc = Self::default();
+        acc.collect(
+            document,
+            &mut footnote_definitions,
+            &mut footnote_reference_labels,
+        );
+
+        // Number the footnotes in the order that they are first referenced in.
+        for label in footnote_reference_labels {
+            if let Some(index) = footnote_definitions
+                .iter()
+                .position(|it: &FootnoteDefinitionData| it.label == label)
+            {
+                acc.footnotes.push(footnote_definitions.remove(index));
+            }
+        }
+        acc.footnotes += footnote_definitions;
+
+        acc
+    }
+
+    /// Returns the URL of the [MdBlockElement::LinkDefinition] w/ the label.
+    pub fn get_link_url(&self, label: &str) -> Option<&'a str> {
+        self.link_urls.get(&label.to_lowercase()).copied()
+    }
+
+    /// Returns the URL of the reference link, if its label is defined.
+    pub fn get_reference_link_url(
+        &self,
+        link_data: &ReferenceLinkData,
+    ) -> Option<&'a str> {
+        self.get_link_url(link_data.get_label())
+    }
+
+    /// Returns the number (starting from 1) of the [MdBlockElement::FootnoteDefinition] w/
+    /// the label.
+    pub fn get_footnote_number(&self, label: &str) -> Option<usize> {
+        self.footnotes
+            .iter()
+            .position(|it| it.label == label)
+            .map(|index| index + 1)
+    }
+
+    fn collect(
+        &mut self,
+        blocks: &[MdBlockElement<'a>],
+        footnote_definitions: &mut Vec<FootnoteDefinitionData<'a>>,
+        footnote_reference_labels: &mut Vec<&'a str>,
+    ) {
+        for block in blocks {
+            match block {
+                MdBlockElement::LinkDefinition(LinkDefinitionData {
+                    label, url, ..
+                }) => {
+                    let url = url
+                        .strip_prefix('<')
+                        .and_then(|it| it.strip_suffix('>'))
+                        .unwrap_or(url);
+                    self.link_urls.entry(label.to_lowercase()).or_insert(url);
+                }
+                MdBlockElement::FootnoteDefinition(footnote_definition) => {
+                    collect_labels(
+                        &footnote_definition.fragments,
+                        footnote_reference_labels,
+                    );
+                    if !footnote_definitions
+                        .iter()
+                        .any(|it| it.label == footnote_definition.label)
+                    {
+                        footnote_definitions.push(footnote_definition.clone());
+                    }
+                }
+                MdBlockElement::Text(fragments) => {
+                    collect_labels(fragments, footnote_reference_labels)
+                }
+                MdBlockElement::SmartList(list_data) => {
+                    for item in list_data.items.iter() {
+                        self.collect(
+                            &item.blocks,
+                            footnote_definitions,
+                            footnote_reference_labels,
+                        );
+                    }
+                }
+                MdBlockElement::Table(table_data) => {
+                    for row in std::iter::once(&table_data.header_row)
+                        .chain(&table_data.body_rows.items)
+                    {
+                        for cell in row.iter() {
+                            collect_labels(&cell.fragments, footnote_reference_labels);
+                        }
+                    }
+                }
+                MdBlockElement::BlockQuote(blocks) => {
+                    self.collect(blocks, footnote_definitions, footnote_reference_labels)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn collect_labels<'a>(fragments: &[MdLineFragment<'a>], acc: &mut Vec<&'a str>) {
+    for fragment in fragments {
+        if let MdLineFragment::FootnoteReference(label) = fragment {
+            if !acc.contains(label) {
+                acc.push(*label);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_md_references() {
+        let input = [
+            "See [a][R3BL], [b][] & the notes[^y] [^x].",
+            "> [^x]: Note *x*.",
+            "[r3bl]: https://r3bl.com",
+            "[R3BL]: https://example.com",
+            "[B]:<https://b.dev>",
+            "[^y]: Note y.",
+            "[^z]: Note z.",
+            "",
+        ]
+        .join("\n");
+        let (_, document) = parse_markdown(&input).unwrap();
+
+        let references = MdReferences::from_document(&document);
+
+        assert_eq2!(
+            references.get_reference_link_url(&ReferenceLinkData::new("a", "R3BL")),
+            Some("https://r3bl.com")
+        );
+        assert_eq2!(
+            references.get_reference_link_url(&ReferenceLinkData::new("b", "")),
+            Some("https://b.dev")
+        );
+        assert_eq2!(
+            references.get_reference_link_url(&ReferenceLinkData::new("c", "")),
+            None
+        );
+        assert_eq2!(references.get_footnote_number("y"), Some(1));
+        assert_eq2!(references.get_footnote_number("x"), Some(2));
+        assert_eq2!(references.get_footnote_number("z"), Some(3));
+        assert_eq2!(references.get_footnote_number("w"), None);
+        assert_eq2!(
+            references.footnotes[1].fragments,
+            list![
+                MdLineFragment::Plain(" Note "),
+                MdLineFragment::Italic("x"),
+                MdLineFragment::Plain("."),
+            ]
+        );
+    }
+}
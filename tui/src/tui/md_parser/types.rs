@@ -15,6 +15,10 @@
  *   limitations under the License.
  */
 
+use std::{collections::HashMap, path::PathBuf};
+
+use r3bl_rs_utils_core::Position;
+
 use crate::{BulletKind, List};
 
 /// This corresponds to a single Markdown document, which is produced after a successful parse
@@ -48,36 +52,100 @@ pub struct HeadingData<'a> {
 #[derive(Clone, Debug, PartialEq)]
 pub enum MdBlockElement<'a> {
     Heading(HeadingData<'a>),
-    SmartList((Lines<'a>, BulletKind, usize)),
+    /// A list, whose items hold the blocks in them (including the lists that are nested in
+    /// them), so this is a tree.
+    SmartList(SmartListData<'a>),
     Text(MdLineFragments<'a>),
     CodeBlock(List<CodeBlockLine<'a>>),
     Title(&'a str),
     Date(&'a str),
     Tags(List<&'a str>),
     Authors(List<&'a str>),
+    /// A `>` blockquote, which contains the blocks that are parsed from its lines (after
+    /// their `> ` prefix is removed). Nested blockquotes are blocks in here too.
+    BlockQuote(List<MdBlockElement<'a>>),
+    /// A `---`, `***` or `___` line (aka horizontal rule). This is the text of the line.
+    ThematicBreak(&'a str),
+    /// A GitHub flavored Markdown table. Each of its rows is a line of text.
+    Table(TableData<'a>),
+    /// The YAML (`---`) or TOML (`+++`) front matter, which can only be at the start of
+    /// the document. Its `title`, `tags`, `authors` & `date` keys are the same as the
+    /// [MdBlockElement::Title], [MdBlockElement::Tags], [MdBlockElement::Authors] &
+    /// [MdBlockElement::Date] metadata.
+    FrontMatter(FrontMatterData<'a>),
+    /// A `[label]: url` line, which is the URL of the [MdLineFragment::ReferenceLink]s w/
+    /// the same label (anywhere in the document).
+    LinkDefinition(LinkDefinitionData<'a>),
+    /// A `[^label]: text` line, which is the text of the
+    /// [MdLineFragment::FootnoteReference]s w/ the same label.
+    FootnoteDefinition(FootnoteDefinitionData<'a>),
 }
 
 /// These are things that show up in a single line of Markdown text [MdLineFragments]. They do not
 /// include other Markdown blocks (like code blocks, lists, headings, etc).
 #[derive(Clone, Debug, PartialEq)]
 pub enum MdLineFragment<'a> {
-    UnorderedListBullet {
-        indent: usize,
-        is_first_line: bool,
-    },
-    OrderedListBullet {
-        indent: usize,
-        number: usize,
-        is_first_line: bool,
-    },
     Plain(&'a str),
     Bold(&'a str),
     Italic(&'a str),
     BoldItalic(&'a str),
+    Strikethrough(&'a str),
     InlineCode(&'a str),
     Link(HyperlinkData<'a>),
     Image(HyperlinkData<'a>),
     Checkbox(bool),
+    /// `[text][label]`, or `[text][]` (where the label is the text). The URL comes from the
+    /// [MdBlockElement::LinkDefinition] w/ the same label, please take a look at
+    /// [MdReferences].
+    ReferenceLink(ReferenceLinkData<'a>),
+    /// A bare `http://` or `https://` URL.
+    Autolink(&'a str),
+    /// `[^label]`, which refers to the [MdBlockElement::FootnoteDefinition] w/ the same
+    /// label.
+    FootnoteReference(&'a str),
+}
+
+/// The items of a list all have the same indent, and are all ordered or all unordered (a
+/// different kind of bullet starts another list).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmartListData<'a> {
+    /// Spaces before the bullet of each item. This is from the start of the line, even for
+    /// the lists that are nested in an item.
+    pub indent: usize,
+    pub items: List<SmartListItem<'a>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmartListItem<'a> {
+    pub bullet_kind: BulletKind,
+    /// The bullet as it is in the source text, eg: `- ` or `10. `. The lines of the item
+    /// after its first one are indented by the indent of the list plus the width of the
+    /// bullet, so that they line up w/ the text after it.
+    pub bullet_str: &'a str,
+    /// The blocks in the item, w/out the indent at the start of their lines:
+    /// 1. The first one is the [MdBlockElement::Text] after the bullet.
+    /// 2. The rest are more lines of text ([MdBlockElement::Text] w/ no fragments for each
+    ///    blank line), code blocks, and nested lists.
+    pub blocks: List<MdBlockElement<'a>>,
+    /// The indices (in `blocks`) of the lazy continuation lines, which are lines of text
+    /// that aren't indented at all (they are kept as is, along w/ any spaces at their
+    /// start).
+    pub lazy_line_indices: List<usize>,
+}
+
+/// A block in a [SmartListData] (w/ the nested lists flattened out), along w/ what goes in
+/// front of its lines when it is displayed. This is produced by [SmartListData::flatten].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmartListBlock<'b, 'a> {
+    pub block: &'b MdBlockElement<'a>,
+    /// Goes in front of the first line of the block. For the first block of an item, this
+    /// is its bullet (eg: `───┤` or `  1.│`), otherwise it's the same as
+    /// `rest_line_prefix`.
+    pub first_line_prefix: String,
+    /// Goes in front of the rest of the lines. These are the guides (eg: ` │ │`) of the
+    /// item that the block is in, and of the items that it is nested in.
+    pub rest_line_prefix: String,
+    pub is_lazy: bool,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -86,6 +154,197 @@ pub struct HyperlinkData<'a> {
     pub url: &'a str,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReferenceLinkData<'a> {
+    pub text: &'a str,
+    /// This is empty for `[text][]`, since the label is the text.
+    pub label: &'a str,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkDefinitionData<'a> {
+    pub label: &'a str,
+    /// The `:` & the spaces after it, which are kept so that the line can be highlighted
+    /// as is.
+    pub separator: &'a str,
+    pub url: &'a str,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FootnoteDefinitionData<'a> {
+    pub label: &'a str,
+    /// The text after the `:`, including the spaces at its start.
+    pub fragments: MdLineFragments<'a>,
+}
+
+/// The link definitions & footnote definitions of a whole [MdDocument] (including the ones
+/// in blockquotes), which the [MdLineFragment::ReferenceLink]s &
+/// [MdLineFragment::FootnoteReference]s in it are resolved against. Labels are case
+/// insensitive, and the first definition of a label wins.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MdReferences<'a> {
+    /// The URL of each (lowercase) label.
+    pub link_urls: HashMap<String, &'a str>,
+    /// The footnote definitions, numbered (from 1) in the order that they are first
+    /// referenced in, followed by the ones that are never referenced.
+    pub footnotes: List<FootnoteDefinitionData<'a>>,
+}
+
+/// The outline (table of contents) of a Markdown document, which is made up of its
+/// headings, in the same order as they are in the document.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct MdOutline {
+    pub entries: Vec<MdOutlineEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MdOutlineEntry {
+    pub level: HeadingLevel,
+    pub text: String,
+    /// The index of the line that the heading is on, in the source text.
+    pub line_index: usize,
+}
+
+/// A task in a Markdown document, which is a line in a list that starts w/ a checkbox, eg:
+/// `- [ ] todo` or `1. [x] done`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MdTask {
+    pub is_done: bool,
+    /// The text after the checkbox, in Markdown.
+    pub text: String,
+    /// The span of the checkbox (`[ ]` or `[x]`) in the source text.
+    pub checkbox_span: SourceSpan,
+}
+
+/// The number of tasks that are done, out of all the tasks (eg: in a section).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct MdTaskProgress {
+    pub done_count: usize,
+    pub total_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MdSectionTaskProgress {
+    /// The heading that starts the section, or [None] for the lines before the first
+    /// heading.
+    pub maybe_heading: Option<MdOutlineEntry>,
+    pub progress: MdTaskProgress,
+}
+
+/// A task in a Markdown file, eg: returned by [crate::collect_open_tasks_in_dir].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MdFileTask {
+    pub file_path: PathBuf,
+    pub task: MdTask,
+}
+
+/// A table is made up of a header row, a delimiter row (which holds the alignment of each
+/// column, eg: `|:---|:---:|---:|`), and any number of body rows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableData<'a> {
+    pub header_row: TableRow<'a>,
+    /// The text of the delimiter row (w/out the EOL).
+    pub delimiter_row: &'a str,
+    pub alignments: List<TableColumnAlignment>,
+    pub body_rows: List<TableRow<'a>>,
+}
+
+/// Alias for [List] of [TableCell].
+pub type TableRow<'a> = List<TableCell<'a>>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableCell<'a> {
+    /// The text between the two `|` separators, including any padding.
+    pub text: &'a str,
+    /// The inline fragments of the text (w/out the padding).
+    pub fragments: MdLineFragments<'a>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum TableColumnAlignment {
+    /// `---`
+    #[default]
+    None,
+    /// `:---`
+    Left,
+    /// `:---:`
+    Center,
+    /// `---:`
+    Right,
+}
+
+/// The front matter is made up of the lines between the fences, which hold key value
+/// pairs, eg: `title: Foo` (YAML) or `title = "Foo"` (TOML).
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrontMatterData<'a> {
+    pub kind: FrontMatterKind,
+    /// The lines between the fences (w/out the EOL).
+    pub lines: List<&'a str>,
+    /// The top level key value pairs in the lines.
+    pub entries: List<FrontMatterEntry<'a>>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FrontMatterKind {
+    /// `---`
+    Yaml,
+    /// `+++`
+    Toml,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrontMatterEntry<'a> {
+    pub key: &'a str,
+    pub value: FrontMatterValue<'a>,
+}
+
+/// Quotes around strings are removed, but escape sequences in them are kept as is.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FrontMatterValue<'a> {
+    Text(&'a str),
+    /// Eg: `[a, b]`, or the `- a` lines after the key (YAML).
+    List(List<&'a str>),
+}
+
+/// The range of the source text that a node of the AST is parsed from. It is stored both as
+/// a byte range (to slice the source text), and as the [Position]s (row index & display col
+/// index, just like the caret in the editor component) of its start & end.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct SourceSpan {
+    pub start_byte_index: usize,
+    /// Exclusive.
+    pub end_byte_index: usize,
+    pub start: Position,
+    /// Exclusive.
+    pub end: Position,
+}
+
+/// A [MdBlockElement] along w/ the [SourceSpan]s of itself & everything in it. This is
+/// produced by [crate::parse_markdown_with_spans].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MdSpannedBlock<'a> {
+    pub block: MdBlockElement<'a>,
+    /// The span of the whole block (w/out the EOL at the end of its last line).
+    pub span: SourceSpan,
+    /// The spans of the fragments in the block, one [List] per line, in the same order as
+    /// the fragments in the block.
+    /// - [MdBlockElement::Text] has one line.
+    /// - [MdBlockElement::SmartList] has one line per line of the list, in the same order
+    ///   as [SmartListData::flatten]. The first span of each line is its bullet (or the
+    ///   indent of the text after it), including the indent before it. Lazy continuation
+    ///   lines have no such span, and blank lines & the lines of code blocks have no spans.
+    /// - [MdBlockElement::Table] has one line per row (including the delimiter row, which
+    ///   has no fragments), w/ the fragments of all the cells in the row.
+    /// - All the other blocks don't have any.
+    pub fragment_spans: List<List<SourceSpan>>,
+    /// The blocks inside a [MdBlockElement::BlockQuote], in the same order as they are in
+    /// the blockquote.
+    pub child_blocks: List<MdSpannedBlock<'a>>,
+}
+
+/// Alias for [List] of [MdSpannedBlock].
+pub type MdSpannedDocument<'a> = List<MdSpannedBlock<'a>>;
+
 mod hyperlink_data_impl {
     use super::*;
 
@@ -98,6 +357,196 @@ mod hyperlink_data_impl {
     }
 }
 
+mod smart_list_data_impl {
+    use std::iter;
+
+    use super::{constants::*, *};
+
+    impl<'a> SmartListData<'a> {
+        /// Returns the blocks of all the items (and of the lists nested in them), in the
+        /// same order as their lines are in the source text. The prefixes are as wide as the
+        /// indent & bullet of the lines in the source text:
+        /// - The bullet of an unordered list item is `─┤` w/ a `─` for each space of indent
+        ///   before it, and that of an ordered list item is eg: `1.│`.
+        /// - The guide of each item is a `│` that lines up w/ the end of its bullet.
+        pub fn flatten(&self) -> Vec<SmartListBlock<'_, 'a>> {
+            let mut acc = vec![];
+            self.flatten_into(&SPACE.repeat(self.indent), &mut acc);
+            acc
+        }
+
+        /// The `guides` are as wide as the indent of the list.
+        fn flatten_into<'b>(
+            &'b self,
+            guides: &str,
+            acc: &mut Vec<SmartListBlock<'b, 'a>>,
+        ) {
+            for item in self.items.iter() {
+                let first_line_prefix = match item.bullet_kind {
+                    BulletKind::Ordered(_) => format!(
+                        "{guides}{}{LIST_SPACE_END_DISPLAY_REST_LINE}",
+                        item.bullet_str.trim_end()
+                    ),
+                    BulletKind::Unordered => format!(
+                        "{}{LIST_SPACE_END_DISPLAY_FIRST_LINE}",
+                        LIST_SPACE_DISPLAY.repeat(self.indent + 1)
+                    ),
+                };
+                let rest_line_prefix = format!(
+                    "{guides}{}{LIST_SPACE_END_DISPLAY_REST_LINE}",
+                    SPACE.repeat(item.bullet_str.len() - 1)
+                );
+
+                for (index, block) in item.blocks.iter().enumerate() {
+                    if let MdBlockElement::SmartList(nested_list) = block {
+                        let nested_guides = rest_line_prefix
+                            .chars()
+                            .chain(iter::repeat(SPACE_CHAR))
+                            .take(nested_list.indent)
+                            .collect::<String>();
+                        nested_list.flatten_into(&nested_guides, acc);
+                        continue;
+                    }
+                    acc.push(SmartListBlock {
+                        block,
+                        first_line_prefix: if index == 0 {
+                            first_line_prefix.clone()
+                        } else {
+                            rest_line_prefix.clone()
+                        },
+                        rest_line_prefix: rest_line_prefix.clone(),
+                        is_lazy: item.lazy_line_indices.contains(&index),
+                    });
+                }
+            }
+        }
+    }
+
+    impl<'b, 'a> SmartListBlock<'b, 'a> {
+        /// Lazy continuation lines & blank lines aren't indented in the source text, so
+        /// the prefixes don't go in front of them (when the widths of the lines have to
+        /// match the source text).
+        pub fn is_indented_in_source(&self) -> bool {
+            !self.is_lazy
+                && !matches!(self.block, MdBlockElement::Text(fragments) if fragments.is_empty())
+        }
+    }
+}
+
+mod reference_link_data_impl {
+    use super::*;
+
+    impl<'a> ReferenceLinkData<'a> {
+        pub fn new(text: &'a str, label: &'a str) -> Self { Self { text, label } }
+
+        /// The label that is used to look up the URL, which is the text for `[text][]`.
+        pub fn get_label(&self) -> &'a str {
+            if self.label.is_empty() {
+                self.text
+            } else {
+                self.label
+            }
+        }
+    }
+}
+
+mod front_matter_data_impl {
+    use super::{constants::*, *};
+
+    impl<'a> FrontMatterData<'a> {
+        pub fn get(&self, key: &str) -> Option<&FrontMatterValue<'a>> {
+            self.entries
+                .iter()
+                .find(|entry| entry.key == key)
+                .map(|entry| &entry.value)
+        }
+
+        /// Same as [MdBlockElement::Title].
+        pub fn get_title(&self) -> Option<&'a str> {
+            self.get_text(FRONT_MATTER_TITLE_KEY)
+        }
+
+        /// Same as [MdBlockElement::Date].
+        pub fn get_date(&self) -> Option<&'a str> { self.get_text(FRONT_MATTER_DATE_KEY) }
+
+        /// Same as [MdBlockElement::Tags]. A text value is split by commas, eg: `tags: a, b`.
+        pub fn get_tags(&self) -> Option<List<&'a str>> {
+            self.get_list(FRONT_MATTER_TAGS_KEY)
+        }
+
+        /// Same as [MdBlockElement::Authors]. A text value is split by commas.
+        pub fn get_authors(&self) -> Option<List<&'a str>> {
+            self.get_list(FRONT_MATTER_AUTHORS_KEY)
+        }
+
+        fn get_text(&self, key: &str) -> Option<&'a str> {
+            match self.get(key)? {
+                FrontMatterValue::Text(text) => Some(*text),
+                FrontMatterValue::List(_) => None,
+            }
+        }
+
+        fn get_list(&self, key: &str) -> Option<List<&'a str>> {
+            match self.get(key)? {
+                FrontMatterValue::Text(text) => Some(List::from(
+                    text.split(COMMA)
+                        .map(str::trim)
+                        .filter(|it| !it.is_empty())
+                        .collect::<Vec<_>>(),
+                )),
+                FrontMatterValue::List(items) => Some(items.clone()),
+            }
+        }
+    }
+}
+
+mod source_span_impl {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    impl SourceSpan {
+        /// The `slice` must be a sub slice of the `source`, which is the case for all the
+        /// string slices in the AST, since the parser doesn't make any copies.
+        pub fn from_slice(source: &str, slice: &str) -> Self {
+            let start_byte_index = slice.as_ptr() as usize - source.as_ptr() as usize;
+            debug_assert!(start_byte_index + slice.len() <= source.len());
+            Self::from_byte_range(
+                source,
+                start_byte_index,
+                start_byte_index + slice.len(),
+            )
+        }
+
+        pub fn from_byte_range(
+            source: &str,
+            start_byte_index: usize,
+            end_byte_index: usize,
+        ) -> Self {
+            Self {
+                start_byte_index,
+                end_byte_index,
+                start: get_position(source, start_byte_index),
+                end: get_position(source, end_byte_index),
+            }
+        }
+
+        /// Returns the text in the `source` that this span covers.
+        pub fn get_text<'a>(&self, source: &'a str) -> &'a str {
+            &source[self.start_byte_index..self.end_byte_index]
+        }
+    }
+
+    fn get_position(source: &str, byte_index: usize) -> Position {
+        let text_before = &source[..byte_index];
+        let row_index = text_before.matches('\n').count();
+        let line_start_byte_index = text_before.rfind('\n').map_or(0, |it| it + 1);
+        let col_index =
+            UnicodeString::str_display_width(&text_before[line_start_byte_index..]);
+        position!(col_index: ch!(col_index), row_index: ch!(row_index))
+    }
+}
+
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum HeadingLevel {
@@ -161,6 +610,7 @@ pub mod constants {
     pub const BOLD_2: &str = "__";
     pub const ITALIC_1: &str = "*";
     pub const ITALIC_2: &str = "_";
+    pub const STRIKETHROUGH: &str = "~~";
     pub const BACK_TICK: &str = "`";
     pub const LEFT_BRACKET: &str = "[";
     pub const RIGHT_BRACKET: &str = "]";
@@ -171,12 +621,54 @@ pub mod constants {
     pub const NEW_LINE: &str = "\n";
     pub const CODE_BLOCK_START_PARTIAL: &str = "```";
     pub const CODE_BLOCK_END: &str = "```";
+    pub const FOOTNOTE_PREFIX: &str = "[^";
+    pub const AUTOLINK_PREFIXES: [&str; 2] = ["https://", "http://"];
+    /// These aren't part of an autolink when they are at its end, eg: `see https://r3bl.com.`
+    pub const AUTOLINK_TRAILING_PUNCTUATION: [char; 9] =
+        ['.', ',', ':', ';', '!', '?', '\'', '"', ')'];
+    /// Only for output to terminal, the [OSC 8](https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda)
+    /// escape sequence that starts a hyperlink (followed by the URL & [OSC_8_END]), and ends
+    /// it (followed by [OSC_8_END]).
+    pub const OSC_8_START: &str = "\x1b]8;;";
+    pub const OSC_8_END: &str = "\x1b\\";
     pub const CHECKED: &str = "[x]";
     pub const UNCHECKED: &str = "[ ]";
     pub const CHECKED_OUTPUT: &str = "┊✔┊";
     pub const UNCHECKED_OUTPUT: &str = "┊┈┊";
 
     pub const MAX_HEADING_LEVEL: usize = 6;
+
+    pub const BLOCK_QUOTE: &str = ">";
+    pub const BLOCK_QUOTE_PREFIX: &str = "> ";
+    pub const THEMATIC_BREAK_CHARS: [char; 3] = ['-', '*', '_'];
+    pub const MIN_THEMATIC_BREAK_CHAR_COUNT: usize = 3;
+    pub const MAX_THEMATIC_BREAK_INDENT: usize = 3;
+
+    /// Only for output to terminal.
+    pub const THEMATIC_BREAK_DISPLAY: &str = "─";
+
+    pub const TABLE_SEPARATOR: &str = "|";
+    pub const TABLE_SEPARATOR_CHAR: char = '|';
+    pub const TABLE_DELIMITER_CHAR: char = '-';
+    pub const TABLE_ALIGNMENT_CHAR: char = ':';
+    pub const TABLE_ESCAPE_CHAR: char = '\\';
+
+    pub const FRONT_MATTER_YAML_FENCE: &str = "---";
+    pub const FRONT_MATTER_TOML_FENCE: &str = "+++";
+    pub const FRONT_MATTER_YAML_SEPARATOR: &str = ":";
+    pub const FRONT_MATTER_TOML_SEPARATOR: &str = "=";
+    pub const FRONT_MATTER_YAML_LIST_ITEM: &str = "-";
+    pub const FRONT_MATTER_COMMENT: &str = "#";
+    pub const FRONT_MATTER_TITLE_KEY: &str = "title";
+    pub const FRONT_MATTER_TAGS_KEY: &str = "tags";
+    pub const FRONT_MATTER_AUTHORS_KEY: &str = "authors";
+    pub const FRONT_MATTER_DATE_KEY: &str = "date";
+
+    /// Only for output to terminal.
+    pub const BLOCK_QUOTE_DISPLAY: &str = "│ ";
+    pub const TABLE_SEPARATOR_DISPLAY: &str = " │ ";
+    pub const TABLE_DELIMITER_DISPLAY: &str = "─";
+    pub const TABLE_DELIMITER_SEPARATOR_DISPLAY: &str = "─┼─";
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -28,7 +28,6 @@ pub use misc_type_aliases::*;
 pub use pretty_print_option::*;
 pub use pretty_print_traits::*;
 use r3bl_rs_utils_core::*;
-use strum_macros::AsRefStr;
 
 use crate::*;
 
@@ -109,33 +108,6 @@ pub mod global_constants {
         GlobalDataCacheSize = 1_000_000,
     }
 
-    #[derive(Debug, Eq, PartialEq, AsRefStr)]
-    pub enum BorderGlyphCharacter {
-        #[strum(to_string = "╮")]
-        TopRight,
-
-        #[strum(to_string = "╭")]
-        TopLeft,
-
-        #[strum(to_string = "╯")]
-        BottomRight,
-
-        #[strum(to_string = "╰")]
-        BottomLeft,
-
-        #[strum(to_string = "─")]
-        Horizontal,
-
-        #[strum(to_string = "│")]
-        Vertical,
-
-        #[strum(to_string = "┤")]
-        LineUpDownLeft,
-
-        #[strum(to_string = "├")]
-        LineUpDownRight,
-    }
-
     pub const SPACER: &str = " ";
     pub const DEFAULT_CURSOR_CHAR: char = '▒';
     pub const DEFAULT_SYN_HI_FILE_EXT: &str = "md";
@@ -48,12 +48,14 @@ pub const DEBUG_TUI_SHOW_TERMINAL_BACKEND: bool = false;
 // Attach sources.
 pub mod animator;
 pub mod color_wheel;
+pub mod completion;
 pub mod dialog;
 pub mod editor;
 pub mod layout;
 pub mod lolcat;
 pub mod md_parser;
 pub mod misc_types;
+pub mod outline;
 pub mod rsx;
 pub mod syntax_highlighting;
 pub mod terminal_lib_backends;
@@ -62,12 +64,14 @@ pub mod terminal_window;
 // Re-export.
 pub use animator::*;
 pub use color_wheel::*;
+pub use completion::*;
 pub use dialog::*;
 pub use editor::*;
 pub use layout::*;
 pub use lolcat::*;
 pub use md_parser::*;
 pub use misc_types::*;
+pub use outline::*;
 pub use rsx::*;
 pub use syntax_highlighting::*;
 pub use terminal_lib_backends::*;
@@ -0,0 +1,497 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// This component shows the outline (table of contents) of the Markdown document in an
+/// [EditorComponent], which is made up of its headings ([MdOutline]). It is meant to be
+/// placed in a [FlexBox] next to the editor.
+///
+/// 1. The section that the editor's caret is in is highlighted (in bold).
+/// 2. When this component has focus, the headings can be selected w/ `Up`, `Down`,
+///    `Home` & `End`. Pressing `Enter` moves the editor's caret to the selected heading,
+///    scrolls it to the top of the editor's viewport, and gives focus back to the editor.
+///
+/// The outline is re-computed only when the lines in the [EditorBuffer] change.
+#[derive(Debug, Default)]
+pub struct OutlineComponent<S, A>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub data: OutlineComponentData<S, A>,
+}
+
+#[derive(Debug, Default)]
+pub struct OutlineComponentData<S, A>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    /// The id of the [EditorComponent] (and its [EditorBuffer]) that this outline is for.
+    pub editor_id: FlexBoxId,
+    pub outline: MdOutline,
+    /// The lines that [outline](OutlineComponentData::outline) was computed from.
+    pub lines: Vec<US>,
+    /// Index into the entries of [outline](OutlineComponentData::outline).
+    pub selected_index: usize,
+    /// The index of the entry that is painted in the first row of the box.
+    pub scroll_offset_index: usize,
+    _phantom: std::marker::PhantomData<(S, A)>,
+}
+
+pub mod outline_component_impl_component_trait {
+    use super::*;
+
+    impl<S, A> Component<S, A> for OutlineComponent<S, A>
+    where
+        S: Debug + Default + Clone + Sync + Send + HasEditorBuffers,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        fn reset(&mut self) {
+            self.data.selected_index = 0;
+            self.data.scroll_offset_index = 0;
+        }
+
+        fn get_id(&self) -> FlexBoxId { self.data.id }
+
+        /// When this component doesn't have focus, the selected entry follows the section
+        /// that the editor's caret is in. If there's no [EditorBuffer] for
+        /// [editor_id](OutlineComponentData::editor_id) in the state, then nothing is
+        /// painted.
+        fn render(
+            &mut self,
+            global_data: &mut GlobalData<S, A>,
+            current_box: FlexBox,
+            _surface_bounds: SurfaceBounds, /* Ignore this. */
+            has_focus: &mut HasFocus,
+        ) -> CommonResult<RenderPipeline> {
+            let GlobalData { state, .. } = global_data;
+            let data = &mut self.data;
+
+            let Some(editor_buffer) = state.get_mut_editor_buffer(data.editor_id) else {
+                return Ok(RenderPipeline::default());
+            };
+
+            data.update_outline(editor_buffer.get_lines());
+
+            let caret_row_index = ch!(@to_usize editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index);
+            let maybe_current_index =
+                data.outline.get_section_entry_index(caret_row_index);
+
+            let is_focused = has_focus.does_id_have_focus(data.id);
+            if !is_focused {
+                if let Some(current_index) = maybe_current_index {
+                    data.selected_index = current_index;
+                }
+            }
+
+            let viewport_row_count =
+                ch!(@to_usize current_box.style_adjusted_bounds_size.row_count);
+            data.scroll_to_selected_index(viewport_row_count);
+
+            let mut render_ops = render_ops!();
+            render_helper::paint_outline(
+                &mut render_ops,
+                data,
+                &current_box,
+                maybe_current_index,
+                is_focused,
+            );
+
+            let mut pipeline = render_pipeline!();
+            pipeline.push(ZOrder::Normal, render_ops);
+            Ok(pipeline)
+        }
+
+        /// The events that aren't handled here are propagated, eg: so that the app can
+        /// move the focus to another component.
+        fn handle_event(
+            &mut self,
+            global_data: &mut GlobalData<S, A>,
+            input_event: InputEvent,
+            has_focus: &mut HasFocus,
+        ) -> CommonResult<EventPropagation> {
+            let GlobalData { state, .. } = global_data;
+            let data = &mut self.data;
+
+            let Some(last_index) = data.outline.len().checked_sub(1) else {
+                return Ok(EventPropagation::Propagate);
+            };
+
+            if input_event.matches_keypress(keypress!(@special SpecialKey::Up)) {
+                data.selected_index = data.selected_index.saturating_sub(1);
+            } else if input_event.matches_keypress(keypress!(@special SpecialKey::Down)) {
+                data.selected_index = (data.selected_index + 1).min(last_index);
+            } else if input_event.matches_keypress(keypress!(@special SpecialKey::Home)) {
+                data.selected_index = 0;
+            } else if input_event.matches_keypress(keypress!(@special SpecialKey::End)) {
+                data.selected_index = last_index;
+            } else if input_event.matches_keypress(keypress!(@special SpecialKey::Enter))
+            {
+                let Some(entry) = data.outline.entries.get(data.selected_index) else {
+                    return Ok(EventPropagation::Propagate);
+                };
+                if let Some(editor_buffer) = state.get_mut_editor_buffer(data.editor_id) {
+                    editor_buffer.jump_to_row(ch!(entry.line_index));
+                }
+                has_focus.set_id(data.editor_id);
+            } else {
+                return Ok(EventPropagation::Propagate);
+            }
+
+            Ok(EventPropagation::ConsumedRender)
+        }
+    }
+}
+
+pub mod outline_component_data_impl {
+    use super::*;
+
+    impl<S, A> OutlineComponentData<S, A>
+    where
+        S: Debug + Default + Clone + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        /// Re-computes the outline if the `lines` have changed since it was last computed.
+        /// The selected index is clamped to the (possibly shorter) new outline.
+        pub fn update_outline(&mut self, lines: &[US]) {
+            if self.lines.as_slice() == lines {
+                return;
+            }
+            self.lines = lines.to_vec();
+            self.outline = MdOutline::from_lines(lines);
+            self.selected_index = self
+                .selected_index
+                .min(self.outline.len().saturating_sub(1));
+        }
+
+        /// Scroll (the least amount possible) so that the selected entry is visible in a box
+        /// that is `viewport_row_count` rows tall.
+        pub fn scroll_to_selected_index(&mut self, viewport_row_count: usize) {
+            let viewport_row_count = viewport_row_count.max(1);
+            if self.selected_index < self.scroll_offset_index {
+                self.scroll_offset_index = self.selected_index;
+            } else if self.selected_index >= self.scroll_offset_index + viewport_row_count
+            {
+                self.scroll_offset_index = self.selected_index + 1 - viewport_row_count;
+            }
+        }
+    }
+}
+
+mod render_helper {
+    use super::*;
+
+    /// Each entry is indented by its heading level, and is clipped to the width of the box.
+    /// - The entry for the section that the caret is in is painted in bold.
+    /// - The selected entry is painted in reverse (only when this component has focus).
+    pub fn paint_outline<S, A>(
+        ops: &mut RenderOps,
+        data: &OutlineComponentData<S, A>,
+        current_box: &FlexBox,
+        maybe_current_index: Option<usize>,
+        is_focused: bool,
+    ) where
+        S: Debug + Default + Clone + Sync + Send,
+        A: Debug + Default + Clone + Sync + Send,
+    {
+        let origin_pos = current_box.style_adjusted_origin_pos;
+        let bounds_size = current_box.style_adjusted_bounds_size;
+        let base_style = current_box.get_computed_style().unwrap_or_default();
+
+        let viewport_entries = data
+            .outline
+            .entries
+            .iter()
+            .enumerate()
+            .skip(data.scroll_offset_index)
+            .take(ch!(@to_usize bounds_size.row_count));
+
+        for (rel_row_index, (index, entry)) in viewport_entries.enumerate() {
+            let indent = " ".repeat(2 * (usize::from(entry.level) - 1));
+            let text = US::from(format!("{indent}{}", entry.text));
+            let clipped_text = text.clip_to_width(ch!(0), bounds_size.col_count);
+
+            let style = Style {
+                bold: maybe_current_index == Some(index),
+                reverse: is_focused && data.selected_index == index,
+                ..base_style
+            };
+            let maybe_style = Some(style);
+
+            ops.push(RenderOp::ResetColor);
+            ops.push(RenderOp::MoveCursorPositionRelTo(
+                origin_pos,
+                position!(col_index: 0, row_index: ch!(rel_row_index)),
+            ));
+            ops.push(RenderOp::ApplyColors(maybe_style));
+            ops.push(RenderOp::PaintTextWithAttributes(
+                clipped_text.to_string(),
+                maybe_style,
+            ));
+        }
+    }
+}
+
+mod constructor {
+    use super::*;
+
+    impl<S, A> OutlineComponent<S, A>
+    where
+        S: Debug + Default + Clone + Sync + Send + HasEditorBuffers + 'static,
+        A: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        /// The `editor_id` is the id of the [EditorComponent] that shows the document.
+        pub fn new(id: FlexBoxId, editor_id: FlexBoxId) -> Self {
+            Self {
+                data: OutlineComponentData {
+                    id,
+                    editor_id,
+                    ..Default::default()
+                },
+            }
+        }
+
+        pub fn new_boxed(
+            id: FlexBoxId,
+            editor_id: FlexBoxId,
+        ) -> BoxedSafeComponent<S, A> {
+            let it = OutlineComponent::new(id, editor_id);
+            Box::new(it)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_outline_component {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::test_outline::mock_real_objects_for_outline::*;
+
+    const LINES: [&str; 8] = [
+        "intro",
+        "# One",
+        "text",
+        "## Two",
+        "text",
+        "text",
+        "# Three is a long heading",
+        "text",
+    ];
+
+    fn make_component() -> OutlineComponent<State, ()> {
+        OutlineComponent::new(FlexBoxId::from(OUTLINE_ID), FlexBoxId::from(EDITOR_ID))
+    }
+
+    fn make_flex_box(size: Size) -> FlexBox {
+        FlexBox {
+            id: FlexBoxId::from(OUTLINE_ID),
+            style_adjusted_bounds_size: size,
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the text & style of each painted row.
+    fn get_painted_rows(pipeline: &RenderPipeline) -> Vec<(String, Option<Style>)> {
+        pipeline
+            .get(&ZOrder::Normal)
+            .unwrap()
+            .iter()
+            .flat_map(|render_ops| render_ops.iter())
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, maybe_style) => {
+                    Some((text.clone(), *maybe_style))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn key(special_key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(keypress!(@special special_key))
+    }
+
+    #[test]
+    fn test_render_highlights_section_of_caret() {
+        let global_data = &mut make_global_data(None, &LINES);
+        global_data
+            .state
+            .get_mut_editor_buffer(FlexBoxId::from(EDITOR_ID))
+            .unwrap()
+            .set_caret_scroll_adjusted(position!(col_index: 0, row_index: 4));
+        let component = &mut make_component();
+        let has_focus = &mut HasFocus::default();
+
+        let pipeline = component
+            .render(
+                global_data,
+                make_flex_box(size!(col_count: 10, row_count: 10)),
+                SurfaceBounds::default(),
+                has_focus,
+            )
+            .unwrap();
+        let rows = get_painted_rows(&pipeline);
+
+        // Indented by heading level, and clipped to the width of the box.
+        assert_eq2!(
+            rows.iter()
+                .map(|(text, _)| text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["One", "  Two", "Three is a"]
+        );
+        // Only the section that the caret is in is bold, & nothing is reverse since the
+        // component doesn't have focus.
+        assert_eq2!(
+            rows.iter()
+                .map(|(_, maybe_style)| maybe_style.unwrap().bold)
+                .collect::<Vec<_>>(),
+            vec![false, true, false]
+        );
+        assert!(rows.iter().all(|(_, it)| !it.unwrap().reverse));
+        assert_eq2!(component.data.selected_index, 1);
+    }
+
+    #[test]
+    fn test_render_without_editor_buffer() {
+        let global_data = &mut make_global_data(None, &LINES);
+        global_data.state.editor_buffers.clear();
+        let component = &mut make_component();
+
+        let pipeline = component
+            .render(
+                global_data,
+                make_flex_box(size!(col_count: 10, row_count: 10)),
+                SurfaceBounds::default(),
+                &mut HasFocus::default(),
+            )
+            .unwrap();
+
+        assert_eq2!(pipeline, RenderPipeline::default());
+    }
+
+    #[test]
+    fn test_render_scrolls_to_selected_entry() {
+        let global_data = &mut make_global_data(None, &LINES);
+        let component = &mut make_component();
+        let has_focus = &mut HasFocus::default();
+        has_focus.set_id(FlexBoxId::from(OUTLINE_ID));
+
+        component.data.update_outline(
+            global_data
+                .state
+                .get_mut_editor_buffer(FlexBoxId::from(EDITOR_ID))
+                .unwrap()
+                .get_lines(),
+        );
+        component.data.selected_index = 2;
+
+        let pipeline = component
+            .render(
+                global_data,
+                make_flex_box(size!(col_count: 30, row_count: 2)),
+                SurfaceBounds::default(),
+                has_focus,
+            )
+            .unwrap();
+        let rows = get_painted_rows(&pipeline);
+
+        assert_eq2!(component.data.scroll_offset_index, 1);
+        assert_eq2!(rows.len(), 2);
+        assert_eq2!(rows[1].0, "Three is a long heading");
+        assert!(rows[1].1.unwrap().reverse);
+        assert!(!rows[0].1.unwrap().reverse);
+    }
+
+    #[test]
+    fn test_handle_event_select_and_jump_to_heading() {
+        let global_data = &mut make_global_data(None, &LINES);
+        let component = &mut make_component();
+        let has_focus = &mut HasFocus::default();
+        has_focus.set_id(FlexBoxId::from(OUTLINE_ID));
+
+        component
+            .render(
+                global_data,
+                make_flex_box(size!(col_count: 10, row_count: 10)),
+                SurfaceBounds::default(),
+                has_focus,
+            )
+            .unwrap();
+
+        for (special_key, expected_selected_index) in [
+            (SpecialKey::Down, 1),
+            (SpecialKey::Down, 2),
+            (SpecialKey::Down, 2),
+            (SpecialKey::Up, 1),
+            (SpecialKey::Home, 0),
+            (SpecialKey::End, 2),
+            (SpecialKey::Up, 1),
+        ] {
+            let propagation = component
+                .handle_event(global_data, key(special_key), has_focus)
+                .unwrap();
+            assert_eq2!(propagation, EventPropagation::ConsumedRender);
+            assert_eq2!(component.data.selected_index, expected_selected_index);
+        }
+
+        let propagation = component
+            .handle_event(global_data, key(SpecialKey::Esc), has_focus)
+            .unwrap();
+        assert_eq2!(propagation, EventPropagation::Propagate);
+
+        // Jump to "## Two" on line 3.
+        let propagation = component
+            .handle_event(global_data, key(SpecialKey::Enter), has_focus)
+            .unwrap();
+        assert_eq2!(propagation, EventPropagation::ConsumedRender);
+        assert!(has_focus.does_id_have_focus(FlexBoxId::from(EDITOR_ID)));
+
+        let editor_buffer = global_data
+            .state
+            .get_mut_editor_buffer(FlexBoxId::from(EDITOR_ID))
+            .unwrap();
+        assert_eq2!(
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 3)
+        );
+        assert_eq2!(
+            editor_buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 3)
+        );
+    }
+
+    #[test]
+    fn test_handle_event_without_headings() {
+        let global_data = &mut make_global_data(None, &["no headings"]);
+        let component = &mut make_component();
+        let has_focus = &mut HasFocus::default();
+
+        let propagation = component
+            .handle_event(global_data, key(SpecialKey::Down), has_focus)
+            .unwrap();
+
+        assert_eq2!(propagation, EventPropagation::Propagate);
+    }
+}
@@ -0,0 +1,76 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+pub mod mock_real_objects_for_outline {
+    use std::collections::HashMap;
+
+    use r3bl_rs_utils_core::Size;
+    use tokio::sync::mpsc;
+
+    use crate::*;
+
+    pub const EDITOR_ID: u8 = 1;
+    pub const OUTLINE_ID: u8 = 2;
+
+    pub fn make_global_data(
+        window_size: Option<Size>,
+        lines: &[&str],
+    ) -> GlobalData<State, ()> {
+        let (main_thread_channel_sender, _) = mpsc::channel::<_>(CHANNEL_WIDTH);
+        let state = create_state(lines);
+        let window_size = window_size.unwrap_or_default();
+        let maybe_saved_offscreen_buffer = Default::default();
+        GlobalData {
+            state,
+            window_size,
+            maybe_saved_offscreen_buffer,
+            main_thread_channel_sender,
+        }
+    }
+
+    #[derive(Clone, PartialEq, Default, Debug)]
+    pub struct State {
+        pub editor_buffers: HashMap<FlexBoxId, EditorBuffer>,
+    }
+
+    impl HasEditorBuffers for State {
+        fn get_mut_editor_buffer(&mut self, id: FlexBoxId) -> Option<&mut EditorBuffer> {
+            self.editor_buffers.get_mut(&id)
+        }
+
+        fn insert_editor_buffer(&mut self, id: FlexBoxId, buffer: EditorBuffer) {
+            self.editor_buffers.insert(id, buffer);
+        }
+
+        fn contains_editor_buffer(&self, id: FlexBoxId) -> bool {
+            self.editor_buffers.contains_key(&id)
+        }
+    }
+
+    /// The [EditorBuffer] for [EDITOR_ID] has the given `lines`.
+    pub fn create_state(lines: &[&str]) -> State {
+        let editor_buffers = {
+            let mut buffer =
+                EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+            buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+            let mut it = HashMap::new();
+            it.insert(FlexBoxId::from(EDITOR_ID), buffer);
+            it
+        };
+        State { editor_buffers }
+    }
+}
@@ -26,6 +26,9 @@ macro_rules! box_end {
 }
 
 /// When calling this, make sure to make a corresponding call to [box_end!].
+///
+/// Any other [FlexBoxProps](crate::FlexBoxProps) fields (eg: `gap`, `margin`, `align`,
+/// `justify`) can be passed after `styles`, and the rest are defaulted.
 #[macro_export]
 macro_rules! box_start {
     (
@@ -34,6 +37,7 @@ macro_rules! box_start {
         dir:                    $arg_dir : expr,                    // Eg: Direction::Horizontal,
         requested_size_percent: $arg_requested_size_percent : expr, // Eg: (50, 100).try_into()?,
         styles:                 [$($args:tt)*]                      // Eg: [ "style1" , "style2" ]
+        $(, $arg_field : ident : $arg_value : expr)*                // Eg: , gap: ch!(1)
         $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
     ) => {
         $arg_surface.box_start(box_props! {
@@ -41,10 +45,13 @@ macro_rules! box_start {
             dir:                    $arg_dir,
             requested_size_percent: $arg_requested_size_percent,
             maybe_styles:           get_styles! { @from: $arg_surface.stylesheet, [$($args)*] }
+            $(, $arg_field: $arg_value)*
         })?
     };
 }
 
+/// Any other [FlexBoxProps](crate::FlexBoxProps) fields (eg: `gap`, `margin`, `align`,
+/// `justify`) can be passed at the end, and the rest are defaulted.
 #[macro_export]
 macro_rules! box_props {
   (
@@ -54,6 +61,7 @@ macro_rules! box_props {
     maybe_styles:           $arg_styles: expr                   // Eg: get_styles! {
                                                                 //     from: stylesheet,
                                                                 //     ["style1", "style2"] };
+    $(, $arg_field : ident : $arg_value : expr)*                // Eg: , gap: ch!(1)
     $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
   ) => {
     FlexBoxProps {
@@ -61,6 +69,8 @@ macro_rules! box_props {
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
       maybe_styles: $arg_styles,
+      $($arg_field: $arg_value,)*
+      ..Default::default()
     }
   };
 
@@ -69,6 +79,7 @@ macro_rules! box_props {
     dir:                    $arg_dir : expr,                    // Eg: Direction::Horizontal,
     requested_size_percent: $arg_requested_size_percent : expr, // Eg: (50, 100).try_into()?,
     maybe_styles:           [$($args:tt)*]                      // Eg: [style!{...} , style!{...}]
+    $(, $arg_field : ident : $arg_value : expr)*                // Eg: , gap: ch!(1)
     $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
   ) => {
     FlexBoxProps {
@@ -76,13 +87,16 @@ macro_rules! box_props {
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
       maybe_styles: Some(vec![$($args)*]),
+      $($arg_field: $arg_value,)*
+      ..Default::default()
     }
   };
 
   (
     id:                     $arg_id : expr,                     // Eg:0,
     dir:                    $arg_dir : expr,                    // Eg: Direction::Horizontal,
-    requested_size_percent: $arg_requested_size_percent : expr, // Eg: (50, 100).try_into()?,
+    requested_size_percent: $arg_requested_size_percent : expr  // Eg: (50, 100).try_into()?,
+    $(, $arg_field : ident : $arg_value : expr)*                // Eg: , gap: ch!(1)
     $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
   ) => {
     FlexBoxProps {
@@ -90,6 +104,8 @@ macro_rules! box_props {
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
       maybe_styles: None,
+      $($arg_field: $arg_value,)*
+      ..Default::default()
     }
   };
 }
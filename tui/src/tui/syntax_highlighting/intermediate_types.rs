@@ -40,13 +40,28 @@ use crate::{constants::*, *};
 pub struct StyleUSSpan {
     pub style: Style,
     pub text: US,
+    /// The URL that the text links to, eg: for a link in a Markdown document. This is only
+    /// used when the span is converted into text w/ ANSI escape sequences, please take a
+    /// look at [crate::convert_line_to_ansi_text].
+    pub maybe_hyperlink_url: Option<String>,
 }
 
 mod style_us_span_impl {
     use super::*;
 
     impl StyleUSSpan {
-        pub fn new(style: Style, text: US) -> Self { Self { style, text } }
+        pub fn new(style: Style, text: US) -> Self {
+            Self {
+                style,
+                text,
+                maybe_hyperlink_url: None,
+            }
+        }
+
+        pub fn with_hyperlink_url(mut self, url: &str) -> Self {
+            self.maybe_hyperlink_url = Some(url.to_string());
+            self
+        }
     }
 
     impl From<(&Style, &US)> for StyleUSSpan {
@@ -126,7 +141,7 @@ impl StyleUSSpanLine {
     /// This applies the given style to every single item in the list. It has the highest
     /// specificity.
     pub fn add_style(&mut self, style: Style) {
-        for StyleUSSpan { style: s, text: _ } in self.iter_mut() {
+        for StyleUSSpan { style: s, .. } in self.iter_mut() {
             *s += style;
         }
     }
@@ -155,6 +170,7 @@ impl StyleUSSpanLine {
             let StyleUSSpan {
                 style,
                 text: formatted_text_unicode_string,
+                ..
             } = span;
 
             let mut clipped_text_fragment = String::new();
@@ -195,11 +211,7 @@ impl StyleUSSpanLine {
 
     pub fn display_width(&self) -> ChUnit {
         let mut size = ch!(0);
-        for StyleUSSpan {
-            style: _,
-            text: item,
-        } in self.iter()
-        {
+        for StyleUSSpan { text: item, .. } in self.iter() {
             size += item.display_width;
         }
         size
@@ -207,11 +219,7 @@ impl StyleUSSpanLine {
 
     pub fn get_plain_text(&self) -> String {
         let mut plain_text = String::new();
-        for StyleUSSpan {
-            style: _,
-            text: item,
-        } in self.iter()
-        {
+        for StyleUSSpan { text: item, .. } in self.iter() {
             plain_text.push_str(&item.string);
         }
         plain_text
@@ -231,7 +239,7 @@ impl StyleUSSpanLine {
 impl From<StyleUSSpanLine> for StyledTexts {
     fn from(styles: StyleUSSpanLine) -> Self {
         let mut acc = StyledTexts::default();
-        for StyleUSSpan { style, text } in styles.iter() {
+        for StyleUSSpan { style, text, .. } in styles.iter() {
             acc += styled_text!(@style: *style, @text: text.string.clone());
         }
         acc
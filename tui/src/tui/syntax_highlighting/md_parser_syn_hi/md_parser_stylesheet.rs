@@ -35,6 +35,52 @@ pub fn get_selection_style() -> Style {
     }
 }
 
+/// This style is for the matches of the search query in the document.
+pub fn get_search_match_style() -> Style {
+    let color_fg = TuiColor::Rgb(RgbValue::from_hex("#1c1c1c"));
+    let color_bg = TuiColor::Rgb(RgbValue::from_hex("#ffd700"));
+    style! {
+        color_fg: color_fg
+        color_bg: color_bg
+    }
+}
+
+/// This style is for the bracket at the caret and its matching bracket in the editor.
+pub fn get_matching_bracket_style() -> Style {
+    style! {
+        attrib: [bold, underline]
+        color_bg: TuiColor::Rgb(RgbValue::from_hex("#4e4e4e"))
+    }
+}
+
+/// This style is for the line numbers in the gutter of the editor.
+pub fn get_gutter_line_number_style() -> Style {
+    style! {
+        attrib: [dim]
+        color_fg: TuiColor::Rgb(RgbValue::from_hex("#5f5f5f"))
+    }
+}
+
+/// This style is for the line number of the caret line in the gutter of the editor.
+pub fn get_gutter_current_line_number_style() -> Style {
+    get_foreground_style()
+        + style! {
+            attrib: [bold]
+        }
+}
+
+/// This style is for the [LineMarker]s in the gutter of the editor.
+pub fn get_line_marker_style(line_marker: &LineMarker) -> Style {
+    let color_fg = match line_marker {
+        LineMarker::Modified => TuiColor::Rgb(RgbValue::from_hex("#5fafff")),
+        LineMarker::Error => TuiColor::Rgb(RgbValue::from_hex("#ff5f5f")),
+        LineMarker::Warning => TuiColor::Rgb(RgbValue::from_hex("#ffd700")),
+    };
+    style! {
+        color_fg: color_fg
+    }
+}
+
 /// This style is for the foreground text of the entire document. This is the default
 /// style. It is overridden by other styles like bold, italic, etc. below.
 pub fn get_foreground_style() -> Style {
@@ -167,6 +213,53 @@ pub fn get_list_bullet_style() -> Style {
     }
 }
 
+/// This is just for the strikethrough content, not the enclosing `~~`.
+pub fn get_strikethrough_style() -> Style {
+    get_foreground_style()
+        + style! {
+            attrib: [strikethrough]
+        }
+}
+
+/// This is for the `>` prefix of each line of a blockquote, not the content.
+pub fn get_block_quote_marker_style() -> Style {
+    style! {
+        attrib: [bold]
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#7d87e6")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(104)), // MediumPurple.
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkCyan),
+            _ => TuiColor::Basic(ANSIBasicColor::DarkCyan),
+        }
+    }
+}
+
+/// This is for the entire line of a thematic break (horizontal rule).
+pub fn get_thematic_break_style() -> Style { get_foreground_dim_style() }
+
+/// This is for the fences & comments of the front matter. Its keys & values use the same
+/// styles as the metadata, eg: [get_metadata_title_marker_style].
+pub fn get_front_matter_fence_style() -> Style { get_foreground_dim_style() }
+
+/// This is for the `|` separators of each row of a table, and its delimiter row.
+pub fn get_table_separator_style() -> Style {
+    style! {
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#7d87e6")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(104)), // MediumPurple.
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkCyan),
+            _ => TuiColor::Basic(ANSIBasicColor::DarkCyan),
+        }
+    }
+}
+
+/// This is applied on top of the style of each fragment in the header row of a table.
+pub fn get_table_header_style() -> Style {
+    style! {
+        attrib: [bold]
+    }
+}
+
 pub fn get_code_block_lang_style() -> Style {
     get_inline_code_style()
         + style! {
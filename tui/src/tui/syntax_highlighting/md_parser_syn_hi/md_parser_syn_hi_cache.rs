@@ -0,0 +1,478 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module is responsible for caching the highlighted lines of the Markdown document in
+//! the [editor], so that only the blocks that are touched by an edit are re-parsed &
+//! re-highlighted on each render.
+
+use std::ops::Range;
+
+use r3bl_rs_utils_core::*;
+use syntect::{highlighting::Theme, parsing::SyntaxSet};
+
+use crate::{constants::*, *};
+
+/// This is the incremental version of [try_parse_and_highlight], which the [editor] uses to
+/// display the Markdown to the user. The blocks of the document are cached in chunks, which
+/// are consecutive blocks that start & end on a line boundary (almost always a single
+/// block). When the lines change:
+/// 1. The changed lines are found by skipping the lines at the start & end that are the
+///    same as before.
+/// 2. The blocks are re-parsed starting from the chunk w/ the line before the first changed
+///    line (since a list, table or blockquote can continue onto the changed line), or from
+///    the chunk before it if it's blank lines (since a list item can continue after them).
+///    This stops once a chunk ends after the last changed line, on a line where an old chunk
+///    starts. The text from there on is the same as before, so the rest of the old chunks
+///    are re-used.
+/// 3. If a code fence (` ``` `) is added or removed, the blocks are re-parsed from the start
+///    of the document, since this can turn text anywhere before it into a code block (or
+///    the other way around). The same goes for the front matter, when the document starts
+///    w/ a front matter fence (`---` or `+++`).
+///
+/// The highlighted lines are the same as the ones that [try_parse_and_highlight] returns
+/// for the same arguments.
+#[derive(Clone, Debug, Default)]
+pub struct MdSynHiCache {
+    /// The lines (w/out the EOL) that the chunks are parsed from.
+    text_lines: Vec<String>,
+    chunks: Vec<MdSynHiCacheChunk>,
+    /// The highlighted lines of all the chunks.
+    style_us_span_lines: StyleUSSpanLines,
+    /// The chunks can only be re-used if they are highlighted w/ the same style, and w/
+    /// (or w/out) syntect.
+    maybe_key: Option<(Option<Style>, bool)>,
+    /// The number of blocks that were parsed by the last call to [MdSynHiCache::update].
+    last_parsed_block_count: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+struct MdSynHiCacheChunk {
+    text_line_count: usize,
+    style_us_span_line_count: usize,
+}
+
+impl MdSynHiCache {
+    /// Re-parses & re-highlights the blocks that are touched by the changes to the lines
+    /// since the last call, and returns the highlighted lines of the whole document.
+    pub fn update(
+        &mut self,
+        editor_text_lines: &[US],
+        maybe_current_box_computed_style: &Option<Style>,
+        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    ) -> &StyleUSSpanLines {
+        let key = (
+            *maybe_current_box_computed_style,
+            maybe_syntect_tuple.is_some(),
+        );
+        if self.maybe_key != Some(key) {
+            *self = Self {
+                maybe_key: Some(key),
+                ..Default::default()
+            };
+        }
+
+        let (old_range, new_range) =
+            get_changed_line_ranges(&self.text_lines, editor_text_lines);
+        if old_range.is_empty() && new_range.is_empty() {
+            self.last_parsed_block_count = 0;
+            return &self.style_us_span_lines;
+        }
+
+        let is_code_fence_changed = self.text_lines[old_range.clone()]
+            .iter()
+            .map(String::as_str)
+            .chain(
+                editor_text_lines[new_range.clone()]
+                    .iter()
+                    .map(|line| line.string.as_str()),
+            )
+            .any(|line| line.contains(CODE_BLOCK_END));
+
+        // The front matter can only be at the start of the document, so a change to its
+        // lines (or to a fence anywhere) can turn it into other blocks, or the other way
+        // around.
+        let is_front_matter_changed =
+            editor_text_lines.first().is_some_and(|first_line| {
+                let fence = first_line.string.as_str();
+                if !is_front_matter_fence(fence) {
+                    return false;
+                }
+                let is_fence_changed = self.text_lines[old_range.clone()]
+                    .iter()
+                    .map(String::as_str)
+                    .chain(
+                        editor_text_lines[new_range.clone()]
+                            .iter()
+                            .map(|line| line.string.as_str()),
+                    )
+                    .any(is_front_matter_fence);
+                let maybe_end_line_index = editor_text_lines
+                    .iter()
+                    .skip(1)
+                    .position(|line| line.string == fence)
+                    .map(|it| it + 1);
+                is_fence_changed
+                    || maybe_end_line_index.is_some_and(|it| new_range.start <= it)
+            });
+
+        // The line & the highlighted line that each chunk starts at, w/ the end of the
+        // last chunk at the end.
+        let (chunk_start_line_indices, chunk_start_span_line_indices) = {
+            let mut line_indices = Vec::with_capacity(self.chunks.len() + 1);
+            let mut span_line_indices = Vec::with_capacity(self.chunks.len() + 1);
+            let (mut line_index, mut span_line_index) = (0, 0);
+            for chunk in &self.chunks {
+                line_indices.push(line_index);
+                span_line_indices.push(span_line_index);
+                line_index += chunk.text_line_count;
+                span_line_index += chunk.style_us_span_line_count;
+            }
+            line_indices.push(line_index);
+            span_line_indices.push(span_line_index);
+            (line_indices, span_line_indices)
+        };
+        // The end of the last chunk is only the end of the old lines if all of them were
+        // parsed, otherwise the new blocks can't be in sync w/ it.
+        let is_old_text_all_parsed =
+            chunk_start_line_indices[self.chunks.len()] == self.text_lines.len();
+
+        let mut start_chunk_index =
+            if is_code_fence_changed || is_front_matter_changed || old_range.start == 0 {
+                0
+            } else {
+                chunk_start_line_indices[..self.chunks.len()]
+                    .partition_point(|it| *it < old_range.start)
+                    .saturating_sub(1)
+            };
+        // A list item can continue after blank lines, so start from the chunk before them.
+        while start_chunk_index > 0
+            && self.text_lines[chunk_start_line_indices[start_chunk_index]
+                ..chunk_start_line_indices[start_chunk_index + 1]]
+                .iter()
+                .all(|line| line.trim().is_empty())
+        {
+            start_chunk_index -= 1;
+        }
+        let start_line_index = chunk_start_line_indices[start_chunk_index];
+
+        let text = {
+            let mut acc = String::new();
+            for line in &editor_text_lines[start_line_index..] {
+                acc.push_str(&line.string);
+                acc.push_str(NEW_LINE);
+            }
+            acc
+        };
+
+        // Parse the blocks until they are in sync w/ the old chunks again.
+        let mut new_chunks = vec![];
+        let mut new_style_us_span_lines = StyleUSSpanLines::default();
+        let mut pending_chunk = MdSynHiCacheChunk::default();
+        let mut maybe_end_chunk_index = None;
+        let mut parsed_block_count = 0;
+        let mut line_index = start_line_index;
+        let mut input = text.as_str();
+        loop {
+            // The front matter can only be the first block of the document.
+            let result = if start_line_index == 0 && parsed_block_count == 0 {
+                parse_first_block(input)
+            } else {
+                parse_block(input)
+            };
+            let Ok((remainder, block)) = result else {
+                break;
+            };
+            let consumed = &input[..input.len() - remainder.len()];
+            if consumed.is_empty() {
+                break;
+            }
+            input = remainder;
+            parsed_block_count += 1;
+
+            let lines = StyleUSSpanLines::from_block(
+                &block,
+                maybe_current_box_computed_style,
+                maybe_syntect_tuple,
+            );
+            pending_chunk.text_line_count += consumed.matches(NEW_LINE).count();
+            pending_chunk.style_us_span_line_count += lines.len();
+            new_style_us_span_lines.items.extend(lines.items);
+
+            // A chunk can only end on a line boundary.
+            if !consumed.ends_with(NEW_LINE) {
+                continue;
+            }
+            line_index += pending_chunk.text_line_count;
+            new_chunks.push(std::mem::take(&mut pending_chunk));
+
+            if line_index >= new_range.end {
+                let old_line_index = line_index - new_range.end + old_range.end;
+                if let Ok(index) = chunk_start_line_indices.binary_search(&old_line_index)
+                {
+                    if index < self.chunks.len() || is_old_text_all_parsed {
+                        maybe_end_chunk_index = Some(index);
+                        break;
+                    }
+                }
+            }
+        }
+        if pending_chunk.text_line_count > 0 || pending_chunk.style_us_span_line_count > 0
+        {
+            new_chunks.push(pending_chunk);
+        }
+        let end_chunk_index = maybe_end_chunk_index.unwrap_or(self.chunks.len());
+
+        self.style_us_span_lines.items.splice(
+            chunk_start_span_line_indices[start_chunk_index]
+                ..chunk_start_span_line_indices[end_chunk_index],
+            new_style_us_span_lines.items,
+        );
+        self.chunks
+            .splice(start_chunk_index..end_chunk_index, new_chunks);
+        self.text_lines.splice(
+            old_range,
+            editor_text_lines[new_range]
+                .iter()
+                .map(|line| line.string.clone()),
+        );
+        self.last_parsed_block_count = parsed_block_count;
+
+        &self.style_us_span_lines
+    }
+
+    /// The highlighted lines of the whole document, as of the last call to
+    /// [MdSynHiCache::update].
+    pub fn get_style_us_span_lines(&self) -> &StyleUSSpanLines {
+        &self.style_us_span_lines
+    }
+
+    /// The number of blocks that were parsed by the last call to [MdSynHiCache::update].
+    pub fn get_last_parsed_block_count(&self) -> usize { self.last_parsed_block_count }
+}
+
+fn is_front_matter_fence(line: &str) -> bool {
+    line == FRONT_MATTER_YAML_FENCE || line == FRONT_MATTER_TOML_FENCE
+}
+
+/// Returns the range of the lines that are different in the old & new lines, after
+/// skipping the lines at the start & end that are the same.
+fn get_changed_line_ranges(
+    old_lines: &[String],
+    new_lines: &[US],
+) -> (Range<usize>, Range<usize>) {
+    let is_same = |(lhs, rhs): &(&String, &US)| **lhs == rhs.string;
+
+    let prefix_count = old_lines.iter().zip(new_lines).take_while(is_same).count();
+    let max_suffix_count = old_lines.len().min(new_lines.len()) - prefix_count;
+    let suffix_count = old_lines
+        .iter()
+        .rev()
+        .zip(new_lines.iter().rev())
+        .take(max_suffix_count)
+        .take_while(is_same)
+        .count();
+
+    (
+        prefix_count..old_lines.len() - suffix_count,
+        prefix_count..new_lines.len() - suffix_count,
+    )
+}
+
+#[cfg(test)]
+mod tests_md_syn_hi_cache {
+    use std::time::{Duration, Instant};
+
+    use r3bl_rs_utils_macro::style;
+
+    use super::*;
+
+    fn to_lines(lines: &[&str]) -> Vec<US> {
+        lines.iter().map(|it| US::from(*it)).collect()
+    }
+
+    /// A multi-thousand-line document w/ all kinds of blocks.
+    fn get_large_document_lines() -> Vec<US> {
+        let section = [
+            "# Heading",
+            "",
+            "Some *italic* and **bold** text w/ a [link](https://r3bl.com).",
+            "- item 1",
+            "  continued",
+            "- [x] done",
+            "1. first",
+            "2. second",
+            "```rust",
+            "let a = 1;",
+            "```",
+            "| a | b |",
+            "|---|---|",
+            "| 1 | 2 |",
+            "> quote",
+            "---",
+            "",
+        ];
+        to_lines(&section.repeat(200))
+    }
+
+    fn assert_same_as_full_parse(cache: &mut MdSynHiCache, lines: &[US]) {
+        let expected = try_parse_and_highlight(&lines.to_vec(), &None, None).unwrap();
+        assert_eq2!(cache.update(lines, &None, None), &expected);
+    }
+
+    #[test]
+    fn test_update_is_same_as_full_parse() {
+        let mut cache = MdSynHiCache::default();
+
+        let mut lines = to_lines(&["# a", "- x", "# b", "```", "code", "```", "c"]);
+        assert_same_as_full_parse(&mut cache, &lines);
+        assert_eq2!(cache.get_last_parsed_block_count(), 5);
+
+        // Nothing changed.
+        assert_same_as_full_parse(&mut cache, &lines);
+        assert_eq2!(cache.get_last_parsed_block_count(), 0);
+
+        // The list continues onto the changed line.
+        lines[2] = US::from("  y");
+        assert_same_as_full_parse(&mut cache, &lines);
+        assert_eq2!(cache.get_last_parsed_block_count(), 1);
+
+        // Typing in the code block.
+        lines[4] = US::from("code 2");
+        assert_same_as_full_parse(&mut cache, &lines);
+        assert_eq2!(cache.get_last_parsed_block_count(), 1);
+
+        // A new code fence turns the text before it into a code block.
+        lines.insert(0, US::from("```"));
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines.remove(0);
+        assert_same_as_full_parse(&mut cache, &lines);
+
+        // Text that can't be parsed, and the lines after it.
+        lines[5] = US::from("c *d");
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines.push(US::from("e"));
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines[5] = US::from("c *d*");
+        assert_same_as_full_parse(&mut cache, &lines);
+
+        // Removing lines.
+        lines.drain(0..2);
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines.clear();
+        assert_same_as_full_parse(&mut cache, &lines);
+
+        // Front matter is only valid once all of its lines are.
+        lines = to_lines(&["---", "title: a", "b", "---", "c", "---"]);
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines[2] = US::from("tags: b");
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines[3] = US::from("--");
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines[3] = US::from("---");
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines[4] = US::from("c d");
+        assert_same_as_full_parse(&mut cache, &lines);
+        assert_eq2!(cache.get_last_parsed_block_count(), 2);
+
+        // A list item continues after blank lines.
+        lines = to_lines(&["- a", "", "b"]);
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines[2] = US::from("  b");
+        assert_same_as_full_parse(&mut cache, &lines);
+        assert_eq2!(cache.get_last_parsed_block_count(), 1);
+
+        // The old lines weren't all parsed, so the end of the old chunks isn't the end of
+        // the document.
+        lines = to_lines(&["```", "_```*", "_"]);
+        assert_same_as_full_parse(&mut cache, &lines);
+        lines[0] = US::from("***");
+        assert_same_as_full_parse(&mut cache, &lines);
+
+        // A different style invalidates the cache.
+        lines = to_lines(&["a", "b"]);
+        assert_same_as_full_parse(&mut cache, &lines);
+        let style = Some(style! { attrib: [bold] });
+        let expected = try_parse_and_highlight(&lines, &style, None).unwrap();
+        assert_eq2!(cache.update(&lines, &style, None), &expected);
+        assert_eq2!(cache.get_last_parsed_block_count(), 2);
+    }
+
+    /// Re-highlighting a multi-thousand-line document after each keystroke only re-parses
+    /// the few blocks around the changed line.
+    #[test]
+    fn test_update_large_document() {
+        let mut lines = get_large_document_lines();
+        assert!(lines.len() > 3_000);
+
+        let mut cache = MdSynHiCache::default();
+        cache.update(&lines, &None, None);
+
+        let row_index = lines.len() / 2 + 2;
+        for character in "typing".chars() {
+            let mut line = lines[row_index].string.clone();
+            line.push(character);
+            lines[row_index] = US::from(line);
+
+            let expected = try_parse_and_highlight(&lines, &None, None).unwrap();
+            assert_eq2!(cache.update(&lines, &None, None), &expected);
+            // The changed line, the (empty) line before it, and the heading before that
+            // (since a list item can continue after blank lines, the blocks are re-parsed
+            // from the chunk before them).
+            assert_eq2!(cache.get_last_parsed_block_count(), 3);
+        }
+    }
+
+    /// Compares re-highlighting a multi-thousand-line document after each keystroke, w/
+    /// [try_parse_and_highlight] & w/ [MdSynHiCache::update]. This depends on wall-clock
+    /// time, so it only runs w/ `cargo test -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn test_update_benchmark() {
+        let mut lines = get_large_document_lines();
+
+        let mut cache = MdSynHiCache::default();
+        cache.update(&lines, &None, None);
+
+        let row_index = lines.len() / 2 + 2;
+        let (mut full_duration, mut incremental_duration) =
+            (Duration::default(), Duration::default());
+        for character in "typing".chars() {
+            let mut line = lines[row_index].string.clone();
+            line.push(character);
+            lines[row_index] = US::from(line);
+
+            let start = Instant::now();
+            let expected = try_parse_and_highlight(&lines, &None, None).unwrap();
+            full_duration += start.elapsed();
+
+            let start = Instant::now();
+            let actual = cache.update(&lines, &None, None);
+            incremental_duration += start.elapsed();
+
+            assert_eq2!(actual, &expected);
+        }
+
+        println!(
+            "{} lines, full: {:?}, incremental: {:?}, speedup: {:.1}x",
+            lines.len(),
+            full_duration,
+            incremental_duration,
+            full_duration.as_secs_f64() / incremental_duration.as_secs_f64()
+        );
+        assert!(incremental_duration < full_duration);
+    }
+}
@@ -291,26 +291,174 @@ impl StyleUSSpanLines {
         }
     }
 
+    /// Each line of the blocks in the list (please take a look at [SmartListData::flatten])
+    /// is prefixed w/ its bullet, or w/ the indentation guides of the items that it is in.
+    /// Just like in the source text, lazy continuation lines & empty lines don't get a
+    /// prefix.
     pub fn from_block_smart_list(
-        input_ul_lines: &Lines,
+        list_data: &SmartListData,
         maybe_current_box_computed_style: &Option<Style>,
+        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
     ) -> Self {
         let mut acc_lines_output = StyleUSSpanLines::default();
 
-        // Process each line in ul_lines.
-        for input_line in input_ul_lines.iter() {
-            let mut acc_line_output = StyleUSSpanLine::default();
+        for it in list_data.flatten() {
+            let block_lines = StyleUSSpanLines::from_block(
+                it.block,
+                maybe_current_box_computed_style,
+                maybe_syntect_tuple,
+            );
+
+            for (line_index, block_line) in block_lines.items.into_iter().enumerate() {
+                let mut acc_line_output = StyleUSSpanLine::default();
+                if !it.is_lazy && !block_line.is_empty() {
+                    let prefix = if line_index == 0 {
+                        &it.first_line_prefix
+                    } else {
+                        &it.rest_line_prefix
+                    };
+                    acc_line_output += StyleUSSpan::new(
+                        maybe_current_box_computed_style.unwrap_or_default()
+                            + get_list_bullet_style(),
+                        US::from(prefix.as_str()),
+                    );
+                }
+                acc_line_output += block_line;
+                acc_lines_output += acc_line_output;
+            }
+        }
+
+        acc_lines_output
+    }
 
-            let postfix_span_list = StyleUSSpanLine::from_fragments(
-                input_line,
+    /// Each line of the blocks in the blockquote is prefixed w/ `> ` (just `>` for empty
+    /// lines). Nested blockquotes end up w/ one prefix per level, eg: `> > `.
+    pub fn from_block_quote(
+        blocks: &List<MdBlockElement>,
+        maybe_current_box_computed_style: &Option<Style>,
+        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    ) -> Self {
+        let mut acc_lines_output = StyleUSSpanLines::default();
+
+        for block in blocks.iter() {
+            let block_lines = StyleUSSpanLines::from_block(
+                block,
                 maybe_current_box_computed_style,
+                maybe_syntect_tuple,
             );
 
-            acc_line_output += postfix_span_list;
+            for block_line in block_lines.items {
+                let prefix = if block_line.is_empty() {
+                    BLOCK_QUOTE
+                } else {
+                    BLOCK_QUOTE_PREFIX
+                };
+                let mut acc_line_output = StyleUSSpanLine::default();
+                acc_line_output += StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + get_block_quote_marker_style(),
+                    US::from(prefix),
+                );
+                acc_line_output += block_line;
+                acc_lines_output += acc_line_output;
+            }
+        }
+
+        acc_lines_output
+    }
+
+    /// Each line of the front matter (including the fences) is translated into a line,
+    /// keeping its text as is. The key value pairs are styled like the metadata, eg:
+    /// [StyleUSSpanLine::from_kvp], and the rest of the lines are dimmed.
+    pub fn from_block_front_matter(
+        front_matter: &FrontMatterData,
+        maybe_current_box_computed_style: &Option<Style>,
+    ) -> Self {
+        let base_style = maybe_current_box_computed_style.unwrap_or_default();
+        let fence = match front_matter.kind {
+            FrontMatterKind::Yaml => FRONT_MATTER_YAML_FENCE,
+            FrontMatterKind::Toml => FRONT_MATTER_TOML_FENCE,
+        };
+
+        let mut acc_lines_output = StyleUSSpanLines::default();
+        acc_lines_output += list![StyleUSSpan::new(
+            base_style + get_front_matter_fence_style(),
+            US::from(fence),
+        )];
 
+        for line in front_matter.lines.iter() {
+            let mut acc_line_output = StyleUSSpanLine::default();
+            if let Some((key, separator, value)) =
+                split_front_matter_key_value(front_matter.kind, line)
+            {
+                acc_line_output += StyleUSSpan::new(
+                    base_style + get_metadata_title_marker_style(),
+                    US::from(key),
+                );
+                acc_line_output += StyleUSSpan::new(
+                    base_style + get_foreground_dim_style(),
+                    US::from(separator),
+                );
+                acc_line_output += StyleUSSpan::new(
+                    base_style + get_metadata_title_value_style(),
+                    US::from(value),
+                );
+            } else if let Some((prefix, value)) = split_front_matter_list_item(line) {
+                acc_line_output += StyleUSSpan::new(
+                    base_style + get_foreground_dim_style(),
+                    US::from(prefix),
+                );
+                acc_line_output += StyleUSSpan::new(
+                    base_style + get_metadata_tags_values_style(),
+                    US::from(value),
+                );
+            } else if !line.is_empty() {
+                acc_line_output += StyleUSSpan::new(
+                    base_style + get_front_matter_fence_style(),
+                    US::from(*line),
+                );
+            }
             acc_lines_output += acc_line_output;
         }
 
+        acc_lines_output += list![StyleUSSpan::new(
+            base_style + get_front_matter_fence_style(),
+            US::from(fence),
+        )];
+
+        acc_lines_output
+    }
+
+    /// Each row of the table is translated into a line, keeping the padding of its cells
+    /// as is (the widths of the lines match the source text).
+    pub fn from_block_table(
+        table_data: &TableData,
+        maybe_current_box_computed_style: &Option<Style>,
+    ) -> Self {
+        let mut acc_lines_output = StyleUSSpanLines::default();
+
+        acc_lines_output += StyleUSSpanLine::from_table_row(
+            &table_data.header_row,
+            maybe_current_box_computed_style,
+            true,
+        );
+
+        let mut delimiter_line = StyleUSSpanLine::default();
+        delimiter_line += StyleUSSpan::new(
+            maybe_current_box_computed_style.unwrap_or_default()
+                + get_table_separator_style(),
+            US::from(table_data.delimiter_row),
+        );
+        acc_lines_output += delimiter_line;
+
+        for body_row in table_data.body_rows.iter() {
+            acc_lines_output += StyleUSSpanLine::from_table_row(
+                body_row,
+                maybe_current_box_computed_style,
+                false,
+            );
+        }
+
         acc_lines_output
     }
 
@@ -365,10 +513,11 @@ impl StyleUSSpanLines {
                     maybe_current_box_computed_style,
                 ))
             }
-            MdBlockElement::SmartList((list_lines, _bullet_kind, _indent)) => {
+            MdBlockElement::SmartList(list_data) => {
                 lines += StyleUSSpanLines::from_block_smart_list(
-                    list_lines,
+                    list_data,
                     maybe_current_box_computed_style,
+                    maybe_syntect_tuple,
                 );
             }
             MdBlockElement::CodeBlock(code_block_lines) => {
@@ -378,6 +527,43 @@ impl StyleUSSpanLines {
                     maybe_syntect_tuple,
                 );
             }
+            MdBlockElement::BlockQuote(blocks) => {
+                lines += StyleUSSpanLines::from_block_quote(
+                    blocks,
+                    maybe_current_box_computed_style,
+                    maybe_syntect_tuple,
+                );
+            }
+            MdBlockElement::ThematicBreak(text) => {
+                lines.push(StyleUSSpanLine::from_thematic_break(
+                    text,
+                    maybe_current_box_computed_style,
+                ));
+            }
+            MdBlockElement::Table(table_data) => {
+                lines += StyleUSSpanLines::from_block_table(
+                    table_data,
+                    maybe_current_box_computed_style,
+                );
+            }
+            MdBlockElement::LinkDefinition(link_definition) => {
+                lines.push(StyleUSSpanLine::from_link_definition(
+                    link_definition,
+                    maybe_current_box_computed_style,
+                ));
+            }
+            MdBlockElement::FootnoteDefinition(footnote_definition) => {
+                lines.push(StyleUSSpanLine::from_footnote_definition(
+                    footnote_definition,
+                    maybe_current_box_computed_style,
+                ));
+            }
+            MdBlockElement::FrontMatter(front_matter) => {
+                lines += StyleUSSpanLines::from_block_front_matter(
+                    front_matter,
+                    maybe_current_box_computed_style,
+                );
+            }
         }
 
         lines
@@ -443,32 +629,6 @@ impl StyleUSSpan {
         maybe_current_box_computed_style: &Option<Style>,
     ) -> Vec<Self> {
         match fragment {
-            MdLineFragment::OrderedListBullet {
-                indent,
-                number,
-                is_first_line,
-            } => {
-                let bullet =
-                    generate_ordered_list_item_bullet(indent, number, is_first_line);
-                vec![StyleUSSpan::new(
-                    maybe_current_box_computed_style.unwrap_or_default()
-                        + get_list_bullet_style(),
-                    US::from(bullet),
-                )]
-            }
-
-            MdLineFragment::UnorderedListBullet {
-                indent,
-                is_first_line,
-            } => {
-                let bullet = generate_unordered_list_item_bullet(indent, is_first_line);
-                vec![StyleUSSpan::new(
-                    maybe_current_box_computed_style.unwrap_or_default()
-                        + get_list_bullet_style(),
-                    US::from(bullet),
-                )]
-            }
-
             MdLineFragment::Plain(plain_text) => vec![StyleUSSpan::new(
                 maybe_current_box_computed_style.unwrap_or_default()
                     + get_foreground_style(),
@@ -531,6 +691,24 @@ impl StyleUSSpan {
                 ),
             ],
 
+            MdLineFragment::Strikethrough(strikethrough_text) => vec![
+                StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + get_foreground_dim_style(),
+                    US::from(STRIKETHROUGH),
+                ),
+                StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + get_strikethrough_style(),
+                    US::from(*strikethrough_text),
+                ),
+                StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + get_foreground_dim_style(),
+                    US::from(STRIKETHROUGH),
+                ),
+            ],
+
             MdLineFragment::InlineCode(inline_code_text) => vec![
                 StyleUSSpan::new(
                     maybe_current_box_computed_style.unwrap_or_default()
@@ -561,6 +739,49 @@ impl StyleUSSpan {
                 HyperlinkType::Image,
             ),
 
+            MdLineFragment::ReferenceLink(ReferenceLinkData { text, label }) => {
+                let base_style = maybe_current_box_computed_style.unwrap_or_default()
+                    + get_foreground_dim_style();
+                vec![
+                    // [text]
+                    StyleUSSpan::new(base_style, US::from(LEFT_BRACKET)),
+                    StyleUSSpan::new(
+                        maybe_current_box_computed_style.unwrap_or_default()
+                            + get_link_text_style(),
+                        US::from(*text),
+                    ),
+                    StyleUSSpan::new(base_style, US::from(RIGHT_BRACKET)),
+                    // [label]
+                    StyleUSSpan::new(base_style, US::from(LEFT_BRACKET)),
+                    StyleUSSpan::new(
+                        maybe_current_box_computed_style.unwrap_or_default()
+                            + get_link_url_style(),
+                        US::from(*label),
+                    ),
+                    StyleUSSpan::new(base_style, US::from(RIGHT_BRACKET)),
+                ]
+            }
+
+            MdLineFragment::Autolink(url) => vec![StyleUSSpan::new(
+                maybe_current_box_computed_style.unwrap_or_default()
+                    + get_link_url_style(),
+                US::from(*url),
+            )],
+
+            MdLineFragment::FootnoteReference(label) => {
+                let base_style = maybe_current_box_computed_style.unwrap_or_default()
+                    + get_foreground_dim_style();
+                vec![
+                    StyleUSSpan::new(base_style, US::from(FOOTNOTE_PREFIX)),
+                    StyleUSSpan::new(
+                        maybe_current_box_computed_style.unwrap_or_default()
+                            + get_link_text_style(),
+                        US::from(*label),
+                    ),
+                    StyleUSSpan::new(base_style, US::from(RIGHT_BRACKET)),
+                ]
+            }
+
             MdLineFragment::Checkbox(done) => {
                 vec![if *done {
                     StyleUSSpan::new(
@@ -584,7 +805,7 @@ impl PrettyPrintDebug for StyleUSSpanLine {
     fn pretty_print_debug(&self) -> String {
         let mut it = vec![];
         for span in &self.items {
-            let StyleUSSpan { style, text } = span;
+            let StyleUSSpan { style, text, .. } = span;
             let line_text = format!("fragment[ {:?} , {:?} ]", text.string, style);
             it.push(line_text);
         }
@@ -655,6 +876,112 @@ impl StyleUSSpanLine {
 
         line
     }
+
+    /// `[label]: url`, which is styled like a [MdLineFragment::ReferenceLink] followed by
+    /// the URL.
+    pub fn from_link_definition(
+        link_definition: &LinkDefinitionData,
+        maybe_current_box_computed_style: &Option<Style>,
+    ) -> Self {
+        let base_style = maybe_current_box_computed_style.unwrap_or_default()
+            + get_foreground_dim_style();
+        let mut line = StyleUSSpanLine::default();
+        line += StyleUSSpan::new(base_style, US::from(LEFT_BRACKET));
+        line += StyleUSSpan::new(
+            maybe_current_box_computed_style.unwrap_or_default() + get_link_text_style(),
+            US::from(link_definition.label),
+        );
+        line += StyleUSSpan::new(
+            base_style,
+            US::from(format!("{RIGHT_BRACKET}{}", link_definition.separator)),
+        );
+        line += StyleUSSpan::new(
+            maybe_current_box_computed_style.unwrap_or_default() + get_link_url_style(),
+            US::from(link_definition.url),
+        );
+        line
+    }
+
+    /// `[^label]: text`, which is styled like a [MdLineFragment::FootnoteReference]
+    /// followed by the fragments of the text (which include the spaces after the `:`).
+    pub fn from_footnote_definition(
+        footnote_definition: &FootnoteDefinitionData,
+        maybe_current_box_computed_style: &Option<Style>,
+    ) -> Self {
+        let mut line = StyleUSSpanLine::from(StyleUSSpan::from_fragment(
+            &MdLineFragment::FootnoteReference(footnote_definition.label),
+            maybe_current_box_computed_style,
+        ));
+        line += StyleUSSpan::new(
+            maybe_current_box_computed_style.unwrap_or_default()
+                + get_foreground_dim_style(),
+            US::from(COLON),
+        );
+        line += StyleUSSpanLine::from_fragments(
+            &footnote_definition.fragments,
+            maybe_current_box_computed_style,
+        );
+        line
+    }
+
+    /// The thematic break is painted as a line that is as wide as its text.
+    pub fn from_thematic_break(
+        text: &str,
+        maybe_current_box_computed_style: &Option<Style>,
+    ) -> Self {
+        let display_width = ch!(@to_usize UnicodeString::from(text).display_width);
+        let mut line = StyleUSSpanLine::default();
+        line += StyleUSSpan::new(
+            maybe_current_box_computed_style.unwrap_or_default()
+                + get_thematic_break_style(),
+            US::from(THEMATIC_BREAK_DISPLAY.repeat(display_width)),
+        );
+        line
+    }
+
+    /// The `|` separators get their own style, and the fragments in the header row are
+    /// highlighted on top of their own style.
+    pub fn from_table_row(
+        table_row: &TableRow,
+        maybe_current_box_computed_style: &Option<Style>,
+        is_header: bool,
+    ) -> Self {
+        let base_style = maybe_current_box_computed_style.unwrap_or_default();
+        let separator_span = StyleUSSpan::new(
+            base_style + get_table_separator_style(),
+            US::from(TABLE_SEPARATOR),
+        );
+        let mut line = StyleUSSpanLine::default();
+
+        line += separator_span.clone();
+        for cell in table_row.iter() {
+            let text_start = cell.text.trim_start();
+            let text = text_start.trim_end();
+            let padding_left = &cell.text[..cell.text.len() - text_start.len()];
+            let padding_right = &text_start[text.len()..];
+
+            if !padding_left.is_empty() {
+                line += StyleUSSpan::new(base_style, US::from(padding_left));
+            }
+            for mut span in StyleUSSpanLine::from_fragments(
+                &cell.fragments,
+                maybe_current_box_computed_style,
+            )
+            .items
+            {
+                if is_header {
+                    span.style += get_table_header_style();
+                }
+                line += span;
+            }
+            if !padding_right.is_empty() {
+                line += StyleUSSpan::new(base_style, US::from(padding_right));
+            }
+            line += separator_span.clone();
+        }
+
+        line
+    }
 }
 
 impl From<StyledTexts> for StyleUSSpanLine {
@@ -974,6 +1301,29 @@ mod tests_style_us_span_lines_from {
             );
         }
 
+        #[test]
+        fn test_strikethrough() {
+            let fragment = MdLineFragment::Strikethrough("Foobar");
+            let style = style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+
+            assert_eq2!(
+                actual[0],
+                StyleUSSpan::new(style + get_foreground_dim_style(), US::from("~~"),)
+            );
+            assert_eq2!(
+                actual[1],
+                StyleUSSpan::new(style + get_strikethrough_style(), US::from("Foobar"),)
+            );
+            assert_eq2!(
+                actual[2],
+                StyleUSSpan::new(style + get_foreground_dim_style(), US::from("~~"),)
+            );
+        }
+
         #[test]
         fn test_bold_italic() {
             let fragment = MdLineFragment::BoldItalic("Foobar");
@@ -1017,8 +1367,6 @@ mod tests_style_us_span_lines_from {
     /// Test each variant of [MdBlockElement] is converted by
     /// [StyleUSSpanLines::from_block](StyleUSSpanLines::from_block).
     mod from_block {
-        use crossterm::style::Stylize;
-
         use super::*;
 
         #[test]
@@ -1173,48 +1521,30 @@ mod tests_style_us_span_lines_from {
                 };
                 let (remainder, doc) = parse_markdown("100. Foo\n200. Bar\n")?;
                 assert_eq2!(remainder, "");
+                assert_eq2!(doc.len(), 1);
 
-                let ol_block_1 = &doc[0];
-                {
-                    // println!("{:#?}", ol_block_1);
-                    let lines =
-                        StyleUSSpanLines::from_block(ol_block_1, &Some(style), None);
-
-                    let line_0 = &lines.items[0];
-                    // println!("{}", line_0..pretty_print_debug());
-                    assert_eq2!(
-                        line_0.items[0],
-                        StyleUSSpan::new(
-                            style + get_list_bullet_style(),
-                            US::from("100.│")
-                        )
-                    );
-                    assert_eq2!(
-                        line_0.items[1],
-                        StyleUSSpan::new(style + get_foreground_style(), US::from("Foo"),)
-                    );
-                }
+                let lines = StyleUSSpanLines::from_block(&doc[0], &Some(style), None);
+                assert_eq2!(lines.len(), 2);
 
-                let ol_block_2 = &doc[1];
-                {
-                    // println!("{:#?}", ol_block_2);
-                    let lines =
-                        StyleUSSpanLines::from_block(ol_block_2, &Some(style), None);
-
-                    let line_0 = &lines.items[0];
-                    // println!("{}", line_0..pretty_print_debug());
-                    assert_eq2!(
-                        line_0.items[0],
-                        StyleUSSpan::new(
-                            style + get_list_bullet_style(),
-                            US::from("200.│")
-                        )
-                    );
-                    assert_eq2!(
-                        line_0.items[1],
-                        StyleUSSpan::new(style + get_foreground_style(), US::from("Bar"),)
-                    );
-                }
+                let line_0 = &lines.items[0];
+                assert_eq2!(
+                    line_0.items[0],
+                    StyleUSSpan::new(style + get_list_bullet_style(), US::from("100.│"))
+                );
+                assert_eq2!(
+                    line_0.items[1],
+                    StyleUSSpan::new(style + get_foreground_style(), US::from("Foo"),)
+                );
+
+                let line_1 = &lines.items[1];
+                assert_eq2!(
+                    line_1.items[0],
+                    StyleUSSpan::new(style + get_list_bullet_style(), US::from("200.│"))
+                );
+                assert_eq2!(
+                    line_1.items[1],
+                    StyleUSSpan::new(style + get_foreground_style(), US::from("Bar"),)
+                );
             });
         }
 
@@ -1224,40 +1554,65 @@ mod tests_style_us_span_lines_from {
                 let style = style! {
                     color_bg: TuiColor::Basic(ANSIBasicColor::Red)
                 };
-                let (_, doc) = parse_markdown("- Foo\n- Bar\n")?;
-                println!("{}", format!("{:#?}", doc).cyan());
-
-                // First smart list.
-                {
-                    let ul_block_0 = &doc[0];
-                    let lines =
-                        StyleUSSpanLines::from_block(ul_block_0, &Some(style), None);
-                    let line_0 = &lines.items[0];
-                    assert_eq2!(
-                        line_0.items[0],
-                        StyleUSSpan::new(style + get_list_bullet_style(), US::from("─┤"))
-                    );
-                    assert_eq2!(
-                        line_0.items[1],
-                        StyleUSSpan::new(style + get_foreground_style(), US::from("Foo"))
-                    );
-                }
+                let (remainder, doc) = parse_markdown("- Foo\n- Bar\n")?;
+                assert_eq2!(remainder, "");
+                assert_eq2!(doc.len(), 1);
 
-                // Second smart list.
-                {
-                    let ul_block_1 = &doc[1];
-                    let lines =
-                        StyleUSSpanLines::from_block(ul_block_1, &Some(style), None);
-                    let line_0 = &lines.items[0];
-                    assert_eq2!(
-                        line_0.items[0],
-                        StyleUSSpan::new(style + get_list_bullet_style(), US::from("─┤"))
-                    );
-                    assert_eq2!(
-                        line_0.items[1],
-                        StyleUSSpan::new(style + get_foreground_style(), US::from("Bar"))
-                    );
-                }
+                let lines = StyleUSSpanLines::from_block(&doc[0], &Some(style), None);
+                assert_eq2!(lines.len(), 2);
+
+                let line_0 = &lines.items[0];
+                assert_eq2!(
+                    line_0.items[0],
+                    StyleUSSpan::new(style + get_list_bullet_style(), US::from("─┤"))
+                );
+                assert_eq2!(
+                    line_0.items[1],
+                    StyleUSSpan::new(style + get_foreground_style(), US::from("Foo"))
+                );
+
+                let line_1 = &lines.items[1];
+                assert_eq2!(
+                    line_1.items[0],
+                    StyleUSSpan::new(style + get_list_bullet_style(), US::from("─┤"))
+                );
+                assert_eq2!(
+                    line_1.items[1],
+                    StyleUSSpan::new(style + get_foreground_style(), US::from("Bar"))
+                );
+            });
+        }
+
+        #[test]
+        fn test_block_nested_list() -> CommonResult<()> {
+            throws!({
+                let input = "- a\n  1. b\n\n     c\nd\n  ```rs\n  let a = 1;\n  ```\n";
+                let (remainder, doc) = parse_markdown(input)?;
+                assert_eq2!(remainder, "");
+                assert_eq2!(doc.len(), 1);
+
+                let lines = StyleUSSpanLines::from_block(&doc[0], &None, None);
+
+                let to_strings = |line: &StyleUSSpanLine| -> Vec<String> {
+                    line.items
+                        .iter()
+                        .map(|span| span.text.string.clone())
+                        .collect()
+                };
+                // Each line is as wide as it is in the source text.
+                assert_eq2!(lines.len(), 8);
+                assert_eq2!(to_strings(&lines[0]), vec!["─┤", "a"]);
+                assert_eq2!(to_strings(&lines[1]), vec![" │1.│", "b"]);
+                assert_eq2!(to_strings(&lines[2]), Vec::<String>::new());
+                assert_eq2!(to_strings(&lines[3]), vec![" │  │", "c"]);
+                assert_eq2!(to_strings(&lines[4]), vec!["d"]);
+                assert_eq2!(to_strings(&lines[5]), vec![" │", "```", "rs"]);
+                assert_eq2!(to_strings(&lines[6]), vec![" │", "let a = 1;"]);
+                assert_eq2!(to_strings(&lines[7]), vec![" │", "```"]);
+                assert_eq2!(
+                    lines[1][0].style,
+                    Style::default() + get_list_bullet_style()
+                );
             });
         }
 
@@ -1273,11 +1628,168 @@ mod tests_style_us_span_lines_from {
 
             let line_0 = &lines.items[0];
             let span_0_in_line_0 = &line_0.items[0];
-            let StyleUSSpan { style, text } = span_0_in_line_0;
+            let StyleUSSpan { style, text, .. } = span_0_in_line_0;
             assert_eq2!(text.string, "Foobar");
             assert_eq2!(style, &(*style + get_foreground_style()));
         }
 
+        #[test]
+        fn test_block_quote() {
+            let quote_block = MdBlockElement::BlockQuote(list![
+                MdBlockElement::Text(list![MdLineFragment::Plain("Foo")]),
+                MdBlockElement::Text(list![]),
+                MdBlockElement::BlockQuote(list![MdBlockElement::Text(list![
+                    MdLineFragment::Plain("Bar")
+                ])]),
+            ]);
+            let style = style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let lines = StyleUSSpanLines::from_block(&quote_block, &Some(style), None);
+
+            let to_strings = |line: &StyleUSSpanLine| -> Vec<String> {
+                line.items
+                    .iter()
+                    .map(|span| span.text.string.clone())
+                    .collect()
+            };
+            assert_eq2!(lines.len(), 3);
+            assert_eq2!(to_strings(&lines[0]), vec!["> ", "Foo"]);
+            assert_eq2!(to_strings(&lines[1]), vec![">"]);
+            assert_eq2!(to_strings(&lines[2]), vec!["> ", "> ", "Bar"]);
+            assert_eq2!(lines[0][0].style, style + get_block_quote_marker_style());
+        }
+
+        #[test]
+        fn test_block_thematic_break() {
+            let thematic_break_block = MdBlockElement::ThematicBreak("* * *");
+            let style = style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let lines =
+                StyleUSSpanLines::from_block(&thematic_break_block, &Some(style), None);
+
+            assert_eq2!(lines.len(), 1);
+            assert_eq2!(
+                lines[0][0],
+                StyleUSSpan::new(style + get_thematic_break_style(), US::from("─────"))
+            );
+        }
+
+        #[test]
+        fn test_block_table() {
+            let (_, table_data) =
+                parse_block_table("| a | *b* |\n|:--|--:|\n|1|  |\n").unwrap();
+            let table_block = MdBlockElement::Table(table_data);
+            let style = style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let lines = StyleUSSpanLines::from_block(&table_block, &Some(style), None);
+
+            let to_strings = |line: &StyleUSSpanLine| -> Vec<String> {
+                line.items
+                    .iter()
+                    .map(|span| span.text.string.clone())
+                    .collect()
+            };
+            assert_eq2!(lines.len(), 3);
+            assert_eq2!(
+                to_strings(&lines[0]),
+                vec!["|", " ", "a", " ", "|", " ", "*", "b", "*", " ", "|"]
+            );
+            assert_eq2!(to_strings(&lines[1]), vec!["|:--|--:|"]);
+            assert_eq2!(to_strings(&lines[2]), vec!["|", "1", "|", "  ", "|"]);
+
+            // Separators.
+            assert_eq2!(lines[0][0].style, style + get_table_separator_style());
+            assert_eq2!(lines[1][0].style, style + get_table_separator_style());
+
+            // Header cells vs body cells.
+            assert_eq2!(
+                lines[0][2].style,
+                style + get_foreground_style() + get_table_header_style()
+            );
+            assert_eq2!(lines[2][1].style, style + get_foreground_style());
+        }
+
+        #[test]
+        fn test_block_front_matter() {
+            let (_, front_matter) =
+                parse_block_front_matter("---\ntitle: Foo\ntags:\n  - a\n# x\n---\n")
+                    .unwrap();
+            let front_matter_block = MdBlockElement::FrontMatter(front_matter);
+            let style = style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let lines =
+                StyleUSSpanLines::from_block(&front_matter_block, &Some(style), None);
+
+            let to_strings = |line: &StyleUSSpanLine| -> Vec<String> {
+                line.items
+                    .iter()
+                    .map(|span| span.text.string.clone())
+                    .collect()
+            };
+            assert_eq2!(lines.len(), 6);
+            assert_eq2!(to_strings(&lines[0]), vec!["---"]);
+            assert_eq2!(to_strings(&lines[1]), vec!["title", ": ", "Foo"]);
+            assert_eq2!(to_strings(&lines[2]), vec!["tags", ":", ""]);
+            assert_eq2!(to_strings(&lines[3]), vec!["  - ", "a"]);
+            assert_eq2!(to_strings(&lines[4]), vec!["# x"]);
+            assert_eq2!(to_strings(&lines[5]), vec!["---"]);
+
+            assert_eq2!(lines[0][0].style, style + get_front_matter_fence_style());
+            assert_eq2!(lines[1][0].style, style + get_metadata_title_marker_style());
+            assert_eq2!(lines[1][2].style, style + get_metadata_title_value_style());
+            assert_eq2!(lines[3][1].style, style + get_metadata_tags_values_style());
+        }
+
+        #[test]
+        fn test_block_link_and_footnote_definitions() {
+            let (_, link_definition) =
+                parse_block_link_definition("[r3bl]:  https://r3bl.com").unwrap();
+            let (_, footnote_definition) =
+                parse_block_footnote_definition("[^1]:  Note.").unwrap();
+            let style = style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let to_strings = |line: &StyleUSSpanLine| -> Vec<String> {
+                line.items
+                    .iter()
+                    .map(|span| span.text.string.clone())
+                    .collect()
+            };
+
+            // The text of the lines is the same as the source text.
+            let lines = StyleUSSpanLines::from_block(
+                &MdBlockElement::LinkDefinition(link_definition),
+                &Some(style),
+                None,
+            );
+            assert_eq2!(lines.len(), 1);
+            assert_eq2!(
+                to_strings(&lines[0]),
+                vec!["[", "r3bl", "]:  ", "https://r3bl.com"]
+            );
+            assert_eq2!(lines[0][1].style, style + get_link_text_style());
+            assert_eq2!(lines[0][3].style, style + get_link_url_style());
+
+            let lines = StyleUSSpanLines::from_block(
+                &MdBlockElement::FootnoteDefinition(footnote_definition),
+                &Some(style),
+                None,
+            );
+            assert_eq2!(lines.len(), 1);
+            assert_eq2!(to_strings(&lines[0]), vec!["[^", "1", "]", ":", "  Note."]);
+            assert_eq2!(lines[0][1].style, style + get_link_text_style());
+            assert_eq2!(lines[0][4].style, style + get_foreground_style());
+        }
+
         #[test]
         fn test_block_heading() {
             let heading_block = MdBlockElement::Heading(HeadingData {
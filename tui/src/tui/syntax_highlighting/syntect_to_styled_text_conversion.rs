@@ -56,7 +56,7 @@ pub fn from_syntect_to_tui(
 
     // Remove the background color from each style in the theme.
     it.iter_mut()
-        .for_each(|StyleUSSpan { style, text: _ }| style.remove_bg_color());
+        .for_each(|StyleUSSpan { style, .. }| style.remove_bg_color());
 
     it
 }